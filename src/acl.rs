@@ -0,0 +1,310 @@
+use crate::*;
+
+/// Who an `AclEntry` applies to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Principal {
+    /// The user owning the file.
+    Owner,
+    /// The group owning the file.
+    OwningGroup,
+    /// Everyone who is neither the owner nor matched by another entry.
+    Everyone,
+    /// A user, by name, or by numeric id when it has no name.
+    User(String),
+    /// A group, by name, or by numeric id when it has no name.
+    Group(String),
+}
+
+/// Whether an `AclEntry` grants or denies its permissions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AclKind {
+    Allow,
+    Deny,
+}
+
+/// A single access rule: which `Principal` may (or may not) read, write or execute the file.
+///
+/// # Examples
+/// ```
+/// use file_access::{AclEntry, Principal};
+///
+/// let entry = AclEntry::allow(Principal::User("svc-backup".to_string())).read();
+/// assert!(entry.read && !entry.write && !entry.execute);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AclEntry {
+    pub principal: Principal,
+    pub kind: AclKind,
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+}
+
+impl AclEntry {
+    /// Creates an entry granting nothing yet to `principal`; chain `read()`, `write()` and `execute()` onto it.
+    pub fn allow(principal: Principal) -> Self {
+        Self {
+            principal,
+            kind: AclKind::Allow,
+            read: false,
+            write: false,
+            execute: false,
+        }
+    }
+
+    /// Creates an entry denying nothing yet to `principal`; chain `read()`, `write()` and `execute()` onto it.
+    pub fn deny(principal: Principal) -> Self {
+        Self {
+            kind: AclKind::Deny,
+            ..Self::allow(principal)
+        }
+    }
+
+    /// Adds the read permission.
+    pub fn read(self) -> Self {
+        Self { read: true, ..self }
+    }
+
+    /// Adds the write permission.
+    pub fn write(self) -> Self {
+        Self {
+            write: true,
+            ..self
+        }
+    }
+
+    /// Adds the execute permission.
+    pub fn execute(self) -> Self {
+        Self {
+            execute: true,
+            ..self
+        }
+    }
+}
+
+/// Reads the access control list of a file or directory. On Linux this is the POSIX ACL,
+/// reported with effective permissions (i.e. with the ACL mask already applied). On macOS it's the permission bits
+/// for the owner, owning group and everyone else, followed by the extended ACL's entries for named users and groups.
+/// On Windows it's the DACL's allow and deny entries, in the order they're checked, with users and groups named
+/// as `DOMAIN\name`, or as a SID string such as `S-1-5-21-…` when they have no name; an entry doesn't distinguish
+/// Windows' finer rights, so any right to read, write or execute data counts. Other platforms return an
+/// `Unsupported` error.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Vec<AclEntry>`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         # #[cfg(any(target_os = "linux", target_os = "macos", windows))]
+///         for entry in file_access::acl(&file_path)? {
+///             println!("{:?}", entry);
+///         }
+///     })
+/// }
+/// ```
+pub fn acl<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Vec<AclEntry>> {
+    #[cfg(target_os = "macos")]
+    use internal::sys::macos_acl::get_acl;
+    #[cfg(target_os = "linux")]
+    use internal::sys::posix_acl::get_acl;
+    #[cfg(windows)]
+    use internal::sys::windows_acl::get_acl;
+
+    #[cfg(any(target_os = "linux", target_os = "macos", windows))]
+    return get_acl(&path_of(file_path)).context("acl", file_path);
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+    return Err(ErrorKind::Unsupported).context("acl", file_path);
+}
+
+/// Replaces the access control list of a file or directory. Missing `Owner`, `OwningGroup` and `Everyone`
+/// entries are treated as granting nothing, and entries for the same principal are merged.
+/// On Linux this writes a POSIX ACL, which cannot express `AclKind::Deny` entries, and keeps the setuid, setgid
+/// and sticky bits, which aren't part of it. On macOS the owner, owning group and everyone else are written to the
+/// permission bits, keeping the same special bits, so only named users and groups can be denied, in the extended ACL.
+/// On Windows this writes a DACL that no longer inherits from the parent directory, with deny entries checked first,
+/// each permission granting the generic file rights for it; users and groups can be given as `DOMAIN\name` or as
+/// a SID string. Other platforms return an `Unsupported` error.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `entries`: **borrowed** `[AclEntry]`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// use file_access::{AclEntry, Principal};
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "set_acl.txt";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"secret")?;
+///
+///         // Only the owner can read and write:
+///         let entries = vec![AclEntry::allow(Principal::Owner).read().write()];
+///
+///         # #[cfg(any(target_os = "linux", target_os = "macos", windows))]
+///         file_access::set_acl(&file_path, &entries)?;
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn set_acl<Path: AsPath + ?Sized>(file_path: &Path, entries: &[AclEntry]) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    use internal::sys::macos_acl::set_acl;
+    #[cfg(target_os = "linux")]
+    use internal::sys::posix_acl::set_acl;
+    #[cfg(windows)]
+    use internal::sys::windows_acl::set_acl;
+
+    #[cfg(any(target_os = "linux", target_os = "macos", windows))]
+    return set_acl(&path_of(file_path), entries).context("set_acl", file_path);
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+    return Err(ErrorKind::Unsupported).context("set_acl", file_path);
+}
+
+#[cfg(test)]
+#[cfg(any(target_os = "linux", target_os = "macos", windows))]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    #[cfg(unix)]
+    fn base_acl() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        Ok({
+            // Arrange
+            let file = "base_acl.txt";
+            write_string(&file, &"Hello, World!")?;
+            let entries = vec![
+                AclEntry::allow(Principal::Owner).read().write(),
+                AclEntry::allow(Principal::OwningGroup).read(),
+                AclEntry::allow(Principal::Everyone),
+            ];
+
+            // Action
+            set_acl(&file, &entries)?;
+
+            // Assert
+            assert_eq!(acl(&file)?, entries);
+            assert_eq!(get_metadata(&file)?.permissions().mode() & 0o777, 0o640);
+
+            // Clean-up
+            delete(&file)?;
+        })
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn base_acl_keeps_special_bits() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        Ok({
+            // Arrange
+            let dir = "base_acl_special";
+            std::fs::create_dir(dir)?;
+            std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o3755))?;
+
+            // Action
+            set_acl(
+                &dir,
+                &[AclEntry::allow(Principal::Owner).read().write().execute()],
+            )?;
+
+            // Assert
+            assert_eq!(get_metadata(&dir)?.permissions().mode() & 0o7777, 0o3700);
+
+            // Clean-up
+            delete(&dir)?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn named_acl() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "named_acl.txt";
+            write_string(&file, &"Hello, World!")?;
+            let reader = AclEntry::allow(Principal::User("0".to_string())).read();
+
+            // Action
+            set_acl(
+                &file,
+                &[AclEntry::allow(Principal::Owner).read().write(), reader],
+            )?;
+
+            // Assert
+            let entries = acl(&file)?;
+            assert!(
+                entries
+                    .iter()
+                    .any(|entry| matches!(&entry.principal, Principal::User(_))
+                        && entry.read
+                        && !entry.write),
+                "{entries:?}"
+            );
+
+            // Clean-up
+            delete(&file)?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn deny_is_rejected() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "deny_acl.txt";
+            write_string(&file, &"Hello, World!")?;
+
+            // Action
+            let result = set_acl(&file, &[AclEntry::deny(Principal::Everyone).read()]);
+
+            // Assert
+            assert!(result.is_err(), "deny entries should be rejected");
+
+            // Clean-up
+            delete(&file)?;
+        })
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn dacl() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "dacl.txt";
+            write_string(&file, &"Hello, World!")?;
+            let entries = vec![
+                AclEntry::deny(Principal::Everyone).execute(),
+                AclEntry::allow(Principal::Owner).read().write(),
+            ];
+
+            // Action
+            set_acl(&file, &entries)?;
+
+            // Assert
+            assert_eq!(acl(&file)?, entries);
+
+            // Clean-up
+            delete(&file)?;
+        })
+    }
+}
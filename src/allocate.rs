@@ -0,0 +1,71 @@
+use crate::*;
+
+/// Reserves disk space for a file up to `len` bytes, such as a database or VM image about to be filled in,
+/// so later writes can't fail for lack of space and the file isn't fragmented; a shorter file is extended with zeros,
+/// and a longer one is left as it is. Space is reserved with `posix_fallocate` on Linux and `F_PREALLOCATE` on macOS;
+/// elsewhere the file is only extended. This function will create the file **and its full directory path**
+/// if they don't exist.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `len`: **owned** `u64`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "allocate_example/disk.img";
+///
+///         file_access::allocate(&file_path, 1024 * 1024)?;
+///         assert_eq!(file_access::stat(&file_path)?.len(), 1024 * 1024);
+///
+///         // Clean-up:
+///         file_access::delete(&"allocate_example")?;
+///     })
+/// }
+/// ```
+pub fn allocate<Path: AsPath + ?Sized>(file_path: &Path, len: u64) -> Result<()> {
+    let allocate = || -> io::Result<()> {
+        if let Some(dir) = path_of(file_path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = File::options()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(file_path.as_path())?;
+        return internal::sys::allocate(&file, len);
+    };
+    allocate().context("allocate", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn allocate() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "allocate/data.db";
+            write_string(&file, &"header")?;
+
+            // Action
+            super::allocate(&file, 4096)?;
+            super::allocate(&file, 16)?;
+
+            // Assert
+            let bytes = read_bytes(&file)?;
+            assert_eq!(bytes.len(), 4096);
+            assert!(bytes.starts_with(b"header"));
+            assert!(bytes[6..].iter().all(|b| *b == 0));
+
+            // Clean-up
+            delete(&"allocate")?;
+        })
+    }
+}
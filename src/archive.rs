@@ -0,0 +1,405 @@
+use crate::*;
+#[cfg(feature = "tar")]
+use ::tar::{Archive, Builder};
+#[cfg(feature = "zip")]
+use ::zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+use std::path::Path as StdPath;
+
+// The name of an entry in an archive: its path relative to `base`, with `/` separators
+fn entry_name(base: &StdPath, path: &StdPath) -> io::Result<String> {
+    let relative = path.strip_prefix(base).unwrap_or(path);
+    let parts = relative
+        .components()
+        .map(|part| part.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>();
+
+    return parts.map(|parts| parts.join("/")).ok_or_else(|| {
+        io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("{} isn't a valid UTF-8 entry name", relative.display()),
+        )
+    });
+}
+
+// What an archive is built from: the source itself, then its descendants when it's a directory.
+// Entries are named relative to the source's parent, so the source's own name is their root.
+fn entries_of(source: &StdPath) -> io::Result<(PathBuf, Vec<PathBuf>)> {
+    let base = match source.file_name() {
+        Some(_) => source.parent().map(PathBuf::from).unwrap_or_default(),
+        None => source.to_path_buf(),
+    };
+    let mut entries = vec![];
+    if source.file_name().is_some() {
+        entries.push(source.to_path_buf());
+    }
+    if fs::symlink_metadata(source)?.is_dir() {
+        for entry in walk(source) {
+            entries.push(path_of(&entry?));
+        }
+    }
+    return Ok((base, entries));
+}
+
+/// Packs a file, or a directory and everything in it, into a zip archive, compressed with deflate.
+/// Entries are named relative to the source's parent, so the file or directory itself is their root,
+/// and symlinks are stored as links rather than followed. On Unix, permissions are kept too.
+/// This function will create the archive's full directory path if it doesn't exist,
+/// and will entirely replace the archive if it already exists. **Requires the `zip` feature**.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"zip_example/reports/q1.csv", &"id,total")?;
+///
+///         file_access::zip(&"zip_example/reports", &"zip_example/reports.zip")?;
+///         file_access::unzip(&"zip_example/reports.zip", &"zip_example/restored")?;
+///         assert_eq!(file_access::read_string(&"zip_example/restored/reports/q1.csv")?, "id,total");
+///
+///         // Clean-up:
+///         file_access::delete(&"zip_example")?;
+///     })
+/// }
+/// ```
+#[cfg(feature = "zip")]
+pub fn zip<From: AsPath + ?Sized, To: AsPath + ?Sized>(from: &From, to: &To) -> Result<()> {
+    let zip = || -> Result<u64> {
+        let (base, entries) = entries_of(from.as_path()).context("zip", from)?;
+        let mut archive = ZipWriter::new(mk_file(to).context("zip", to)?);
+        for path in entries {
+            let pack = |archive: &mut ZipWriter<File>| -> io::Result<()> {
+                let name = entry_name(&base, &path)?;
+                let metadata = fs::symlink_metadata(&path)?;
+                let options =
+                    SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+                #[cfg(unix)]
+                let options = {
+                    use std::os::unix::fs::PermissionsExt;
+                    options.unix_permissions(metadata.permissions().mode() & 0o7777)
+                };
+
+                if metadata.is_symlink() {
+                    let target = fs::read_link(&path)?;
+                    let target = entry_name(StdPath::new(""), &target)?;
+                    archive.add_symlink(name, target, options)?;
+                } else if metadata.is_dir() {
+                    archive.add_directory(name, options)?;
+                } else {
+                    archive.start_file(name, options)?;
+                    io::copy(&mut File::open(&path)?, archive)?;
+                }
+                return Ok(());
+            };
+            pack(&mut archive).context("zip", &path)?;
+        }
+        return archive
+            .finish()
+            .map_err(io::Error::from)
+            .and_then(|mut file| file.stream_position())
+            .context("zip", to);
+    };
+    return metered_with_context("zip", |written| (0, *written), zip).map(|_| ());
+}
+
+/// Unpacks a zip archive into a directory, keeping the paths of its entries below it, and on Unix
+/// their permissions and symlinks. Entries that would land outside the directory, such as `../escape`,
+/// fail the extraction. This function will create the directory's full path if it doesn't exist.
+/// **Requires the `zip` feature**.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to_dir`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>, failing with `ErrorKind::InvalidData` if the file isn't a valid zip archive
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"unzip_example/notes.txt", &"Hello, World!")?;
+///         file_access::zip(&"unzip_example/notes.txt", &"unzip_example/notes.zip")?;
+///
+///         file_access::unzip(&"unzip_example/notes.zip", &"unzip_example/out")?;
+///         assert_eq!(file_access::read_string(&"unzip_example/out/notes.txt")?, "Hello, World!");
+///
+///         // Clean-up:
+///         file_access::delete(&"unzip_example")?;
+///     })
+/// }
+/// ```
+#[cfg(feature = "zip")]
+pub fn unzip<From: AsPath + ?Sized, ToDir: AsPath + ?Sized>(
+    from: &From,
+    to_dir: &ToDir,
+) -> Result<()> {
+    let unzip = || -> Result<u64> {
+        let file = get_file(from).context("unzip", from)?;
+        let read = file.metadata().context("unzip", from)?.len();
+        let mut archive = ZipArchive::new(file)
+            .map_err(io::Error::from)
+            .context("unzip", from)?;
+        fs::create_dir_all(to_dir.as_path()).context("unzip", to_dir)?;
+
+        archive
+            .extract(to_dir.as_path())
+            .map_err(io::Error::from)
+            .context("unzip", from)?;
+        return Ok(read);
+    };
+    return metered_with_context("unzip", |read| (*read, 0), unzip).map(|_| ());
+}
+
+// Writes a tar stream of `from` into `writer`, handing the writer back once the archive is complete
+#[cfg(feature = "tar")]
+fn pack_tar<W: Write>(from: &StdPath, writer: W) -> Result<W> {
+    let (base, entries) = entries_of(from).context("tar", from)?;
+    let mut archive = Builder::new(writer);
+    archive.follow_symlinks(false);
+    for path in entries {
+        entry_name(&base, &path)
+            .and_then(|name| archive.append_path_with_name(&path, name))
+            .context("tar", &path)?;
+    }
+    return archive.into_inner().context("tar", from);
+}
+
+/// Packs a file, or a directory and everything in it, into a tar archive, keeping permissions, modification times
+/// and symlinks. Entries are named relative to the source's parent, so the file or directory itself is their root.
+/// This function will create the archive's full directory path if it doesn't exist,
+/// and will entirely replace the archive if it already exists. **Requires the `tar` feature**.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"tar_example/backup/db.sql", &"CREATE TABLE t;")?;
+///
+///         file_access::tar(&"tar_example/backup", &"tar_example/backup.tar")?;
+///         file_access::untar(&"tar_example/backup.tar", &"tar_example/restored")?;
+///         assert_eq!(file_access::read_string(&"tar_example/restored/backup/db.sql")?, "CREATE TABLE t;");
+///
+///         // Clean-up:
+///         file_access::delete(&"tar_example")?;
+///     })
+/// }
+/// ```
+#[cfg(feature = "tar")]
+pub fn tar<From: AsPath + ?Sized, To: AsPath + ?Sized>(from: &From, to: &To) -> Result<()> {
+    let tar = || -> Result<u64> {
+        let file = mk_file(to).context("tar", to)?;
+        let mut file = pack_tar(from.as_path(), file)?;
+        return file
+            .sync_all()
+            .and_then(|_| file.stream_position())
+            .context("tar", to);
+    };
+    return metered_with_context("tar", |written| (0, *written), tar).map(|_| ());
+}
+
+/// Packs a file, or a directory and everything in it, into a gzip-compressed tar archive (`.tar.gz`),
+/// like `tar` otherwise. **Requires the `tar` and `gzip` features**.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"tar_gz_example/logs/app.log", &"started")?;
+///
+///         file_access::tar_gz(&"tar_gz_example/logs", &"tar_gz_example/logs.tar.gz")?;
+///         file_access::untar(&"tar_gz_example/logs.tar.gz", &"tar_gz_example/restored")?;
+///         assert_eq!(file_access::read_string(&"tar_gz_example/restored/logs/app.log")?, "started");
+///
+///         // Clean-up:
+///         file_access::delete(&"tar_gz_example")?;
+///     })
+/// }
+/// ```
+#[cfg(all(feature = "tar", feature = "gzip"))]
+pub fn tar_gz<From: AsPath + ?Sized, To: AsPath + ?Sized>(from: &From, to: &To) -> Result<()> {
+    use flate2::{write::GzEncoder, Compression};
+
+    let tar_gz = || -> Result<u64> {
+        let file = mk_file(to).context("tar_gz", to)?;
+        return pack_tar(from.as_path(), GzEncoder::new(file, Compression::default()))?
+            .finish()
+            .and_then(|mut file| {
+                file.sync_all()?;
+                return file.stream_position();
+            })
+            .context("tar_gz", to);
+    };
+    return metered_with_context("tar_gz", |written| (0, *written), tar_gz).map(|_| ());
+}
+
+/// Unpacks a tar archive into a directory, keeping the paths of its entries below it, along with their permissions,
+/// modification times and symlinks. With the `gzip` feature, gzip-compressed archives (`.tar.gz`) are unpacked too.
+/// Entries that would land outside the directory, such as `../escape`, are skipped.
+/// This function will create the directory's full path if it doesn't exist. **Requires the `tar` feature**.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to_dir`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"untar_example/notes.txt", &"Hello, World!")?;
+///         file_access::tar(&"untar_example/notes.txt", &"untar_example/notes.tar")?;
+///
+///         file_access::untar(&"untar_example/notes.tar", &"untar_example/out")?;
+///         assert_eq!(file_access::read_string(&"untar_example/out/notes.txt")?, "Hello, World!");
+///
+///         // Clean-up:
+///         file_access::delete(&"untar_example")?;
+///     })
+/// }
+/// ```
+#[cfg(feature = "tar")]
+pub fn untar<From: AsPath + ?Sized, ToDir: AsPath + ?Sized>(
+    from: &From,
+    to_dir: &ToDir,
+) -> Result<()> {
+    let unpack = || -> io::Result<u64> {
+        let file = get_file(from)?;
+        let read = file.metadata()?.len();
+        let file = io::BufReader::new(file);
+        fs::create_dir_all(to_dir.as_path())?;
+        // Peeks at the leading bytes to tell whether the archive is compressed
+        #[cfg(feature = "gzip")]
+        let file = {
+            let mut file = file;
+            if file.fill_buf()?.starts_with(gzip::GZIP_MAGIC) {
+                let decoder = flate2::bufread::MultiGzDecoder::new(file);
+                return unpack_tar(decoder, to_dir.as_path()).map(|_| read);
+            }
+            file
+        };
+        return unpack_tar(file, to_dir.as_path()).map(|_| read);
+    };
+    metered("untar", |read| (*read, 0), unpack)
+        .map(|_| ())
+        .context("untar", from)
+}
+
+#[cfg(feature = "tar")]
+fn unpack_tar<R: Read>(reader: R, to_dir: &StdPath) -> io::Result<()> {
+    let mut archive = Archive::new(reader);
+    archive.set_preserve_permissions(true);
+    archive.set_preserve_mtime(true);
+
+    return archive.unpack(to_dir);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    #[cfg(feature = "zip")]
+    fn zip() -> Result<()> {
+        Ok({
+            // Arrange
+            let bytes: Vec<u8> = (0..=255).cycle().take(100_000).collect();
+            write_string(&"zip/src/a.txt", &"a")?;
+            write_bytes(&"zip/src/b/c.bin", &bytes)?;
+            fs::create_dir_all("zip/src/empty")?;
+            #[cfg(unix)]
+            std::os::unix::fs::symlink("a.txt", "zip/src/link")?;
+
+            // Action
+            super::zip(&"zip/src", &"zip/archives/src.zip")?;
+            super::zip(&"zip/src/a.txt", &"zip/archives/a.zip")?;
+            unzip(&"zip/archives/src.zip", &"zip/out")?;
+            unzip(&"zip/archives/a.zip", &"zip/single")?;
+
+            // Assert
+            assert_eq!(read_string(&"zip/out/src/a.txt")?, "a");
+            assert_eq!(read_bytes(&"zip/out/src/b/c.bin")?, bytes);
+            assert!(path_of(&"zip/out/src/empty").is_dir());
+            assert_eq!(read_string(&"zip/single/a.txt")?, "a");
+            #[cfg(unix)]
+            assert_eq!(fs::read_link("zip/out/src/link")?, PathBuf::from("a.txt"));
+            let invalid = unzip(&"zip/src/a.txt", &"zip/invalid");
+            assert_eq!(invalid.unwrap_err().kind(), ErrorKind::InvalidData);
+
+            // Clean-up
+            delete(&"zip")?;
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "tar")]
+    fn tar() -> Result<()> {
+        Ok({
+            // Arrange
+            use std::time::{Duration, SystemTime};
+            let bytes: Vec<u8> = (0..=255).cycle().take(100_000).collect();
+            let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+            write_string(&"tar/src/a.txt", &"a")?;
+            write_bytes(&"tar/src/b/c.bin", &bytes)?;
+            File::options()
+                .write(true)
+                .open("tar/src/a.txt")?
+                .set_modified(modified)?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions("tar/src/b/c.bin", fs::Permissions::from_mode(0o600))?;
+                std::os::unix::fs::symlink("a.txt", "tar/src/link")?;
+            }
+
+            // Action
+            super::tar(&"tar/src", &"tar/archives/src.tar")?;
+            untar(&"tar/archives/src.tar", &"tar/out")?;
+
+            // Assert
+            assert_eq!(read_string(&"tar/out/src/a.txt")?, "a");
+            assert_eq!(read_bytes(&"tar/out/src/b/c.bin")?, bytes);
+            assert_eq!(fs::metadata("tar/out/src/a.txt")?.modified()?, modified);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mode = fs::metadata("tar/out/src/b/c.bin")?.permissions().mode();
+                assert_eq!(mode & 0o777, 0o600);
+                assert_eq!(fs::read_link("tar/out/src/link")?, PathBuf::from("a.txt"));
+            }
+            #[cfg(feature = "gzip")]
+            {
+                super::tar_gz(&"tar/src", &"tar/archives/src.tar.gz")?;
+                untar(&"tar/archives/src.tar.gz", &"tar/out_gz")?;
+                assert_eq!(read_bytes(&"tar/out_gz/src/b/c.bin")?, bytes);
+                assert!(same_content(&"tar/out_gz/src/a.txt", &"tar/src/a.txt")?);
+            }
+
+            // Clean-up
+            delete(&"tar")?;
+        })
+    }
+}
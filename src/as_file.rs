@@ -4,8 +4,8 @@ pub trait AsFile {
     fn as_file(&self) -> FilePath;
 }
 
-impl<Path: AsRef<str>> AsFile for Path {
-    /// Converts an `AsRef<str>`, such as `String` or `&str`, into a `FilePath`.
+impl<Path: AsPath + ?Sized> AsFile for Path {
+    /// Converts an `AsPath`, such as `String`, `&str` or `PathBuf`, into a `FilePath`.
     ///
     /// # Examples
     /// ```
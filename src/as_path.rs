@@ -0,0 +1,86 @@
+use std::path::Path as StdPath;
+
+/// Anything a path can be borrowed from: everything that's `AsRef<std::path::Path>`, such as `str` and `String`
+/// like before, as well as `Path`, `PathBuf`, `OsStr` and `OsString`, and `FilePath`, so existing paths, including
+/// ones that aren't valid UTF-8, are used as they are. Every function taking a path accepts it. A type that's only
+/// `AsRef<str>`, such as `Box<str>` or a string newtype, is passed as `&*text` or `text.as_ref()`,
+/// or can implement `AsPath` itself.
+///
+/// # Examples
+/// ```
+/// use std::path::PathBuf;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: PathBuf = PathBuf::from("as_path_example").join("file.txt");
+///
+///         file_access::write_string(&file_path, &"Hello, World!")?;
+///         assert_eq!(file_access::read_string(&"as_path_example/file.txt")?, "Hello, World!");
+///
+///         // Clean-up:
+///         file_access::delete(&"as_path_example")?;
+///     })
+/// }
+/// ```
+pub trait AsPath {
+    fn as_path(&self) -> &StdPath;
+}
+
+impl<T: AsRef<StdPath> + ?Sized> AsPath for T {
+    fn as_path(&self) -> &StdPath {
+        self.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use std::io::Result;
+
+    #[test]
+    #[cfg(unix)]
+    fn non_utf8_path() -> Result<()> {
+        Ok({
+            // Arrange
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+            let file_path = PathBuf::from("non_utf8_path").join(OsStr::from_bytes(b"caf\xe9.txt"));
+
+            // Action
+            write_string(&file_path, &"Hello, World!")?;
+
+            // Assert
+            assert_eq!(read_string(&file_path)?, "Hello, World!");
+            assert_eq!(file_path.as_file().read_string()?, "Hello, World!");
+            let listed = list_dir(&"non_utf8_path", ListOptions::default())?;
+            assert_eq!(listed[0].as_path(), file_path);
+
+            // Clean-up
+            delete(&"non_utf8_path")?;
+        })
+    }
+
+    #[test]
+    fn any_as_ref_path() -> Result<()> {
+        Ok({
+            // Arrange
+            struct Name(String);
+            impl AsRef<StdPath> for Name {
+                fn as_ref(&self) -> &StdPath {
+                    StdPath::new(&self.0)
+                }
+            }
+            let name = Name(String::from("any_as_ref_path.txt"));
+            let boxed: Box<str> = Box::from("any_as_ref_path.txt");
+
+            // Action
+            write_string(&name, &"Hello, World!")?;
+
+            // Assert
+            assert_eq!(read_string(&*boxed)?, "Hello, World!");
+
+            // Clean-up
+            delete(&name)?;
+        })
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{
+    copy, delete,
+    internal::{parallel, traits::context::*},
+    rename, AsPath, Error, Result,
+};
+use std::{
+    fs::{self, Metadata},
+    path::PathBuf,
+};
+
+/// The outcome of a batch operation such as `copy_all` or `delete_all`, which carries on past failures:
+/// the paths it succeeded on, and those it failed on along with why, both in input order.
+///
+/// # Examples
+/// ```
+/// use file_access::batch;
+///
+/// let report = batch::delete_all(&["batch_report_example/missing.txt"]);
+/// for (path, error) in &report.failed {
+///     eprintln!("{}: {}", path.display(), error);
+/// }
+/// assert!(!report.is_ok());
+/// ```
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    /// The paths the operation succeeded on; for copies and renames, their sources.
+    pub succeeded: Vec<PathBuf>,
+    /// The paths the operation failed on, with the error; for copies and renames, their sources.
+    pub failed: Vec<(PathBuf, Error)>,
+}
+
+impl BatchReport {
+    /// Returns `true` if nothing failed.
+    pub fn is_ok(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+// Applies `f` to every item in order, recording each outcome under its path
+fn each<Item, Path: AsPath + ?Sized>(
+    items: &[Item],
+    path: impl Fn(&Item) -> &Path,
+    f: impl Fn(&Item) -> Result<()>,
+) -> BatchReport {
+    let mut report = BatchReport::default();
+    for item in items {
+        let path = path(item).as_path().to_path_buf();
+        match f(item) {
+            Ok(()) => report.succeeded.push(path),
+            Err(x) => report.failed.push((path, x)),
+        }
+    }
+    return report;
+}
+
+/// Queries metadata about many files or directories concurrently, spread over a pool of threads.
+/// Like `stat`, the files aren't opened, so this also works for files that can be stat-ed but not read,
+/// and symbolic links are followed.
+///
+/// # Parameters
+/// - `file_paths`: **borrowed** `[AsPath]` such as `[String]`, `[&str]` or `[PathBuf]`
+///
+/// # Returns
+/// `Vec<Result<Metadata>>`, in the same order as `file_paths`
+///
+/// # Examples
+/// ```
+/// use file_access::batch;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_paths = vec!["Cargo.toml", "src/lib.rs", "missing.txt"];
+///
+///         let metadata = batch::metadata_all(&file_paths);
+///         assert!(metadata[0].is_ok());
+///         assert!(metadata[2].is_err());
+///     })
+/// }
+/// ```
+pub fn metadata_all<Path: AsPath + Sync>(file_paths: &[Path]) -> Vec<Result<Metadata>> {
+    parallel::map(file_paths, |path| {
+        fs::metadata(path.as_path()).context("metadata_all", path)
+    })
+}
+
+/// Copies many files, like `copy`, one after another, carrying on past the ones that fail.
+///
+/// # Parameters
+/// - `pairs`: **borrowed** `[(AsPath, AsPath)]` of sources and destinations, such as `[(&str, &str)]`
+///
+/// # Returns
+/// `BatchReport`
+///
+/// # Examples
+/// ```
+/// use file_access::batch;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let pairs = [
+///             ("Cargo.toml", "copy_all_example/Cargo.toml"),
+///             ("missing.txt", "copy_all_example/missing.txt"),
+///         ];
+///
+///         let report = batch::copy_all(&pairs);
+///         assert_eq!(report.succeeded.len(), 1);
+///         assert_eq!(report.failed.len(), 1);
+///
+///         // Clean-up:
+///         file_access::delete(&"copy_all_example")?;
+///     })
+/// }
+/// ```
+pub fn copy_all<From: AsPath, To: AsPath>(pairs: &[(From, To)]) -> BatchReport {
+    each(pairs, |(from, _)| from, |(from, to)| copy(from, to))
+}
+
+/// Renames many files or directories, like `rename`, one after another, carrying on past the ones that fail.
+///
+/// # Parameters
+/// - `pairs`: **borrowed** `[(AsPath, AsPath)]` of sources and destinations, such as `[(&str, &str)]`
+///
+/// # Returns
+/// `BatchReport`
+///
+/// # Examples
+/// ```
+/// use file_access::batch;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"rename_all_example/a.txt", &"a")?;
+///
+///         let report = batch::rename_all(&[("rename_all_example/a.txt", "rename_all_example/b.txt")]);
+///         assert!(report.is_ok());
+///
+///         // Clean-up:
+///         file_access::delete(&"rename_all_example")?;
+///     })
+/// }
+/// ```
+pub fn rename_all<From: AsPath, To: AsPath>(pairs: &[(From, To)]) -> BatchReport {
+    each(pairs, |(from, _)| from, |(from, to)| rename(from, to))
+}
+
+/// Deletes many files or directories (recursively), like `delete`, one after another, carrying on past the ones that fail.
+///
+/// # Parameters
+/// - `file_paths`: **borrowed** `[AsPath]` such as `[String]`, `[&str]` or `[PathBuf]`
+///
+/// # Returns
+/// `BatchReport`
+///
+/// # Examples
+/// ```
+/// use file_access::batch;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"delete_all_example/a.txt", &"a")?;
+///
+///         let report = batch::delete_all(&["delete_all_example", "delete_all_example_missing"]);
+///         assert_eq!(report.failed.len(), 1);
+///     })
+/// }
+/// ```
+pub fn delete_all<Path: AsPath>(file_paths: &[Path]) -> BatchReport {
+    each(file_paths, |path| path, |path| delete(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn metadata_all() -> Result<()> {
+        Ok({
+            // Arrange
+            let mut paths = vec!["Cargo.toml".to_string(), "src".to_string()];
+            paths.extend((0..100).map(|i| format!("missing_{i}.txt")));
+
+            // Action
+            let metadata = super::metadata_all(&paths);
+
+            // Assert
+            assert_eq!(metadata.len(), paths.len());
+            assert!(metadata[0].as_ref().is_ok_and(Metadata::is_file));
+            assert!(metadata[1].as_ref().is_ok_and(Metadata::is_dir));
+            assert!(metadata[2..].iter().all(|m| m.is_err()));
+            assert!(super::metadata_all::<&str>(&[]).is_empty());
+        })
+    }
+
+    #[test]
+    fn copy_rename_delete_all() -> Result<()> {
+        Ok({
+            // Arrange
+            crate::write_string(&"batch_all/a.txt", &"a")?;
+            crate::write_string(&"batch_all/b.txt", &"b")?;
+
+            // Action
+            let copied = super::copy_all(&[
+                ("batch_all/a.txt", "batch_all/copies/a.txt"),
+                ("batch_all/missing.txt", "batch_all/copies/missing.txt"),
+                ("batch_all/b.txt", "batch_all/copies/b.txt"),
+            ]);
+            let renamed = super::rename_all(&[("batch_all/copies", "batch_all/renamed")]);
+            let deleted = super::delete_all(&["batch_all/a.txt", "batch_all/missing.txt"]);
+
+            // Assert
+            assert_eq!(
+                copied.succeeded,
+                [
+                    PathBuf::from("batch_all/a.txt"),
+                    PathBuf::from("batch_all/b.txt")
+                ]
+            );
+            assert_eq!(copied.failed.len(), 1);
+            assert_eq!(copied.failed[0].0, PathBuf::from("batch_all/missing.txt"));
+            assert!(renamed.is_ok());
+            assert_eq!(crate::read_string(&"batch_all/renamed/b.txt")?, "b");
+            assert_eq!(deleted.succeeded, [PathBuf::from("batch_all/a.txt")]);
+            assert!(!deleted.is_ok());
+
+            // Clean-up
+            crate::delete(&"batch_all")?;
+        })
+    }
+}
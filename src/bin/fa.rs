@@ -0,0 +1,276 @@
+//! `fa`, a command line front-end to the `file_access` library. Build it with the `cli` feature:
+//! `cargo install file_access --features cli`.
+#![allow(clippy::needless_return, clippy::unit_arg)]
+
+use file_access::{AsFile, AsPath, Error, FilePath, HashAlgorithm, Index, Result, SizeUnits};
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    io::{self, ErrorKind, Read, Write},
+    path::Path,
+    process::ExitCode,
+    thread,
+    time::Duration,
+};
+
+const USAGE: &str = "\
+usage: fa <command> [arguments]
+
+commands:
+  read <file>                   print a file
+  write <file> [text]           write text, or stdin, to a file
+  lines append <file> <line>... append lines to a file
+  copy [--verify] <from> <to>   copy a file, optionally checking the copy
+  hash <path>...                print the SHA-256 of files, and the tree hash of directories
+  watch [--interval <ms>] <dir> print files added (+), modified (~) and removed (-) below a directory
+  tree <dir>                    print a directory tree with sizes";
+
+fn usage() -> Error {
+    Error::new(ErrorKind::InvalidInput, USAGE)
+}
+
+// Prints the entries below `dir` indented by depth, sorted by name
+fn print_tree(out: &mut impl Write, dir: &FilePath, depth: usize) -> Result<()> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir.as_path())?.collect::<io::Result<_>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let file = entry.path().as_file();
+        let metadata = file.lstat()?;
+        if metadata.is_dir() {
+            writeln!(out, "{}{name}/", "  ".repeat(depth))?;
+            print_tree(out, &file, depth + 1)?;
+        } else {
+            let size = file_access::format_size(metadata.len(), SizeUnits::Binary);
+            writeln!(out, "{}{name} ({size})", "  ".repeat(depth))?;
+        }
+    }
+    return Ok(());
+}
+
+// Gets an argument that has to be text, such as a number or what to write, failing with the usage otherwise
+fn text(arg: &OsStr) -> Result<&str> {
+    arg.to_str().ok_or_else(usage)
+}
+
+// Runs a command, printing to `out`; `watch` runs until interrupted.
+// Paths are taken as they are, so names that aren't valid UTF-8 work too
+fn run(args: &[OsString], out: &mut impl Write) -> Result<()> {
+    let args: Vec<&OsStr> = args.iter().map(OsString::as_os_str).collect();
+    let (command, args) = args.split_first().ok_or_else(usage)?;
+    match (text(command)?, args) {
+        ("read", [file]) => Ok(write!(out, "{}", file.as_file().read_string()?)?),
+        ("write", [file]) => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text)?;
+            file.as_file().write_string(&text)
+        }
+        ("write", [file, line]) => file.as_file().write_string(text(line)?),
+        ("lines", [append, file, lines @ ..]) if *append == "append" && !lines.is_empty() => {
+            let lines = lines
+                .iter()
+                .map(|line| text(line))
+                .collect::<Result<Vec<_>>>()?;
+            file.as_file().append_lines(&lines)
+        }
+        ("copy", [from, to]) => from.as_file().copy_to(to),
+        ("copy", [verify, from, to]) if *verify == "--verify" => {
+            from.as_file().copy_verified_to(to)?;
+            Ok(writeln!(out, "verified {}", Path::new(to).display())?)
+        }
+        ("hash", paths) if !paths.is_empty() => {
+            // files in the `sha256sum` layout, so its output can be checked with `sha256sum -c`,
+            // and directories labelled, as their tree hash isn't the SHA-256 of any contents
+            for path in paths {
+                let file = path.as_file();
+                let shown = Path::new(path).display();
+                match file.is_dir() {
+                    true => writeln!(out, "tree {}  {shown}", file.tree_hash()?)?,
+                    false => writeln!(out, "{}  {shown}", file.hash(HashAlgorithm::Sha256)?)?,
+                }
+            }
+            Ok(())
+        }
+        ("watch", [dir]) => watch(out, dir, Duration::from_secs(1)),
+        ("watch", [interval, ms, dir]) if *interval == "--interval" => {
+            let ms = text(ms)?.parse().map_err(|_| usage())?;
+            watch(out, dir, Duration::from_millis(ms))
+        }
+        ("tree", [dir]) => {
+            writeln!(out, "{}/", Path::new(dir).display())?;
+            print_tree(out, &dir.as_file(), 1)
+        }
+        _ => Err(usage()),
+    }
+}
+
+// Polls a directory for changes forever
+fn watch(out: &mut impl Write, dir: &OsStr, interval: Duration) -> Result<()> {
+    let mut index = Index::scan(dir)?;
+    loop {
+        thread::sleep(interval);
+        print_changes(out, &mut index)?;
+    }
+}
+
+// Prints what changed since the index was last scanned or refreshed
+fn print_changes(out: &mut impl Write, index: &mut Index) -> Result<()> {
+    let changes = index.refresh()?;
+    for (sign, paths) in [
+        ('+', &changes.added),
+        ('~', &changes.modified),
+        ('-', &changes.removed),
+    ] {
+        for path in paths {
+            writeln!(out, "{sign} {}", path.display())?;
+        }
+    }
+    return Ok(out.flush()?);
+}
+
+fn main() -> ExitCode {
+    let args: Vec<OsString> = env::args_os().skip(1).collect();
+    match run(&args, &mut io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("fa: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    // Runs a command line, returning what it printed
+    fn fa(command_line: &str) -> Result<String> {
+        let args: Vec<OsString> = command_line.split(' ').map(OsString::from).collect();
+        let mut out = vec![];
+        run(&args, &mut out)?;
+
+        return Ok(String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn write_read_append_copy() -> Result<()> {
+        Ok({
+            // Action
+            fa("write fa_test/a.txt hello")?;
+            fa("lines append fa_test/a.txt world !")?;
+            let copied = fa("copy --verify fa_test/a.txt fa_test/b.txt")?;
+
+            // Assert
+            assert_eq!(fa("read fa_test/b.txt")?, "hello\nworld\n!");
+            assert_eq!(copied, "verified fa_test/b.txt\n");
+            assert!(fa("nonsense").is_err());
+
+            // Clean-up
+            file_access::delete(&"fa_test")?;
+        })
+    }
+
+    #[test]
+    fn hash_and_tree() -> Result<()> {
+        Ok({
+            // Arrange
+            file_access::write_string(&"fa_tree/x/a.txt", &"hello")?;
+            file_access::write_string(&"fa_tree/y/a.txt", &"hello")?;
+
+            // Action
+            let hashes = fa("hash fa_tree/x fa_tree/y fa_tree/x/a.txt")?;
+            let tree = fa("tree fa_tree")?;
+
+            // Assert
+            let hashes: Vec<&str> = hashes.lines().collect();
+            assert!(hashes[0].starts_with("tree ") && hashes[0].ends_with("  fa_tree/x"));
+            assert_eq!(hashes[0][..69], hashes[1][..69]);
+            assert_eq!(
+                hashes[2],
+                "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824  fa_tree/x/a.txt"
+            );
+            assert_eq!(
+                tree,
+                "fa_tree/\n  x/\n    a.txt (5 B)\n  y/\n    a.txt (5 B)\n"
+            );
+
+            // Clean-up
+            file_access::delete(&"fa_tree")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn tree_non_utf8() -> Result<()> {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
+
+        Ok({
+            // Arrange
+            let dir = Path::new("fa_tree_non_utf8").join(OsStr::from_bytes(b"d\xff"));
+            file_access::write_string(&dir.join("a.txt"), &"hello")?;
+
+            // Action
+            let tree = fa("tree fa_tree_non_utf8")?;
+
+            // Assert
+            assert_eq!(tree, "fa_tree_non_utf8/\n  d\u{fffd}/\n    a.txt (5 B)\n");
+
+            // Clean-up
+            file_access::delete(&"fa_tree_non_utf8")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn non_utf8_arguments() -> Result<()> {
+        use std::os::unix::ffi::OsStrExt;
+
+        Ok({
+            // Arrange
+            let file = Path::new("fa_non_utf8").join(OsStr::from_bytes(b"caf\xe9.txt"));
+            file_access::write_string(&file, &"hello")?;
+            let args = |words: &[&OsStr]| words.iter().map(OsString::from).collect::<Vec<_>>();
+
+            // Action
+            let mut out = vec![];
+            run(&args(&["read".as_ref(), file.as_os_str()]), &mut out)?;
+            let text = run(
+                &args(&[
+                    "write".as_ref(),
+                    file.as_os_str(),
+                    OsStr::from_bytes(b"\xff"),
+                ]),
+                &mut vec![],
+            );
+
+            // Assert
+            assert_eq!(out, b"hello");
+            assert_eq!(text.unwrap_err().kind(), ErrorKind::InvalidInput);
+
+            // Clean-up
+            file_access::delete(&"fa_non_utf8")?;
+        })
+    }
+
+    #[test]
+    fn watch() -> Result<()> {
+        Ok({
+            // Arrange
+            file_access::write_string(&"fa_watch/old.txt", &"old")?;
+            let mut index = Index::scan(&"fa_watch")?;
+            file_access::write_string(&"fa_watch/new.txt", &"new")?;
+
+            // Action
+            let mut out = vec![];
+            print_changes(&mut out, &mut index)?;
+
+            // Assert
+            assert_eq!(String::from_utf8(out).unwrap(), "+ new.txt\n");
+
+            // Clean-up
+            file_access::delete(&"fa_watch")?;
+        })
+    }
+}
@@ -0,0 +1,181 @@
+use crate::*;
+use std::{
+    fmt::{self, Display, Formatter},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// A flag that stops long operations partway through once it's set, such as from a Ctrl-C handler.
+/// Clones share the flag, so one can be moved into the handler while another is passed to the operation:
+/// directly, as a `Progress` to `copy_with_progress`, `copy_dir_with_progress`, `hash_with_progress` and
+/// `sync_dir_with_progress`, along with a progress hook through `with_progress`, or to `Walk::cancel_on`.
+///
+/// An operation checks the flag before each chunk it reads and each file it starts, and once it's set fails
+/// with an `Interrupted` `Error` wrapping `Cancelled`, which `is_retryable` doesn't retry. What's done is kept:
+/// the files copied before are left in place, and the file being copied is deleted from the destination rather than
+/// left truncated, so running a sync again finishes the job.
+///
+/// # Examples
+/// ```
+/// use file_access::{CancelToken, Cancelled};
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let token = CancelToken::new();
+///         token.clone().cancel(); // such as from a Ctrl-C handler
+///
+///         let error = file_access::copy_dir_with_progress(&"src", &"cancel_token_example/src", &mut token.clone())
+///             .unwrap_err();
+///         assert!(error.get_ref().and_then(|e| e.downcast_ref::<Cancelled>()).is_some());
+///
+///         // Clean-up:
+///         file_access::delete(&"cancel_token_example")?;
+///     })
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// Creates a token that isn't cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the operations watching this token, or any clone of it.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` once `cancel` was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Pairs the token with a progress hook, to pass both to an operation such as `copy_with_progress`.
+    pub fn with_progress<P: Progress>(&self, progress: P) -> Cancellable<P> {
+        Cancellable {
+            progress,
+            token: self.clone(),
+        }
+    }
+}
+
+impl Progress for CancelToken {
+    fn update(&mut self, _: &ProgressUpdate) {}
+
+    fn is_cancelled(&self) -> bool {
+        CancelToken::is_cancelled(self)
+    }
+}
+
+/// A progress hook that also stops its operation when a `CancelToken` is cancelled, as made by
+/// `CancelToken::with_progress`.
+///
+/// # Examples
+/// ```
+/// use file_access::{CancelToken, ProgressUpdate};
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let token = CancelToken::new();
+///         let mut progress = token.with_progress(|update: &ProgressUpdate| eprint!("\r{} bytes", update.bytes));
+///         file_access::copy_with_progress(&"Cargo.toml", &"cancellable_example/Cargo.toml", &mut progress)?;
+///
+///         // Clean-up:
+///         file_access::delete(&"cancellable_example")?;
+///     })
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cancellable<P: Progress> {
+    progress: P,
+    token: CancelToken,
+}
+
+impl<P: Progress> Progress for Cancellable<P> {
+    fn update(&mut self, update: &ProgressUpdate) {
+        self.progress.update(update)
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled() || self.progress.is_cancelled()
+    }
+}
+
+/// The error inside the `Interrupted` `Error` returned by an operation stopped by a `CancelToken`, with what it got
+/// done before; see `CancelToken` for the state it leaves behind. Get it with
+/// `error.get_ref().and_then(|e| e.downcast_ref::<Cancelled>())`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cancelled {
+    /// The bytes copied or hashed before the operation stopped.
+    pub bytes: u64,
+    /// The files done before the operation stopped, or the entries returned for a walk.
+    pub files: u64,
+}
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cancelled after {} files and {} bytes",
+            self.files, self.bytes
+        )
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+impl From<Cancelled> for io::Error {
+    fn from(cancelled: Cancelled) -> Self {
+        io::Error::new(ErrorKind::Interrupted, cancelled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn cancel_token() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"cancel_token/src/a.txt", &"a")?;
+            write_string(&"cancel_token/src/b.txt", &"b")?;
+            let token = CancelToken::new();
+            let mut copied = vec![];
+
+            // Action
+            let error = copy_dir_with_progress(
+                &"cancel_token/src",
+                &"cancel_token/dest",
+                &mut token.with_progress(|update: &ProgressUpdate| {
+                    if update.files == 1 {
+                        copied.push(update.path.to_path_buf());
+                        token.cancel();
+                    }
+                }),
+            )
+            .unwrap_err();
+
+            // Assert
+            assert_eq!(error.kind(), ErrorKind::Interrupted);
+            assert!(!is_retryable(&error));
+            assert_eq!(
+                error.get_ref().and_then(|e| e.downcast_ref::<Cancelled>()),
+                Some(&Cancelled { bytes: 1, files: 1 })
+            );
+            assert_eq!(copied, [PathBuf::from("cancel_token/src/a.txt")]);
+            assert_eq!(read_string(&"cancel_token/dest/a.txt")?, "a");
+            assert!(!PathBuf::from("cancel_token/dest/b.txt").exists());
+
+            // Clean-up
+            delete(&"cancel_token")?;
+        })
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{internal::sha256::hex, *};
+use sha2::{Digest, Sha256};
+use std::fmt::{self, Display, Formatter};
+
+// Separates the text from the digest of the footer written by `write_string_checked`
+const FOOTER: &str = "\n-- sha256 ";
+// The footer, followed by 64 hex digits and a newline
+const FOOTER_LEN: usize = FOOTER.len() + 64 + 1;
+
+/// The error inside the `InvalidData` `Error` returned by `read_string_checked`
+/// when a file doesn't match the checksum in its footer, or has no footer. Get it with
+/// `error.get_ref().and_then(|e| e.downcast_ref::<Corrupted>())`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Corrupted {
+    /// The file that didn't match its checksum.
+    pub path: PathBuf,
+    /// The digest recorded in the footer, or `None` if the footer is missing or malformed.
+    pub expected: Option<String>,
+    /// The digest of the contents as read.
+    pub actual: String,
+}
+
+impl Display for Corrupted {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.expected {
+            Some(expected) => write!(
+                f,
+                "{} is corrupted: expected sha256 {expected}, found {}",
+                self.path.display(),
+                self.actual
+            ),
+            None => write!(
+                f,
+                "{} is corrupted: its checksum footer is missing",
+                self.path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Corrupted {}
+
+fn sha256(bytes: &[u8]) -> String {
+    hex(&Sha256::digest(bytes))
+}
+
+/// Writes text to a file followed by a footer line holding its SHA-256 checksum, for `read_string_checked` to verify.
+/// This function will create the file **and its full directory path** if they don't exist,
+/// and will entirely replace the contents. **Requires the `hash` feature**, which is on by default.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "write_string_checked_example/state.json";
+///         let file_path: String = String::from(file_path);
+///
+///         file_access::write_string_checked(&file_path, &r#"{"step": 3}"#)?;
+///         assert_eq!(file_access::read_string_checked(&file_path)?, r#"{"step": 3}"#);
+///
+///         // Clean-up:
+///         file_access::delete(&"write_string_checked_example")?;
+///     })
+/// }
+/// ```
+pub fn write_string_checked<Path: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    text: &Text,
+) -> Result<()> {
+    let text = text.as_ref();
+    let footer = format!("{FOOTER}{}\n", sha256(text.as_bytes()));
+
+    return write_string(file_path, &(text.to_string() + &footer));
+}
+
+/// Reads text written by `write_string_checked`, verifying it against the checksum in its footer,
+/// which isn't part of the returned text. Returns an `InvalidData` error wrapping `Corrupted`
+/// when the checksum doesn't match, the footer is missing or the text isn't valid UTF-8.
+/// **Requires the `hash` feature**, which is on by default.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`String`>
+///
+/// # Examples
+/// ```
+/// use file_access::Corrupted;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "read_string_checked_example.txt";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"no footer")?;
+///
+///         let error = file_access::read_string_checked(&file_path).unwrap_err();
+///         assert!(error.get_ref().and_then(|e| e.downcast_ref::<Corrupted>()).is_some());
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn read_string_checked<Path: AsPath + ?Sized>(file_path: &Path) -> Result<String> {
+    let mut contents = read_bytes(file_path)?;
+
+    let split = contents.len().saturating_sub(FOOTER_LEN);
+    let expected = match std::str::from_utf8(&contents[split..]) {
+        Ok(footer) if footer.starts_with(FOOTER) && footer.ends_with('\n') => {
+            Some(footer[FOOTER.len()..FOOTER_LEN - 1].to_string())
+        }
+        _ => None,
+    };
+    if expected.is_some() {
+        contents.truncate(split);
+    }
+
+    // Bytes that aren't valid UTF-8 are damage like any other, even if they happen to match the footer
+    let actual = sha256(&contents);
+    let corrupted = |expected| Corrupted {
+        path: path_of(file_path),
+        expected,
+        actual: actual.clone(),
+    };
+    if expected.as_ref() != Some(&actual) {
+        return Err(Error::new(ErrorKind::InvalidData, corrupted(expected)))
+            .context("read_string_checked", file_path);
+    }
+    return String::from_utf8(contents)
+        .map_err(|_| Error::new(ErrorKind::InvalidData, corrupted(expected)))
+        .context("read_string_checked", file_path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn write_and_read_checked() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "checked.txt";
+            let text = "Hello, World!\n";
+
+            // Action
+            write_string_checked(&file_path, &text)?;
+
+            // Assert
+            assert_eq!(read_string_checked(&file_path)?, text);
+            assert_eq!(
+                read_string(&file_path)?,
+                "Hello, World!\n\n-- sha256 c98c24b677eff44860afea6f493bbaec5bb1c4cbb209c6fc2bbb47f66ff2ad31\n"
+            );
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+
+    #[test]
+    fn read_checked_corrupted() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "checked_corrupted.txt";
+            write_string_checked(&file_path, &"Hello, World!")?;
+            let flipped = read_string(&file_path)?.replacen('W', "w", 1);
+            write_string(&file_path, &flipped)?;
+
+            // Action
+            let error = read_string_checked(&file_path).unwrap_err();
+
+            // Assert
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+            let corrupted = error
+                .get_ref()
+                .and_then(|e| e.downcast_ref::<Corrupted>())
+                .unwrap();
+            assert_eq!(corrupted.path, PathBuf::from(file_path));
+            assert_eq!(corrupted.actual, super::sha256(b"Hello, world!"));
+            assert!(corrupted.expected.is_some());
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+
+    #[test]
+    fn read_checked_not_utf8() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "checked_not_utf8.txt";
+            write_string_checked(&file_path, &"Hello, World!")?;
+            let mut bytes = read_bytes(&file_path)?;
+            bytes[0] = 0xff;
+            write_bytes(&file_path, &bytes)?;
+
+            // Action
+            let error = read_string_checked(&file_path).unwrap_err();
+
+            // Assert
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+            let corrupted = error
+                .get_ref()
+                .and_then(|e| e.downcast_ref::<Corrupted>())
+                .unwrap();
+            assert_eq!(corrupted.actual, super::sha256(b"\xffello, World!"));
+            assert!(corrupted.expected.is_some());
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+}
@@ -0,0 +1,92 @@
+use crate::*;
+
+/// The contents of a file read lazily in chunks of a fixed size, as started by `read_chunks`, each one read when
+/// it's reached; every chunk is full except the last. Reading stops at the end of the file or after an error.
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let mut sum: u64 = 0;
+///         for chunk in file_access::read_chunks(&"Cargo.toml", 64 * 1024)? {
+///             sum += chunk?.iter().map(|b| *b as u64).sum::<u64>();
+///         }
+///         println!("byte sum: {sum}");
+///     })
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Chunks {
+    file: Option<File>,
+    path: PathBuf,
+    chunk_size: usize,
+}
+
+impl Iterator for Chunks {
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let file = self.file.as_mut()?;
+        let mut chunk = Vec::with_capacity(self.chunk_size);
+        match file.take(self.chunk_size as u64).read_to_end(&mut chunk) {
+            Ok(0) => {
+                self.file = None;
+                return None;
+            }
+            Ok(_) => return Some(Ok(chunk)),
+            Err(x) => {
+                self.file = None;
+                return Some(Err(x).context("read_chunks", &self.path));
+            }
+        }
+    }
+}
+
+/// Reads a file in chunks of `chunk_size` bytes through an iterator, such as to hash, upload or scan a file
+/// of any size with bounded memory; the file is opened now, and each chunk read when it's reached.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `chunk_size`: **owned** `usize`, the size of every chunk but the last
+///
+/// # Returns
+/// Result<`Chunks`>, failing with `ErrorKind::InvalidInput` if `chunk_size` is `0`
+pub fn read_chunks<Path: AsPath + ?Sized>(file_path: &Path, chunk_size: usize) -> Result<Chunks> {
+    if chunk_size == 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "the chunk size is 0"))
+            .context("read_chunks", file_path);
+    }
+    let file = get_file(file_path).context("read_chunks", file_path)?;
+
+    return Ok(Chunks {
+        file: Some(file),
+        path: path_of(file_path),
+        chunk_size,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn read_chunks() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "read_chunks.bin";
+            write_bytes(&file, &b"abcdefghij")?;
+
+            // Action
+            let chunks = super::read_chunks(&file, 4)?.collect::<crate::Result<Vec<_>>>()?;
+            let zero = super::read_chunks(&file, 0);
+
+            // Assert
+            assert_eq!(chunks, [&b"abcd"[..], b"efgh", b"ij"]);
+            assert_eq!(zero.unwrap_err().kind(), ErrorKind::InvalidInput);
+
+            // Clean-up
+            delete(&file)?;
+        })
+    }
+}
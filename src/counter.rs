@@ -0,0 +1,180 @@
+use crate::*;
+
+/// A persistent counter, such as for sequence or invoice numbers, that is safe to share across threads and processes:
+/// every operation holds an exclusive lock on a `<path>.lock` sidecar file, and the value is replaced atomically
+/// and synced to disk, so readers never see a torn write and a crash or power loss never loses more than
+/// the operation in progress.
+///
+/// # Examples
+/// ```
+/// use file_access::counter::Counter;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let counter = Counter::open(&"counter_example/invoices")?;
+///         assert_eq!(counter.increment()?, 1);
+///         assert_eq!(counter.increment()?, 2);
+///         assert_eq!(counter.get()?, 2);
+///
+///         // Clean-up:
+///         file_access::delete(&"counter_example")?;
+///     })
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Counter {
+    path: PathBuf,
+    lock_path: PathBuf,
+}
+
+impl Counter {
+    /// Opens the counter stored at a path, creating its full directory path if it doesn't exist.
+    /// A counter that was never incremented is `0`.
+    ///
+    /// # Parameters
+    /// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`Counter`>
+    pub fn open<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Self> {
+        let path = path_of(file_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Counter::open", file_path)?;
+        }
+        let mut lock_path = path.clone().into_os_string();
+        lock_path.push(".lock");
+
+        return Ok(Self {
+            path,
+            lock_path: PathBuf::from(lock_path),
+        });
+    }
+
+    // Runs `f` while holding the lock
+    fn locked<T>(&self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        let _lock = internal::lock::exclusive(&self.lock_path)?;
+        return f();
+    }
+
+    fn read(&self) -> Result<u64> {
+        match fs::read_to_string(&self.path) {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(0),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn write(&self, value: u64) -> Result<()> {
+        Ok(write_via_rename(
+            &self.path,
+            format!("{value}\n").as_bytes(),
+            true,
+        )?)
+    }
+
+    /// Adds one to the counter.
+    ///
+    /// # Returns
+    /// Result<`u64`> of the new value
+    pub fn increment(&self) -> Result<u64> {
+        self.locked(|| {
+            let value = self
+                .read()?
+                .checked_add(1)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, "the counter would overflow"))?;
+            self.write(value)?;
+
+            return Ok(value);
+        })
+        .context("Counter::increment", &self.path)
+    }
+
+    /// Reads the current value of the counter.
+    ///
+    /// # Returns
+    /// Result<`u64`>
+    pub fn get(&self) -> Result<u64> {
+        self.locked(|| self.read())
+            .context("Counter::get", &self.path)
+    }
+
+    /// Sets the counter back to `0`.
+    ///
+    /// # Returns
+    /// Result<`()`>
+    pub fn reset(&self) -> Result<()> {
+        self.locked(|| self.write(0))
+            .context("Counter::reset", &self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Result, thread};
+
+    #[test]
+    fn increment_get_reset() -> Result<()> {
+        Ok({
+            // Arrange
+            let counter = Counter::open(&"counter_basics/count")?;
+
+            // Action
+            let first = counter.increment()?;
+            let second = counter.increment()?;
+
+            // Assert
+            assert_eq!((first, second), (1, 2));
+            assert_eq!(Counter::open(&"counter_basics/count")?.get()?, 2);
+            counter.reset()?;
+            assert_eq!(counter.get()?, 0);
+            write_string(&"counter_basics/count", &"not a number")?;
+            let error = counter.increment().unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+            assert_eq!(error.operation, Some("Counter::increment"));
+            assert_eq!(error.path, Some(PathBuf::from("counter_basics/count")));
+
+            // Clean-up
+            delete(&"counter_basics")?;
+        })
+    }
+
+    #[test]
+    fn concurrent_increments() -> Result<()> {
+        Ok({
+            // Arrange
+            let counter = Counter::open(&"counter_concurrent/count")?;
+
+            // Action
+            let mut values: Vec<u64> = thread::scope(|scope| {
+                let workers: Vec<_> = (0..4)
+                    .map(|_| {
+                        // Each thread opens its own counter, as another process would
+                        scope.spawn(|| -> Result<Vec<u64>> {
+                            let counter = Counter::open(&"counter_concurrent/count")?;
+                            Ok((0..10)
+                                .map(|_| counter.increment())
+                                .collect::<crate::Result<_>>()?)
+                        })
+                    })
+                    .collect();
+                workers
+                    .into_iter()
+                    .map(|worker| worker.join().unwrap())
+                    .collect::<Result<Vec<_>>>()
+            })?
+            .concat();
+            values.sort_unstable();
+
+            // Assert
+            assert_eq!(values, (1..=40).collect::<Vec<_>>());
+            assert_eq!(counter.get()?, 40);
+
+            // Clean-up
+            delete(&"counter_concurrent")?;
+        })
+    }
+}
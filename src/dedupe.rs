@@ -0,0 +1,308 @@
+use crate::*;
+use sha2::{Digest, Sha256};
+
+// The start of a saved set's header, followed by its bit count and hash count
+const HEADER: &str = "file_access seen-set v1";
+
+/// A persistent set of seen keys, such as processed IDs in crawl or ingest pipelines, backed by a bloom filter:
+/// it takes a fixed amount of space however many keys are inserted, never forgets a key,
+/// but may claim to contain a key it never saw, at about the false-positive rate it was sized for.
+/// Inserts are kept in memory until `flush` (or until the set is dropped), which replaces the file atomically
+/// and syncs it to disk. Processes can share a set: each flush holds an exclusive lock on a `<path>.lock` sidecar file
+/// and merges in the keys the others saved since, so no insert is lost, though a process only sees the others' keys
+/// after its own next flush. **Requires the `hash` feature**, which is on by default.
+///
+/// # Examples
+/// ```
+/// use file_access::dedupe::SeenSet;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let mut seen = SeenSet::open(&"seen_set_example/ids.bloom", 100_000, 0.001)?;
+///         for id in ["a1", "b2", "a1"] {
+///             if seen.insert(&id) {
+///                 println!("processing {id}");
+///             }
+///         }
+///         seen.flush()?;
+///
+///         // Clean-up:
+///         drop(seen);
+///         file_access::delete(&"seen_set_example")?;
+///     })
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SeenSet {
+    path: PathBuf,
+    bits: Vec<u8>,
+    hashes: u32,
+    dirty: bool,
+}
+
+fn invalid<Message: AsRef<str>>(message: Message) -> Error {
+    Error::new(ErrorKind::InvalidData, message.as_ref())
+}
+
+impl SeenSet {
+    /// Opens the set saved at a path, or creates an empty one sized to hold `capacity` keys
+    /// with a false-positive rate of `fp_rate` (such as `0.01` for 1%). An existing set keeps the size it was created with.
+    ///
+    /// # Parameters
+    /// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    /// - `capacity`: **owned** `u64`
+    /// - `fp_rate`: **owned** `f64`, between `0` and `1` exclusive
+    ///
+    /// # Returns
+    /// Result<`SeenSet`>
+    pub fn open<Path: AsPath + ?Sized>(
+        file_path: &Path,
+        capacity: u64,
+        fp_rate: f64,
+    ) -> Result<Self> {
+        let path = path_of(file_path);
+        match fs::read(&path) {
+            Ok(bytes) => return Self::load(path, &bytes).context("SeenSet::open", file_path),
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e).context("SeenSet::open", file_path),
+        }
+        if !(fp_rate > 0.0 && fp_rate < 1.0) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "the false-positive rate must be between 0 and 1",
+            ))
+            .context("SeenSet::open", file_path);
+        }
+
+        // The optimal bloom filter size and hash count, see https://en.wikipedia.org/wiki/Bloom_filter
+        let capacity = capacity.max(1) as f64;
+        let bits = (-capacity * fp_rate.ln() / (2f64.ln() * 2f64.ln())).ceil();
+        let hashes = (bits / capacity * 2f64.ln()).round().max(1.0);
+
+        return Ok(Self {
+            path,
+            bits: vec![0; (bits as usize).div_ceil(8)],
+            hashes: hashes as u32,
+            dirty: true,
+        });
+    }
+
+    fn load(path: PathBuf, bytes: &[u8]) -> Result<Self> {
+        let newline = bytes
+            .iter()
+            .position(|&b| b == b'\n')
+            .ok_or_else(|| invalid("not a seen-set file"))?;
+        let header =
+            std::str::from_utf8(&bytes[..newline]).map_err(|_| invalid("not a seen-set file"))?;
+        let numbers = header
+            .strip_prefix(HEADER)
+            .ok_or_else(|| invalid(format!("not a seen-set header: {header:?}")))?;
+        let numbers: Vec<u64> = numbers
+            .split_whitespace()
+            .map(|n| {
+                n.parse()
+                    .map_err(|_| invalid(format!("not a seen-set header: {header:?}")))
+            })
+            .collect::<Result<_>>()?;
+        let bits = bytes[newline + 1..].to_vec();
+        let [len, hashes] = numbers[..] else {
+            return Err(invalid(format!("not a seen-set header: {header:?}")));
+        };
+        if len == 0 || len != bits.len() as u64 || hashes == 0 {
+            return Err(invalid("the seen-set file is truncated or corrupted"));
+        }
+
+        return Ok(Self {
+            path,
+            bits,
+            hashes: hashes as u32,
+            dirty: false,
+        });
+    }
+
+    // Gets the bit positions of a key, by double hashing (https://doi.org/10.1002/rsa.20208)
+    fn positions<Key: AsRef<[u8]>>(&self, key: &Key) -> impl Iterator<Item = usize> {
+        let digest = Sha256::digest(key.as_ref());
+        let h1 = u64::from_le_bytes(digest[..8].try_into().unwrap());
+        let h2 = u64::from_le_bytes(digest[8..16].try_into().unwrap()) | 1;
+        let len = self.bits.len() as u64 * 8;
+
+        (0..self.hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % len) as usize)
+    }
+
+    /// Adds a key to the set.
+    ///
+    /// # Parameters
+    /// - `key`: **borrowed** `AsRef<[u8]>` such as `&str`, `String` or `Vec<u8>`
+    ///
+    /// # Returns
+    /// `bool`: `true` if the key wasn't seen before, `false` if it (probably) was
+    pub fn insert<Key: AsRef<[u8]>>(&mut self, key: &Key) -> bool {
+        let mut inserted = false;
+        for position in self.positions(key).collect::<Vec<_>>() {
+            let (byte, mask) = (position / 8, 1 << (position % 8));
+            if self.bits[byte] & mask == 0 {
+                self.bits[byte] |= mask;
+                inserted = true;
+            }
+        }
+        self.dirty |= inserted;
+
+        return inserted;
+    }
+
+    /// Checks whether a key was (probably) seen before.
+    ///
+    /// # Parameters
+    /// - `key`: **borrowed** `AsRef<[u8]>` such as `&str`, `String` or `Vec<u8>`
+    ///
+    /// # Returns
+    /// `bool`
+    pub fn contains<Key: AsRef<[u8]>>(&self, key: &Key) -> bool {
+        self.positions(key)
+            .all(|position| self.bits[position / 8] & (1 << (position % 8)) != 0)
+    }
+
+    /// Saves the set, creating its full directory path if it doesn't exist, by atomically replacing the file.
+    /// Keys another process saved to the file since are merged in first, under an exclusive lock on `<path>.lock`.
+    /// Does nothing when nothing was inserted since the last flush.
+    ///
+    /// # Returns
+    /// Result<`()`>, failing with `ErrorKind::InvalidData` if the saved set is corrupted or was created with another size
+    pub fn flush(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let mut save = || -> Result<()> {
+            if let Some(parent) = self.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut lock_path = self.path.clone().into_os_string();
+            lock_path.push(".lock");
+            let _lock = internal::lock::exclusive(lock_path.as_ref())?;
+            match fs::read(&self.path) {
+                Ok(bytes) => {
+                    let saved = Self::load(self.path.clone(), &bytes)?;
+                    if (saved.bits.len(), saved.hashes) != (self.bits.len(), self.hashes) {
+                        return Err(invalid("the saved seen-set was created with another size"));
+                    }
+                    for (byte, saved) in self.bits.iter_mut().zip(&saved.bits) {
+                        *byte |= saved;
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+            let mut bytes = format!("{HEADER} {} {}\n", self.bits.len(), self.hashes).into_bytes();
+            bytes.extend(&self.bits);
+            return Ok(write_via_rename(&self.path, &bytes, true)?);
+        };
+        save().context("SeenSet::flush", &self.path)?;
+        self.dirty = false;
+
+        return Ok(());
+    }
+}
+
+impl Drop for SeenSet {
+    // Flushes on a best-effort basis; call `flush` to handle errors
+    fn drop(&mut self) {
+        _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn insert_and_contains() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "seen_set/ids.bloom";
+            let mut seen = SeenSet::open(&file_path, 1_000, 0.01)?;
+
+            // Action
+            let first = seen.insert(&"id-1");
+            let again = seen.insert(&"id-1");
+            seen.flush()?;
+            let reopened = SeenSet::open(&file_path, 0, 0.5)?;
+
+            // Assert
+            assert!(first && !again);
+            assert!(reopened.contains(&"id-1"));
+            assert!(!reopened.contains(&"id-2"));
+            assert_eq!(
+                reopened.bits.len(),
+                seen.bits.len(),
+                "an existing set keeps its size"
+            );
+            write_string(&"seen_set/other.bloom", &"garbage")?;
+            let error = SeenSet::open(&"seen_set/other.bloom", 1_000, 0.01).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+            assert_eq!(error.operation, Some("SeenSet::open"));
+            assert_eq!(error.path, Some(PathBuf::from("seen_set/other.bloom")));
+            write_string(&"seen_set/empty.bloom", &format!("{HEADER} 0 3\n"))?;
+            let empty = SeenSet::open(&"seen_set/empty.bloom", 1_000, 0.01).unwrap_err();
+            assert_eq!(empty.kind(), ErrorKind::InvalidData);
+
+            // Clean-up
+            drop((seen, reopened));
+            delete(&"seen_set")?;
+        })
+    }
+
+    #[test]
+    fn false_positive_rate() -> Result<()> {
+        Ok({
+            // Arrange
+            let mut seen = SeenSet::open(&"seen_set_fp_rate/ids.bloom", 10_000, 0.01)?;
+            for i in 0..10_000 {
+                seen.insert(&format!("seen-{i}"));
+            }
+
+            // Action
+            let false_positives = (0..10_000)
+                .filter(|i| seen.contains(&format!("unseen-{i}")))
+                .count();
+
+            // Assert
+            assert!(false_positives < 200, "{false_positives} false positives");
+            assert!((0..10_000).all(|i| seen.contains(&format!("seen-{i}"))));
+
+            // Clean-up
+            drop(seen);
+            delete(&"seen_set_fp_rate")?;
+        })
+    }
+
+    #[test]
+    fn shared_between_sets() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "seen_set_shared/ids.bloom";
+            let mut first = SeenSet::open(&file_path, 1_000, 0.01)?;
+            let mut second = SeenSet::open(&file_path, 1_000, 0.01)?;
+            first.insert(&"from-first");
+            second.insert(&"from-second");
+
+            // Action
+            first.flush()?;
+            second.flush()?;
+            let reopened = SeenSet::open(&file_path, 1_000, 0.01)?;
+
+            // Assert
+            assert!(reopened.contains(&"from-first"));
+            assert!(reopened.contains(&"from-second"));
+            assert!(
+                second.contains(&"from-first"),
+                "a flush merges in the saved keys"
+            );
+
+            // Clean-up
+            drop((first, second, reopened));
+            delete(&"seen_set_shared")?;
+        })
+    }
+}
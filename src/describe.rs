@@ -0,0 +1,280 @@
+use crate::*;
+use std::{
+    fmt::{self, Display, Formatter},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A human-readable summary of a file, as returned by `describe`.
+/// Its `Display` lists one labelled property per line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Description {
+    pub path: String,
+    /// `file`, `directory`, `symlink`, `fifo`, `socket`, `block device` or `char device`.
+    pub file_type: String,
+    /// The size, in bytes.
+    pub size: u64,
+    /// The permission bits, such as `0o644`. Outside of Unix, they're approximated from the read-only attribute.
+    pub mode: u32,
+    /// The owning user's name, or id when it has no name. `None` outside of Unix.
+    pub owner: Option<String>,
+    /// The owning group's name, or id when it has no name. `None` outside of Unix.
+    pub group: Option<String>,
+    pub modified: Option<SystemTime>,
+    pub accessed: Option<SystemTime>,
+    pub created: Option<SystemTime>,
+    /// What the symlink points to, if the file is one.
+    pub symlink_target: Option<String>,
+}
+
+impl Description {
+    /// Formats the permission bits like `ls -l` does, such as `rwxr-xr-x`.
+    pub fn permissions(&self) -> String {
+        let mut rwx: Vec<char> = "rwxrwxrwx"
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match self.mode & (0o400 >> i) {
+                0 => '-',
+                _ => c,
+            })
+            .collect();
+        for (bit, i, c) in [(0o4000, 2, 's'), (0o2000, 5, 's'), (0o1000, 8, 't')] {
+            if self.mode & bit != 0 {
+                rwx[i] = match rwx[i] {
+                    'x' => c,
+                    _ => c.to_ascii_uppercase(),
+                };
+            }
+        }
+        return rwx.into_iter().collect();
+    }
+}
+
+// Formats a time in the local time zone, such as `2024-02-29 13:05:09 +07:00`
+fn local_time(time: SystemTime) -> String {
+    let secs = match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs() as i64,
+        Err(before) => -(before.duration().as_secs() as i64),
+    };
+    let offset = internal::sys::utc_offset(secs);
+    let local = secs + offset;
+    let (days, time_of_day) = (local.div_euclid(86_400), local.rem_euclid(86_400));
+
+    // Converts days since the epoch to a civil date (https://howardhinnant.github.io/date_algorithms.html)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    return format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} {}{:02}:{:02}",
+        time_of_day / 3_600,
+        time_of_day % 3_600 / 60,
+        time_of_day % 60,
+        if offset < 0 { '-' } else { '+' },
+        offset.abs() / 3_600,
+        offset.abs() % 3_600 / 60,
+    );
+}
+
+impl Display for Description {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Path:        {}", self.path)?;
+        writeln!(f, "Type:        {}", self.file_type)?;
+        if let Some(target) = &self.symlink_target {
+            writeln!(f, "Target:      {target}")?;
+        }
+        writeln!(
+            f,
+            "Size:        {} ({} bytes)",
+            format_size(self.size, SizeUnits::Binary),
+            self.size
+        )?;
+        writeln!(f, "Permissions: {} ({:04o})", self.permissions(), self.mode)?;
+        if let (Some(owner), Some(group)) = (&self.owner, &self.group) {
+            writeln!(f, "Owner:       {owner}:{group}")?;
+        }
+        for (label, time) in [
+            ("Modified:   ", self.modified),
+            ("Accessed:   ", self.accessed),
+            ("Created:    ", self.created),
+        ] {
+            if let Some(time) = time {
+                writeln!(f, "{label} {}", local_time(time))?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(unix)]
+fn file_type_of(metadata: &Metadata) -> &'static str {
+    use std::os::unix::fs::FileTypeExt;
+
+    let file_type = metadata.file_type();
+    match () {
+        _ if file_type.is_symlink() => "symlink",
+        _ if file_type.is_dir() => "directory",
+        _ if file_type.is_fifo() => "fifo",
+        _ if file_type.is_socket() => "socket",
+        _ if file_type.is_block_device() => "block device",
+        _ if file_type.is_char_device() => "char device",
+        _ => "file",
+    }
+}
+
+#[cfg(not(unix))]
+fn file_type_of(metadata: &Metadata) -> &'static str {
+    let file_type = metadata.file_type();
+    match () {
+        _ if file_type.is_symlink() => "symlink",
+        _ if file_type.is_dir() => "directory",
+        _ => "file",
+    }
+}
+
+#[cfg(unix)]
+fn owner_of(metadata: &Metadata) -> (u32, Option<String>, Option<String>) {
+    use internal::sys::users::{group_name, user_name};
+    use std::os::unix::fs::MetadataExt;
+
+    let (uid, gid) = (metadata.uid(), metadata.gid());
+    return (
+        metadata.mode() & 0o7777,
+        Some(user_name(uid).unwrap_or_else(|| uid.to_string())),
+        Some(group_name(gid).unwrap_or_else(|| gid.to_string())),
+    );
+}
+
+#[cfg(not(unix))]
+fn owner_of(metadata: &Metadata) -> (u32, Option<String>, Option<String>) {
+    let mode = if metadata.is_dir() { 0o755 } else { 0o644 };
+    let mode = if metadata.permissions().readonly() {
+        mode & 0o555
+    } else {
+        mode
+    };
+    return (mode, None, None);
+}
+
+/// Summarizes a file, directory or symlink (without following it) for end users: its type, size,
+/// permissions in `rwx` and octal form, owner, timestamps in local time, and symlink target.
+/// Print it with `{}`; its fields remain available for structured use.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Description`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         let description = file_access::describe(&file_path)?;
+///         assert_eq!(description.file_type, "file");
+///         println!("{description}");
+///     })
+/// }
+/// ```
+pub fn describe<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Description> {
+    let metadata = fs::symlink_metadata(file_path.as_path()).context("describe", file_path)?;
+    let (mode, owner, group) = owner_of(&metadata);
+    let symlink_target = match metadata.file_type().is_symlink() {
+        true => Some(
+            fs::read_link(file_path.as_path())
+                .context("describe", file_path)?
+                .to_string_lossy()
+                .to_string(),
+        ),
+        false => None,
+    };
+
+    return Ok(Description {
+        path: file_path.as_path().to_string_lossy().to_string(),
+        file_type: file_type_of(&metadata).to_string(),
+        size: metadata.len(),
+        mode,
+        owner,
+        group,
+        modified: metadata.modified().ok(),
+        accessed: metadata.accessed().ok(),
+        created: metadata.created().ok(),
+        symlink_target,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn describe() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "describe.txt";
+            write_string(&file_path, &"x".repeat(1_536))?;
+
+            // Action
+            let description = super::describe(&file_path)?;
+            let text = description.to_string();
+
+            // Assert
+            assert_eq!(description.file_type, "file");
+            assert_eq!(description.size, 1_536);
+            assert!(text.contains("Size:        1.5 KiB (1536 bytes)"), "{text}");
+            assert!(text.contains("Modified:    "), "{text}");
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+
+    #[test]
+    fn permissions() -> Result<()> {
+        Ok({
+            // Arrange
+            let description = Description {
+                path: String::new(),
+                file_type: String::from("file"),
+                size: 0,
+                mode: 0o4755,
+                owner: None,
+                group: None,
+                modified: None,
+                accessed: None,
+                created: None,
+                symlink_target: None,
+            };
+
+            // Action
+            let permissions = description.permissions();
+
+            // Assert
+            assert_eq!(permissions, "rwsr-xr-x");
+        })
+    }
+
+    #[test]
+    fn local_time() -> Result<()> {
+        Ok({
+            // Arrange
+            let leap_day_noon = UNIX_EPOCH + std::time::Duration::from_secs(951_825_600);
+
+            // Action
+            let text = super::local_time(leap_day_noon);
+
+            // Assert
+            assert_eq!(text.len(), "2000-02-29 12:00:00 +00:00".len());
+            assert!(text.starts_with("2000-02-29 "), "{text}");
+        })
+    }
+}
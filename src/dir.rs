@@ -0,0 +1,411 @@
+use crate::{internal::traits::context::*, AsPath, Result};
+use std::{
+    ffi::{CStr, CString},
+    fs::File,
+    io::{self, Error, ErrorKind, Read, Write},
+    os::{
+        fd::{AsRawFd, FromRawFd, OwnedFd},
+        unix::ffi::OsStrExt,
+    },
+    path::{Component, Path},
+};
+
+/// A handle to an open directory. Every operation resolves its `name` relative to the directory itself
+/// (`openat(2)` semantics) rather than to a path, so it keeps working on the same directory even if it,
+/// or any of its ancestors, is renamed in the meantime. Symbolic links are **never** followed below the handle,
+/// and `..` or absolute names are rejected, so a name can't escape the directory. **Unix only**.
+///
+/// # Examples
+/// ```
+/// use file_access::Dir;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"dir_handle/.keep", &"")?;
+///
+///         let dir = Dir::open(&"dir_handle")?;
+///         dir.write_string(&"config/app.toml", &"debug = true")?;
+///         assert_eq!(dir.read_string(&"config/app.toml")?, "debug = true");
+///         dir.remove(&"config")?;
+///
+///         // Clean-up:
+///         file_access::delete(&"dir_handle")?;
+///     })
+/// }
+/// ```
+pub struct Dir {
+    fd: OwnedFd,
+}
+
+// Wraps a raw return value from libc, treating -1 as the last OS error
+fn cvt(ret: libc::c_int) -> io::Result<libc::c_int> {
+    match ret {
+        -1 => Err(Error::last_os_error()),
+        ret => Ok(ret),
+    }
+}
+
+// Splits a relative name into its components, rejecting anything that would leave the directory
+fn components(name: &Path) -> io::Result<Vec<CString>> {
+    let invalid = || Error::new(ErrorKind::InvalidInput, name.display().to_string());
+    let mut components = vec![];
+    for component in name.components() {
+        match component {
+            Component::Normal(c) => components.push(
+                CString::new(c.as_bytes()).map_err(|x| Error::new(ErrorKind::InvalidInput, x))?,
+            ),
+            Component::CurDir => {}
+            _ => return Err(invalid()),
+        }
+    }
+    if components.is_empty() {
+        return Err(invalid());
+    }
+
+    return Ok(components);
+}
+
+impl Dir {
+    /// Opens a handle to an **existing** directory.
+    ///
+    /// # Parameters
+    /// - `dir_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`Dir`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::Dir;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir = Dir::open(&"src")?;
+    ///         let text = dir.read_string(&"lib.rs")?;
+    ///         println!("{}", text);
+    ///     })
+    /// }
+    /// ```
+    pub fn open<Path: AsPath + ?Sized>(dir_path: &Path) -> Result<Self> {
+        let file = File::open(dir_path.as_path()).context("Dir::open", dir_path)?;
+        if !file.metadata().context("Dir::open", dir_path)?.is_dir() {
+            return Err(ErrorKind::NotADirectory).context("Dir::open", dir_path);
+        }
+        return Ok(Self { fd: file.into() });
+    }
+
+    // Wraps a file descriptor already opened on a directory
+    pub(crate) fn from_fd(fd: OwnedFd) -> Self {
+        Self { fd }
+    }
+
+    // Opens `name` relative to this directory, without following a symlink at `name`
+    fn openat(&self, name: &CStr, flags: libc::c_int) -> io::Result<OwnedFd> {
+        let flags = flags | libc::O_NOFOLLOW | libc::O_CLOEXEC;
+        let fd = cvt(unsafe { libc::openat(self.fd.as_raw_fd(), name.as_ptr(), flags, 0o666) })?;
+        return Ok(unsafe { OwnedFd::from_raw_fd(fd) });
+    }
+
+    // Walks every component but the last one, optionally creating missing directories,
+    // and returns the innermost directory along with the remaining file name
+    fn walk(&self, name: &Path, create: bool) -> io::Result<(Self, CString)> {
+        let mut components = components(name)?;
+        let leaf = components.pop().unwrap();
+
+        let mut dir = Self {
+            fd: self.fd.try_clone()?,
+        };
+        for component in components {
+            if create {
+                let ret = unsafe { libc::mkdirat(dir.fd.as_raw_fd(), component.as_ptr(), 0o777) };
+                if let Err(x) = cvt(ret) {
+                    if x.kind() != ErrorKind::AlreadyExists {
+                        return Err(x);
+                    }
+                }
+            }
+            dir = Self {
+                fd: dir.openat(&component, libc::O_RDONLY | libc::O_DIRECTORY)?,
+            };
+        }
+        return Ok((dir, leaf));
+    }
+
+    // Resolves `name` to its parent directory and leaf, then runs `f` on them, naming `operation` and `name` in errors
+    fn at<Name: AsPath + ?Sized, T>(
+        &self,
+        operation: &'static str,
+        name: &Name,
+        create: bool,
+        f: impl FnOnce(&Self, &CStr) -> io::Result<T>,
+    ) -> Result<T> {
+        self.walk(name.as_path(), create)
+            .and_then(|(dir, leaf)| f(&dir, &leaf))
+            .context(operation, name)
+    }
+
+    // Lists the names of the entries directly inside this directory
+    pub(crate) fn entries(&self) -> io::Result<Vec<CString>> {
+        let fd = self.fd.try_clone()?;
+        let stream = unsafe { libc::fdopendir(fd.as_raw_fd()) };
+        if stream.is_null() {
+            return Err(Error::last_os_error());
+        }
+        std::mem::forget(fd); // now owned by the stream
+
+        let mut entries = vec![];
+        loop {
+            let entry = unsafe { libc::readdir(stream) };
+            if entry.is_null() {
+                break;
+            }
+            let name = unsafe { CStr::from_ptr((*entry).d_name.as_ptr()) };
+            if name != c"." && name != c".." {
+                entries.push(name.to_owned());
+            }
+        }
+        unsafe { libc::closedir(stream) };
+
+        return Ok(entries);
+    }
+
+    // Removes `name`, and everything in it if it's a directory
+    pub(crate) fn remove_entry(&self, name: &CStr) -> io::Result<()> {
+        let mut stat: libc::stat = unsafe { std::mem::zeroed() };
+        let flags = libc::AT_SYMLINK_NOFOLLOW;
+        cvt(unsafe { libc::fstatat(self.fd.as_raw_fd(), name.as_ptr(), &mut stat, flags) })?;
+
+        if stat.st_mode & libc::S_IFMT != libc::S_IFDIR {
+            cvt(unsafe { libc::unlinkat(self.fd.as_raw_fd(), name.as_ptr(), 0) })?;
+            return Ok(());
+        }
+
+        let dir = Self {
+            fd: self.openat(name, libc::O_RDONLY | libc::O_DIRECTORY)?,
+        };
+        for entry in dir.entries()? {
+            dir.remove_entry(&entry)?;
+        }
+        cvt(unsafe { libc::unlinkat(self.fd.as_raw_fd(), name.as_ptr(), libc::AT_REMOVEDIR) })?;
+
+        return Ok(());
+    }
+
+    /// Opens a handle to a subdirectory.
+    ///
+    /// # Parameters
+    /// - `name`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`, relative to this directory
+    ///
+    /// # Returns
+    /// Result<`Dir`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::Dir;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir = Dir::open(&".")?.open_dir(&"src")?;
+    ///         println!("{}", dir.read_string(&"lib.rs")?);
+    ///     })
+    /// }
+    /// ```
+    pub fn open_dir<Name: AsPath + ?Sized>(&self, name: &Name) -> Result<Self> {
+        self.at("Dir::open_dir", name, false, |dir, leaf| {
+            let fd = dir.openat(leaf, libc::O_RDONLY | libc::O_DIRECTORY)?;
+            return Ok(Self { fd });
+        })
+    }
+
+    /// Reads the contents of a file inside this directory.
+    ///
+    /// # Parameters
+    /// - `name`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`, relative to this directory
+    ///
+    /// # Returns
+    /// Result<`String`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::Dir;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let text: String = Dir::open(&".")?.read_string(&"Cargo.toml")?;
+    ///         println!("{}", text);
+    ///     })
+    /// }
+    /// ```
+    pub fn read_string<Name: AsPath + ?Sized>(&self, name: &Name) -> Result<String> {
+        self.at("Dir::read_string", name, false, |dir, leaf| {
+            let mut buf = String::new();
+            File::from(dir.openat(leaf, libc::O_RDONLY)?).read_to_string(&mut buf)?;
+
+            return Ok(buf);
+        })
+    }
+
+    /// Writes text to a file inside this directory. This function will create the file **and its full directory path**
+    /// if they don't exist, and will entirely replace the contents.
+    ///
+    /// # Parameters
+    /// - `name`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`, relative to this directory
+    /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::Dir;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir = Dir::open(&".")?;
+    ///         dir.write_string(&"dir_write/hello.txt", &"Hello, World!")?;
+    ///
+    ///         // Clean-up:
+    ///         dir.remove(&"dir_write")?;
+    ///     })
+    /// }
+    /// ```
+    pub fn write_string<Name: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
+        &self,
+        name: &Name,
+        text: &Text,
+    ) -> Result<()> {
+        self.at("Dir::write_string", name, true, |dir, leaf| {
+            let flags = libc::O_WRONLY | libc::O_CREAT | libc::O_TRUNC;
+            return File::from(dir.openat(leaf, flags)?).write_all(text.as_ref().as_bytes());
+        })
+    }
+
+    /// Creates a new, empty file inside this directory, along with its full directory path,
+    /// and returns it opened for reading and writing. Fails if the file already exists.
+    ///
+    /// # Parameters
+    /// - `name`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`, relative to this directory
+    ///
+    /// # Returns
+    /// Result<`File`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::Dir;
+    /// use std::io::Write;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir = Dir::open(&".")?;
+    ///         let mut file = dir.create(&"dir_create/new.txt")?;
+    ///         file.write_all(b"Hello, World!")?;
+    ///         assert!(dir.create(&"dir_create/new.txt").is_err());
+    ///
+    ///         // Clean-up:
+    ///         dir.remove(&"dir_create")?;
+    ///     })
+    /// }
+    /// ```
+    pub fn create<Name: AsPath + ?Sized>(&self, name: &Name) -> Result<File> {
+        self.at("Dir::create", name, true, |dir, leaf| {
+            let flags = libc::O_RDWR | libc::O_CREAT | libc::O_EXCL;
+            return Ok(File::from(dir.openat(leaf, flags)?));
+        })
+    }
+
+    /// Deletes a file, or a directory **recursively**, inside this directory.
+    /// A symbolic link is removed itself, never what it points to.
+    ///
+    /// # Parameters
+    /// - `name`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`, relative to this directory
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::Dir;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir = Dir::open(&".")?;
+    ///         dir.write_string(&"dir_remove/a/b.txt", &"Hello, World!")?;
+    ///         dir.remove(&"dir_remove")?;
+    ///     })
+    /// }
+    /// ```
+    pub fn remove<Name: AsPath + ?Sized>(&self, name: &Name) -> Result<()> {
+        self.at("Dir::remove", name, false, |dir, leaf| {
+            dir.remove_entry(leaf)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use std::io::Result;
+
+    #[test]
+    fn write_read_remove() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"dir_rw/.keep", &"")?;
+            let dir = Dir::open(&"dir_rw")?;
+
+            // Action
+            dir.write_string(&"a/b/c.txt", &"Hello, World!")?;
+
+            // Assert
+            assert_eq!(dir.read_string("a/b/c.txt")?, "Hello, World!");
+            assert_eq!(read_string(&"dir_rw/a/b/c.txt")?, "Hello, World!");
+            dir.remove(&"a")?;
+            assert!(
+                !path_of(&"dir_rw/a").exists(),
+                "dir_rw/a should no longer exist"
+            );
+
+            // Clean-up
+            delete(&"dir_rw")?;
+        })
+    }
+
+    #[test]
+    fn survives_rename() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"dir_renamed_from/.keep", &"")?;
+            let dir = Dir::open(&"dir_renamed_from")?;
+            std::fs::rename("dir_renamed_from", "dir_renamed_to")?;
+
+            // Action
+            dir.write_string(&"file.txt", &"Hello, World!")?;
+
+            // Assert
+            assert_eq!(read_string(&"dir_renamed_to/file.txt")?, "Hello, World!");
+
+            // Clean-up
+            delete(&"dir_renamed_to")?;
+        })
+    }
+
+    #[test]
+    fn rejects_escapes() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"dir_escape/inner/.keep", &"")?;
+            std::os::unix::fs::symlink("..", "dir_escape/inner/up")?;
+            let dir = Dir::open(&"dir_escape/inner")?;
+
+            // Assert
+            assert!(dir.read_string(&"../../Cargo.toml").is_err());
+            assert!(dir.read_string(&"/etc/hostname").is_err());
+            assert!(dir.read_string(&"up/inner/.keep").is_err());
+            assert!(dir.create(&".keep").is_err());
+
+            // Clean-up
+            delete(&"dir_escape")?;
+        })
+    }
+}
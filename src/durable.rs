@@ -0,0 +1,133 @@
+use crate::*;
+use std::path::Path as StdPath;
+
+// Flushes a file, or (on Unix) a directory's entries, to disk; Windows only flushes handles open for writing,
+// and can't open directories as files, which it needn't flush
+fn flush(path: &StdPath, data_only: bool) -> io::Result<()> {
+    #[cfg(windows)]
+    let file = match fs::metadata(path)?.is_dir() {
+        true => return Ok(()),
+        false => File::options().write(true).open(path)?,
+    };
+    #[cfg(not(windows))]
+    let file = File::open(path)?;
+
+    return match data_only {
+        true => file.sync_data(),
+        false => file.sync_all(),
+    };
+}
+
+/// Flushes a file's contents and metadata to disk, so they survive a power loss, such as after writing it through
+/// another handle. On Unix a directory can be synced too, which makes the files created, renamed or deleted in it durable.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "sync_all_example/journal.log";
+///
+///         file_access::append_string(&file_path, &"BEGIN\n")?;
+///         file_access::sync_all(&file_path)?;
+///
+///         // Clean-up:
+///         file_access::delete(&"sync_all_example")?;
+///     })
+/// }
+/// ```
+pub fn sync_all<Path: AsPath + ?Sized>(file_path: &Path) -> Result<()> {
+    flush(file_path.as_path(), false).context("sync_all", file_path)
+}
+
+/// Flushes a file's contents to disk like `sync_all`, but only the metadata needed to read them back, such as its size
+/// and not its modification time, which saves a disk write.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+pub fn sync_data<Path: AsPath + ?Sized>(file_path: &Path) -> Result<()> {
+    flush(file_path.as_path(), true).context("sync_data", file_path)
+}
+
+/// Moves a file like `rename`, then flushes it and the directories it was moved out of and into to disk,
+/// so that after a power loss it's found at the destination, not at the source or nowhere.
+/// This function will create the destination's full directory path if it doesn't exist,
+/// and will entirely replace the contents of the destination if it already exists.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"rename_durable_example/db.tmp", &"{}")?;
+///         file_access::rename_durable(&"rename_durable_example/db.tmp", &"rename_durable_example/db.json")?;
+///
+///         // Clean-up:
+///         file_access::delete(&"rename_durable_example")?;
+///     })
+/// }
+/// ```
+pub fn rename_durable<From: AsPath + ?Sized, To: AsPath + ?Sized>(
+    from: &From,
+    to: &To,
+) -> Result<()> {
+    rename(from, to)?;
+
+    let (from, to) = (from.as_path(), to.as_path());
+    let flush = || -> io::Result<()> {
+        flush(to, false)?;
+        sync_parent(to)?;
+        if from.parent() != to.parent() {
+            sync_parent(from)?;
+        }
+        return Ok(());
+    };
+    flush().context("rename_durable", to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn durable() -> Result<()> {
+        Ok({
+            // Arrange
+            let (from, to) = ("durable/a/db.tmp", "durable/b/db.json");
+            write_string_with(&from, &"{}", WriteOptions::new().durable(true))?;
+
+            // Action
+            sync_all(&from)?;
+            sync_data(&from)?;
+            rename_durable(&from, &to)?;
+            #[cfg(unix)]
+            sync_all(&"durable/b")?;
+
+            // Assert
+            assert!(!PathBuf::from(from).exists());
+            assert_eq!(read_string(&to)?, "{}");
+            assert_eq!(
+                sync_all(&"durable/missing").unwrap_err().kind(),
+                ErrorKind::NotFound
+            );
+
+            // Clean-up
+            delete(&"durable")?;
+        })
+    }
+}
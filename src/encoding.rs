@@ -0,0 +1,218 @@
+use crate::*;
+pub use ::encoding_rs::{self, Encoding}; // re-export the encodings, such as encoding_rs::UTF_16LE
+use std::borrow::Cow;
+
+// Takes the decoded text, failing if some of the bytes weren't valid in the encoding
+fn decoded(text: Cow<str>, encoding: &'static Encoding, had_errors: bool) -> io::Result<String> {
+    match had_errors {
+        true => Err(io::Error::new(
+            ErrorKind::InvalidData,
+            format!("the file isn't valid {}", encoding.name()),
+        )),
+        false => Ok(text.into_owned()),
+    }
+}
+
+/// Reads a file in an encoding other than UTF-8, such as UTF-16LE, Windows-1252 or Shift_JIS, and returns it as a string.
+/// A leading byte order mark of that encoding is dropped. **Requires the `encoding` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `encoding`: `&'static Encoding` such as `encoding_rs::WINDOWS_1252`
+///
+/// # Returns
+/// Result<`String`>, failing with `ErrorKind::InvalidData` if the file isn't valid in the encoding
+///
+/// # Examples
+/// ```
+/// use file_access::encoding_rs::WINDOWS_1252;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "read_string_with_encoding_example/legacy.txt";
+///         file_access::write_bytes(&file_path, &b"caf\xE9")?;
+///
+///         assert_eq!(file_access::read_string_with_encoding(&file_path, WINDOWS_1252)?, "café");
+///
+///         // Clean-up:
+///         file_access::delete(&"read_string_with_encoding_example")?;
+///     })
+/// }
+/// ```
+pub fn read_string_with_encoding<Path: AsPath + ?Sized>(
+    file_path: &Path,
+    encoding: &'static Encoding,
+) -> Result<String> {
+    let bytes = read_bytes(file_path)?;
+    let (text, had_errors) = encoding.decode_with_bom_removal(&bytes);
+
+    return decoded(text, encoding, had_errors).context("read_string_with_encoding", file_path);
+}
+
+/// Reads a file and returns it as a string, in the encoding told by its byte order mark (UTF-8, UTF-16LE or UTF-16BE),
+/// or in `fallback` if it has none. The BOM is dropped. **Requires the `encoding` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `fallback`: `&'static Encoding` such as `encoding_rs::UTF_8`
+///
+/// # Returns
+/// Result<`(String, &'static Encoding)`>, the text and the encoding it was read in,
+/// failing with `ErrorKind::InvalidData` if the file isn't valid in that encoding
+///
+/// # Examples
+/// ```
+/// use file_access::encoding_rs::{UTF_16LE, UTF_8};
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "read_string_detect_encoding_example/export.csv";
+///         file_access::write_string_with_encoding(&file_path, &"id,name", UTF_16LE, true)?;
+///
+///         let (text, encoding) = file_access::read_string_detect_encoding(&file_path, UTF_8)?;
+///         assert_eq!((text.as_str(), encoding), ("id,name", UTF_16LE));
+///
+///         // Clean-up:
+///         file_access::delete(&"read_string_detect_encoding_example")?;
+///     })
+/// }
+/// ```
+pub fn read_string_detect_encoding<Path: AsPath + ?Sized>(
+    file_path: &Path,
+    fallback: &'static Encoding,
+) -> Result<(String, &'static Encoding)> {
+    let bytes = read_bytes(file_path)?;
+    let (text, encoding, had_errors) = fallback.decode(&bytes);
+    let text =
+        decoded(text, encoding, had_errors).context("read_string_detect_encoding", file_path)?;
+
+    return Ok((text, encoding));
+}
+
+/// Writes text to a file in an encoding other than UTF-8, such as UTF-16LE, Windows-1252 or Shift_JIS,
+/// optionally starting it with a byte order mark (only UTF-8 and UTF-16 have one). This function will create the file
+/// **and its full directory path** if they don't exist, and will entirely replace the contents.
+/// **Requires the `encoding` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+/// - `encoding`: `&'static Encoding` such as `encoding_rs::UTF_16LE`
+/// - `bom`: whether to write a byte order mark
+///
+/// # Returns
+/// Result<`()`>, failing with `ErrorKind::InvalidData` if the text has characters the encoding can't represent
+///
+/// # Examples
+/// ```
+/// use file_access::encoding_rs::UTF_16LE;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "write_string_with_encoding_example/unicode.txt";
+///         file_access::write_string_with_encoding(&file_path, &"Hi", UTF_16LE, true)?;
+///
+///         assert_eq!(file_access::read_bytes(&file_path)?, b"\xFF\xFEH\0i\0");
+///
+///         // Clean-up:
+///         file_access::delete(&"write_string_with_encoding_example")?;
+///     })
+/// }
+/// ```
+pub fn write_string_with_encoding<Path: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    text: &Text,
+    encoding: &'static Encoding,
+    bom: bool,
+) -> Result<()> {
+    let text = text.as_ref();
+    let mut bytes = vec![];
+    // encoding_rs only decodes UTF-16, encoding it as UTF-8 instead, so it's encoded here
+    if encoding == encoding_rs::UTF_16LE || encoding == encoding_rs::UTF_16BE {
+        let little_endian = encoding == encoding_rs::UTF_16LE;
+        for unit in std::iter::once(0xFEFF)
+            .filter(|_| bom)
+            .chain(text.encode_utf16())
+        {
+            bytes.extend(match little_endian {
+                true => unit.to_le_bytes(),
+                false => unit.to_be_bytes(),
+            });
+        }
+    } else {
+        if bom && encoding == encoding_rs::UTF_8 {
+            bytes.extend(b"\xEF\xBB\xBF");
+        }
+        let (encoded, _, had_errors) = encoding.encode(text);
+        if had_errors {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("the text can't be written in {}", encoding.name()),
+            ))
+            .context("write_string_with_encoding", file_path);
+        }
+        bytes.extend(encoded.iter());
+    }
+
+    return write_bytes(file_path, &bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::{SHIFT_JIS, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+    use std::io::Result;
+
+    #[test]
+    fn encoding() -> Result<()> {
+        Ok({
+            // Arrange
+            let dir = "encoding";
+            let text = "naïve café";
+
+            // Action
+            write_string_with_encoding(&"encoding/le.txt", &text, UTF_16LE, true)?;
+            write_string_with_encoding(&"encoding/be.txt", &text, UTF_16BE, false)?;
+            write_string_with_encoding(&"encoding/1252.txt", &text, WINDOWS_1252, true)?;
+            write_string_with_encoding(&"encoding/sjis.txt", &"日本語", SHIFT_JIS, false)?;
+            let unrepresentable =
+                write_string_with_encoding(&"encoding/x.txt", &"日本", WINDOWS_1252, false);
+
+            // Assert
+            assert_eq!(
+                read_string_with_encoding(&"encoding/le.txt", UTF_16LE)?,
+                text
+            );
+            assert_eq!(
+                read_string_with_encoding(&"encoding/be.txt", UTF_16BE)?,
+                text
+            );
+            assert_eq!(
+                read_bytes(&"encoding/1252.txt")?.len(),
+                text.chars().count()
+            );
+            assert_eq!(
+                read_string_with_encoding(&"encoding/1252.txt", WINDOWS_1252)?,
+                text
+            );
+            assert_eq!(
+                read_string_with_encoding(&"encoding/sjis.txt", SHIFT_JIS)?,
+                "日本語"
+            );
+            assert_eq!(
+                read_string_detect_encoding(&"encoding/le.txt", UTF_8)?,
+                (text.to_string(), UTF_16LE)
+            );
+            assert_eq!(
+                read_string_detect_encoding(&"encoding/1252.txt", UTF_8)
+                    .unwrap_err()
+                    .kind(),
+                ErrorKind::InvalidData
+            );
+            assert_eq!(unrepresentable.unwrap_err().kind(), ErrorKind::InvalidData);
+
+            // Clean-up
+            delete(&dir)?;
+        })
+    }
+}
@@ -0,0 +1,143 @@
+use crate::AsPath;
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    io::{self, ErrorKind},
+    path::PathBuf,
+};
+
+/// The error returned by this crate: the `std::io::Error` that occurred, along with the operation that failed
+/// and the path it failed on, when known. It mirrors the `std::io::Error` API (`kind`, `raw_os_error`, `get_ref`, ...),
+/// and converts to and from it, so `?` works both ways, e.g. in functions returning `std::io::Result`.
+///
+/// # Examples
+/// ```
+/// use std::{io::ErrorKind, path::Path};
+///
+/// let error = file_access::read_string(&"missing/file.txt").unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::NotFound);
+/// assert_eq!(error.path.as_deref(), Some(Path::new("missing/file.txt")));
+/// assert_eq!(error.operation, Some("read_string"));
+/// assert!(error.to_string().starts_with("read_string missing/file.txt: "));
+///
+/// let error: std::io::Error = error.into();
+/// assert_eq!(error.kind(), ErrorKind::NotFound);
+/// ```
+#[derive(Debug)]
+pub struct Error {
+    /// The path the operation failed on, or `None` if it isn't known.
+    pub path: Option<PathBuf>,
+    /// The name of the function that failed, such as `read_string`, or `None` if it isn't known.
+    pub operation: Option<&'static str>,
+    /// The underlying error.
+    pub source: io::Error,
+}
+
+/// A `Result` with this crate's `Error`.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Creates an error of a kind, without a path or operation, like `std::io::Error::new`.
+    pub fn new<E: Into<Box<dyn error::Error + Send + Sync>>>(kind: ErrorKind, error: E) -> Self {
+        Self::from(io::Error::new(kind, error))
+    }
+
+    /// Creates an error from the last OS error, like `std::io::Error::last_os_error`.
+    pub fn last_os_error() -> Self {
+        Self::from(io::Error::last_os_error())
+    }
+
+    /// Returns the kind of the underlying error.
+    pub fn kind(&self) -> ErrorKind {
+        self.source.kind()
+    }
+
+    /// Returns the OS error code of the underlying error, if it came from the OS.
+    pub fn raw_os_error(&self) -> Option<i32> {
+        self.source.raw_os_error()
+    }
+
+    /// Returns the error wrapped by the underlying error, such as a `Corrupted`, if any.
+    pub fn get_ref(&self) -> Option<&(dyn error::Error + Send + Sync + 'static)> {
+        self.source.get_ref()
+    }
+
+    // Names the operation and path that failed, unless an inner operation already did
+    pub(crate) fn context<Path: AsPath + ?Sized>(
+        mut self,
+        operation: &'static str,
+        path: &Path,
+    ) -> Self {
+        if self.operation.is_none() {
+            self.operation = Some(operation);
+            self.path = Some(path.as_path().to_path_buf());
+        }
+        return self;
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (&self.operation, &self.path) {
+            (Some(operation), Some(path)) => {
+                write!(f, "{operation} {}: {}", path.display(), self.source)
+            }
+            _ => write!(f, "{}", self.source),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Self {
+            path: None,
+            operation: None,
+            source,
+        }
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self::from(io::Error::from(kind))
+    }
+}
+
+impl From<Error> for io::Error {
+    /// Keeps the kind; the path and operation, when known, are kept in the message.
+    fn from(error: Error) -> Self {
+        match error.operation {
+            Some(_) => io::Error::new(error.kind(), error),
+            None => error.source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn context() -> Result<()> {
+        Ok({
+            // Arrange
+            let error = Error::from(ErrorKind::NotFound);
+
+            // Action
+            let inner = error.context("read_string", "a.txt");
+            let outer = inner.context("read_lines", "b.txt");
+
+            // Assert
+            assert_eq!(outer.operation, Some("read_string"));
+            assert_eq!(outer.path, Some(PathBuf::from("a.txt")));
+            assert_eq!(io::Error::from(outer).kind(), ErrorKind::NotFound);
+        })
+    }
+}
@@ -0,0 +1,199 @@
+use crate::*;
+
+// Linux ignores anything past this many bytes of a shebang line
+const MAX_SHEBANG: u64 = 256;
+
+/// Reads the interpreter line of a script, such as `/usr/bin/env python3` for a file starting with
+/// `#!/usr/bin/env python3`. Returns `None` when the file doesn't start with `#!`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Option<String>`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "shebang_example.sh";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_lines(&file_path, &vec!["#!/bin/sh -e", "echo hello"])?;
+///
+///         let interpreter = file_access::shebang(&file_path)?;
+///         assert_eq!(interpreter.as_deref(), Some("/bin/sh -e"));
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn shebang<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Option<String>> {
+    let mut head = vec![];
+    get_file(file_path)
+        .and_then(|file| file.take(MAX_SHEBANG).read_to_end(&mut head))
+        .context("shebang", file_path)?;
+
+    let Some(line) = head.strip_prefix(b"#!") else {
+        return Ok(None);
+    };
+    let line = line.split(|&b| b == b'\n').next().unwrap_or_default();
+
+    return Ok(Some(String::from_utf8_lossy(line).trim().to_string()));
+}
+
+/// Checks whether a file can be run: on Unix, whether it's a regular file with any execute permission bit set;
+/// on Windows, whether its extension is listed in `PATHEXT` (`.COM`, `.EXE`, `.BAT`, `.CMD`, ... by default).
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`bool`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         assert!(!file_access::is_executable(&file_path)?);
+///     })
+/// }
+/// ```
+pub fn is_executable<Path: AsPath + ?Sized>(file_path: &Path) -> Result<bool> {
+    let metadata = fs::metadata(file_path.as_path()).context("is_executable", file_path)?;
+    if !metadata.is_file() {
+        return Ok(false);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        return Ok(metadata.permissions().mode() & 0o111 != 0);
+    }
+    #[cfg(not(unix))]
+    {
+        let extension = match path_of(file_path).extension() {
+            Some(extension) => format!(".{}", extension.to_string_lossy()),
+            None => return Ok(false),
+        };
+        let path_ext = std::env::var("PATHEXT").unwrap_or_else(|_| {
+            String::from(".COM;.EXE;.BAT;.CMD;.VBS;.VBE;.JS;.JSE;.WSF;.WSH;.MSC")
+        });
+
+        return Ok(path_ext
+            .split(';')
+            .any(|ext| ext.eq_ignore_ascii_case(&extension)));
+    }
+}
+
+/// Adds or removes the execute permission of a file. On Unix, making a file executable grants execute
+/// to whoever may read it (like `chmod +x` under the usual umask), and the opposite removes it from everyone.
+/// Windows decides executability by extension instead, so there this fails with `ErrorKind::Unsupported`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `executable`: **owned** `bool`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "set_executable_example.sh";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_lines(&file_path, &vec!["#!/bin/sh", "echo hello"])?;
+///
+///         # #[cfg(unix)]
+///         file_access::set_executable(&file_path, true)?;
+///         # #[cfg(unix)]
+///         assert!(file_access::is_executable(&file_path)?);
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn set_executable<Path: AsPath + ?Sized>(file_path: &Path, executable: bool) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut permissions = fs::metadata(file_path.as_path())
+            .context("set_executable", file_path)?
+            .permissions();
+        let mode = permissions.mode();
+        permissions.set_mode(if executable {
+            mode | (mode & 0o444) >> 2
+        } else {
+            mode & !0o111
+        });
+
+        return fs::set_permissions(file_path.as_path(), permissions)
+            .context("set_executable", file_path);
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = executable;
+        return Err(ErrorKind::Unsupported).context("set_executable", file_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn shebang() -> Result<()> {
+        Ok({
+            // Arrange
+            let script = "shebang.py";
+            let text = "shebang.txt";
+            write_string(&script, &"#! /usr/bin/env python3\r\nprint('hello')")?;
+            write_string(&text, &"print('hello')")?;
+
+            // Action
+            let interpreter = super::shebang(&script)?;
+
+            // Assert
+            assert_eq!(interpreter.as_deref(), Some("/usr/bin/env python3"));
+            assert_eq!(super::shebang(&text)?, None);
+
+            // Clean-up
+            delete(&script)?;
+            delete(&text)?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn set_executable() -> Result<()> {
+        Ok({
+            use std::os::unix::fs::PermissionsExt;
+
+            // Arrange
+            let file_path = "set_executable.sh";
+            write_string(&file_path, &"#!/bin/sh")?;
+            fs::set_permissions(file_path, fs::Permissions::from_mode(0o640))?;
+
+            // Action
+            super::set_executable(&file_path, true)?;
+            let mode = fs::metadata(file_path)?.permissions().mode() & 0o777;
+
+            // Assert
+            assert_eq!(mode, 0o750);
+            assert!(is_executable(&file_path)?);
+            super::set_executable(&file_path, false)?;
+            assert!(!is_executable(&file_path)?);
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+}
@@ -0,0 +1,124 @@
+use crate::*;
+use std::env;
+
+// The home directory, from `HOME`, or `USERPROFILE` on Windows
+fn home() -> io::Result<String> {
+    let vars: &[&str] = match cfg!(windows) {
+        true => &["USERPROFILE", "HOME"],
+        false => &["HOME"],
+    };
+    vars.iter()
+        .find_map(|var| env::var(var).ok().filter(|home| !home.is_empty()))
+        .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "the home directory isn't known"))
+}
+
+fn var(name: &str) -> io::Result<String> {
+    env::var(name).map_err(|_| {
+        io::Error::new(
+            ErrorKind::NotFound,
+            format!("the environment variable {name} isn't set"),
+        )
+    })
+}
+
+fn is_name(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+// Expands `~` at the start and `$VAR`, `${VAR}` or, on Windows, `%VAR%` anywhere
+fn expand_str(path: &str) -> io::Result<String> {
+    let mut rest = path;
+    let mut expanded = String::with_capacity(path.len());
+    if let Some(after) = rest.strip_prefix('~') {
+        if after.is_empty() || after.starts_with(['/', '\\']) {
+            expanded.push_str(&home()?);
+            rest = after;
+        }
+    }
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+        match c {
+            '$' if rest.starts_with('{') => {
+                let end = rest.find('}').ok_or_else(|| {
+                    io::Error::new(ErrorKind::InvalidInput, "a `${` isn't closed")
+                })?;
+                expanded.push_str(&var(&rest[1..end])?);
+                rest = &rest[end + 1..];
+            }
+            '$' if rest.starts_with(is_name) => {
+                let end = rest.find(|c| !is_name(c)).unwrap_or(rest.len());
+                expanded.push_str(&var(&rest[..end])?);
+                rest = &rest[end..];
+            }
+            '%' if cfg!(windows)
+                && rest
+                    .find('%')
+                    .is_some_and(|end| end > 0 && rest[..end].chars().all(is_name)) =>
+            {
+                let end = rest.find('%').unwrap();
+                expanded.push_str(&var(&rest[..end])?);
+                rest = &rest[end + 1..];
+            }
+            c => expanded.push(c),
+        }
+    }
+    return Ok(expanded);
+}
+
+/// Expands a user-supplied path the way a shell would: a leading `~` becomes the home directory, and `$VAR` or
+/// `${VAR}` anywhere, or `%VAR%` on Windows, becomes the value of the environment variable. `~user` and a `$` that
+/// isn't followed by a name are left as they are. **Requires the `expand` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`FilePath`>, failing with `ErrorKind::NotFound` if a variable isn't set or the home directory isn't known
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let notes = file_access::expand_path(&"~/notes/todo.txt")?;
+///         assert!(!notes.as_ref().starts_with('~'));
+///
+///         let logs = file_access::expand_path(&"${HOME}/logs/app.log")?;
+///         assert!(logs.as_ref().ends_with("app.log"));
+///     })
+/// }
+/// ```
+pub fn expand_path<Path: AsPath + ?Sized>(file_path: &Path) -> Result<FilePath> {
+    let path = file_path.as_path().to_string_lossy();
+    expand_str(&path)
+        .map(|expanded| FilePath::access(&expanded))
+        .context("expand_path", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn expand_path() -> Result<()> {
+        Ok({
+            // Arrange
+            let home = home()?;
+            let path = env::var("PATH").unwrap_or_default();
+
+            // Action
+            let tilde = expand_str("~/notes/todo.txt")?;
+            let vars = expand_str("$PATH:${PATH}/x")?;
+            let untouched = expand_str("~user/$/a$-b")?;
+            let missing = expand_str("$FILE_ACCESS_UNSET_VARIABLE/x");
+            let unclosed = expand_str("${PATH");
+
+            // Assert
+            assert_eq!(tilde, format!("{home}/notes/todo.txt"));
+            assert_eq!(vars, format!("{path}:{path}/x"));
+            assert_eq!(untouched, "~user/$/a$-b");
+            assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+            assert_eq!(unclosed.unwrap_err().kind(), ErrorKind::InvalidInput);
+        })
+    }
+}
@@ -1,17 +1,23 @@
-use crate::{internal::types::*, *};
+use crate::{
+    internal::{paths, types::*, uri},
+    *,
+};
 use std::{
     env::current_dir,
-    fs::{canonicalize, Metadata},
-    io::{Error, ErrorKind, Result},
+    fs::{canonicalize, File, Metadata},
+    io::{self, BufReader, BufWriter, ErrorKind},
+    time::{Duration, SystemTime},
 };
 
 /// A wrapper that acts as a file handle.
 pub struct FilePath {
+    path: PathBuf,
+    // The path as a string, lossily converted if it isn't valid UTF-8
     get_path: String,
 }
 
 impl FilePath {
-    /// Wraps a **borrowed** `AsRef<str>`, such as `String` or `&str`, into a `FilePath`.
+    /// Wraps a **borrowed** `AsPath`, such as `String`, `&str` or `PathBuf`, into a `FilePath`.
     ///
     /// # Returns
     /// file_access::`FilePath`
@@ -30,13 +36,13 @@ impl FilePath {
     ///     })
     /// }
     /// ```
-    pub fn access<Path: AsRef<str>>(file_path: &Path) -> Self {
-        Self {
-            get_path: file_path.as_ref().to_string(),
-        }
+    pub fn access<Path: AsPath + ?Sized>(file_path: &Path) -> Self {
+        Self::from(path_of(file_path))
     }
 
     /// Attempts to get the absolute path of an **existing** file or directory.
+    /// On Windows, the path is given without the `\\?\` prefix when it fits within MAX_PATH, with UNC shares as
+    /// `\\server\share\...`, and with the prefix when it's longer, so it still works when passed back to the crate.
     ///
     /// # Returns
     /// Result<`String`>
@@ -57,16 +63,19 @@ impl FilePath {
     /// }
     /// ```
     pub fn get_full_path(&self) -> Result<String> {
-        Ok(canonicalize(&self.get_path)?.display().to_string())
+        Ok(paths::simplified(canonicalize(&self.path)?)
+            .display()
+            .to_string())
     }
 
     /// Attempts to get the relative path of an **existing** file or directory.
+    /// The path has to be inside of the current directory, even when either is longer than MAX_PATH on Windows.
     ///
     /// # Returns
     /// Result<`String`>
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use file_access::FilePath;
     ///
     /// fn main() -> std::io::Result<()> {
@@ -81,7 +90,8 @@ impl FilePath {
     /// }
     /// ```
     pub fn get_relative_path(&self) -> Result<String> {
-        match path_of(&self.get_full_path()?).strip_prefix(&current_dir()?) {
+        // Both sides are compared in their canonical form, which is the `\\?\` form on Windows whatever their length
+        match canonicalize(&self.path)?.strip_prefix(canonicalize(current_dir()?)?) {
             Ok(p) => match p.to_str() {
                 Some(s) => Ok(s.to_string()),
                 None => Err(Error::new(ErrorKind::InvalidData, "&Path.to_str() error")),
@@ -90,6 +100,209 @@ impl FilePath {
         }
     }
 
+    /// Normalizes the path lexically, without touching the disk: `.` segments and redundant separators are removed,
+    /// and `..` segments cancel out the segment before them, so unlike `get_full_path` it works for files that don't
+    /// exist yet. `..` segments that climb above the start of a relative path are kept, and those above the root
+    /// of an absolute one are dropped; an empty result is `.`. Symlinks aren't resolved, so `link/..` becomes `.`
+    /// even when `link` points elsewhere.
+    ///
+    /// # Returns
+    /// file_access::`FilePath`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    /// use std::path::Path;
+    ///
+    /// let file: FilePath = FilePath::access(&"./logs//2024/../2025/./app.log");
+    /// assert_eq!(file.normalized().as_ref(), Path::new("logs/2025/app.log").to_str().unwrap());
+    /// assert_eq!(FilePath::access(&"../a/..").normalized().as_ref(), "..");
+    /// ```
+    pub fn normalized(&self) -> Self {
+        Self::from(paths::normalize(&self.path))
+    }
+
+    /// Builds the path of a child of this directory, such as a file in it. The path isn't touched on disk.
+    /// Joining an absolute path replaces this one, like `std::path::Path::join`.
+    ///
+    /// # Parameters
+    /// - `segment`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`, possibly of several components like `"a/b.txt"`
+    ///
+    /// # Returns
+    /// file_access::`FilePath`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let config_dir: FilePath = FilePath::access(&"join_example");
+    ///
+    ///         let config: FilePath = config_dir.join("app.toml");
+    ///         config.write_string(&"debug = true")?;
+    ///         assert_eq!(file_access::read_string(&"join_example/app.toml")?, "debug = true");
+    ///
+    ///         // Clean-up:
+    ///         config_dir.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn join<Segment: AsPath + ?Sized>(&self, segment: &Segment) -> Self {
+        Self::from(self.path.join(segment.as_path()))
+    }
+
+    /// Builds the path of a file or directory in the same directory as this one. The path isn't touched on disk.
+    ///
+    /// # Parameters
+    /// - `name`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// file_access::`FilePath`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// let file: FilePath = FilePath::access(&"src/lib.rs");
+    /// assert_eq!(file.sibling("main.rs").as_ref(), FilePath::access(&"src").join("main.rs").as_ref());
+    /// ```
+    pub fn sibling<Name: AsPath + ?Sized>(&self, name: &Name) -> Self {
+        match self.path.parent() {
+            Some(parent) => Self::from(parent.join(name.as_path())),
+            None => Self::access(name),
+        }
+    }
+
+    /// Joins an untrusted segment under this directory, like `join`, but fails instead of building a path that could
+    /// lead elsewhere: the segment must be relative, and each of its components a name that `sanitize_file_name`
+    /// would leave as it is, so `..`, absolute paths, drive letters, backslashes, control characters and reserved
+    /// Windows names are refused. The path isn't touched on disk.
+    ///
+    /// # Parameters
+    /// - `segment`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`, possibly of several components like `"a/b.txt"`
+    ///
+    /// # Returns
+    /// Result<`FilePath`>, failing with `ErrorKind::InvalidInput` if the segment isn't safe
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    /// use std::io::ErrorKind;
+    ///
+    /// let uploads: FilePath = FilePath::access(&"uploads");
+    ///
+    /// assert!(uploads.safe_join("2024/photo.jpg").is_ok());
+    /// assert_eq!(uploads.safe_join("../secret.txt").err().unwrap().kind(), ErrorKind::InvalidInput);
+    /// assert_eq!(uploads.safe_join("/etc/passwd").err().unwrap().kind(), ErrorKind::InvalidInput);
+    /// ```
+    pub fn safe_join<Segment: AsPath + ?Sized>(&self, segment: &Segment) -> Result<Self> {
+        let check = || -> io::Result<()> {
+            let mut components = segment.as_path().components().peekable();
+            if components.peek().is_none() {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidInput,
+                    "the segment is empty",
+                ));
+            }
+            for component in components {
+                let std::path::Component::Normal(name) = component else {
+                    return Err(io::Error::new(
+                        ErrorKind::InvalidInput,
+                        "the segment isn't a plain relative path",
+                    ));
+                };
+                let name = name.to_str().ok_or(ErrorKind::InvalidInput)?;
+                if crate::sanitize::clean(name)? != name {
+                    return Err(io::Error::new(
+                        ErrorKind::InvalidInput,
+                        "the segment has an unsafe name",
+                    ));
+                }
+            }
+            return Ok(());
+        };
+        check().context("safe_join", segment)?;
+
+        return Ok(self.join(segment));
+    }
+
+    /// Expands a leading `~` to the home directory and environment variables such as `$HOME`, `${HOME}`
+    /// or, on Windows, `%USERPROFILE%`, like `expand_path`. **Requires the `expand` feature**.
+    ///
+    /// # Returns
+    /// Result<`FilePath`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::AsFile;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let todo = "~/notes/todo.txt".as_file().expand()?;
+    ///         println!("{}", todo.as_ref());
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "expand")]
+    pub fn expand(&self) -> Result<Self> {
+        expand_path(self)
+    }
+
+    /// Converts the path into a `file://` URI, such as the ones editors and language servers exchange:
+    /// a relative path is made absolute against the current directory, without touching the disk, and characters
+    /// such as spaces, `#` and `%` are percent-encoded. On Windows, `C:\a b` becomes `file:///C:/a%20b`
+    /// and the UNC path `\\server\share\a` becomes `file://server/share/a`.
+    ///
+    /// # Returns
+    /// Result<`String`>, failing with `ErrorKind::InvalidData` on Windows if the path isn't valid Unicode
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let uri: String = FilePath::access(&"docs/read me.md").to_file_uri()?;
+    ///         assert!(uri.starts_with("file:///"));
+    ///         assert!(uri.ends_with("docs/read%20me.md"));
+    ///     })
+    /// }
+    /// ```
+    pub fn to_file_uri(&self) -> Result<String> {
+        uri::to_uri(&self.path).context("to_file_uri", &self.path)
+    }
+
+    /// Converts a `file:` URI, such as one from an editor or a language server, into a path, decoding percent-encoded
+    /// characters. The host may be empty or `localhost`; on Windows, any other host makes a UNC path, and a drive
+    /// letter may be written as `/C:/` or `/c%3A/`. The query and fragment, if any, are ignored.
+    ///
+    /// # Parameters
+    /// - `uri`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// Result<`FilePath`>, failing with `ErrorKind::InvalidInput` if the URI isn't a `file:` URI with an absolute path,
+    /// or, outside of Windows, if it points to another host
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::from_file_uri(&"file:///tmp/read%20me.md")?;
+    ///         if cfg!(unix) {
+    ///             assert_eq!(file.as_ref(), "/tmp/read me.md");
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    pub fn from_file_uri<Text: AsRef<str> + ?Sized>(uri: &Text) -> Result<Self> {
+        uri::from_uri(uri.as_ref())
+            .map(Self::from)
+            .context("from_file_uri", uri.as_ref())
+    }
+
     /// Reads the contents of a file.
     ///
     /// # Returns
@@ -114,140 +327,138 @@ impl FilePath {
         read_string(self)
     }
 
-    /// Reads the contents of a file and returns it as lines.
+    /// Reads the contents of a file and returns it as a string, as set by `ReadOptions`,
+    /// such as stripping a UTF-8 byte order mark or replacing invalid UTF-8 instead of failing.
+    ///
+    /// # Parameters
+    /// - `options`: `ReadOptions`, such as `ReadOptions::new().strip_bom(true)`
     ///
     /// # Returns
-    /// Result<`Vec<String>`>
+    /// Result<`String`>
     ///
     /// # Examples
     /// ```
-    /// use file_access::FilePath;
+    /// use file_access::{FilePath, ReadOptions};
     ///
     /// fn main() -> std::io::Result<()> {
     ///     Ok({
-    ///         let file_path: &str = "Cargo.toml";
-    ///         let file_path: String = String::from(file_path);
+    ///         let file: FilePath = FilePath::access(&"fp_read_string_with_example.txt");
+    ///         file.write_bytes(&b"\xEF\xBB\xBFHello, World!")?;
     ///
-    ///         let file: FilePath = FilePath::access(&file_path);
-    ///         let lines: Vec<String> = file.read_lines()?;
-    ///         lines.iter().for_each(|line| println!("{}", line));
+    ///         let text: String = file.read_string_with(ReadOptions::new().strip_bom(true))?;
+    ///         assert_eq!(text, "Hello, World!");
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
     ///     })
     /// }
     /// ```
-    pub fn read_lines(&self) -> Result<Lines> {
-        read_lines(self)
+    pub fn read_string_with(&self, options: ReadOptions) -> Result<String> {
+        read_string_with(self, options)
     }
 
-    /// Writes text to a file. This function will create the file **and its full directory path** if they don't exist,
-    /// and will entirely replace the contents.
+    /// Reads the file in an encoding other than UTF-8, such as UTF-16LE or Windows-1252, and returns it as a string.
+    /// A leading byte order mark of that encoding is dropped. **Requires the `encoding` feature**.
     ///
     /// # Parameters
-    /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    /// - `encoding`: `&'static Encoding` such as `encoding_rs::WINDOWS_1252`
     ///
     /// # Returns
-    /// Result<`()`>
+    /// Result<`String`>
     ///
     /// # Examples
     /// ```
-    /// use file_access::FilePath;
+    /// use file_access::{encoding_rs::WINDOWS_1252, FilePath};
     ///
     /// fn main() -> std::io::Result<()> {
     ///     Ok({
-    ///         let file_path: &str = "fp_write/absolute_or_relative.path";
-    ///         let file_path: String = String::from(file_path);
-    ///
-    ///         let text: &str = "Hello, World!";
-    ///         let text: String = String::from(text);
-    ///
-    ///         let file: FilePath = FilePath::access(&file_path);
-    ///         file.write_string(&text)?;
+    ///         let file: FilePath = FilePath::access(&"fp_read_string_with_encoding_example.txt");
+    ///         file.write_bytes(&b"caf\xE9")?;
+    ///         assert_eq!(file.read_string_with_encoding(WINDOWS_1252)?, "café");
     ///
     ///         // Clean-up:
-    ///         let file = FilePath::access(&"fp_write"); // ./fp_write/
     ///         file.delete()?;
     ///     })
     /// }
     /// ```
-    pub fn write_string<Text: AsRef<str>>(&self, text: &Text) -> Result<()> {
-        write_string(self, text)
+    #[cfg(feature = "encoding")]
+    pub fn read_string_with_encoding(&self, encoding: &'static Encoding) -> Result<String> {
+        read_string_with_encoding(self, encoding)
     }
 
-    /// Writes a list of text as lines to a file. This function will create the file **and its full directory path** if they don't exist,
-    /// and will entirely replace the contents with the provided strings each on its own line.
+    /// Reads the file and returns it as a string, in the encoding told by its byte order mark, or in `fallback` if it has none.
+    /// **Requires the `encoding` feature**.
     ///
     /// # Parameters
-    /// - `lines`: **borrowed** `Vec<AsRef<str>>` such as `Vec<String>` or `Vec<&str>`
+    /// - `fallback`: `&'static Encoding` such as `encoding_rs::UTF_8`
     ///
     /// # Returns
-    /// Result<`()`>
+    /// Result<`(String, &'static Encoding)`>
     ///
     /// # Examples
     /// ```
-    /// use file_access::FilePath;
+    /// use file_access::{encoding_rs::{UTF_16BE, UTF_8}, FilePath};
     ///
     /// fn main() -> std::io::Result<()> {
     ///     Ok({
-    ///         let file_path: &str = "fp_lines/absolute_or_relative.path";
-    ///         let file_path: String = String::from(file_path);
-    ///
-    ///         let lines: Vec<&str> = "Hello, World!".split_whitespace().collect();
-    ///         let lines: Vec<String> = lines.iter().map(ToString::to_string).collect();
-    ///
-    ///         let file: FilePath = FilePath::access(&file_path);
-    ///         file.write_lines(&lines)?;
+    ///         let file: FilePath = FilePath::access(&"fp_read_string_detect_encoding_example.txt");
+    ///         file.write_string_with_encoding(&"Hello", UTF_16BE, true)?;
+    ///         assert_eq!(file.read_string_detect_encoding(UTF_8)?.1, UTF_16BE);
     ///
     ///         // Clean-up:
-    ///         let file = FilePath::access(&"fp_lines"); // ./fp_lines/
     ///         file.delete()?;
     ///     })
     /// }
     /// ```
-    pub fn write_lines<Line: AsRef<str>>(&self, lines: &Vec<Line>) -> Result<()> {
-        write_lines(self, lines)
+    #[cfg(feature = "encoding")]
+    pub fn read_string_detect_encoding(
+        &self,
+        fallback: &'static Encoding,
+    ) -> Result<(String, &'static Encoding)> {
+        read_string_detect_encoding(self, fallback)
     }
 
-    /// Appends text to a file. This function will append the contents of the file,
-    /// or write a new one **and its full directory path** if they don't exist yet.
+    /// Writes text to the file in an encoding other than UTF-8, such as UTF-16LE or Windows-1252,
+    /// optionally starting it with a byte order mark. This function will create the file
+    /// **and its full directory path** if they don't exist. **Requires the `encoding` feature**.
     ///
     /// # Parameters
     /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    /// - `encoding`: `&'static Encoding` such as `encoding_rs::UTF_16LE`
+    /// - `bom`: whether to write a byte order mark
     ///
     /// # Returns
     /// Result<`()`>
     ///
     /// # Examples
     /// ```
-    /// use file_access::FilePath;
+    /// use file_access::{encoding_rs::UTF_16LE, FilePath};
     ///
     /// fn main() -> std::io::Result<()> {
     ///     Ok({
-    ///         let file_path: &str = "fp_append/absolute_or_relative.path";
-    ///         let file_path: String = String::from(file_path);
-    ///
-    ///         let text: &str = "Hello, World!";
-    ///         let text: String = String::from(text);
-    ///
-    ///         let file: FilePath = FilePath::access(&file_path);
-    ///         file.append_string(&text)?;
+    ///         let file: FilePath = FilePath::access(&"fp_write_string_with_encoding_example.txt");
+    ///         file.write_string_with_encoding(&"Hello", UTF_16LE, true)?;
+    ///         assert_eq!(file.read_string_with_encoding(UTF_16LE)?, "Hello");
     ///
     ///         // Clean-up:
-    ///         let file = FilePath::access(&"fp_append"); // ./fp_append/
     ///         file.delete()?;
     ///     })
     /// }
     /// ```
-    pub fn append_string<Text: AsRef<str>>(&self, text: &Text) -> Result<()> {
-        append_string(self, text)
+    #[cfg(feature = "encoding")]
+    pub fn write_string_with_encoding<Text: AsRef<str> + ?Sized>(
+        &self,
+        text: &Text,
+        encoding: &'static Encoding,
+        bom: bool,
+    ) -> Result<()> {
+        write_string_with_encoding(self, text, encoding, bom)
     }
 
-    /// Appends a list of text as lines to a file. This function will append the contents of the file,
-    /// or write a new one **and its full directory path** if they don't exist yet.
-    ///
-    /// # Parameters
-    /// - `lines`: **borrowed** `Vec<AsRef<str>>` such as `Vec<String>` or `Vec<&str>`
+    /// Reads the gzip-compressed file and returns its decompressed contents. **Requires the `gzip` feature**.
     ///
     /// # Returns
-    /// Result<`()`>
+    /// Result<`Vec<u8>`>
     ///
     /// # Examples
     /// ```
@@ -255,29 +466,24 @@ impl FilePath {
     ///
     /// fn main() -> std::io::Result<()> {
     ///     Ok({
-    ///         let file_path: &str = "fp_append_lines/absolute_or_relative.path";
-    ///         let file_path: String = String::from(file_path);
-    ///
-    ///         let lines: Vec<&str> = "Hello, World!".split_whitespace().collect();
-    ///         let lines: Vec<String> = lines.iter().map(ToString::to_string).collect();
-    ///
-    ///         let file: FilePath = FilePath::access(&file_path);
-    ///         file.append_lines(&lines)?;
+    ///         let file: FilePath = FilePath::access(&"fp_read_bytes_gz_example.bin.gz");
+    ///         file.write_bytes_gz(&[0, 1, 2])?;
+    ///         assert_eq!(file.read_bytes_gz()?, [0, 1, 2]);
     ///
     ///         // Clean-up:
-    ///         let file = FilePath::access(&"fp_append_lines"); // ./fp_append_lines/
     ///         file.delete()?;
     ///     })
     /// }
     /// ```
-    pub fn append_lines<Line: AsRef<str>>(&self, lines: &Vec<Line>) -> Result<()> {
-        append_lines(self, lines)
+    #[cfg(feature = "gzip")]
+    pub fn read_bytes_gz(&self) -> Result<Vec<u8>> {
+        read_bytes_gz(self)
     }
 
-    /// Deletes a file, or a directory **recursively**.
+    /// Reads the gzip-compressed file and returns its decompressed contents as a string. **Requires the `gzip` feature**.
     ///
     /// # Returns
-    /// Result<`()`>
+    /// Result<`String`>
     ///
     /// # Examples
     /// ```
@@ -285,28 +491,25 @@ impl FilePath {
     ///
     /// fn main() -> std::io::Result<()> {
     ///     Ok({
-    ///         let file_path: &str = "absolute_or_relative_path/to_a_file/or_a_directory";
-    ///         let file_path: String = String::from(file_path);
-    ///
-    ///         let file: FilePath = FilePath::access(&file_path);
-    ///         file.write_string(&"Hello, World!");
-    ///         file.delete()?; // delete file
+    ///         let file: FilePath = FilePath::access(&"fp_read_string_gz_example.log.gz");
+    ///         file.write_string_gz(&"Hello, World!")?;
+    ///         assert_eq!(file.read_string_gz()?, "Hello, World!");
     ///
-    ///         // Delete directory:
-    ///         let dir = FilePath::access(&"absolute_or_relative_path");
-    ///         dir.delete()?;
+    ///         // Clean-up:
+    ///         file.delete()?;
     ///     })
     /// }
     /// ```
-    pub fn delete(&self) -> Result<()> {
-        delete(self)
+    #[cfg(feature = "gzip")]
+    pub fn read_string_gz(&self) -> Result<String> {
+        read_string_gz(self)
     }
 
-    /// Copies the contents of a file and write it to a destination.
-    /// This function will entirely replace the contents of the destination if it already exists.
+    /// Compresses bytes with gzip and writes them to the file. This function will create the file
+    /// **and its full directory path** if they don't exist. **Requires the `gzip` feature**.
     ///
     /// # Parameters
-    /// - `to`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    /// - `bytes`: **borrowed** `AsRef<[u8]>` such as `&[u8]` or `Vec<u8>`
     ///
     /// # Returns
     /// Result<`()`>
@@ -317,30 +520,24 @@ impl FilePath {
     ///
     /// fn main() -> std::io::Result<()> {
     ///     Ok({
-    ///         let source: &str = "Cargo.toml";
-    ///         let source: String = String::from(source);
-    ///
-    ///         let destination: &str = "Cargo.toml.2";
-    ///         let destination: String = String::from(destination);
-    ///
-    ///         let file: FilePath = FilePath::access(&source);
-    ///         file.copy_to(&destination)?;
+    ///         let file: FilePath = FilePath::access(&"fp_write_bytes_gz_example.bin.gz");
+    ///         file.write_bytes_gz(&vec![0; 1024])?;
     ///
-    ///         // Delete file:
-    ///         let destination = FilePath::access(&destination);
-    ///         destination.delete()?;
+    ///         // Clean-up:
+    ///         file.delete()?;
     ///     })
     /// }
     /// ```
-    pub fn copy_to<Path: AsRef<str>>(&self, to: &Path) -> Result<()> {
-        copy(self, to)
+    #[cfg(feature = "gzip")]
+    pub fn write_bytes_gz<Bytes: AsRef<[u8]> + ?Sized>(&self, bytes: &Bytes) -> Result<()> {
+        write_bytes_gz(self, bytes)
     }
 
-    /// Copies the contents of a file, writes it to a destination and then deletes the source.
-    /// This function will entirely replace the contents of the destination if it already exists.
+    /// Compresses text with gzip and writes it to the file. This function will create the file
+    /// **and its full directory path** if they don't exist. **Requires the `gzip` feature**.
     ///
     /// # Parameters
-    /// - `to`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
     ///
     /// # Returns
     /// Result<`()`>
@@ -351,30 +548,23 @@ impl FilePath {
     ///
     /// fn main() -> std::io::Result<()> {
     ///     Ok({
-    ///         let source: &str = "file.1";
-    ///         let source: String = String::from(source);
-    ///
-    ///         let destination: &str = "file.2";
-    ///         let destination: String = String::from(destination);
-    ///
-    ///         let file: FilePath = FilePath::access(&source);
-    ///         file.write_string(&"Hello, World!")?;
-    ///         file.rename_to(&destination)?;
+    ///         let file: FilePath = FilePath::access(&"fp_write_string_gz_example.log.gz");
+    ///         file.write_string_gz(&"Hello, World!")?;
     ///
     ///         // Clean-up:
-    ///         let file = FilePath::access(&destination);
     ///         file.delete()?;
     ///     })
     /// }
     /// ```
-    pub fn rename_to<Path: AsRef<str>>(&self, to: &Path) -> Result<()> {
-        rename(self, to)
+    #[cfg(feature = "gzip")]
+    pub fn write_string_gz<Text: AsRef<str> + ?Sized>(&self, text: &Text) -> Result<()> {
+        write_string_gz(self, text)
     }
 
-    /// Queries metadata about the underlying file.
+    /// Reads the contents of a file and returns it as lines.
     ///
     /// # Returns
-    /// Result<`Metadata`>
+    /// Result<`Vec<String>`>
     ///
     /// # Examples
     /// ```
@@ -386,74 +576,4697 @@ impl FilePath {
     ///         let file_path: String = String::from(file_path);
     ///
     ///         let file: FilePath = FilePath::access(&file_path);
-    ///         let metadata: std::fs::Metadata = file.get_metadata()?;
-    ///         println!("{:#?}", metadata);
+    ///         let lines: Vec<String> = file.read_lines()?;
+    ///         lines.iter().for_each(|line| println!("{}", line));
     ///     })
     /// }
     /// ```
-    pub fn get_metadata(&self) -> Result<Metadata> {
-        get_metadata(self)
-    }
-}
-
-impl AsRef<str> for FilePath {
-    fn as_ref(&self) -> &str {
-        self.get_path.as_str()
+    pub fn read_lines(&self) -> Result<Lines> {
+        read_lines(self)
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Result;
 
-    // cargo test -- --show-output --test-threads=1
+    /// Lazily reads a file line by line through a buffer, so large files such as logs
+    /// can be processed without loading them fully. Line endings (`\n` or `\r\n`) are stripped.
+    ///
+    /// # Returns
+    /// Result<`impl Iterator<Item = Result<String>>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         let count = file.lines()?.filter(|line| line.is_ok()).count();
+    ///         println!("{count} lines");
+    ///     })
+    /// }
+    /// ```
+    pub fn lines(&self) -> Result<impl Iterator<Item = Result<String>>> {
+        lines(self)
+    }
+
+    /// Reads the first `n` lines of the file, stopping as soon as they were read.
+    ///
+    /// # Parameters
+    /// - `n`: how many lines to read, fewer being returned if the file is shorter
+    ///
+    /// # Returns
+    /// Result<`Vec<String>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         assert_eq!(file.head(1)?, ["[package]"]);
+    ///     })
+    /// }
+    /// ```
+    pub fn head(&self, n: usize) -> Result<Lines> {
+        head(self, n)
+    }
+
+    /// Reads the last `n` lines of the file, searching backwards from its end a chunk at a time.
+    ///
+    /// # Parameters
+    /// - `n`: how many lines to read, fewer being returned if the file is shorter
+    ///
+    /// # Returns
+    /// Result<`Vec<String>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         for line in file.tail(5)? {
+    ///             println!("{line}");
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    pub fn tail(&self, n: usize) -> Result<Lines> {
+        tail(self, n)
+    }
+
+    /// Reads the lines of the file within a range of line numbers, counted from `0`, streaming through
+    /// the file only up to the end of the range.
+    ///
+    /// # Parameters
+    /// - `range`: the line numbers to read, such as `20..40`, fewer being returned if the file is shorter
+    ///
+    /// # Returns
+    /// Result<`Vec<String>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         assert_eq!(file.read_lines_range(0..1)?, ["[package]"]);
+    ///     })
+    /// }
+    /// ```
+    pub fn read_lines_range(&self, range: Range<usize>) -> Result<Lines> {
+        read_lines_range(self, range)
+    }
+
+    /// Writes text to a file. This function will create the file **and its full directory path** if they don't exist,
+    /// and will entirely replace the contents.
+    ///
+    /// # Parameters
+    /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_write/absolute_or_relative.path";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let text: &str = "Hello, World!";
+    ///         let text: String = String::from(text);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_string(&text)?;
+    ///
+    ///         // Clean-up:
+    ///         let file = FilePath::access(&"fp_write"); // ./fp_write/
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn write_string<Text: AsRef<str> + ?Sized>(&self, text: &Text) -> Result<()> {
+        write_string(self, text)
+    }
+
+    /// Writes text to the file as set by `WriteOptions`, such as refusing to replace an existing file,
+    /// the permissions to create it with, or flushing it to disk. This function will create the file
+    /// **and its full directory path** if they don't exist.
+    ///
+    /// # Parameters
+    /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    /// - `options`: `WriteOptions`, such as `WriteOptions::new().create_new(true)`
+    ///
+    /// # Returns
+    /// Result<`()`>, failing with `ErrorKind::AlreadyExists` if `create_new` is set and the file exists
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, WriteOptions};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_write_string_with_example.txt");
+    ///         file.write_string_with(&"Hello, World!", WriteOptions::new().create_new(true).mode(0o600))?;
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn write_string_with<Text: AsRef<str> + ?Sized>(
+        &self,
+        text: &Text,
+        options: WriteOptions,
+    ) -> Result<()> {
+        write_string_with(self, text, options)
+    }
+
+    /// Replaces the file with one of its previous versions, as kept by writing it with `WriteOptions::versioned`:
+    /// `1` is the latest, `2` the one before it, and so on.
+    ///
+    /// # Parameters
+    /// - `n`: **owned** `usize`, from `1`
+    ///
+    /// # Returns
+    /// Result<`()`>, failing with `ErrorKind::NotFound` if there's no such version
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, WriteOptions};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_restore_version_example/app.conf");
+    ///         file.write_string_with(&"debug = false", WriteOptions::new().versioned(5))?;
+    ///         file.write_string_with(&"debug = true", WriteOptions::new().versioned(5))?;
+    ///
+    ///         file.restore_version(1)?;
+    ///         assert_eq!(file.read_string()?, "debug = false");
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_restore_version_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn restore_version(&self, n: usize) -> Result<()> {
+        restore_version(self, n)
+    }
+
+    /// Writes a list of text as lines to a file. This function will create the file **and its full directory path** if they don't exist,
+    /// and will entirely replace the contents with the provided strings each on its own line.
+    /// The last line isn't followed by a newline; for files that should end with one, such as `.gitignore`,
+    /// use `write_lines_with` and `WriteOptions::trailing_newline`.
+    ///
+    /// # Parameters
+    /// - `lines`: **borrowed** `Vec<AsRef<str>>` such as `Vec<String>` or `Vec<&str>`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_lines/absolute_or_relative.path";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let lines: Vec<&str> = "Hello, World!".split_whitespace().collect();
+    ///         let lines: Vec<String> = lines.iter().map(ToString::to_string).collect();
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_lines(&lines)?;
+    ///
+    ///         // Clean-up:
+    ///         let file = FilePath::access(&"fp_lines"); // ./fp_lines/
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn write_lines<Line: AsRef<str>>(&self, lines: &Vec<Line>) -> Result<()> {
+        write_lines(self, lines)
+    }
+
+    /// Writes a list of text as lines to the file as set by `WriteOptions`, such as ending the last line with a newline.
+    /// This function will create the file **and its full directory path** if they don't exist.
+    ///
+    /// # Parameters
+    /// - `lines`: **borrowed** `Vec<AsRef<str>>` such as `Vec<String>` or `Vec<&str>`
+    /// - `options`: `WriteOptions`, such as `WriteOptions::new().trailing_newline(true)`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, WriteOptions};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_write_lines_with_example.txt");
+    ///         file.write_lines_with(&vec!["a", "b"], WriteOptions::new().trailing_newline(true))?;
+    ///         assert_eq!(file.read_string()?, "a\nb\n");
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn write_lines_with<Line: AsRef<str>>(
+        &self,
+        lines: &Vec<Line>,
+        options: WriteOptions,
+    ) -> Result<()> {
+        write_lines_with(self, lines, options)
+    }
+
+    /// Checks whether the file ends with a newline. Only the last byte is read.
+    ///
+    /// # Returns
+    /// Result<`bool`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_ends_with_newline_example.txt");
+    ///         file.write_string(&"no newline")?;
+    ///         assert!(!file.ends_with_newline()?);
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn ends_with_newline(&self) -> Result<bool> {
+        ends_with_newline(self)
+    }
+
+    /// Writes text to the file without it ever existing in an incomplete state under any name.
+    /// On Linux the text is written to an anonymous `O_TMPFILE` first, which is then linked into place;
+    /// elsewhere, or when the filesystem doesn't support it, it's written to a temporary sibling file which is then renamed.
+    /// This function will create the file **and its full directory path** if they don't exist, and will entirely replace the contents.
+    ///
+    /// # Parameters
+    /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_anon_link/config.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_string_anon_then_link(&"debug = true")?;
+    ///
+    ///         // Clean-up:
+    ///         let file = FilePath::access(&"fp_anon_link"); // ./fp_anon_link/
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn write_string_anon_then_link<Text: AsRef<str> + ?Sized>(
+        &self,
+        text: &Text,
+    ) -> Result<()> {
+        write_string_anon_then_link(self, text)
+    }
+
+    /// Writes text to the file atomically: it's written to a temporary sibling file first, which is then renamed over the file,
+    /// so a crash mid-write can never leave a truncated file behind. With `sync`, the contents and the rename are also
+    /// flushed to disk before returning. This function will create the file **and its full directory path** if they don't exist.
+    ///
+    /// # Parameters
+    /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    /// - `sync`: `bool` whether to fsync before returning
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_atomic/config.toml");
+    ///         file.write_atomic(&"debug = true", true)?;
+    ///
+    ///         // Clean-up:
+    ///         let file = FilePath::access(&"fp_atomic"); // ./fp_atomic/
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn write_atomic<Text: AsRef<str> + ?Sized>(&self, text: &Text, sync: bool) -> Result<()> {
+        write_string_atomic(self, text, sync)
+    }
+
+    /// Reads a YAML file and deserializes it into a value. **Requires the `yaml` feature**.
+    ///
+    /// # Returns
+    /// Result<`T`>, failing with `ErrorKind::InvalidData` if the file isn't valid YAML for `T`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    /// use std::collections::BTreeMap;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_read_yaml/values.yaml");
+    ///         file.write_string(&"replicas: 3")?;
+    ///
+    ///         let values = file.read_yaml::<BTreeMap<String, u32>>()?;
+    ///         assert_eq!(values["replicas"], 3);
+    ///
+    ///         // Clean-up:
+    ///         let file = FilePath::access(&"fp_read_yaml"); // ./fp_read_yaml/
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn read_yaml<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        read_yaml(self)
+    }
+
+    /// Serializes a value as YAML and writes it to the file. This function will create the file **and its full directory path**
+    /// if they don't exist, and will entirely replace the contents. **Requires the `yaml` feature**.
+    ///
+    /// # Parameters
+    /// - `value`: **borrowed** `Serialize` such as a `#[derive(Serialize)]` struct, a `Vec` or a `BTreeMap`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_write_yaml/hosts.yaml");
+    ///         file.write_yaml(&vec!["web-1", "web-2"])?;
+    ///
+    ///         // Clean-up:
+    ///         let file = FilePath::access(&"fp_write_yaml"); // ./fp_write_yaml/
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn write_yaml<T: serde::Serialize + ?Sized>(&self, value: &T) -> Result<()> {
+        write_yaml(self, value)
+    }
+
+    /// Writes text to the file followed by a footer line holding its SHA-256 checksum, for `read_string_checked` to verify.
+    /// This function will create the file **and its full directory path** if they don't exist,
+    /// and will entirely replace the contents. **Requires the `hash` feature**, which is on by default.
+    ///
+    /// # Parameters
+    /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_write_string_checked.txt";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_string_checked(&"cached state")?;
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "hash")]
+    pub fn write_string_checked<Text: AsRef<str> + ?Sized>(&self, text: &Text) -> Result<()> {
+        write_string_checked(self, text)
+    }
+
+    /// Reads text written by `write_string_checked`, verifying it against the checksum in its footer.
+    /// Returns an `InvalidData` error wrapping `Corrupted` when the checksum doesn't match or the footer is missing.
+    /// **Requires the `hash` feature**, which is on by default.
+    ///
+    /// # Returns
+    /// Result<`String`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_read_string_checked.txt";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_string_checked(&"cached state")?;
+    ///         assert_eq!(file.read_string_checked()?, "cached state");
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "hash")]
+    pub fn read_string_checked(&self) -> Result<String> {
+        read_string_checked(self)
+    }
+
+    /// Edits the lines of the file in place: reads them, hands them to a closure, then atomically writes them back
+    /// joined with `\n`, keeping the file's trailing newline, if any.
+    ///
+    /// # Parameters
+    /// - `edit`: `FnOnce(&mut Vec<String>)` that edits the lines
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_edit_lines_example.txt");
+    ///         file.write_lines(&vec!["b", "a"])?;
+    ///
+    ///         file.edit_lines(|lines| lines.sort())?;
+    ///         assert_eq!(file.read_lines()?, ["a", "b"]);
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn edit_lines<Edit: FnOnce(&mut Lines)>(&self, edit: Edit) -> Result<()> {
+        edit_lines(self, edit)
+    }
+
+    /// Replaces every occurrence of a string in the file, rewriting it atomically if anything was replaced.
+    ///
+    /// # Parameters
+    /// - `from`: **borrowed** `AsRef<str>` such as `String` or `&str`, which can't be empty
+    /// - `to`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// Result<`usize`> of how many occurrences were replaced
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_replace_string_example.txt");
+    ///         file.write_string(&"Hello, World!")?;
+    ///
+    ///         assert_eq!(file.replace_string(&"World", &"Rust")?, 1);
+    ///         assert_eq!(file.read_string()?, "Hello, Rust!");
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn replace_string<From: AsRef<str> + ?Sized, To: AsRef<str> + ?Sized>(
+        &self,
+        from: &From,
+        to: &To,
+    ) -> Result<usize> {
+        replace_string(self, from, to)
+    }
+
+    /// Replaces every match of a regular expression in the file, where the replacement may refer to capture groups
+    /// as `$1` or `${name}`, rewriting it atomically if anything was replaced. **Requires the `regex` feature**.
+    ///
+    /// # Parameters
+    /// - `pattern`: **borrowed** `AsRef<str>` such as `String` or `&str`, in the `regex` crate's syntax
+    /// - `replacement`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// Result<`usize`> of how many matches were replaced
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_replace_regex_example.txt");
+    ///         file.write_string(&"a1 b22 c333")?;
+    ///
+    ///         assert_eq!(file.replace_regex(&r"\d+", &"#")?, 3);
+    ///         assert_eq!(file.read_string()?, "a# b# c#");
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn replace_regex<Pattern: AsRef<str> + ?Sized, Replacement: AsRef<str> + ?Sized>(
+        &self,
+        pattern: &Pattern,
+        replacement: &Replacement,
+    ) -> Result<usize> {
+        replace_regex(self, pattern, replacement)
+    }
+
+    /// Searches the file for the lines containing a string, like `grep -F`, streaming through it.
+    ///
+    /// # Parameters
+    /// - `pattern`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// Result<`Vec<(usize, String)>`> of the matching lines, with their line number counted from `1`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         for (line_number, line) in file.find_lines(&"version")? {
+    ///             println!("{line_number}: {line}");
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    pub fn find_lines<Pattern: AsRef<str> + ?Sized>(
+        &self,
+        pattern: &Pattern,
+    ) -> Result<Vec<(usize, String)>> {
+        find_lines(self, pattern)
+    }
+
+    /// Searches the file for the lines matching a regular expression, like `grep -E`, streaming through it.
+    /// **Requires the `regex` feature**.
+    ///
+    /// # Parameters
+    /// - `pattern`: **borrowed** `AsRef<str>` such as `String` or `&str`, in the `regex` crate's syntax
+    ///
+    /// # Returns
+    /// Result<`Vec<(usize, String)>`> of the matching lines, with their line number counted from `1`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         assert_eq!(file.find_lines_regex(&r"^\[package\]$")?[0].0, 1);
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn find_lines_regex<Pattern: AsRef<str> + ?Sized>(
+        &self,
+        pattern: &Pattern,
+    ) -> Result<Vec<(usize, String)>> {
+        find_lines_regex(self, pattern)
+    }
+
+    /// Appends text to a file. This function will append the contents of the file,
+    /// or write a new one **and its full directory path** if they don't exist yet.
+    ///
+    /// # Parameters
+    /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_append/absolute_or_relative.path";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let text: &str = "Hello, World!";
+    ///         let text: String = String::from(text);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.append_string(&text)?;
+    ///
+    ///         // Clean-up:
+    ///         let file = FilePath::access(&"fp_append"); // ./fp_append/
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn append_string<Text: AsRef<str> + ?Sized>(&self, text: &Text) -> Result<()> {
+        append_string(self, text)
+    }
+
+    /// Appends text to the file as one whole write, so that concurrent writers, across threads and processes,
+    /// interleave entire entries rather than pieces of them; with `lock`, an exclusive advisory lock is held for the write.
+    /// The text is flushed to disk before returning. See `append_string_sync`.
+    ///
+    /// # Parameters
+    /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    /// - `lock`: **owned** `bool`, whether to lock the file for the write
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let log: FilePath = FilePath::access(&"fp_append_string_sync_example/shared.log");
+    ///         log.append_string_sync(&"worker 2: done\n", true)?;
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_append_string_sync_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn append_string_sync<Text: AsRef<str> + ?Sized>(
+        &self,
+        text: &Text,
+        lock: bool,
+    ) -> Result<()> {
+        append_string_sync(self, text, lock)
+    }
+
+    /// Archives the file, such as a log, if it's due under a `RotationPolicy`, renaming it to `file.1`
+    /// (gzipped with `compress`) after shifting the older archives along; the next write starts a new file.
+    ///
+    /// # Parameters
+    /// - `policy`: `RotationPolicy`, such as `RotationPolicy { max_size: Some(1024), max_files: 3, ..Default::default() }`
+    ///
+    /// # Returns
+    /// Result<`bool`>, whether the file was rotated
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, RotationPolicy};
+    /// use std::time::Duration;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let log: FilePath = FilePath::access(&"fp_rotate_example/app.log");
+    ///         let policy = RotationPolicy {
+    ///             max_age: Some(Duration::from_secs(24 * 60 * 60)),
+    ///             max_files: 7,
+    ///             ..RotationPolicy::default()
+    ///         };
+    ///
+    ///         log.append_string(&"started\n")?;
+    ///         log.rotate(policy)?;
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_rotate_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn rotate(&self, policy: RotationPolicy) -> Result<bool> {
+        rotate(self, policy)
+    }
+
+    /// Takes an exclusive advisory lock on the file, waiting until no other process or handle holds a lock on it;
+    /// see `lock_exclusive`. The file is created if it doesn't exist, and the lock is released when the guard is dropped.
+    ///
+    /// # Returns
+    /// Result<`FileLock`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let state: FilePath = FilePath::access(&"fp_lock_exclusive_example/state.json");
+    ///         let lock = state.lock_exclusive()?;
+    ///         state.write_string(&"{}")?;
+    ///         drop(lock);
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_lock_exclusive_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn lock_exclusive(&self) -> Result<FileLock> {
+        lock_exclusive(self)
+    }
+
+    /// Takes a shared advisory lock on the file, waiting until no other process or handle holds an exclusive lock on it;
+    /// see `lock_shared`. The file is created if it doesn't exist, and the lock is released when the guard is dropped.
+    ///
+    /// # Returns
+    /// Result<`FileLock`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let state: FilePath = FilePath::access(&"fp_lock_shared_example/state.json");
+    ///         let lock = state.lock_shared()?;
+    ///         println!("{}", state.read_string()?);
+    ///         drop(lock);
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_lock_shared_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn lock_shared(&self) -> Result<FileLock> {
+        lock_shared(self)
+    }
+
+    /// Takes an exclusive advisory lock on the file like `lock_exclusive`, without waiting if it's held.
+    ///
+    /// # Returns
+    /// Result<`Option<FileLock>`>, `None` if another lock is held
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let state: FilePath = FilePath::access(&"fp_try_lock_exclusive_example/state.json");
+    ///         if let Some(lock) = state.try_lock_exclusive()? {
+    ///             state.write_string(&"{}")?;
+    ///             drop(lock);
+    ///         }
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_try_lock_exclusive_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn try_lock_exclusive(&self) -> Result<Option<FileLock>> {
+        try_lock_exclusive(self)
+    }
+
+    /// Takes a shared advisory lock on the file like `lock_shared`, without waiting if an exclusive lock is held.
+    ///
+    /// # Returns
+    /// Result<`Option<FileLock>`>, `None` if an exclusive lock is held
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let state: FilePath = FilePath::access(&"fp_try_lock_shared_example/state.json");
+    ///         assert!(state.try_lock_shared()?.is_some());
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_try_lock_shared_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn try_lock_shared(&self) -> Result<Option<FileLock>> {
+        try_lock_shared(self)
+    }
+
+    /// Takes an exclusive advisory lock on the file like `lock_exclusive`, waiting for at most `timeout`.
+    ///
+    /// # Parameters
+    /// - `timeout`: **owned** `Duration`
+    ///
+    /// # Returns
+    /// Result<`Option<FileLock>`>, `None` if the lock couldn't be taken in time
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    /// use std::time::Duration;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let state: FilePath = FilePath::access(&"fp_lock_exclusive_timeout_example/state.json");
+    ///         let lock = state.lock_exclusive_timeout(Duration::from_secs(1))?;
+    ///         assert!(lock.is_some());
+    ///
+    ///         // Clean-up:
+    ///         drop(lock);
+    ///         FilePath::access(&"fp_lock_exclusive_timeout_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn lock_exclusive_timeout(&self, timeout: Duration) -> Result<Option<FileLock>> {
+        lock_exclusive_timeout(self, timeout)
+    }
+
+    /// Takes a shared advisory lock on the file like `lock_shared`, waiting for at most `timeout`.
+    ///
+    /// # Parameters
+    /// - `timeout`: **owned** `Duration`
+    ///
+    /// # Returns
+    /// Result<`Option<FileLock>`>, `None` if the lock couldn't be taken in time
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    /// use std::time::Duration;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let state: FilePath = FilePath::access(&"fp_lock_shared_timeout_example/state.json");
+    ///         let lock = state.lock_shared_timeout(Duration::from_secs(1))?;
+    ///         assert!(lock.is_some());
+    ///
+    ///         // Clean-up:
+    ///         drop(lock);
+    ///         FilePath::access(&"fp_lock_shared_timeout_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn lock_shared_timeout(&self, timeout: Duration) -> Result<Option<FileLock>> {
+        lock_shared_timeout(self, timeout)
+    }
+
+    /// Appends a list of text as lines to a file. This function will append the contents of the file,
+    /// or write a new one **and its full directory path** if they don't exist yet.
+    ///
+    /// # Parameters
+    /// - `lines`: **borrowed** `Vec<AsRef<str>>` such as `Vec<String>` or `Vec<&str>`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_append_lines/absolute_or_relative.path";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let lines: Vec<&str> = "Hello, World!".split_whitespace().collect();
+    ///         let lines: Vec<String> = lines.iter().map(ToString::to_string).collect();
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.append_lines(&lines)?;
+    ///
+    ///         // Clean-up:
+    ///         let file = FilePath::access(&"fp_append_lines"); // ./fp_append_lines/
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn append_lines<Line: AsRef<str>>(&self, lines: &Vec<Line>) -> Result<()> {
+        append_lines(self, lines)
+    }
+
+    /// Appends a single line to a file, starting it on a new line unless the file is empty or already ends with a newline.
+    /// This function will write a new file **and its full directory path** if they don't exist yet.
+    ///
+    /// # Parameters
+    /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_append_line_example.txt");
+    ///         file.write_string(&"first")?;
+    ///
+    ///         file.append_line(&"second")?;
+    ///         assert_eq!(file.read_lines()?, ["first", "second"]);
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn append_line<Text: AsRef<str> + ?Sized>(&self, text: &Text) -> Result<()> {
+        append_line(self, text)
+    }
+
+    /// Inserts text at the start of a file, streaming the file into a temporary sibling that then replaces it atomically.
+    /// This function will write a new file **and its full directory path** if they don't exist yet.
+    ///
+    /// # Parameters
+    /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_prepend_string_example.txt");
+    ///         file.write_string(&"World!")?;
+    ///
+    ///         file.prepend_string(&"Hello, ")?;
+    ///         assert_eq!(file.read_string()?, "Hello, World!");
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn prepend_string<Text: AsRef<str> + ?Sized>(&self, text: &Text) -> Result<()> {
+        prepend_string(self, text)
+    }
+
+    /// Inserts lines at the start of a file, each on its own line, streaming the file into a temporary sibling
+    /// that then replaces it atomically. This function will write a new file **and its full directory path**
+    /// if they don't exist yet.
+    ///
+    /// # Parameters
+    /// - `lines`: **borrowed** `Vec<AsRef<str>>` such as `Vec<String>` or `Vec<&str>`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_prepend_lines_example.txt");
+    ///         file.write_string(&"body")?;
+    ///
+    ///         file.prepend_lines(&vec!["header"])?;
+    ///         assert_eq!(file.read_lines()?, ["header", "body"]);
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn prepend_lines<Line: AsRef<str>>(&self, lines: &Vec<Line>) -> Result<()> {
+        prepend_lines(self, lines)
+    }
+
+    /// Reads the contents of a file as bytes, such as an image or an archive.
+    ///
+    /// # Returns
+    /// Result<`Vec<u8>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         let bytes: Vec<u8> = file.read_bytes()?;
+    ///         println!("{} bytes", bytes.len());
+    ///     })
+    /// }
+    /// ```
+    pub fn read_bytes(&self) -> Result<Vec<u8>> {
+        read_bytes(self)
+    }
+
+    /// Writes bytes to a file. This function will create the file **and its full directory path** if they don't exist,
+    /// and will entirely replace the contents.
+    ///
+    /// # Parameters
+    /// - `bytes`: **borrowed** `AsRef<[u8]>` such as `Vec<u8>`, `[u8; N]` or `&[u8]`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_write_bytes/image.png");
+    ///         file.write_bytes(&vec![0x89, b'P', b'N', b'G'])?;
+    ///
+    ///         // Clean-up:
+    ///         let file = FilePath::access(&"fp_write_bytes"); // ./fp_write_bytes/
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn write_bytes<Bytes: AsRef<[u8]> + ?Sized>(&self, bytes: &Bytes) -> Result<()> {
+        write_bytes(self, bytes)
+    }
+
+    /// Reads up to `len` bytes of the file starting at byte `offset`, without reading the rest;
+    /// fewer bytes are returned if the file ends first.
+    ///
+    /// # Parameters
+    /// - `offset`: **owned** `u64`
+    /// - `len`: **owned** `usize`
+    ///
+    /// # Returns
+    /// Result<`Vec<u8>`>
+    pub fn read_bytes_at(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        read_bytes_at(self, offset, len)
+    }
+
+    /// Maps the file read-only into memory, dereferencing to its bytes, such as to search or parse a large file
+    /// without reading it into a buffer. **Requires the `mmap` feature**.
+    ///
+    /// # Safety
+    /// The file must not be truncated or written to while the `MappedFile` lives, see `map_readonly`.
+    ///
+    /// # Returns
+    /// Result<`MappedFile`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         // SAFETY: Cargo.toml isn't modified while mapped
+    ///         let map = unsafe { file.map_readonly()? };
+    ///         assert!(map.windows(4).any(|bytes| bytes == b"name"));
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "mmap")]
+    pub unsafe fn map_readonly(&self) -> Result<MappedFile> {
+        unsafe { map_readonly(self) }
+    }
+
+    /// Empties the **existing** file in place, such as a log file, keeping its permissions, owner and hard links.
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let log: FilePath = FilePath::access(&"fp_truncate_example.log");
+    ///         log.write_string(&"lots of lines")?;
+    ///         log.truncate()?;
+    ///         assert_eq!(log.size()?, 0);
+    ///
+    ///         // Clean-up:
+    ///         log.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn truncate(&self) -> Result<()> {
+        truncate(self)
+    }
+
+    /// Resizes the **existing** file to `len` bytes in place, cutting it short or extending it with zeros.
+    ///
+    /// # Parameters
+    /// - `len`: **owned** `u64`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    pub fn set_len(&self, len: u64) -> Result<()> {
+        set_len(self, len)
+    }
+
+    /// Opens the file for reading through a buffer, to hand it to anything that reads from a `BufRead`/`Read`.
+    ///
+    /// # Returns
+    /// Result<`BufReader<File>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    /// use std::io::BufRead;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         let first = file.open_reader()?.lines().next().unwrap()?;
+    ///         assert_eq!(first, "[package]");
+    ///     })
+    /// }
+    /// ```
+    pub fn open_reader(&self) -> Result<BufReader<File>> {
+        open_reader(self)
+    }
+
+    /// Opens the file for writing through a buffer, to hand it to anything that writes to a `Write`,
+    /// appending to or replacing its contents as set by `WriteMode`; creates the file **and its full directory path**
+    /// if they don't exist. Call `flush` when done to get the errors of the last writes.
+    ///
+    /// # Parameters
+    /// - `mode`: `WriteMode`, such as `WriteMode::Append`
+    ///
+    /// # Returns
+    /// Result<`BufWriter<File>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, WriteMode};
+    /// use std::io::Write;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_open_writer_example.log");
+    ///         let mut writer = file.open_writer(WriteMode::Append)?;
+    ///         writeln!(writer, "started")?;
+    ///         writer.flush()?;
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn open_writer(&self, mode: WriteMode) -> Result<BufWriter<File>> {
+        open_writer(self, mode)
+    }
+
+    /// Writes everything a reader yields to the file, streaming it through a buffer, such as the body of a download;
+    /// creates the file **and its full directory path** if they don't exist, and entirely replaces the contents.
+    ///
+    /// # Parameters
+    /// - `reader`: **mutably borrowed** `Read` such as `File`, `TcpStream` or `&[u8]`
+    ///
+    /// # Returns
+    /// Result<`u64`>, the number of bytes written
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_write_from_example.txt");
+    ///         file.write_from(&mut "Hello, World!".as_bytes())?;
+    ///
+    ///         let mut copy: Vec<u8> = vec![];
+    ///         file.read_into(&mut copy)?;
+    ///         assert_eq!(copy, b"Hello, World!");
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn write_from<Reader: io::Read + ?Sized>(&self, reader: &mut Reader) -> Result<u64> {
+        write_from(self, reader)
+    }
+
+    /// Streams the contents of the file into a writer through a buffer, such as into a socket.
+    ///
+    /// # Parameters
+    /// - `writer`: **mutably borrowed** `Write` such as `File`, `TcpStream` or `Vec<u8>`
+    ///
+    /// # Returns
+    /// Result<`u64`>, the number of bytes read
+    pub fn read_into<Writer: io::Write + ?Sized>(&self, writer: &mut Writer) -> Result<u64> {
+        read_into(self, writer)
+    }
+
+    /// Reads the file in chunks of `chunk_size` bytes through an iterator, such as to hash or upload it
+    /// with bounded memory; every chunk is full except the last.
+    ///
+    /// # Parameters
+    /// - `chunk_size`: **owned** `usize`
+    ///
+    /// # Returns
+    /// Result<`Chunks`>, failing with `ErrorKind::InvalidInput` if `chunk_size` is `0`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         let mut size = 0;
+    ///         for chunk in file.read_chunks(4096)? {
+    ///             size += chunk?.len() as u64;
+    ///         }
+    ///         assert_eq!(size, file.size()?);
+    ///     })
+    /// }
+    /// ```
+    pub fn read_chunks(&self, chunk_size: usize) -> Result<Chunks> {
+        read_chunks(self, chunk_size)
+    }
+
+    /// Reads the bytes of the file in a range of offsets, such as its header or magic bytes, without reading the rest;
+    /// the range is cut short where the file ends.
+    ///
+    /// # Parameters
+    /// - `range`: **owned** `Range<u64>`, such as `0..4`
+    ///
+    /// # Returns
+    /// Result<`Vec<u8>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         assert_eq!(file.read_range(0..9)?, b"[package]");
+    ///     })
+    /// }
+    /// ```
+    pub fn read_range(&self, range: Range<u64>) -> Result<Vec<u8>> {
+        read_range(self, range)
+    }
+
+    /// Writes bytes into the **existing** file starting at byte `offset`, overwriting just that span in place;
+    /// writing past the end extends the file, with zeros filling any gap.
+    ///
+    /// # Parameters
+    /// - `offset`: **owned** `u64`
+    /// - `bytes`: **borrowed** `AsRef<[u8]>` such as `Vec<u8>`, `[u8; N]` or `&[u8]`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_write_bytes_at_example.bin");
+    ///         file.write_bytes(&b"v1:ok")?;
+    ///         file.write_bytes_at(1, &b"2")?;
+    ///         assert_eq!(file.read_bytes_at(0, 2)?, b"v2");
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn write_bytes_at<Bytes: AsRef<[u8]> + ?Sized>(
+        &self,
+        offset: u64,
+        bytes: &Bytes,
+    ) -> Result<()> {
+        write_bytes_at(self, offset, bytes)
+    }
+
+    /// Appends bytes to a file. This function will append the contents of the file,
+    /// or write a new one **and its full directory path** if they don't exist yet.
+    ///
+    /// # Parameters
+    /// - `bytes`: **borrowed** `AsRef<[u8]>` such as `Vec<u8>`, `[u8; N]` or `&[u8]`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_append_bytes/data.bin");
+    ///         file.append_bytes(&[0x00, 0xFF])?;
+    ///
+    ///         // Clean-up:
+    ///         let file = FilePath::access(&"fp_append_bytes"); // ./fp_append_bytes/
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn append_bytes<Bytes: AsRef<[u8]> + ?Sized>(&self, bytes: &Bytes) -> Result<()> {
+        append_bytes(self, bytes)
+    }
+
+    /// Creates this path as an empty directory. Its parent has to exist already; see `create_dir_all` to create it too.
+    ///
+    /// # Returns
+    /// Result<`()`>, failing with `ErrorKind::AlreadyExists` if something is already at the path,
+    /// or `ErrorKind::NotFound` if its parent doesn't exist
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir: FilePath = FilePath::access(&"fp_create_dir_example");
+    ///         dir.create_dir()?;
+    ///         assert!(dir.is_dir());
+    ///
+    ///         // Clean-up:
+    ///         dir.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn create_dir(&self) -> Result<()> {
+        create_dir(self)
+    }
+
+    /// Creates this path as a directory along with its full directory path, doing nothing if it exists already.
+    ///
+    /// # Returns
+    /// Result<`()`>, failing if a file is in the way
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir: FilePath = FilePath::access(&"fp_create_dir_all_example/a/b");
+    ///         dir.create_dir_all()?;
+    ///         assert!(dir.is_dir());
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_create_dir_all_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn create_dir_all(&self) -> Result<()> {
+        create_dir_all(self)
+    }
+
+    /// Creates the full directory path this file would go in, without creating the file.
+    /// Does nothing if the directory exists, or if the path has no parent.
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let log: FilePath = FilePath::access(&"fp_ensure_parent_example/logs/app.log");
+    ///         log.ensure_parent_exists()?;
+    ///         assert!(!log.exists());
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_ensure_parent_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn ensure_parent_exists(&self) -> Result<()> {
+        ensure_parent_exists(self)
+    }
+
+    /// Deletes a file, or a directory **recursively**.
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "absolute_or_relative_path/to_a_file/or_a_directory";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_string(&"Hello, World!");
+    ///         file.delete()?; // delete file
+    ///
+    ///         // Delete directory:
+    ///         let dir = FilePath::access(&"absolute_or_relative_path");
+    ///         dir.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn delete(&self) -> Result<()> {
+        delete(self)
+    }
+
+    /// Moves the file or directory to the platform's trash, where it can be restored from, instead of deleting
+    /// it permanently like `delete`. **Requires the `trash` feature**.
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_trash_example/notes.txt");
+    ///         file.write_string(&"Hello, World!")?;
+    ///         file.trash()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "trash")]
+    pub fn trash(&self) -> Result<()> {
+        trash(self)
+    }
+
+    /// Returns a guard which deletes the file, or the directory along with everything in it, when it's dropped,
+    /// unless `keep` is called. The guard dereferences to the `FilePath`.
+    ///
+    /// # Returns
+    /// `DeleteOnDrop`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file = FilePath::access(&"fp_delete_on_drop_example.txt");
+    ///         let _guard = file.delete_on_drop();
+    ///         file.write_string(&"Hello, World!")?;
+    ///     }) // deleted here
+    /// }
+    /// ```
+    pub fn delete_on_drop(&self) -> DeleteOnDrop {
+        delete_on_drop(self)
+    }
+
+    /// Copies the contents of a file and write it to a destination.
+    /// The contents are streamed byte-for-byte, so binary and very large files copy in constant memory.
+    /// This function will create the destination's full directory path if it doesn't exist,
+    /// and will entirely replace the contents of the destination if it already exists.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let source: &str = "Cargo.toml";
+    ///         let source: String = String::from(source);
+    ///
+    ///         let destination: &str = "Cargo.toml.2";
+    ///         let destination: String = String::from(destination);
+    ///
+    ///         let file: FilePath = FilePath::access(&source);
+    ///         file.copy_to(&destination)?;
+    ///
+    ///         // Delete file:
+    ///         let destination = FilePath::access(&destination);
+    ///         destination.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn copy_to<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<()> {
+        copy(self, to)
+    }
+
+    /// Copies the file to a destination as set by `CopyOptions`, such as keeping its permissions and timestamps.
+    /// Creates the destination's full directory path if it doesn't exist, and entirely replaces an existing destination.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    /// - `options`: `CopyOptions`, such as `CopyOptions::new().preserve_permissions(true)`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{CopyOptions, FilePath};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         let options = CopyOptions::new().preserve_permissions(true).preserve_times(true);
+    ///         file.copy_to_with(&"fp_copy_to_with_example/Cargo.toml", options)?;
+    ///
+    ///         // Clean-up:
+    ///         file_access::delete(&"fp_copy_to_with_example")?;
+    ///     })
+    /// }
+    /// ```
+    pub fn copy_to_with<Path: AsPath + ?Sized>(
+        &self,
+        to: &Path,
+        options: CopyOptions,
+    ) -> Result<()> {
+        copy_with(self, to, options)
+    }
+
+    /// Copies the file like `copy_to`, calling `progress` after each chunk copied.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    /// - `progress`: **mutably borrowed** `Progress` such as a `FnMut(&ProgressUpdate)` closure
+    ///
+    /// # Returns
+    /// Result<`u64`>, the number of bytes copied
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, ProgressUpdate};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         file.copy_to_with_progress(&"fp_copy_to_with_progress_example/Cargo.toml", &mut |update: &ProgressUpdate| {
+    ///             eprint!("\r{} bytes", update.bytes);
+    ///         })?;
+    ///
+    ///         // Clean-up:
+    ///         file_access::delete(&"fp_copy_to_with_progress_example")?;
+    ///     })
+    /// }
+    /// ```
+    pub fn copy_to_with_progress<Path: AsPath + ?Sized, P: Progress>(
+        &self,
+        to: &Path,
+        progress: &mut P,
+    ) -> Result<u64> {
+        copy_with_progress(self, to, progress)
+    }
+
+    /// Reserves disk space for the file up to `len` bytes, extending it with zeros if it's shorter;
+    /// creates the file **and its full directory path** if they don't exist.
+    ///
+    /// # Parameters
+    /// - `len`: **owned** `u64`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_allocate_example/data.db");
+    ///         file.allocate(64 * 1024)?;
+    ///         assert_eq!(file.size()?, 64 * 1024);
+    ///
+    ///         // Clean-up:
+    ///         file_access::delete(&"fp_allocate_example")?;
+    ///     })
+    /// }
+    /// ```
+    pub fn allocate(&self, len: u64) -> Result<()> {
+        allocate(self, len)
+    }
+
+    /// Copies the file like `copy_to`, then syncs the destination and reads it back to compare it with the source.
+    /// If they differ, the destination is removed and `ErrorKind::InvalidData` is returned.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         file.copy_verified_to(&"fp_copy_verified_example/Cargo.toml")?;
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_copy_verified_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn copy_verified_to<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<()> {
+        copy_verified(self, to)
+    }
+
+    /// Copies the file like `copy_to`, but only if the destination doesn't exist or was modified before it,
+    /// leaving a destination that is as new untouched.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`bool`>, whether the file was copied
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         if file.copy_to_if_newer(&"fp_copy_if_newer_example/Cargo.toml")? {
+    ///             println!("updated");
+    ///         }
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_copy_if_newer_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn copy_to_if_newer<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<bool> {
+        copy_if_newer(self, to)
+    }
+
+    /// Copies the file like `copy_to`, but only if the destination doesn't exist or its contents differ,
+    /// leaving a destination with the same contents untouched.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`bool`>, whether the file was copied
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         if file.copy_to_if_different(&"fp_copy_if_different_example/Cargo.toml")? {
+    ///             println!("updated");
+    ///         }
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_copy_if_different_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn copy_to_if_different<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<bool> {
+        copy_if_different(self, to)
+    }
+
+    /// Makes a destination directory mirror this directory, recursively, as set by `SyncOptions`:
+    /// new or changed files are copied over, and extraneous ones can be deleted.
+    ///
+    /// # Parameters
+    /// - `dest`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    /// - `options`: **borrowed** `SyncOptions`, such as `&SyncOptions::new().delete_extraneous(true)`
+    ///
+    /// # Returns
+    /// Result<`SyncReport`> of the files added, updated, deleted and left unchanged
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, SyncOptions};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir: FilePath = FilePath::access(&"src");
+    ///         let report = dir.sync_to(&"fp_sync_to_example/src", &SyncOptions::new())?;
+    ///         println!("{} files copied", report.added.len() + report.updated.len());
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_sync_to_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn sync_to<Path: AsPath + ?Sized>(
+        &self,
+        dest: &Path,
+        options: &SyncOptions,
+    ) -> Result<SyncReport> {
+        sync_dir(self, dest, options)
+    }
+
+    /// Streams the file through a transformation into a destination, such as to compress, encrypt or re-encode it.
+    /// The closure is given a buffered reader of the file and a buffered writer, which writes to a temporary sibling
+    /// of the destination that replaces it (after being synced to disk) only if the closure succeeds.
+    /// This function will create the destination's full directory path if it doesn't exist.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    /// - `transform`: **owned** `FnOnce(&mut BufReader<File>, &mut BufWriter<File>) -> std::io::Result<()>`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    /// use std::io::{Read, Write};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_pipe_to.txt";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_string(&"Hello, World!")?;
+    ///         file.pipe_to(&"fp_pipe_to.rot13", |reader, writer| {
+    ///             for byte in reader.bytes() {
+    ///                 let byte = byte?;
+    ///                 let rotated = match byte {
+    ///                     b'a'..=b'z' => (byte - b'a' + 13) % 26 + b'a',
+    ///                     b'A'..=b'Z' => (byte - b'A' + 13) % 26 + b'A',
+    ///                     _ => byte,
+    ///                 };
+    ///                 writer.write_all(&[rotated])?;
+    ///             }
+    ///             Ok(())
+    ///         })?;
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///         FilePath::access(&"fp_pipe_to.rot13").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn pipe_to<Path: AsPath + ?Sized>(
+        &self,
+        to: &Path,
+        transform: impl FnOnce(&mut BufReader<File>, &mut BufWriter<File>) -> io::Result<()>,
+    ) -> Result<()> {
+        pipe(self, to, transform)
+    }
+
+    /// Streams the file through a transformation into a destination like `pipe_to`, as set by `PipeOptions`,
+    /// such as to skip the fsyncs of a scratch file that doesn't need to survive a power loss.
+    /// This function will create the destination's full directory path if it doesn't exist.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    /// - `options`: `PipeOptions`, such as `PipeOptions::new().sync(true)`
+    /// - `transform`: **owned** `FnOnce(&mut BufReader<File>, &mut BufWriter<File>) -> std::io::Result<()>`
+    ///
+    /// # Returns
+    /// Result<`()`>, failing with `ErrorKind::InvalidInput` if `atomic` isn't set and the destination is the file itself
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, PipeOptions};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file = FilePath::access(&"fp_pipe_to_with.txt");
+    ///         file.write_string(&"Hello, World!")?;
+    ///
+    ///         file.pipe_to_with(&"fp_pipe_to_with.bak", PipeOptions::new(), |reader, writer| {
+    ///             std::io::copy(reader, writer).map(|_| ())
+    ///         })?;
+    ///         assert_eq!(FilePath::access(&"fp_pipe_to_with.bak").read_string()?, "Hello, World!");
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///         FilePath::access(&"fp_pipe_to_with.bak").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn pipe_to_with<Path: AsPath + ?Sized>(
+        &self,
+        to: &Path,
+        options: PipeOptions,
+        transform: impl FnOnce(&mut BufReader<File>, &mut BufWriter<File>) -> io::Result<()>,
+    ) -> Result<()> {
+        pipe_with(self, to, options, transform)
+    }
+
+    /// Copies the file to a destination together with the attributes selected by `preserve`,
+    /// such as extended attributes, ACLs, SELinux contexts and (on macOS) file flags. The contents are copied byte-for-byte,
+    /// along with the permissions. This function will create the destination's full directory path if it doesn't exist,
+    /// and will entirely replace the contents of the destination if it already exists.
+    ///
+    /// Attributes that cannot be carried over don't fail the copy; each of them is reported as a `PreserveWarning` instead.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    /// - `preserve`: `Preserve` such as `Preserve::ALL` or `Preserve::XATTRS | Preserve::ACLS`
+    ///
+    /// # Returns
+    /// Result<`Vec<PreserveWarning>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, Preserve};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let source: &str = "Cargo.toml";
+    ///         let source: String = String::from(source);
+    ///
+    ///         let destination: &str = "Cargo.toml.bak";
+    ///         let destination: String = String::from(destination);
+    ///
+    ///         let file: FilePath = FilePath::access(&source);
+    ///         for warning in file.copy_to_preserving(&destination, Preserve::ALL)? {
+    ///             eprintln!("not preserved: {}", warning);
+    ///         }
+    ///
+    ///         // Delete file:
+    ///         let destination = FilePath::access(&destination);
+    ///         destination.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn copy_to_preserving<Path: AsPath + ?Sized>(
+        &self,
+        to: &Path,
+        preserve: Preserve,
+    ) -> Result<Vec<PreserveWarning>> {
+        copy_preserving(self, to, preserve)
+    }
+
+    /// Clones a file to a destination. On macOS this creates a copy-on-write clone via `clonefile(2)`,
+    /// which is instantaneous on APFS; on other platforms, or when the volume doesn't support cloning,
+    /// it falls back to a regular `copy_to`. This function will entirely replace the contents of the destination if it already exists.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let source: &str = "Cargo.toml";
+    ///         let source: String = String::from(source);
+    ///
+    ///         let destination: &str = "Cargo.toml.clone";
+    ///         let destination: String = String::from(destination);
+    ///
+    ///         let file: FilePath = FilePath::access(&source);
+    ///         file.clone_to(&destination)?;
+    ///
+    ///         // Delete file:
+    ///         let destination = FilePath::access(&destination);
+    ///         destination.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn clone_to<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<()> {
+        clone(self, to)
+    }
+
+    /// Moves a file to a destination. This is an atomic `fs::rename` when both live on the same filesystem;
+    /// across filesystems it falls back to copying the contents to the destination and then deleting the source.
+    /// This function will create the destination's full directory path if it doesn't exist,
+    /// and will entirely replace the contents of the destination if it already exists.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let source: &str = "file.1";
+    ///         let source: String = String::from(source);
+    ///
+    ///         let destination: &str = "file.2";
+    ///         let destination: String = String::from(destination);
+    ///
+    ///         let file: FilePath = FilePath::access(&source);
+    ///         file.write_string(&"Hello, World!")?;
+    ///         file.rename_to(&destination)?;
+    ///
+    ///         // Clean-up:
+    ///         let file = FilePath::access(&destination);
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn rename_to<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<()> {
+        rename(self, to)
+    }
+
+    /// Moves the file like `rename_to`, then flushes it and the directories it was moved out of and into to disk,
+    /// so that after a power loss it's found at the destination.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    pub fn rename_to_durable<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<()> {
+        rename_durable(self, to)
+    }
+
+    /// Flushes the file's contents and metadata to disk, so they survive a power loss;
+    /// on Unix a directory can be synced too, making the entries created, renamed or deleted in it durable.
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_sync_all_example/journal.log");
+    ///         file.append_string(&"BEGIN\n")?;
+    ///         file.sync_all()?;
+    ///
+    ///         // Clean-up:
+    ///         file_access::delete(&"fp_sync_all_example")?;
+    ///     })
+    /// }
+    /// ```
+    pub fn sync_all(&self) -> Result<()> {
+        sync_all(self)
+    }
+
+    /// Flushes the file's contents to disk like `sync_all`, but only the metadata needed to read them back.
+    ///
+    /// # Returns
+    /// Result<`()`>
+    pub fn sync_data(&self) -> Result<()> {
+        sync_data(self)
+    }
+
+    /// Queries metadata about the underlying file.
+    ///
+    /// # Returns
+    /// Result<`Metadata`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         let metadata: std::fs::Metadata = file.get_metadata()?;
+    ///         println!("{:#?}", metadata);
+    ///     })
+    /// }
+    /// ```
+    pub fn get_metadata(&self) -> Result<Metadata> {
+        get_metadata(self)
+    }
+
+    /// Queries metadata about the file or directory, following symlinks, without opening it:
+    /// this works for files the process may stat but not open, and doesn't consume a file descriptor.
+    ///
+    /// # Returns
+    /// Result<`Metadata`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         println!("{} bytes", file.stat()?.len());
+    ///     })
+    /// }
+    /// ```
+    pub fn stat(&self) -> Result<Metadata> {
+        stat(self)
+    }
+
+    /// Like `stat`, but queries a symlink itself rather than what it points to.
+    ///
+    /// # Returns
+    /// Result<`Metadata`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         assert!(!file.lstat()?.file_type().is_symlink());
+    ///     })
+    /// }
+    /// ```
+    pub fn lstat(&self) -> Result<Metadata> {
+        lstat(self)
+    }
+
+    /// Checks whether the file or directory exists, following symlinks: a broken symlink doesn't exist.
+    /// Any error, such as a denied permission, counts as `false`.
+    ///
+    /// # Returns
+    /// `bool`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         assert!(file.exists());
+    ///     })
+    /// }
+    /// ```
+    pub fn exists(&self) -> bool {
+        path_of(self).exists()
+    }
+
+    /// Checks whether the path is an existing regular file, following symlinks.
+    /// Any error, such as a denied permission, counts as `false`.
+    ///
+    /// # Returns
+    /// `bool`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         assert!(file.is_file());
+    ///     })
+    /// }
+    /// ```
+    pub fn is_file(&self) -> bool {
+        path_of(self).is_file()
+    }
+
+    /// Checks whether the path is an existing directory, following symlinks.
+    /// Any error, such as a denied permission, counts as `false`.
+    ///
+    /// # Returns
+    /// `bool`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "src";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         assert!(file.is_dir());
+    ///     })
+    /// }
+    /// ```
+    pub fn is_dir(&self) -> bool {
+        path_of(self).is_dir()
+    }
+
+    /// Checks whether the path is an existing directory with nothing in it, following symlinks.
+    /// Any error, such as a denied permission, counts as `false`.
+    ///
+    /// # Returns
+    /// `bool`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir: FilePath = FilePath::access(&"is_empty_dir_example");
+    ///         dir.create_dir()?;
+    ///         assert!(dir.is_empty_dir());
+    ///         assert!(!FilePath::access(&"src").is_empty_dir());
+    ///
+    ///         // Clean-up:
+    ///         dir.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn is_empty_dir(&self) -> bool {
+        fs::read_dir(&self.path).is_ok_and(|mut entries| entries.next().is_none())
+    }
+
+    /// Checks whether the path is a symlink itself, whether or not what it points to exists.
+    /// Any error, such as a denied permission, counts as `false`.
+    ///
+    /// # Returns
+    /// `bool`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         assert!(!file.is_symlink());
+    ///     })
+    /// }
+    /// ```
+    pub fn is_symlink(&self) -> bool {
+        path_of(self).is_symlink()
+    }
+
+    /// Makes this path a symbolic link pointing to `target`, creating its full directory path if it doesn't exist;
+    /// a relative `target` is resolved from the link's directory. Fails with `ErrorKind::AlreadyExists`
+    /// if something is already at this path.
+    ///
+    /// # Parameters
+    /// - `target`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let current: FilePath = FilePath::access(&"fp_symlink_example/current");
+    ///         current.symlink_to(&"releases/v2")?;
+    ///         assert!(current.is_symlink());
+    ///
+    ///         // Clean-up:
+    ///         file_access::delete(&"fp_symlink_example")?;
+    ///     })
+    /// }
+    /// ```
+    pub fn symlink_to<Path: AsPath + ?Sized>(&self, target: &Path) -> Result<()> {
+        symlink(target, self)
+    }
+
+    /// Reads where the symbolic link points to, as it was created, without following it.
+    ///
+    /// # Returns
+    /// Result<`PathBuf`>
+    pub fn read_link(&self) -> Result<PathBuf> {
+        read_link(self)
+    }
+
+    /// Resolves the path to the absolute path of what it refers to, following every symlink along the way;
+    /// the path must exist.
+    ///
+    /// # Returns
+    /// Result<`PathBuf`>
+    pub fn resolve(&self) -> Result<PathBuf> {
+        resolve(self)
+    }
+
+    /// Copies the file like `copy_to`, except that a symlink is copied as a symlink with the same target.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    pub fn copy_symlink_to<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<()> {
+        copy_symlink(self, to)
+    }
+
+    /// Creates a hard link at `to` for the file: another name for the same contents, on the same filesystem.
+    /// Creates the destination's full directory path if it doesn't exist, and fails with `ErrorKind::AlreadyExists`
+    /// if something is already there.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_hard_link_example/build.tmp");
+    ///         file.write_string(&"done")?;
+    ///         file.hard_link_to(&"fp_hard_link_example/build.out")?;
+    ///         assert_eq!(file.link_count()?, 2);
+    ///
+    ///         // Clean-up:
+    ///         file_access::delete(&"fp_hard_link_example")?;
+    ///     })
+    /// }
+    /// ```
+    pub fn hard_link_to<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<()> {
+        hard_link(self, to)
+    }
+
+    /// Counts the hard links to the file, which is how many names its contents go by.
+    ///
+    /// # Returns
+    /// Result<`u64`>
+    pub fn link_count(&self) -> Result<u64> {
+        link_count(self)
+    }
+
+    /// Queries the filesystem containing the **existing** file or directory:
+    /// its type, its total/free/available space and its block size.
+    ///
+    /// # Returns
+    /// Result<`FilesystemInfo`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         let info = file.filesystem_info()?;
+    ///         println!("{:#?}", info);
+    ///     })
+    /// }
+    /// ```
+    pub fn filesystem_info(&self) -> Result<FilesystemInfo> {
+        filesystem_info(self)
+    }
+
+    /// Checks whether the file and another path live on the same filesystem, by comparing their device ids
+    /// (volume serial numbers on Windows). A path that doesn't exist yet is judged by its nearest existing ancestor,
+    /// so this can tell ahead of time whether a `rename_to` will stay on one device, or whether a hard link is possible.
+    ///
+    /// # Parameters
+    /// - `other`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`bool`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         if file.same_filesystem(&"/mnt/backup")? {
+    ///             println!("a rename will do");
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    pub fn same_filesystem<Other: AsPath + ?Sized>(&self, other: &Other) -> Result<bool> {
+        same_filesystem(self, other)
+    }
+
+    /// Classifies the volume containing the **existing** file or directory as `Local`, `Network`, `Removable` or `Ramdisk`,
+    /// so that e.g. memory maps and advisory locks can be avoided on network shares. Linux and Windows can tell all of them apart;
+    /// macOS only tells `Local` from `Network`, and other platforms report `Unknown`.
+    ///
+    /// # Returns
+    /// Result<`VolumeKind`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, VolumeKind};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         let kind: VolumeKind = file.volume_kind()?;
+    ///         println!("{:?}", kind);
+    ///     })
+    /// }
+    /// ```
+    pub fn volume_kind(&self) -> Result<VolumeKind> {
+        volume_kind(self)
+    }
+
+    /// Reads the interpreter line of the script, such as `/usr/bin/env python3` for a file starting with
+    /// `#!/usr/bin/env python3`. Returns `None` when the file doesn't start with `#!`.
+    ///
+    /// # Returns
+    /// Result<`Option<String>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_shebang.sh";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_lines(&vec!["#!/bin/bash", "echo hello"])?;
+    ///         assert_eq!(file.shebang()?.as_deref(), Some("/bin/bash"));
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn shebang(&self) -> Result<Option<String>> {
+        shebang(self)
+    }
+
+    /// Checks whether the file can be run: on Unix, whether any execute permission bit is set;
+    /// on Windows, whether its extension is listed in `PATHEXT`.
+    ///
+    /// # Returns
+    /// Result<`bool`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         assert!(!file.is_executable()?);
+    ///     })
+    /// }
+    /// ```
+    pub fn is_executable(&self) -> Result<bool> {
+        is_executable(self)
+    }
+
+    /// Adds or removes the execute permission of the file, like `chmod +x` / `chmod -x`.
+    /// Windows decides executability by extension instead, so there this fails with `ErrorKind::Unsupported`.
+    ///
+    /// # Parameters
+    /// - `executable`: **owned** `bool`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_set_executable.sh";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_lines(&vec!["#!/bin/sh", "echo hello"])?;
+    ///         # #[cfg(unix)]
+    ///         file.set_executable(true)?;
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn set_executable(&self, executable: bool) -> Result<()> {
+        set_executable(self, executable)
+    }
+
+    /// Queries the permissions of the file or directory, following symlinks.
+    ///
+    /// # Returns
+    /// Result<`Permissions`>
+    pub fn permissions(&self) -> Result<std::fs::Permissions> {
+        permissions(self)
+    }
+
+    /// Makes the file read-only, or writable again (on Unix, for the owner only).
+    ///
+    /// # Parameters
+    /// - `readonly`: **owned** `bool`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_set_readonly_example.lock");
+    ///         file.write_string(&"generated")?;
+    ///         file.set_readonly(true)?;
+    ///         assert!(file.permissions()?.readonly());
+    ///
+    ///         // Clean-up:
+    ///         file.set_readonly(false)?;
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn set_readonly(&self, readonly: bool) -> Result<()> {
+        set_readonly(self, readonly)
+    }
+
+    /// Gets the permission bits of the file or directory, such as `0o644`. **Unix only**.
+    ///
+    /// # Returns
+    /// Result<`u32`>
+    #[cfg(unix)]
+    pub fn mode(&self) -> Result<u32> {
+        mode(self)
+    }
+
+    /// Sets the permission bits of the file or directory, such as `0o600`, like `chmod`. **Unix only**.
+    ///
+    /// # Parameters
+    /// - `mode`: **owned** `u32`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    #[cfg(unix)]
+    pub fn set_mode(&self, mode: u32) -> Result<()> {
+        set_mode(self, mode)
+    }
+
+    /// Changes the user and/or group owning the file or directory, like `chown`; `None` leaves that one as it is.
+    /// **Requires the `unix` feature**, Unix only.
+    ///
+    /// # Parameters
+    /// - `uid`: **owned** `Option<u32>`, such as from `lookup_user`
+    /// - `gid`: **owned** `Option<u32>`, such as from `lookup_group`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"/var/lib/myapp/state.db");
+    ///         file.set_owner(Some(file_access::lookup_user(&"myapp")?), None)?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(all(unix, feature = "unix"))]
+    pub fn set_owner(&self, uid: Option<u32>, gid: Option<u32>) -> Result<()> {
+        set_owner(self, uid, gid)
+    }
+
+    /// Gets the size of the file in bytes, following symlinks.
+    ///
+    /// # Returns
+    /// Result<`u64`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         println!("{} bytes", file.size()?);
+    ///     })
+    /// }
+    /// ```
+    pub fn size(&self) -> Result<u64> {
+        size(self)
+    }
+
+    /// Checks whether the file has no contents, or the directory has no entries.
+    ///
+    /// # Returns
+    /// Result<`bool`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         assert!(!file.is_empty()?);
+    ///     })
+    /// }
+    /// ```
+    pub fn is_empty(&self) -> Result<bool> {
+        is_empty(self)
+    }
+
+    /// Gets the size of the file, formatted with binary units such as `1.4 GiB`.
+    ///
+    /// # Returns
+    /// Result<`String`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         println!("{}", file.human_size()?);
+    ///     })
+    /// }
+    /// ```
+    pub fn human_size(&self) -> Result<String> {
+        human_size(self)
+    }
+
+    /// Gets the size of the file formatted with binary units, such as `1.4 MiB`; the same as `human_size`.
+    ///
+    /// # Returns
+    /// Result<`String`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         assert_eq!(file.size_formatted()?, file.human_size()?);
+    ///     })
+    /// }
+    /// ```
+    pub fn size_formatted(&self) -> Result<String> {
+        human_size(self)
+    }
+
+    /// Gets when the file was last modified, following symlinks.
+    ///
+    /// # Returns
+    /// Result<`SystemTime`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         println!("{:?}", file.modified()?);
+    ///     })
+    /// }
+    /// ```
+    pub fn modified(&self) -> Result<SystemTime> {
+        modified(self)
+    }
+
+    /// Gets when the file was created, following symlinks, or `ErrorKind::Unsupported` where it isn't recorded.
+    ///
+    /// # Returns
+    /// Result<`SystemTime`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         if let Ok(created) = file.created() {
+    ///             println!("{:?}", created);
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    pub fn created(&self) -> Result<SystemTime> {
+        created(self)
+    }
+
+    /// Gets when the file was last accessed, following symlinks.
+    ///
+    /// # Returns
+    /// Result<`SystemTime`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         println!("{:?}", file.accessed()?);
+    ///     })
+    /// }
+    /// ```
+    pub fn accessed(&self) -> Result<SystemTime> {
+        accessed(self)
+    }
+
+    /// Sets when the file or directory was last modified, leaving the access time as it is.
+    ///
+    /// # Parameters
+    /// - `modified`: **owned** `SystemTime`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    /// use std::time::SystemTime;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_set_modified_example.txt");
+    ///         file.write_string(&"Hello, World!")?;
+    ///         file.set_modified(SystemTime::UNIX_EPOCH)?;
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn set_modified(&self, modified: SystemTime) -> Result<()> {
+        set_modified(self, modified)
+    }
+
+    /// Sets when the file or directory was last accessed and modified, like `touch -d`.
+    ///
+    /// # Parameters
+    /// - `accessed`: **owned** `SystemTime`
+    /// - `modified`: **owned** `SystemTime`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    /// use std::time::SystemTime;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"fp_set_times_example.txt");
+    ///         file.write_string(&"Hello, World!")?;
+    ///         file.set_times(SystemTime::UNIX_EPOCH, SystemTime::UNIX_EPOCH)?;
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn set_times(&self, accessed: SystemTime, modified: SystemTime) -> Result<()> {
+        set_times(self, accessed, modified)
+    }
+
+    /// Checks whether the file was modified more recently than another. A file that doesn't exist counts as older
+    /// than any that does, so a missing output is always out of date, and a missing source is never newer.
+    ///
+    /// # Parameters
+    /// - `other`: **borrowed** `AsPath` such as `String`, `&str`, `PathBuf` or `FilePath`
+    ///
+    /// # Returns
+    /// Result<`bool`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let source: FilePath = FilePath::access(&"Cargo.toml");
+    ///         if source.is_newer_than(&"fp_is_newer_than_example.toml")? {
+    ///             source.copy_to(&"fp_is_newer_than_example.toml")?;
+    ///         }
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_is_newer_than_example.toml").delete()?;
+    ///     })
+    /// }
+    /// ```
+    pub fn is_newer_than<Other: AsPath + ?Sized>(&self, other: &Other) -> Result<bool> {
+        is_newer_than(self, other)
+    }
+
+    /// Checks whether the file was modified less recently than another. A file that doesn't exist counts as older
+    /// than any that does.
+    ///
+    /// # Parameters
+    /// - `other`: **borrowed** `AsPath` such as `String`, `&str`, `PathBuf` or `FilePath`
+    ///
+    /// # Returns
+    /// Result<`bool`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let output: FilePath = FilePath::access(&"fp_is_older_than_example.o");
+    ///         assert!(output.is_older_than(&"Cargo.toml")?);
+    ///     })
+    /// }
+    /// ```
+    pub fn is_older_than<Other: AsPath + ?Sized>(&self, other: &Other) -> Result<bool> {
+        is_older_than(self, other)
+    }
+
+    /// Summarizes the file, directory or symlink (without following it) for end users: its type, size,
+    /// permissions in `rwx` and octal form, owner, timestamps in local time, and symlink target.
+    ///
+    /// # Returns
+    /// Result<`Description`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         println!("{}", file.describe()?);
+    ///     })
+    /// }
+    /// ```
+    pub fn describe(&self) -> Result<Description> {
+        describe(self)
+    }
+
+    /// Profiles the text file in one pass: its encoding and byte order mark, its dominant and any mixed line endings,
+    /// how many lines have trailing whitespace, and its indentation style.
+    ///
+    /// # Returns
+    /// Result<`TextProfile`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         println!("{:?}", file.text_profile()?);
+    ///     })
+    /// }
+    /// ```
+    pub fn text_profile(&self) -> Result<TextProfile> {
+        text_profile(self)
+    }
+
+    /// Detects the format of the file from its leading bytes rather than its extension, such as `png`, `zip`, `gzip`,
+    /// `pdf`, `elf` or `utf16le`; see `register_format` to recognize more. Returns `None` when no rule matches.
+    ///
+    /// # Returns
+    /// Result<`Option<String>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         assert_eq!(file.detect_format()?, None);
+    ///     })
+    /// }
+    /// ```
+    pub fn detect_format(&self) -> Result<Option<String>> {
+        detect_format(self)
+    }
+
+    /// Computes a SHA-256 Merkle hash over the file or directory tree: its relative paths, permission modes and contents,
+    /// so that two machines can verify they hold identical trees by comparing a single value.
+    /// The name and location of the root itself are not part of the hash, and file contents are hashed in parallel.
+    /// **Requires the `hash` feature**, which is on by default.
+    ///
+    /// # Returns
+    /// Result<`String`> of 64 lowercase hex digits
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "src";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         println!("{}", file.tree_hash()?);
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "hash")]
+    pub fn tree_hash(&self) -> Result<String> {
+        tree_hash(self)
+    }
+
+    /// Computes the digest of the file with an algorithm, reading it in chunks so it's never fully in memory.
+    /// **Requires the `hash`, `md5`, `sha1`, `crc32` or `blake3` feature**.
+    ///
+    /// # Parameters
+    /// - `algorithm`: `HashAlgorithm`, such as `HashAlgorithm::Sha256`
+    ///
+    /// # Returns
+    /// Result<`String`> of lowercase hex digits
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, HashAlgorithm};
+    ///
+    /// # #[cfg(feature = "hash")]
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         println!("{}", file.hash(HashAlgorithm::Sha256)?);
+    ///     })
+    /// }
+    /// # #[cfg(not(feature = "hash"))]
+    /// # fn main() {}
+    /// ```
+    #[cfg(any(
+        feature = "hash",
+        feature = "md5",
+        feature = "sha1",
+        feature = "crc32",
+        feature = "blake3"
+    ))]
+    pub fn hash(&self, algorithm: HashAlgorithm) -> Result<String> {
+        hash(self, algorithm)
+    }
+
+    /// Computes the digest of the file like `hash`, calling `progress` after each chunk read.
+    /// **Requires the `hash`, `md5`, `sha1`, `crc32` or `blake3` feature**.
+    ///
+    /// # Parameters
+    /// - `algorithm`: `HashAlgorithm`, such as `HashAlgorithm::Sha256`
+    /// - `progress`: **mutably borrowed** `Progress` such as a `FnMut(&ProgressUpdate)` closure
+    ///
+    /// # Returns
+    /// Result<`String`> of lowercase hex digits
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, HashAlgorithm, ProgressUpdate};
+    ///
+    /// # #[cfg(feature = "hash")]
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         let digest = file.hash_with_progress(HashAlgorithm::Sha256, &mut |update: &ProgressUpdate| {
+    ///             eprint!("\r{} bytes", update.bytes);
+    ///         })?;
+    ///         println!("{digest}");
+    ///     })
+    /// }
+    /// # #[cfg(not(feature = "hash"))]
+    /// # fn main() {}
+    /// ```
+    #[cfg(any(
+        feature = "hash",
+        feature = "md5",
+        feature = "sha1",
+        feature = "crc32",
+        feature = "blake3"
+    ))]
+    pub fn hash_with_progress<P: Progress>(
+        &self,
+        algorithm: HashAlgorithm,
+        progress: &mut P,
+    ) -> Result<String> {
+        hash_with_progress(self, algorithm, progress)
+    }
+
+    /// Routes the operations on this path through a `FileSystem`, such as a `MemoryFileSystem` in tests,
+    /// instead of the real disk.
+    ///
+    /// # Parameters
+    /// - `fs`: **borrowed** `FileSystem` such as `OsFileSystem` or `MemoryFileSystem`
+    ///
+    /// # Returns
+    /// `RoutedPath`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, MemoryFileSystem};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let fs = MemoryFileSystem::new();
+    ///         let file: FilePath = FilePath::access(&"settings/app.json");
+    ///
+    ///         file.on(&fs).write_string(&"{}")?;
+    ///         assert_eq!(file.on(&fs).read_string()?, "{}");
+    ///         assert!(!file.exists()); // nothing was written to disk
+    ///     })
+    /// }
+    /// ```
+    pub fn on<'a>(&self, fs: &'a dyn FileSystem) -> RoutedPath<'a> {
+        RoutedPath::new(fs, self.path.clone())
+    }
+
+    /// Checks whether the file has the same contents as another, stopping at the first difference.
+    /// Files of different sizes are told apart without reading them.
+    ///
+    /// # Parameters
+    /// - `other`: **borrowed** `AsPath` such as `String`, `&str`, `PathBuf` or `FilePath`
+    ///
+    /// # Returns
+    /// Result<`bool`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file: FilePath = FilePath::access(&"Cargo.toml");
+    ///         assert!(file.same_content_as(&"Cargo.toml")?);
+    ///     })
+    /// }
+    /// ```
+    pub fn same_content_as<Other: AsPath + ?Sized>(&self, other: &Other) -> Result<bool> {
+        same_content(self, other)
+    }
+
+    /// Packs the file, or the directory and everything in it, into a zip archive whose entries have the file
+    /// or directory as their root. This function will create the archive's full directory path if it doesn't exist.
+    /// **Requires the `zip` feature**.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir: FilePath = FilePath::access(&"src");
+    ///         dir.zip_to(&"fp_zip_to_example/src.zip")?;
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_zip_to_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "zip")]
+    pub fn zip_to<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<()> {
+        zip(self, to)
+    }
+
+    /// Unpacks the zip archive into a directory, creating it if it doesn't exist. **Requires the `zip` feature**.
+    ///
+    /// # Parameters
+    /// - `to_dir`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let archive: FilePath = FilePath::access(&"fp_unzip_to_example/Cargo.zip");
+    ///         FilePath::access(&"Cargo.toml").zip_to(&archive)?;
+    ///         archive.unzip_to(&"fp_unzip_to_example/out")?;
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_unzip_to_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "zip")]
+    pub fn unzip_to<Path: AsPath + ?Sized>(&self, to_dir: &Path) -> Result<()> {
+        unzip(self, to_dir)
+    }
+
+    /// Packs the file, or the directory and everything in it, into a tar archive, keeping permissions,
+    /// modification times and symlinks. This function will create the archive's full directory path if it doesn't exist.
+    /// **Requires the `tar` feature**.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir: FilePath = FilePath::access(&"src");
+    ///         dir.tar_to(&"fp_tar_to_example/src.tar")?;
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_tar_to_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "tar")]
+    pub fn tar_to<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<()> {
+        tar(self, to)
+    }
+
+    /// Packs the file, or the directory and everything in it, into a gzip-compressed tar archive (`.tar.gz`).
+    /// **Requires the `tar` and `gzip` features**.
+    ///
+    /// # Parameters
+    /// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir: FilePath = FilePath::access(&"src");
+    ///         dir.tar_gz_to(&"fp_tar_gz_to_example/src.tar.gz")?;
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_tar_gz_to_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(all(feature = "tar", feature = "gzip"))]
+    pub fn tar_gz_to<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<()> {
+        tar_gz(self, to)
+    }
+
+    /// Unpacks the tar archive (or, with the `gzip` feature, `.tar.gz` archive) into a directory,
+    /// creating it if it doesn't exist. **Requires the `tar` feature**.
+    ///
+    /// # Parameters
+    /// - `to_dir`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let archive: FilePath = FilePath::access(&"fp_untar_to_example/Cargo.tar");
+    ///         FilePath::access(&"Cargo.toml").tar_to(&archive)?;
+    ///         archive.untar_to(&"fp_untar_to_example/out")?;
+    ///
+    ///         // Clean-up:
+    ///         FilePath::access(&"fp_untar_to_example").delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "tar")]
+    pub fn untar_to<Path: AsPath + ?Sized>(&self, to_dir: &Path) -> Result<()> {
+        untar(self, to_dir)
+    }
+
+    /// Lists the entries (files, directories and links) of a directory, sorted by path, not recursively.
+    ///
+    /// # Parameters
+    /// - `options`: `ListOptions`, such as `ListOptions::default()` for the visible entries, with relative paths
+    ///
+    /// # Returns
+    /// Result<`Vec<FilePath>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, ListOptions};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir: FilePath = FilePath::access(&"src");
+    ///         for entry in dir.list(ListOptions::default())? {
+    ///             println!("{}", entry.as_ref());
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    pub fn list(&self, options: ListOptions) -> Result<Vec<FilePath>> {
+        list_dir(self, options)
+    }
+
+    /// Walks the descendants of a directory recursively and lazily, yielding each as a `FilePath`;
+    /// see `Walk` for the filters that can be chained onto it, such as `max_depth`, `extension` or `files_only`.
+    ///
+    /// # Returns
+    /// `Walk`, an `Iterator` of Result<`FilePath`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let dir: FilePath = FilePath::access(&"src");
+    ///         let sources = dir.walk().extension("rs").files_only();
+    ///         println!("{} source files", sources.count());
+    ///     })
+    /// }
+    /// ```
+    pub fn walk(&self) -> Walk {
+        walk(self)
+    }
+
+    /// Checks whether the path matches a glob pattern, such as `"logs/**/*.log"`, with the same syntax as `glob`;
+    /// the file doesn't need to exist.
+    ///
+    /// # Parameters
+    /// - `pattern`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// `bool`
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// let file: FilePath = FilePath::access(&"src/lib.rs");
+    /// assert!(file.matches_glob(&"src/*.rs"));
+    /// assert!(!file.matches_glob(&"tests/**"));
+    /// ```
+    pub fn matches_glob<Pattern: AsRef<str> + ?Sized>(&self, pattern: &Pattern) -> bool {
+        matches_glob(self, pattern)
+    }
+
+    /// Gets the SELinux security context of the file or directory, such as `system_u:object_r:httpd_sys_content_t:s0`.
+    /// Returns `None` when the file has no context, e.g. on systems without SELinux. **Linux only**.
+    ///
+    /// # Returns
+    /// Result<`Option<String>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         # #[cfg(target_os = "linux")]
+    ///         println!("{:?}", file.selinux_context()?);
+    ///     })
+    /// }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub fn selinux_context(&self) -> Result<Option<String>> {
+        selinux_context(self)
+    }
+
+    /// Sets the SELinux security context of the file or directory. **Linux only**.
+    ///
+    /// # Parameters
+    /// - `context`: **borrowed** `AsRef<str>` such as `String` or `&str`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "/var/www/html/index.html";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         # #[cfg(target_os = "linux")]
+    ///         file.set_selinux_context(&"system_u:object_r:httpd_sys_content_t:s0")?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub fn set_selinux_context<Context: AsRef<str> + ?Sized>(
+        &self,
+        context: &Context,
+    ) -> Result<()> {
+        set_selinux_context(self, context)
+    }
+
+    /// Reads the access control list of the file or directory: the POSIX ACL on Linux, with effective permissions,
+    /// the permission bits and extended ACL on macOS, and the DACL on Windows; see `file_access::acl`.
+    /// Other platforms return an `Unsupported` error.
+    ///
+    /// # Returns
+    /// Result<`Vec<AclEntry>`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "Cargo.toml";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         # #[cfg(any(target_os = "linux", target_os = "macos", windows))]
+    ///         for entry in file.acl()? {
+    ///             println!("{:?}", entry);
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "acl")]
+    pub fn acl(&self) -> Result<Vec<AclEntry>> {
+        acl(self)
+    }
+
+    /// Replaces the access control list of the file or directory. Missing `Owner`, `OwningGroup` and `Everyone`
+    /// entries are treated as granting nothing, and entries for the same principal are merged.
+    /// On Linux this writes a POSIX ACL, which cannot express `AclKind::Deny` entries, on macOS the permission bits
+    /// and extended ACL, and on Windows a DACL; see `file_access::set_acl`. Other platforms return an `Unsupported` error.
+    ///
+    /// # Parameters
+    /// - `entries`: **borrowed** `[AclEntry]`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{AclEntry, FilePath, Principal};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_set_acl.txt";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_string(&"secret")?;
+    ///
+    ///         // Only the owner and the service account can read:
+    ///         let entries = vec![
+    ///             AclEntry::allow(Principal::Owner).read().write(),
+    ///             AclEntry::allow(Principal::User("root".to_string())).read(),
+    ///         ];
+    ///         # #[cfg(any(target_os = "linux", target_os = "macos"))]
+    ///         file.set_acl(&entries)?;
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "acl")]
+    pub fn set_acl(&self, entries: &[AclEntry]) -> Result<()> {
+        set_acl(self, entries)
+    }
+
+    /// Writes Reed-Solomon parity blocks for the file to `<file>.par`, so that `repair` can later reconstruct
+    /// damaged regions. `redundancy` is the size of the parity relative to the file, in percent.
+    /// **Requires the `parity` feature**.
+    ///
+    /// # Parameters
+    /// - `redundancy`: **owned** `u8`, from `1` to `100` percent
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_protect.tar";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_string(&"precious archive")?;
+    ///         file.protect(10)?;
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///         FilePath::access(&"fp_protect.tar.par").delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "parity")]
+    pub fn protect(&self, redundancy: u8) -> Result<()> {
+        protect(self, redundancy)
+    }
+
+    /// Checks the file against the parity written by `protect`, and rewrites any damaged blocks from it.
+    /// **Requires the `parity` feature**.
+    ///
+    /// # Returns
+    /// Result<`usize`> of how many blocks were repaired
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::FilePath;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_repair.tar";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_string(&"precious archive")?;
+    ///         file.protect(10)?;
+    ///         assert_eq!(file.repair()?, 0);
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///         FilePath::access(&"fp_repair.tar.par").delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "parity")]
+    pub fn repair(&self) -> Result<usize> {
+        repair(self)
+    }
+
+    /// Signs the file with an Ed25519 key, writing the detached 64-byte signature next to it as `<file>.sig`.
+    /// What gets signed is the SHA-256 digest of the contents. **Requires the `sign` feature**.
+    ///
+    /// # Parameters
+    /// - `key`: **borrowed** `SigningKey`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, SigningKey};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_sign.txt";
+    ///         let file_path: String = String::from(file_path);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_string(&"release artifact")?;
+    ///         file.sign(&SigningKey::from_bytes(&[7; 32]))?;
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///         FilePath::access(&"fp_sign.txt.sig").delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "sign")]
+    pub fn sign(&self, key: &SigningKey) -> Result<()> {
+        sign(self, key)
+    }
+
+    /// Verifies the file against the detached signature written by `sign`. Returns `false` if the file or its signature
+    /// were tampered with or signed by another key. **Requires the `sign` feature**.
+    ///
+    /// # Parameters
+    /// - `key`: **borrowed** `VerifyingKey`
+    ///
+    /// # Returns
+    /// Result<`bool`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::{FilePath, SigningKey};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let file_path: &str = "fp_verify.txt";
+    ///         let file_path: String = String::from(file_path);
+    ///         let key = SigningKey::from_bytes(&[7; 32]);
+    ///
+    ///         let file: FilePath = FilePath::access(&file_path);
+    ///         file.write_string(&"release artifact")?;
+    ///         file.sign(&key)?;
+    ///         assert!(file.verify(&key.verifying_key())?);
+    ///
+    ///         // Clean-up:
+    ///         file.delete()?;
+    ///         FilePath::access(&"fp_verify.txt.sig").delete()?;
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "sign")]
+    pub fn verify(&self, key: &VerifyingKey) -> Result<bool> {
+        verify(self, key)
+    }
+
+    /// Writes a signed `sha256sum`-style manifest of every file below the directory. **Requires the `sign` feature**.
+    ///
+    /// # Parameters
+    /// - `manifest_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    /// - `key`: **borrowed** `SigningKey`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    #[cfg(feature = "sign")]
+    pub fn sign_manifest<Manifest: AsPath + ?Sized>(
+        &self,
+        manifest_path: &Manifest,
+        key: &SigningKey,
+    ) -> Result<()> {
+        sign_manifest(self, manifest_path, key)
+    }
+
+    /// Verifies a manifest written by `sign_manifest` against the files below the directory.
+    /// **Requires the `sign` feature**.
+    ///
+    /// # Parameters
+    /// - `manifest_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    /// - `key`: **borrowed** `VerifyingKey`
+    ///
+    /// # Returns
+    /// Result<`bool`>
+    #[cfg(feature = "sign")]
+    pub fn verify_manifest<Manifest: AsPath + ?Sized>(
+        &self,
+        manifest_path: &Manifest,
+        key: &VerifyingKey,
+    ) -> Result<bool> {
+        verify_manifest(self, manifest_path, key)
+    }
+}
+
+impl AsRef<str> for FilePath {
+    /// Borrows the path as a string, in which any part that isn't valid UTF-8 was replaced with `U+FFFD`.
+    fn as_ref(&self) -> &str {
+        self.get_path.as_str()
+    }
+}
+
+impl AsPath for FilePath {
+    fn as_path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+impl From<PathBuf> for FilePath {
+    fn from(path: PathBuf) -> Self {
+        Self {
+            get_path: path.to_string_lossy().to_string(),
+            path,
+        }
+    }
+}
+
+#[cfg(feature = "camino")]
+impl FilePath {
+    /// Borrows the path as a `camino::Utf8Path`, or `None` if it isn't valid UTF-8. **Requires the `camino` feature**.
+    ///
+    /// # Returns
+    /// Option<&`Utf8Path`>
+    ///
+    /// # Examples
+    /// ```
+    /// use camino::Utf8Path;
+    /// use file_access::FilePath;
+    ///
+    /// let file: FilePath = FilePath::access(&"src/lib.rs");
+    /// let path: &Utf8Path = file.as_utf8_path().unwrap();
+    /// assert_eq!(path.extension(), Some("rs"));
+    /// ```
+    pub fn as_utf8_path(&self) -> Option<&camino::Utf8Path> {
+        camino::Utf8Path::from_path(&self.path)
+    }
+}
+
+#[cfg(feature = "camino")]
+impl From<camino::Utf8PathBuf> for FilePath {
+    fn from(path: camino::Utf8PathBuf) -> Self {
+        Self::from(path.into_std_path_buf())
+    }
+}
+
+#[cfg(feature = "camino")]
+impl From<&camino::Utf8Path> for FilePath {
+    fn from(path: &camino::Utf8Path) -> Self {
+        Self::access(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    // cargo test -- --show-output --test-threads=1
     // cargo test <TESTNAME> --show-output
 
     #[test]
-    fn read_string() -> Result<()> {
+    fn join() -> Result<()> {
+        Ok({
+            // Arrange
+            let dir = FilePath::access(&"fp_join");
+
+            // Action
+            let file = dir.join("a/b.txt");
+            file.write_string(&"b")?;
+            let sibling = file.sibling(&String::from("c.txt"));
+            sibling.write_string(&"c")?;
+
+            // Assert
+            assert_eq!(path_of(&file), PathBuf::from("fp_join/a/b.txt"));
+            assert_eq!(path_of(&sibling), PathBuf::from("fp_join/a/c.txt"));
+            assert_eq!(FilePath::access(&"fp_join/a/c.txt").read_string()?, "c");
+            assert_eq!(
+                FilePath::access(&"top.txt").sibling("other.txt").as_ref(),
+                "other.txt"
+            );
+
+            // Clean-up
+            dir.delete()?;
+        })
+    }
+
+    #[test]
+    fn read_string() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"Cargo.toml");
+
+            // Action
+            let text = file.read_string()?;
+            println!("{text}");
+
+            // Assert
+            assert_ne!(text.len(), 0);
+        })
+    }
+
+    #[test]
+    fn read_string_with() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_read_string_with.txt");
+            file.write_bytes(&b"\xEF\xBB\xBFcaf\xE9")?;
+
+            // Action
+            let text = file.read_string_with(ReadOptions::new().strip_bom(true).lossy(true))?;
+
+            // Assert
+            assert_eq!(text, "caf\u{FFFD}");
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "encoding")]
+    fn encoding() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_encoding.txt");
+
+            // Action
+            file.write_string_with_encoding(&"Grüße", encoding_rs::UTF_16LE, true)?;
+
+            // Assert
+            assert_eq!(
+                file.read_string_with_encoding(encoding_rs::UTF_16LE)?,
+                "Grüße"
+            );
+            assert_eq!(
+                file.read_string_detect_encoding(encoding_rs::WINDOWS_1252)?,
+                ("Grüße".to_string(), encoding_rs::UTF_16LE)
+            );
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn gzip() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_gzip.log.gz");
+
+            // Action
+            file.write_string_gz(&"Hello, World!")?;
+
+            // Assert
+            assert_eq!(file.read_string_gz()?, "Hello, World!");
+            assert_eq!(
+                file.read_string_with(ReadOptions::new().gunzip(true))?,
+                "Hello, World!"
+            );
+            assert!(file.read_string().is_err());
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn read_lines() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"Cargo.toml");
+
+            // Action
+            let lines = file.read_lines()?;
+            for line in &lines {
+                println!("{line}");
+            }
+
+            // Assert
+            assert_ne!(lines.len(), 0);
+        })
+    }
+
+    #[test]
+    fn lines() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"Cargo.toml");
+
+            // Action
+            let lines = file.lines()?.collect::<crate::Result<Vec<_>>>()?;
+
+            // Assert
+            assert_eq!(lines, file.read_lines()?);
+        })
+    }
+
+    #[test]
+    fn head_and_tail() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_head_and_tail.txt");
+            file.write_lines(&vec!["a", "b", "c"])?;
+
+            // Action
+            let head = file.head(2)?;
+            let tail = file.tail(2)?;
+
+            // Assert
+            assert_eq!(head, ["a", "b"]);
+            assert_eq!(tail, ["b", "c"]);
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn read_lines_range() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_read_lines_range.txt");
+            file.write_lines(&vec!["a", "b", "c", "d"])?;
+
+            // Action
+            let page = file.read_lines_range(1..3)?;
+
+            // Assert
+            assert_eq!(page, ["b", "c"]);
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn write_string() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"write_string.txt");
+            let text = "Hello, World!";
+
+            // Action
+            file.write_string(&text)?;
+
+            // Assert
+            assert_eq!(file.read_string()?, text);
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn write_string_with() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_write_string_with.txt");
+            let options = WriteOptions::new().create_new(true);
+
+            // Action
+            file.write_string_with(&"first", options)?;
+
+            // Assert
+            assert!(file.write_string_with(&"second", options).is_err());
+            assert_eq!(file.read_string()?, "first");
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn restore_version() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_restore_version/file.txt");
+            let options = WriteOptions::new().versioned(1);
+            file.write_string_with(&"first", options)?;
+            file.write_string_with(&"second", options)?;
+
+            // Action
+            file.restore_version(1)?;
+
+            // Assert
+            assert_eq!(file.read_string()?, "first");
+            assert!(file.restore_version(0).is_err());
+
+            // Clean-up
+            FilePath::access(&"fp_restore_version").delete()?;
+        })
+    }
+
+    #[test]
+    fn write_lines_with() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_write_lines_with.txt");
+            file.write_string(&"a\nb\n")?;
+
+            // Action
+            let lines = file.read_lines()?;
+            let options = WriteOptions::new().trailing_newline(file.ends_with_newline()?);
+            file.write_lines_with(&lines, options)?;
+
+            // Assert
+            assert_eq!(file.read_string()?, "a\nb\n");
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn write_lines() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"write_lines.txt");
+            let lines = "Hello, World!"
+                .split_whitespace()
+                .map(ToString::to_string)
+                .collect();
+
+            // Action
+            file.write_lines(&lines)?;
+
+            // Assert
+            assert_eq!(file.read_lines()?, lines);
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn write_string_anon_then_link() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"write_anon.txt");
+            let text = "Hello, World!";
+
+            // Action
+            file.write_string_anon_then_link(&text)?;
+            file.write_string_anon_then_link(&text)?; // replace
+
+            // Assert
+            assert_eq!(file.read_string()?, text);
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn write_atomic() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"write_atomic.txt");
+
+            // Action
+            file.write_atomic(&"Hello", true)?;
+            file.write_atomic(&"Hello, World!", false)?; // replace
+
+            // Assert
+            assert_eq!(file.read_string()?, "Hello, World!");
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn yaml() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_yaml/hosts.yaml");
+            let hosts = vec!["web-1".to_string(), "web-2".to_string()];
+
+            // Action
+            file.write_yaml(&hosts)?;
+
+            // Assert
+            assert_eq!(file.read_yaml::<Vec<String>>()?, hosts);
+
+            // Clean-up
+            "fp_yaml".as_file().delete()?;
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "hash")]
+    fn write_string_checked() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_checked.txt");
+
+            // Action
+            file.write_string_checked(&"Hello, World!")?;
+
+            // Assert
+            assert_eq!(file.read_string_checked()?, "Hello, World!");
+            file.append_string(&"tampered")?;
+            assert_eq!(
+                file.read_string_checked().unwrap_err().kind(),
+                ErrorKind::InvalidData
+            );
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn edit_lines() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_edit_lines.txt");
+            file.write_lines(&vec!["a", "b", "c"])?;
+
+            // Action
+            file.edit_lines(|lines| {
+                lines.remove(0);
+            })?;
+
+            // Assert
+            assert_eq!(file.read_string()?, "b\nc");
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn replace() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_replace.txt");
+            file.write_string(&"red, green, red")?;
+
+            // Action
+            let replaced = file.replace_string(&"red", &"blue")?;
+
+            // Assert
+            assert_eq!(replaced, 2);
+            assert_eq!(file.read_string()?, "blue, green, blue");
+            #[cfg(feature = "regex")]
+            {
+                assert_eq!(file.replace_regex(&"b(lu)e", &"${1}")?, 2);
+                assert_eq!(file.read_string()?, "lu, green, lu");
+            }
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn find_lines() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_find_lines.txt");
+            file.write_lines(&vec!["alpha", "beta", "alphabet"])?;
+
+            // Action
+            let found = file.find_lines(&"alpha")?;
+
+            // Assert
+            assert_eq!(
+                found,
+                [(1, "alpha".to_string()), (3, "alphabet".to_string())]
+            );
+            #[cfg(feature = "regex")]
+            assert_eq!(file.find_lines_regex(&"^beta$")?, [(2, "beta".to_string())]);
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn append_string() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"append_string.txt");
+            let text = "Hello, World!";
+            file.write_string(&text)?;
+
+            // Action
+            file.append_string(&text)?;
+
+            // Assert
+            assert_eq!(file.read_string()?, format!("{text}{text}"));
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn append_lines() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"append_lines.txt");
+            let lines1 = vec!["1", "2"]; // .to_vec_string();
+            file.write_lines(&lines1)?;
+
+            // Action
+            let lines2 = vec!["3", "4"]; //.to_vec_string();
+            file.append_lines(&lines2)?;
+
+            // Assert
+            assert_eq!(file.read_lines()?, vec!["1", "2", "3", "4"]); // .to_vec_string());
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn append_line() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_append_line.txt");
+            file.write_string(&"a\n")?;
+
+            // Action
+            file.append_line(&"b")?;
+            file.append_line(&"c")?;
+
+            // Assert
+            assert_eq!(file.read_string()?, "a\nb\nc");
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn prepend() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_prepend.txt");
+            file.write_string(&"c")?;
+
+            // Action
+            file.prepend_lines(&vec!["b"])?;
+            file.prepend_string(&"a")?;
+
+            // Assert
+            assert_eq!(file.read_string()?, "ab\nc");
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn bytes() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"bytes.bin");
+            let bytes = [0x00, 0xFF, 0xFE, 0x80];
+
+            // Action
+            file.write_bytes(&bytes)?;
+            file.append_bytes(&bytes[..2])?;
+
+            // Assert
+            assert_eq!(file.read_bytes()?, [0x00, 0xFF, 0xFE, 0x80, 0x00, 0xFF]);
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn create_dir() -> Result<()> {
+        Ok({
+            // Arrange
+            let dir = FilePath::access(&"fp_create_dir/a");
+            let file = FilePath::access(&"fp_create_dir/b/c/file_access.txt");
+
+            // Action
+            let orphan = dir.join(&"b").create_dir();
+            dir.create_dir_all()?;
+            dir.join(&"b").create_dir()?;
+            file.ensure_parent_exists()?;
+
+            // Assert
+            assert_eq!(orphan.unwrap_err().kind(), ErrorKind::NotFound);
+            assert!(dir.join(&"b").is_dir());
+            assert!(path_of(&"fp_create_dir/b/c").is_dir());
+            assert!(!file.exists());
+
+            // Clean-up
+            FilePath::access(&"fp_create_dir").delete()?;
+        })
+    }
+
+    #[test]
+    fn delete() -> Result<()> {
+        Ok({
+            // Arrange
+            let path = "delete.txt";
+            let file = FilePath::access(&path);
+            mk_file(&path)?;
+
+            // Action
+            file.delete()?;
+
+            // Assert
+            assert!(!path_of(&path).exists(), "{path} should no longer exist");
+        })
+    }
+
+    #[test]
+    fn delete_on_drop() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_delete_on_drop.txt");
+
+            // Action
+            {
+                let guard = file.delete_on_drop();
+                guard.write_string(&"Hello, World!")?;
+            }
+
+            // Assert
+            assert!(!file.exists());
+        })
+    }
+
+    #[test]
+    fn copy() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "copy_from.txt";
+            let to = "copy_to.txt";
+            let file = FilePath::access(&from);
+            file.write_string(&"Hello, World!")?;
+
+            // Action
+            file.copy_to(&to)?;
+
+            // Assert
+            assert_eq!(
+                from.as_file().read_string()?,
+                to.as_file().read_string()?,
+                "{from} and {to} should contain the same text"
+            );
+
+            // Clean-up
+            from.as_file().delete()?;
+            to.as_file().delete()?;
+        })
+    }
+
+    #[test]
+    fn copy_verified() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_copy_verified_from.txt");
+            let to = FilePath::access(&"fp_copy_verified_to.txt");
+            file.write_string(&"Hello, World!")?;
+
+            // Action
+            file.copy_verified_to(&to)?;
+
+            // Assert
+            assert!(file.same_content_as(&to)?);
+
+            // Clean-up
+            file.delete()?;
+            to.delete()?;
+        })
+    }
+
+    #[test]
+    fn copy_to_if_newer_or_different() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_copy_if/from.txt");
+            let to = FilePath::access(&"fp_copy_if/to.txt");
+            file.write_string(&"Hello, World!")?;
+
+            // Action
+            let first = file.copy_to_if_newer(&to)?;
+            let second = file.copy_to_if_newer(&to)?;
+            let same = file.copy_to_if_different(&to)?;
+            to.write_string(&"Goodbye, World!")?;
+            let different = file.copy_to_if_different(&to)?;
+
+            // Assert
+            assert!(first && !second);
+            assert!(!same && different);
+            assert!(file.same_content_as(&to)?);
+
+            // Clean-up
+            FilePath::access(&"fp_copy_if").delete()?;
+        })
+    }
+
+    #[test]
+    fn sync_to() -> Result<()> {
+        Ok({
+            // Arrange
+            let dir = FilePath::access(&"fp_sync_to/src");
+            FilePath::access(&"fp_sync_to/src/a.txt").write_string(&"a")?;
+            FilePath::access(&"fp_sync_to/dest/b.txt").write_string(&"b")?;
+            let options = SyncOptions::new().delete_extraneous(true);
+
+            // Action
+            let report = dir.sync_to(&"fp_sync_to/dest", &options)?;
+
+            // Assert
+            assert_eq!(report.added, ["a.txt"].map(PathBuf::from));
+            assert_eq!(report.deleted, ["b.txt"].map(PathBuf::from));
+            assert_eq!(
+                FilePath::access(&"fp_sync_to/dest/a.txt").read_string()?,
+                "a"
+            );
+
+            // Clean-up
+            FilePath::access(&"fp_sync_to").delete()?;
+        })
+    }
+
+    #[test]
+    fn matches_glob() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_matches_glob/logs/2024/app.log");
+
+            // Assert
+            assert!(file.matches_glob(&"fp_matches_glob/**/*.log"));
+            assert!(file.matches_glob(&"*/logs/20[0-9][0-9]/app.???"));
+            assert!(!file.matches_glob(&"fp_matches_glob/*.log"));
+        })
+    }
+
+    #[test]
+    fn rotate() -> Result<()> {
+        Ok({
+            // Arrange
+            let log = FilePath::access(&"fp_rotate/app.log");
+            let policy = RotationPolicy {
+                max_files: 1,
+                ..RotationPolicy::default()
+            };
+            log.append_string(&"first")?;
+
+            // Action
+            let rotated = log.rotate(policy)?;
+
+            // Assert
+            assert!(rotated);
+            assert!(!log.as_path().exists());
+            assert_eq!(
+                FilePath::access(&"fp_rotate/app.log.1").read_string()?,
+                "first"
+            );
+
+            // Clean-up
+            FilePath::access(&"fp_rotate").delete()?;
+        })
+    }
+
+    #[test]
+    fn lock_exclusive_and_shared() -> Result<()> {
+        Ok({
+            // Arrange
+            let state = FilePath::access(&"fp_locks/state.txt");
+
+            // Action
+            let exclusive = state.lock_exclusive()?;
+            let blocked = state.try_lock_shared()?;
+            drop(exclusive);
+            let shared = state.lock_shared()?;
+
+            // Assert
+            assert!(blocked.is_none());
+            assert!(state.try_lock_shared()?.is_some());
+            assert!(state.lock_exclusive_timeout(Duration::ZERO)?.is_none());
+            drop(shared);
+            assert!(state.try_lock_exclusive()?.is_some());
+
+            // Clean-up
+            FilePath::access(&"fp_locks").delete()?;
+        })
+    }
+
+    #[test]
+    fn append_string_sync() -> Result<()> {
+        Ok({
+            // Arrange
+            let log = FilePath::access(&"fp_append_string_sync/shared.log");
+
+            // Action
+            log.append_string_sync(&"first\n", false)?;
+            log.append_string_sync(&"second\n", true)?;
+
+            // Assert
+            assert_eq!(log.read_string()?, "first\nsecond\n");
+
+            // Clean-up
+            FilePath::access(&"fp_append_string_sync").delete()?;
+        })
+    }
+
+    #[test]
+    fn sync_all() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_sync_all/a.txt");
+            file.write_string(&"a")?;
+
+            // Action
+            file.sync_all()?;
+            file.sync_data()?;
+            file.rename_to_durable(&"fp_sync_all/b.txt")?;
+
+            // Assert
+            assert!(!file.exists());
+            assert_eq!("fp_sync_all/b.txt".as_file().read_string()?, "a");
+
+            // Clean-up
+            "fp_sync_all".as_file().delete()?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn set_mode() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_set_mode.sh");
+            file.write_string(&"#!/bin/sh")?;
+
+            // Action
+            file.set_mode(0o755)?;
+
+            // Assert
+            assert_eq!(file.mode()?, 0o755);
+            assert!(file.is_executable()?);
+            file.set_readonly(true)?;
+            assert!(file.permissions()?.readonly());
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn symlink_to() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_symlink_to/file.txt");
+            let link = FilePath::access(&"fp_symlink_to/link.txt");
+            file.write_string(&"Hello, World!")?;
+
+            // Action
+            link.symlink_to(&"file.txt")?;
+            link.copy_symlink_to(&"fp_symlink_to/copy.txt")?;
+
+            // Assert
+            assert!(link.is_symlink());
+            assert_eq!(link.read_link()?, PathBuf::from("file.txt"));
+            assert_eq!(link.resolve()?, file.resolve()?);
+            assert!("fp_symlink_to/copy.txt".as_file().is_symlink());
+
+            // Clean-up
+            "fp_symlink_to".as_file().delete()?;
+        })
+    }
+
+    #[test]
+    fn file_uri() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"file_uri/a b#1%.txt");
+
+            // Action
+            let uri = file.to_file_uri()?;
+            let round_trip = FilePath::from_file_uri(&uri)?;
+
+            // Assert
+            assert!(uri.starts_with("file:///"));
+            assert!(uri.ends_with("/file_uri/a%20b%231%25.txt"));
+            assert_eq!(path_of(&round_trip), current_dir()?.join(path_of(&file)));
+            for uri in ["http://a/b", "file:a/b", "file:///a%zz"] {
+                let error = FilePath::from_file_uri(&uri).err().unwrap();
+                assert_eq!(error.kind(), ErrorKind::InvalidInput);
+            }
+            if cfg!(unix) {
+                let local = FilePath::from_file_uri(&"FILE://localhost/a/b?q#f")?;
+                assert_eq!(local.as_ref(), "/a/b");
+                assert!(FilePath::from_file_uri(&"file://server/share/a").is_err());
+            }
+        })
+    }
+
+    #[test]
+    fn long_paths() -> Result<()> {
+        Ok({
+            // Arrange
+            let deep = format!("long_paths/{}", vec!["a".repeat(40); 8].join("/"));
+            let file = FilePath::access(&format!("{deep}/file.txt"));
+            file.write_string(&"deep")?;
+
+            // Action
+            let full = FilePath::access(&file.get_full_path()?);
+            let relative = full.get_relative_path()?;
+
+            // Assert
+            assert!(full.as_ref().len() > 260);
+            assert_eq!(full.read_string()?, "deep");
+            assert_eq!(path_of(&relative), path_of(&file));
+
+            // Clean-up
+            FilePath::access(&"long_paths").delete()?;
+        })
+    }
+
+    #[test]
+    fn normalized() -> Result<()> {
+        Ok({
+            // Arrange
+            let paths = [
+                "a/./b//c/../d.txt",
+                "./",
+                "a/..",
+                "../../a",
+                "a/../../b",
+                "/../a/.",
+            ];
+
+            // Action
+            let normalized = paths.map(|path| path_of(&FilePath::access(&path).normalized()));
+
+            // Assert
+            assert_eq!(
+                normalized,
+                ["a/b/d.txt", ".", ".", "../../a", "../b", "/a"].map(PathBuf::from)
+            );
+        })
+    }
+
+    #[test]
+    fn pipe_to() -> Result<()> {
+        Ok({
+            use std::io::Read;
+
+            // Arrange
+            let file = FilePath::access(&"fp_pipe_from.txt");
+            let to = "fp_pipe_to/reversed.txt";
+            file.write_string(&"Hello, World!")?;
+
+            // Action
+            file.pipe_to(&to, |reader, writer| {
+                let mut text = String::new();
+                reader.read_to_string(&mut text)?;
+                writer.write_all(text.chars().rev().collect::<String>().as_bytes())
+            })?;
+
+            // Assert
+            assert_eq!(to.as_file().read_string()?, "!dlroW ,olleH");
+
+            // Clean-up
+            file.delete()?;
+            "fp_pipe_to".as_file().delete()?;
+        })
+    }
+
+    #[test]
+    fn copy_preserving() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "copy_preserving_from.txt";
+            let to = "copy_preserving_to.txt";
+            let file = FilePath::access(&from);
+            file.write_string(&"Hello, World!")?;
+
+            // Action
+            let warnings = file.copy_to_preserving(&to, Preserve::ALL)?;
+
+            // Assert
+            assert_eq!(
+                from.as_file().read_string()?,
+                to.as_file().read_string()?,
+                "{from} and {to} should contain the same text"
+            );
+            assert!(warnings.is_empty(), "{warnings:?}");
+
+            // Clean-up
+            from.as_file().delete()?;
+            to.as_file().delete()?;
+        })
+    }
+
+    #[test]
+    fn clone() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "clone_from.txt";
+            let to = "clone_to.txt";
+            let file = FilePath::access(&from);
+            file.write_string(&"Hello, World!")?;
+
+            // Action
+            file.clone_to(&to)?;
+
+            // Assert
+            assert_eq!(
+                from.as_file().read_string()?,
+                to.as_file().read_string()?,
+                "{from} and {to} should contain the same text"
+            );
+
+            // Clean-up
+            from.as_file().delete()?;
+            to.as_file().delete()?;
+        })
+    }
+
+    #[test]
+    fn rename() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "rename_from.txt";
+            let to = "rename_to.txt";
+            let text = "Hello, World!";
+            let file = FilePath::access(&from);
+            file.write_string(&text)?;
+
+            // Action
+            file.rename_to(&to)?;
+
+            // Assert
+            assert!(!path_of(&from).exists(), "{from} should no longer exist");
+            assert_eq!(
+                to.as_file().read_string()?,
+                text,
+                "{to} should contain: {text}"
+            );
+
+            // Clean-up
+            to.as_file().delete()?;
+        })
+    }
+
+    #[test]
+    fn shebang() -> Result<()> {
         Ok({
             // Arrange
-            let file = FilePath::access(&"Cargo.toml");
+            let file = FilePath::access(&"fp_shebang_test.sh");
+            file.write_lines(&vec!["#!/bin/sh", "echo hello"])?;
 
             // Action
-            let text = file.read_string()?;
-            println!("{text}");
+            #[cfg(unix)]
+            file.set_executable(true)?;
 
             // Assert
-            assert_ne!(text.len(), 0);
+            assert_eq!(file.shebang()?.as_deref(), Some("/bin/sh"));
+            #[cfg(unix)]
+            assert!(file.is_executable()?);
+
+            // Clean-up
+            file.delete()?;
         })
     }
 
     #[test]
-    fn read_lines() -> Result<()> {
+    fn stat() -> Result<()> {
         Ok({
             // Arrange
-            let file = FilePath::access(&"Cargo.toml");
+            let file = FilePath::access(&"fp_stat.txt");
+            file.write_string(&"Hello, World!")?;
 
             // Action
-            let lines = file.read_lines()?;
-            for line in &lines {
-                println!("{line}");
+            let metadata = file.stat()?;
+
+            // Assert
+            assert_eq!(metadata.len(), 13);
+            assert!(file.lstat()?.is_file());
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    fn predicates() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_predicates/file.txt");
+            let dir = FilePath::access(&"fp_predicates");
+            let missing = FilePath::access(&"fp_predicates/missing.txt");
+
+            // Action
+            file.write_string(&"Hello, World!")?;
+
+            // Assert
+            assert!(file.exists() && file.is_file() && !file.is_dir() && !file.is_symlink());
+            assert!(dir.exists() && dir.is_dir() && !dir.is_file());
+            assert!(!missing.exists() && !missing.is_file() && !missing.is_dir());
+            #[cfg(unix)]
+            {
+                let link = FilePath::access(&"fp_predicates/link");
+                std::os::unix::fs::symlink("missing.txt", "fp_predicates/link")?;
+                assert!(link.is_symlink() && !link.exists());
             }
 
+            // Clean-up
+            dir.delete()?;
+        })
+    }
+
+    #[test]
+    fn size() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_size.txt");
+            file.write_string(&"")?;
+
+            // Action
+            let empty = file.is_empty()?;
+            file.write_string(&"Hello, World!")?;
+
             // Assert
-            assert_ne!(lines.len(), 0);
+            assert!(empty);
+            assert_eq!(file.size()?, 13);
+            assert!(!file.is_empty()?);
+
+            // Clean-up
+            file.delete()?;
         })
     }
 
     #[test]
-    fn write_string() -> Result<()> {
+    fn times() -> Result<()> {
         Ok({
             // Arrange
-            let file = FilePath::access(&"write_string.txt");
-            let text = "Hello, World!";
+            let file = FilePath::access(&"fp_times.txt");
+            let stamp = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+            file.write_string(&"Hello, World!")?;
 
             // Action
-            file.write_string(&text)?;
+            file.set_times(stamp, stamp)?;
 
             // Assert
-            assert_eq!(file.read_string()?, text);
+            assert_eq!(file.modified()?, stamp);
+            assert_eq!(file.accessed()?, stamp);
 
             // Clean-up
             file.delete()?;
@@ -461,20 +5274,40 @@ mod tests {
     }
 
     #[test]
-    fn write_lines() -> Result<()> {
+    fn newer_and_older() -> Result<()> {
         Ok({
             // Arrange
-            let file = FilePath::access(&"write_lines.txt");
-            let lines = "Hello, World!"
-                .split_whitespace()
-                .map(ToString::to_string)
-                .collect();
+            let old = FilePath::access(&"fp_newer_old.txt");
+            let new = FilePath::access(&"fp_newer_new.txt");
+            old.write_string(&"old")?;
+            new.write_string(&"new")?;
+            old.set_modified(SystemTime::UNIX_EPOCH)?;
+
+            // Assert
+            assert!(new.is_newer_than(&old)?);
+            assert!(old.is_older_than(&new)?);
+            assert!(new.is_newer_than(&"fp_newer_missing.txt")?);
+
+            // Clean-up
+            old.delete()?;
+            new.delete()?;
+        })
+    }
+
+    #[test]
+    fn human_size() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_human_size.txt");
+            file.write_string(&"Hello, World!")?;
 
             // Action
-            file.write_lines(&lines)?;
+            let size = file.human_size()?;
+            let formatted = file.size_formatted()?;
 
             // Assert
-            assert_eq!(file.read_lines()?, lines);
+            assert_eq!(size, "13 B");
+            assert_eq!(formatted, "13 B");
 
             // Clean-up
             file.delete()?;
@@ -482,18 +5315,19 @@ mod tests {
     }
 
     #[test]
-    fn append_string() -> Result<()> {
+    fn describe() -> Result<()> {
         Ok({
             // Arrange
-            let file = FilePath::access(&"append_string.txt");
-            let text = "Hello, World!";
-            file.write_string(&text)?;
+            let file = FilePath::access(&"fp_describe.txt");
+            file.write_string(&"Hello, World!")?;
 
             // Action
-            file.append_string(&text)?;
+            let description = file.describe()?;
 
             // Assert
-            assert_eq!(file.read_string()?, format!("{text}{text}"));
+            assert_eq!(description.path, "fp_describe.txt");
+            assert_eq!(description.size, 13);
+            assert!(description.to_string().contains("Type:        file"));
 
             // Clean-up
             file.delete()?;
@@ -501,19 +5335,20 @@ mod tests {
     }
 
     #[test]
-    fn append_lines() -> Result<()> {
+    fn text_profile() -> Result<()> {
         Ok({
             // Arrange
-            let file = FilePath::access(&"append_lines.txt");
-            let lines1 = vec!["1", "2"]; // .to_vec_string();
-            file.write_lines(&lines1)?;
+            let file = FilePath::access(&"fp_text_profile.txt");
+            file.write_string(&"\u{feff}a\r\n\tb\r\n")?;
 
             // Action
-            let lines2 = vec!["3", "4"]; //.to_vec_string();
-            file.append_lines(&lines2)?;
+            let profile = file.text_profile()?;
 
             // Assert
-            assert_eq!(file.read_lines()?, vec!["1", "2", "3", "4"]); // .to_vec_string());
+            assert_eq!(profile.encoding, TextEncoding::Utf8);
+            assert!(profile.bom);
+            assert_eq!(profile.line_ending, LineEnding::CrLf);
+            assert_eq!(profile.indentation, Indentation::Tabs);
 
             // Clean-up
             file.delete()?;
@@ -521,69 +5356,278 @@ mod tests {
     }
 
     #[test]
-    fn delete() -> Result<()> {
+    fn detect_format() -> Result<()> {
         Ok({
             // Arrange
-            let path = "delete.txt";
-            let file = FilePath::access(&path);
-            mk_file(&path)?;
+            let file = FilePath::access(&"fp_detect_format.gz");
+            std::fs::write(file.as_ref(), b"\x1f\x8b\x08\x00")?;
 
             // Action
+            let format = file.detect_format()?;
+
+            // Assert
+            assert_eq!(format.as_deref(), Some("gzip"));
+
+            // Clean-up
             file.delete()?;
+        })
+    }
+
+    #[test]
+    fn hash() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_hash.txt");
+            let copy = FilePath::access(&"fp_hash_copy.txt");
+            file.write_string(&"abc")?;
+            copy.write_string(&"abc")?;
+
+            // Action
+            #[cfg(feature = "hash")]
+            let digest = file.hash(HashAlgorithm::Sha256)?;
 
             // Assert
-            assert!(!path_of(&path).exists(), "{path} should no longer exist");
+            #[cfg(feature = "hash")]
+            assert_eq!(
+                digest,
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            );
+            assert!(file.same_content_as(&copy)?);
+            copy.write_string(&"abd")?;
+            assert!(!file.same_content_as(&copy)?);
+
+            // Clean-up
+            file.delete()?;
+            copy.delete()?;
         })
     }
 
     #[test]
-    fn copy() -> Result<()> {
+    #[cfg(feature = "zip")]
+    fn zip() -> Result<()> {
         Ok({
             // Arrange
-            let from = "copy_from.txt";
-            let to = "copy_to.txt";
-            let file = FilePath::access(&from);
-            file.write_string(&"Hello, World!")?;
+            let dir = FilePath::access(&"fp_zip/data");
+            dir.join("a.txt").write_string(&"a")?;
+            let archive = FilePath::access(&"fp_zip/data.zip");
 
             // Action
-            file.copy_to(&to)?;
+            dir.zip_to(&archive)?;
+            archive.unzip_to(&"fp_zip/out")?;
 
             // Assert
             assert_eq!(
-                from.as_file().read_string()?,
-                to.as_file().read_string()?,
-                "{from} and {to} should contain the same text"
+                FilePath::access(&"fp_zip/out/data/a.txt").read_string()?,
+                "a"
             );
 
             // Clean-up
-            from.as_file().delete()?;
-            to.as_file().delete()?;
+            FilePath::access(&"fp_zip").delete()?;
         })
     }
 
     #[test]
-    fn rename() -> Result<()> {
+    #[cfg(feature = "tar")]
+    fn tar() -> Result<()> {
         Ok({
             // Arrange
-            let from = "rename_from.txt";
-            let to = "rename_to.txt";
-            let text = "Hello, World!";
-            let file = FilePath::access(&from);
-            file.write_string(&text)?;
+            let dir = FilePath::access(&"fp_tar/data");
+            dir.join("a.txt").write_string(&"a")?;
+            let archive = FilePath::access(&"fp_tar/data.tar");
 
             // Action
-            file.rename_to(&to)?;
+            dir.tar_to(&archive)?;
+            archive.untar_to(&"fp_tar/out")?;
 
             // Assert
-            assert!(!path_of(&from).exists(), "{from} should no longer exist");
             assert_eq!(
-                to.as_file().read_string()?,
-                text,
-                "{to} should contain: {text}"
+                FilePath::access(&"fp_tar/out/data/a.txt").read_string()?,
+                "a"
             );
 
             // Clean-up
-            to.as_file().delete()?;
+            FilePath::access(&"fp_tar").delete()?;
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "hash")]
+    fn tree_hash() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"file_path_tree_hash/a");
+            "file_path_tree_hash/a/x.txt"
+                .as_file()
+                .write_string(&"Hello, World!")?;
+            "file_path_tree_hash/b/x.txt"
+                .as_file()
+                .write_string(&"Hello, World!")?;
+
+            // Action
+            let hash = file.tree_hash()?;
+
+            // Assert
+            assert_eq!(hash, "file_path_tree_hash/b".as_file().tree_hash()?);
+
+            // Clean-up
+            "file_path_tree_hash".as_file().delete()?;
+        })
+    }
+
+    #[test]
+    fn list() -> Result<()> {
+        Ok({
+            // Arrange
+            let dir = FilePath::access(&"file_path_list");
+            "file_path_list/a.txt".as_file().write_string(&"a")?;
+            "file_path_list/.b.txt".as_file().write_string(&"b")?;
+
+            // Action
+            let entries = dir.list(ListOptions::default())?;
+
+            // Assert
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].read_string()?, "a");
+
+            // Clean-up
+            dir.delete()?;
+        })
+    }
+
+    #[test]
+    fn walk() -> Result<()> {
+        Ok({
+            // Arrange
+            let dir = FilePath::access(&"file_path_walk");
+            "file_path_walk/a/b.txt".as_file().write_string(&"b")?;
+
+            // Action
+            let files = dir.walk().files_only().collect::<crate::Result<Vec<_>>>()?;
+
+            // Assert
+            assert_eq!(files.len(), 1);
+            assert_eq!(files[0].read_string()?, "b");
+
+            // Clean-up
+            dir.delete()?;
+        })
+    }
+
+    #[test]
+    #[cfg(all(feature = "acl", any(target_os = "linux", target_os = "macos")))]
+    fn set_acl() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"set_acl.txt");
+            file.write_string(&"Hello, World!")?;
+            let entries = vec![
+                AclEntry::allow(Principal::Owner).read().write(),
+                AclEntry::allow(Principal::OwningGroup).read(),
+                AclEntry::allow(Principal::Everyone),
+            ];
+
+            // Action
+            file.set_acl(&entries)?;
+
+            // Assert
+            assert_eq!(file.acl()?, entries);
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "parity")]
+    fn protect() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_protect_test.txt");
+            file.write_string(&"Hello, World!")?;
+            file.protect(50)?;
+
+            // Action
+            file.write_string(&"Hello, world!")?;
+            let repaired = file.repair()?;
+
+            // Assert
+            assert_eq!(repaired, 1);
+            assert_eq!(file.read_string()?, "Hello, World!");
+
+            // Clean-up
+            file.delete()?;
+            "fp_protect_test.txt.par".as_file().delete()?;
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "sign")]
+    fn sign() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = FilePath::access(&"fp_sign_test/app.bin");
+            let key = SigningKey::from_bytes(&[1; 32]);
+            file.write_string(&"Hello, World!")?;
+
+            // Action
+            file.sign(&key)?;
+            "fp_sign_test"
+                .as_file()
+                .sign_manifest(&"fp_sign_test.sums", &key)?;
+
+            // Assert
+            assert!(file.verify(&key.verifying_key())?);
+            assert!("fp_sign_test"
+                .as_file()
+                .verify_manifest(&"fp_sign_test.sums", &key.verifying_key())?);
+
+            // Clean-up
+            "fp_sign_test".as_file().delete()?;
+            "fp_sign_test.sums".as_file().delete()?;
+            "fp_sign_test.sums.sig".as_file().delete()?;
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "camino")]
+    fn camino() -> Result<()> {
+        Ok({
+            use camino::{Utf8Path, Utf8PathBuf};
+
+            // Arrange
+            let path: &Utf8Path = Utf8Path::new("fp_camino.txt");
+            let file = FilePath::from(Utf8PathBuf::from("fp_camino.txt"));
+
+            // Action
+            file.write_string(&"Hello, World!")?;
+
+            // Assert
+            assert_eq!(crate::read_string(path)?, "Hello, World!");
+            assert_eq!(FilePath::from(path).as_utf8_path(), Some(path));
+
+            // Clean-up
+            file.delete()?;
+        })
+    }
+
+    #[test]
+    #[cfg(all(feature = "camino", unix))]
+    fn camino_non_utf8() -> Result<()> {
+        Ok({
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+            // Arrange
+            let file = FilePath::access(OsStr::from_bytes(b"caf\xe9.txt"));
+            file.write_string(&"Hello, World!")?;
+
+            // Action
+            let path = file.as_utf8_path();
+
+            // Assert
+            assert_eq!(path, None);
+
+            // Clean-up
+            file.delete()?;
         })
     }
 }
@@ -0,0 +1,177 @@
+use crate::*;
+use std::path::PathBuf;
+
+/// Describes the filesystem (or volume) a file lives on, as returned by `filesystem_info`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilesystemInfo {
+    /// The filesystem type, such as `ext4`, `apfs` or `NTFS`.
+    pub fs_type: String,
+    /// The size of the filesystem, in bytes.
+    pub total_space: u64,
+    /// The free space, in bytes, including any space reserved for privileged users.
+    pub free_space: u64,
+    /// The free space, in bytes, available to the current user.
+    pub available_space: u64,
+    /// The preferred I/O block size (the cluster size on Windows), in bytes.
+    pub block_size: u64,
+}
+
+/// Queries the filesystem containing an **existing** file or directory:
+/// its type, its total/free/available space and its block size.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`FilesystemInfo`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = ".";
+///         let file_path: String = String::from(file_path);
+///
+///         let info = file_access::filesystem_info(&file_path)?;
+///         println!("{}: {} of {} bytes available", info.fs_type, info.available_space, info.total_space);
+///     })
+/// }
+/// ```
+pub fn filesystem_info<Path: AsPath + ?Sized>(file_path: &Path) -> Result<FilesystemInfo> {
+    internal::sys::filesystem_info(&path_of(file_path)).context("filesystem_info", file_path)
+}
+
+/// What kind of storage a volume is, as far as the platform can tell; see `volume_kind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VolumeKind {
+    /// A fixed, local disk.
+    Local,
+    /// A network share, such as NFS or SMB.
+    Network,
+    /// Removable media, such as a USB stick or an optical disc.
+    Removable,
+    /// Memory-backed storage, such as `tmpfs`.
+    Ramdisk,
+    /// The platform can't tell.
+    Unknown,
+}
+
+// Gets the path itself if it exists, or else its nearest existing ancestor
+fn existing_ancestor(path: PathBuf) -> PathBuf {
+    path.ancestors()
+        .map(|ancestor| match ancestor.as_os_str().is_empty() {
+            true => std::path::Path::new("."),
+            false => ancestor,
+        })
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or(std::path::Path::new("."))
+        .to_path_buf()
+}
+
+/// Checks whether two paths live on the same filesystem, by comparing their device ids
+/// (volume serial numbers on Windows). A path that doesn't exist yet is judged by its nearest existing ancestor,
+/// so this can tell ahead of time whether a `rename` will stay on one device, or whether a hard link is possible.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `other`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`bool`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         let other: &str = "not_yet/created.toml";
+///         let other: String = String::from(other);
+///
+///         assert!(file_access::same_filesystem(&file_path, &other)?);
+///     })
+/// }
+/// ```
+pub fn same_filesystem<Path: AsPath + ?Sized, Other: AsPath + ?Sized>(
+    file_path: &Path,
+    other: &Other,
+) -> Result<bool> {
+    let device_id = |path| internal::sys::device_id(&existing_ancestor(path));
+
+    return Ok(device_id(path_of(file_path))? == device_id(path_of(other))?);
+}
+
+/// Classifies the volume containing an **existing** file or directory as `Local`, `Network`, `Removable` or `Ramdisk`,
+/// so that e.g. memory maps and advisory locks can be avoided on network shares. Linux and Windows can tell all of them apart;
+/// macOS only tells `Local` from `Network`, and other platforms report `Unknown`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`VolumeKind`>
+///
+/// # Examples
+/// ```
+/// use file_access::VolumeKind;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         if file_access::volume_kind(&file_path)? == VolumeKind::Network {
+///             println!("skipping advisory locks");
+///         }
+///     })
+/// }
+/// ```
+pub fn volume_kind<Path: AsPath + ?Sized>(file_path: &Path) -> Result<VolumeKind> {
+    internal::sys::volume_kind(&path_of(file_path)).context("volume_kind", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(target_os = "linux")]
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn filesystem_info() -> Result<()> {
+        Ok({
+            // Action
+            let info = super::filesystem_info(&"Cargo.toml")?;
+
+            // Assert
+            assert!(!info.fs_type.is_empty(), "{info:?}");
+            assert!(info.total_space >= info.free_space, "{info:?}");
+            assert!(info.free_space >= info.available_space, "{info:?}");
+            assert_ne!(info.block_size, 0, "{info:?}");
+        })
+    }
+
+    #[test]
+    fn same_filesystem() -> Result<()> {
+        Ok({
+            // Assert
+            assert!(super::same_filesystem(&"Cargo.toml", &"src/lib.rs")?);
+            assert!(super::same_filesystem(
+                &"Cargo.toml",
+                &"missing/dir/file.txt"
+            )?);
+            #[cfg(target_os = "linux")]
+            assert!(!super::same_filesystem(&"Cargo.toml", &"/proc/self")?);
+        })
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn volume_kind() -> Result<()> {
+        Ok({
+            // Assert
+            assert_eq!(super::volume_kind(&"/dev/shm")?, VolumeKind::Ramdisk);
+            assert_ne!(super::volume_kind(&"Cargo.toml")?, VolumeKind::Ramdisk);
+        })
+    }
+}
@@ -0,0 +1,237 @@
+use crate::*;
+
+/// A line found by `find_in_dir`: the file it's in, its line number counted from `1`, and the line itself.
+pub struct LineMatch {
+    pub path: FilePath,
+    pub line_number: usize,
+    pub line: String,
+}
+
+// Streams through a file's lines, keeping the ones `matches` accepts along with their line number, counted from 1
+fn matching_lines<Path: AsPath + ?Sized>(
+    file_path: &Path,
+    matches: impl Fn(&str) -> bool,
+) -> io::Result<Vec<(usize, String)>> {
+    let mut found = vec![];
+    for (i, line) in BufReader::new(get_file(file_path)?).lines().enumerate() {
+        let line = line?;
+        if matches(&line) {
+            found.push((i + 1, line));
+        }
+    }
+    return Ok(found);
+}
+
+// Searches every file below a directory, skipping the ones that aren't text
+fn matching_lines_in_dir<Path: AsPath + ?Sized>(
+    dir_path: &Path,
+    operation: &'static str,
+    matches: impl Fn(&str) -> bool,
+) -> Result<Vec<LineMatch>> {
+    let mut found = vec![];
+    for file in walk(dir_path).files_only() {
+        let file = file?;
+        let lines = match matching_lines(&file, &matches) {
+            Err(x) if x.kind() == ErrorKind::InvalidData => continue,
+            lines => lines.context(operation, &file)?,
+        };
+        for (line_number, line) in lines {
+            found.push(LineMatch {
+                path: FilePath::access(&file),
+                line_number,
+                line,
+            });
+        }
+    }
+    return Ok(found);
+}
+
+/// Searches a file for the lines containing a string, like `grep -F`, streaming through it.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `pattern`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// Result<`Vec<(usize, String)>`> of the matching lines, with their line number counted from `1`
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         let found: Vec<(usize, String)> = file_access::find_lines(&file_path, &"name")?;
+///         assert_eq!(found[0], (2, "name = \"file_access\"".to_string()));
+///     })
+/// }
+/// ```
+pub fn find_lines<Path: AsPath + ?Sized, Pattern: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    pattern: &Pattern,
+) -> Result<Vec<(usize, String)>> {
+    matching_lines(file_path, |line| line.contains(pattern.as_ref()))
+        .context("find_lines", file_path)
+}
+
+/// Searches a file for the lines matching a regular expression, like `grep -E`, streaming through it.
+/// **Requires the `regex` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `pattern`: **borrowed** `AsRef<str>` such as `String` or `&str`, in the `regex` crate's syntax
+///
+/// # Returns
+/// Result<`Vec<(usize, String)>`> of the matching lines, with their line number counted from `1`,
+/// failing with `ErrorKind::InvalidInput` if the pattern is invalid
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         let found = file_access::find_lines_regex(&file_path, &r"^version = .\d")?;
+///         assert_eq!(found.len(), 1);
+///     })
+/// }
+/// ```
+#[cfg(feature = "regex")]
+pub fn find_lines_regex<Path: AsPath + ?Sized, Pattern: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    pattern: &Pattern,
+) -> Result<Vec<(usize, String)>> {
+    let regex = regex::Regex::new(pattern.as_ref())
+        .map_err(|x| Error::new(ErrorKind::InvalidInput, x))
+        .context("find_lines_regex", file_path)?;
+
+    return matching_lines(file_path, |line| regex.is_match(line))
+        .context("find_lines_regex", file_path);
+}
+
+/// Searches every file below a directory, recursively, for the lines containing a string, like `grep -rF`.
+/// Files that aren't valid UTF-8 text, such as images, are skipped.
+///
+/// # Parameters
+/// - `dir_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `pattern`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// Result<`Vec<LineMatch>`>, ordered by path then line number
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         for found in file_access::find_in_dir(&"src", &"TODO")? {
+///             println!("{}:{}: {}", found.path.as_ref(), found.line_number, found.line);
+///         }
+///     })
+/// }
+/// ```
+pub fn find_in_dir<Path: AsPath + ?Sized, Pattern: AsRef<str> + ?Sized>(
+    dir_path: &Path,
+    pattern: &Pattern,
+) -> Result<Vec<LineMatch>> {
+    matching_lines_in_dir(dir_path, "find_in_dir", |line| {
+        line.contains(pattern.as_ref())
+    })
+}
+
+/// Searches every file below a directory, recursively, for the lines matching a regular expression, like `grep -rE`.
+/// Files that aren't valid UTF-8 text, such as images, are skipped. **Requires the `regex` feature**.
+///
+/// # Parameters
+/// - `dir_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `pattern`: **borrowed** `AsRef<str>` such as `String` or `&str`, in the `regex` crate's syntax
+///
+/// # Returns
+/// Result<`Vec<LineMatch>`>, ordered by path then line number
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         for found in file_access::find_regex_in_dir(&"src", &r"fn \w+_dir\b")? {
+///             println!("{}:{}: {}", found.path.as_ref(), found.line_number, found.line);
+///         }
+///     })
+/// }
+/// ```
+#[cfg(feature = "regex")]
+pub fn find_regex_in_dir<Path: AsPath + ?Sized, Pattern: AsRef<str> + ?Sized>(
+    dir_path: &Path,
+    pattern: &Pattern,
+) -> Result<Vec<LineMatch>> {
+    let regex = regex::Regex::new(pattern.as_ref())
+        .map_err(|x| Error::new(ErrorKind::InvalidInput, x))
+        .context("find_regex_in_dir", dir_path)?;
+
+    return matching_lines_in_dir(dir_path, "find_regex_in_dir", |line| regex.is_match(line));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn find_lines() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "find_lines/file_access.txt";
+            write_string(&file, &"apple\nbanana\npineapple")?;
+
+            // Action
+            let found = super::find_lines(&file, &"apple")?;
+
+            // Assert
+            assert_eq!(
+                found,
+                [(1, "apple".to_string()), (3, "pineapple".to_string())]
+            );
+            assert!(super::find_lines(&file, &"cherry")?.is_empty());
+            #[cfg(feature = "regex")]
+            assert_eq!(
+                super::find_lines_regex(&file, &"^(apple|banana)$")?,
+                [(1, "apple".to_string()), (2, "banana".to_string())]
+            );
+
+            // Clean-up
+            delete(&"find_lines")?;
+        })
+    }
+
+    #[test]
+    fn find_in_dir() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"find_in_dir/a.txt", &"x\nneedle")?;
+            write_string(&"find_in_dir/sub/b.txt", &"needle here")?;
+            write_bytes(&"find_in_dir/sub/c.bin", &[0xff, 0xfe, b'n'])?;
+
+            // Action
+            let found = super::find_in_dir(&"find_in_dir", &"needle")?;
+
+            // Assert
+            let found: Vec<_> = found
+                .iter()
+                .map(|found| (path_of(&found.path), found.line_number))
+                .collect();
+            assert_eq!(
+                found,
+                [
+                    (PathBuf::from("find_in_dir/a.txt"), 2),
+                    (PathBuf::from("find_in_dir/sub/b.txt"), 1)
+                ]
+            );
+            #[cfg(feature = "regex")]
+            assert_eq!(super::find_regex_in_dir(&"find_in_dir", &"^need")?.len(), 2);
+
+            // Clean-up
+            delete(&"find_in_dir")?;
+        })
+    }
+}
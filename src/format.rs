@@ -0,0 +1,219 @@
+use crate::*;
+use std::sync::RwLock;
+
+/// A rule recognizing a file format by the magic bytes it starts with, for `detect_format`.
+/// A rule matches when all of its byte sequences are found at their offsets.
+///
+/// # Examples
+/// ```
+/// use file_access::FormatRule;
+///
+/// // RIFF containers carry their actual format at offset 8:
+/// let avi = FormatRule::new("avi").at(0, b"RIFF").at(8, b"AVI ");
+/// assert!(avi.matches(b"RIFF\x00\x00\x00\x00AVI LIST"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatRule {
+    pub name: String,
+    pub magic: Vec<(usize, Vec<u8>)>,
+}
+
+impl FormatRule {
+    /// Creates a rule, matching anything yet, for the format `name`; chain `at()` onto it.
+    pub fn new<Name: AsRef<str>>(name: Name) -> Self {
+        Self {
+            name: name.as_ref().to_string(),
+            magic: vec![],
+        }
+    }
+
+    /// Requires `bytes` to be found at `offset`.
+    pub fn at(mut self, offset: usize, bytes: &[u8]) -> Self {
+        self.magic.push((offset, bytes.to_vec()));
+        self
+    }
+
+    /// Checks whether the leading bytes of a file match this rule.
+    pub fn matches(&self, head: &[u8]) -> bool {
+        self.magic.iter().all(|(offset, bytes)| {
+            head.get(*offset..offset + bytes.len()) == Some(bytes.as_slice())
+        })
+    }
+
+    // How many leading bytes are needed to check this rule
+    fn len(&self) -> usize {
+        self.magic
+            .iter()
+            .map(|(offset, bytes)| offset + bytes.len())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+// A sequence of magic bytes and its offset
+type Magic = (usize, &'static [u8]);
+
+// The built-in rules, by precedence (e.g. UTF-32 before UTF-16, since both start with a UTF-16 BOM)
+const BUILT_IN: &[(&str, &[Magic])] = &[
+    ("png", &[(0, b"\x89PNG\r\n\x1a\n")]),
+    ("jpeg", &[(0, b"\xff\xd8\xff")]),
+    ("gif", &[(0, b"GIF8")]),
+    ("webp", &[(0, b"RIFF"), (8, b"WEBP")]),
+    ("wav", &[(0, b"RIFF"), (8, b"WAVE")]),
+    ("bmp", &[(0, b"BM")]),
+    ("pdf", &[(0, b"%PDF-")]),
+    ("zip", &[(0, b"PK\x03\x04")]),
+    ("zip", &[(0, b"PK\x05\x06")]),
+    ("gzip", &[(0, b"\x1f\x8b")]),
+    ("bzip2", &[(0, b"BZh")]),
+    ("xz", &[(0, b"\xfd7zXZ\x00")]),
+    ("zstd", &[(0, b"\x28\xb5\x2f\xfd")]),
+    ("7z", &[(0, b"7z\xbc\xaf\x27\x1c")]),
+    ("tar", &[(257, b"ustar")]),
+    ("sqlite", &[(0, b"SQLite format 3\x00")]),
+    ("elf", &[(0, b"\x7fELF")]),
+    ("mach-o", &[(0, b"\xcf\xfa\xed\xfe")]),
+    ("mach-o", &[(0, b"\xce\xfa\xed\xfe")]),
+    ("mach-o", &[(0, b"\xca\xfe\xba\xbe")]),
+    ("pe", &[(0, b"MZ")]),
+    ("wasm", &[(0, b"\x00asm")]),
+    ("utf32le", &[(0, b"\xff\xfe\x00\x00")]),
+    ("utf32be", &[(0, b"\x00\x00\xfe\xff")]),
+    ("utf16le", &[(0, b"\xff\xfe")]),
+    ("utf16be", &[(0, b"\xfe\xff")]),
+    ("utf8", &[(0, b"\xef\xbb\xbf")]),
+];
+
+// The rules added by `register_format`, most recent first
+static REGISTRY: RwLock<Vec<FormatRule>> = RwLock::new(Vec::new());
+
+fn built_in() -> impl Iterator<Item = FormatRule> {
+    BUILT_IN.iter().map(|(name, magic)| FormatRule {
+        name: name.to_string(),
+        magic: magic
+            .iter()
+            .map(|(offset, bytes)| (*offset, bytes.to_vec()))
+            .collect(),
+    })
+}
+
+/// Adds a rule to the ones `detect_format` tries. Registered rules take precedence over the built-in ones,
+/// and over the ones registered before them, so that they can refine or override a built-in format.
+///
+/// # Parameters
+/// - `rule`: **owned** `FormatRule`
+///
+/// # Examples
+/// ```
+/// use file_access::FormatRule;
+///
+/// file_access::register_format(FormatRule::new("parquet").at(0, b"PAR1"));
+/// ```
+pub fn register_format(rule: FormatRule) {
+    REGISTRY
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(0, rule);
+}
+
+/// Detects the format of a file from its leading bytes rather than its extension, by trying the registered
+/// and then the built-in `FormatRule`s: `png`, `jpeg`, `gif`, `webp`, `wav`, `bmp`, `pdf`, `zip`, `gzip`, `bzip2`,
+/// `xz`, `zstd`, `7z`, `tar`, `sqlite`, `elf`, `mach-o`, `pe`, `wasm`, and text with a `utf8`, `utf16le`/`be`
+/// or `utf32le`/`be` byte order mark. Returns `None` when no rule matches.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Option<String>`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "detect_format_example.pdf";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"%PDF-1.7")?;
+///
+///         let format = file_access::detect_format(&file_path)?;
+///         assert_eq!(format.as_deref(), Some("pdf"));
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn detect_format<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Option<String>> {
+    let registry = REGISTRY
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let rules: Vec<FormatRule> = registry.iter().cloned().chain(built_in()).collect();
+    drop(registry);
+
+    let len = rules.iter().map(FormatRule::len).max().unwrap_or(0);
+    let mut head = Vec::with_capacity(len);
+    File::open(path_of(file_path))
+        .and_then(|file| file.take(len as u64).read_to_end(&mut head))
+        .context("detect_format", file_path)?;
+
+    return Ok(rules
+        .into_iter()
+        .find(|rule| rule.matches(&head))
+        .map(|rule| rule.name));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn detect_format() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "detect_format.bin";
+
+            // Action & Assert
+            for (bytes, expected) in [
+                (&b"\x89PNG\r\n\x1a\n...."[..], Some("png")),
+                (b"\x1f\x8b\x08\x00", Some("gzip")),
+                (b"\x7fELF\x02\x01\x01", Some("elf")),
+                (b"\xff\xfeh\x00i\x00", Some("utf16le")),
+                (b"\xff\xfe\x00\x00h\x00\x00\x00", Some("utf32le")),
+                (b"RIFF\x00\x00\x00\x00WEBPVP8 ", Some("webp")),
+                (b"plain text", None),
+                (b"", None),
+            ] {
+                fs::write(file_path, bytes)?;
+                assert_eq!(super::detect_format(&file_path)?.as_deref(), expected);
+            }
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+
+    #[test]
+    fn register_format() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "register_format.bin";
+            fs::write(file_path, b"PK\x03\x04....mimetypeapplication/epub+zip")?;
+            let before = super::detect_format(&file_path)?;
+
+            // Action
+            super::register_format(
+                FormatRule::new("epub")
+                    .at(0, b"PK\x03\x04")
+                    .at(16, b"application/epub+zip"),
+            );
+
+            // Assert
+            assert_eq!(before.as_deref(), Some("zip"));
+            assert_eq!(super::detect_format(&file_path)?.as_deref(), Some("epub"));
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{internal::glob::glob_match, *};
+use std::path::{Component, Path as StdPath};
+
+// Writes a path `/`-separated, without `.` components, to be matched against a pattern
+fn slashed(path: &StdPath) -> String {
+    let components: Vec<_> = path
+        .components()
+        .filter(|component| *component != Component::CurDir)
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect();
+
+    return components.join("/");
+}
+
+/// Finds the files and directories matching a glob pattern, such as to drive batch operations, in walk order:
+/// siblings by name, each directory before its contents, so `b`, `b/c` and then `b.txt`.
+/// `*` matches within a path segment, `?` matches one character, `[abc]`, `[a-z]` and `[!abc]` match a character class,
+/// and `**` matches any number of whole segments. Only the directory below the pattern's leading literal segments
+/// is walked, and symlinks aren't followed into directories. A pattern matching nothing returns an empty `Vec`.
+///
+/// # Parameters
+/// - `pattern`: **borrowed** `AsRef<str>` such as `String` or `&str`, such as `"logs/**/*.log"`
+///
+/// # Returns
+/// Result<`Vec<FilePath>`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"glob_example/logs/2024/app.log", &"")?;
+///         file_access::write_string(&"glob_example/logs/app.txt", &"")?;
+///
+///         let logs = file_access::glob(&"glob_example/logs/**/*.log")?;
+///         assert_eq!(logs.len(), 1);
+///         assert!(logs[0].as_ref().ends_with("app.log"));
+///
+///         // Clean-up:
+///         file_access::delete(&"glob_example")?;
+///     })
+/// }
+/// ```
+pub fn glob<Pattern: AsRef<str> + ?Sized>(pattern: &Pattern) -> Result<Vec<FilePath>> {
+    let pattern = pattern.as_ref();
+    // The segments before the first wildcard are a directory to walk, the rest is matched below it
+    let literal = pattern.find(['*', '?', '[']).unwrap_or(pattern.len());
+    let split = pattern[..literal].rfind('/');
+    let (base, rest) = match split {
+        Some(0) => ("/", &pattern[1..]),
+        Some(split) => (&pattern[..split], &pattern[split + 1..]),
+        None => ("", pattern),
+    };
+    if literal == pattern.len() {
+        return Ok(match StdPath::new(pattern).symlink_metadata() {
+            Ok(_) => vec![FilePath::access(pattern)],
+            Err(_) => vec![],
+        });
+    }
+    let root = match base.is_empty() {
+        true => ".",
+        false => base,
+    };
+    if !StdPath::new(root).is_dir() {
+        return Ok(vec![]);
+    }
+    let depth = match rest.contains("**") {
+        true => usize::MAX,
+        false => rest.split('/').filter(|s| !s.is_empty()).count(),
+    };
+
+    let mut matches = vec![];
+    for entry in walk(root).max_depth(depth) {
+        let path = path_of(&entry?);
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        if glob_match(rest, &slashed(relative)) {
+            matches.push(FilePath::from(StdPath::new(base).join(relative)));
+        }
+    }
+    return Ok(matches);
+}
+
+/// Checks whether a path matches a glob pattern, with the same syntax as `glob`; the file doesn't need to exist.
+/// `.` components are ignored, so `./src/lib.rs` matches `src/*.rs`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `pattern`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// `bool`
+///
+/// # Examples
+/// ```
+/// assert!(file_access::matches_glob(&"logs/2024/app.log", &"logs/**/*.log"));
+/// assert!(!file_access::matches_glob(&"logs/app.txt", &"logs/**/*.log"));
+/// ```
+pub fn matches_glob<Path: AsPath + ?Sized, Pattern: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    pattern: &Pattern,
+) -> bool {
+    glob_match(pattern.as_ref(), &slashed(file_path.as_path()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    // Collects the paths globbed, `/`-separated
+    fn globbed(pattern: &str) -> Result<Vec<String>> {
+        Ok(super::glob(pattern)?
+            .iter()
+            .map(|file| slashed(file.as_path()))
+            .collect())
+    }
+
+    #[test]
+    fn glob() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"glob_test/a.rs", &"")?;
+            write_string(&"glob_test/b/c.rs", &"")?;
+            write_string(&"glob_test/b/d/e.rs", &"")?;
+            write_string(&"glob_test/b/f.txt", &"")?;
+            write_string(&"glob_test/b.rs", &"")?;
+
+            // Action
+            let all_rs = globbed("glob_test/**/*.rs")?;
+            let shallow = globbed("glob_test/*/*")?;
+            let classes = globbed("glob_test/[a-c].?s")?;
+            let literal = globbed("glob_test/b/f.txt")?;
+            let missing = globbed("glob_test/missing/*.rs")?;
+
+            // Assert
+            assert_eq!(
+                all_rs,
+                [
+                    "glob_test/a.rs",
+                    "glob_test/b/c.rs",
+                    "glob_test/b/d/e.rs",
+                    "glob_test/b.rs"
+                ]
+            );
+            assert_eq!(
+                shallow,
+                ["glob_test/b/c.rs", "glob_test/b/d", "glob_test/b/f.txt"]
+            );
+            assert_eq!(classes, ["glob_test/a.rs", "glob_test/b.rs"]);
+            assert_eq!(literal, ["glob_test/b/f.txt"]);
+            assert!(missing.is_empty());
+            assert!(matches_glob(&"./glob_test/b/c.rs", &"glob_test/**/*.rs"));
+
+            // Clean-up
+            delete(&"glob_test")?;
+        })
+    }
+}
@@ -0,0 +1,190 @@
+use crate::*;
+use flate2::{read::MultiGzDecoder, write::GzEncoder, Compression};
+
+// The magic bytes every gzip stream starts with
+pub(crate) const GZIP_MAGIC: &[u8] = b"\x1f\x8b";
+
+// Decompresses gzip data, all of its members if it has several, as `cat a.gz b.gz` produces
+pub(crate) fn gunzip(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let mut decompressed = vec![];
+    MultiGzDecoder::new(bytes).read_to_end(&mut decompressed)?;
+
+    return Ok(decompressed);
+}
+
+// Compresses a file into another, streaming it
+pub(crate) fn gzip_file(from: &std::path::Path, to: &std::path::Path) -> io::Result<()> {
+    let mut encoder = GzEncoder::new(File::create(to)?, Compression::default());
+    io::copy(&mut File::open(from)?, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+
+    return Ok(());
+}
+
+/// Reads a gzip-compressed file, such as a rotated log or a data dump, and returns its decompressed contents.
+/// **Requires the `gzip` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Vec<u8>`>, failing with `ErrorKind::InvalidInput` if the file isn't valid gzip
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "read_bytes_gz_example/dump.bin.gz";
+///         file_access::write_bytes_gz(&file_path, &[0, 1, 2])?;
+///
+///         assert_eq!(file_access::read_bytes_gz(&file_path)?, [0, 1, 2]);
+///
+///         // Clean-up:
+///         file_access::delete(&"read_bytes_gz_example")?;
+///     })
+/// }
+/// ```
+pub fn read_bytes_gz<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Vec<u8>> {
+    let decompress = || -> io::Result<(Vec<u8>, u64)> {
+        let mut compressed = vec![];
+        let read = get_file(file_path)?.read_to_end(&mut compressed)?;
+        return Ok((gunzip(&compressed)?, read as u64));
+    };
+    metered("read_bytes_gz", |(_, read)| (*read, 0), decompress)
+        .map(|(bytes, _)| bytes)
+        .context("read_bytes_gz", file_path)
+}
+
+/// Reads a gzip-compressed file, such as a rotated log, and returns its decompressed contents as a string.
+/// **Requires the `gzip` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`String`>, failing with `ErrorKind::InvalidData` if the contents aren't valid UTF-8
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "read_string_gz_example/app.log.gz";
+///         file_access::write_string_gz(&file_path, &"started\nstopped\n")?;
+///
+///         assert_eq!(file_access::read_string_gz(&file_path)?, "started\nstopped\n");
+///
+///         // Clean-up:
+///         file_access::delete(&"read_string_gz_example")?;
+///     })
+/// }
+/// ```
+pub fn read_string_gz<Path: AsPath + ?Sized>(file_path: &Path) -> Result<String> {
+    String::from_utf8(read_bytes_gz(file_path)?)
+        .map_err(|x| Error::new(ErrorKind::InvalidData, x))
+        .context("read_string_gz", file_path)
+}
+
+/// Compresses bytes with gzip and writes them to a file. This function will create the file
+/// **and its full directory path** if they don't exist, and will entirely replace the contents.
+/// **Requires the `gzip` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `bytes`: **borrowed** `AsRef<[u8]>` such as `&[u8]` or `Vec<u8>`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "write_bytes_gz_example/zeros.bin.gz";
+///         file_access::write_bytes_gz(&file_path, &vec![0; 1024 * 1024])?;
+///
+///         assert!(file_access::read_bytes(&file_path)?.len() < 4096);
+///
+///         // Clean-up:
+///         file_access::delete(&"write_bytes_gz_example")?;
+///     })
+/// }
+/// ```
+pub fn write_bytes_gz<Path: AsPath + ?Sized, Bytes: AsRef<[u8]> + ?Sized>(
+    file_path: &Path,
+    bytes: &Bytes,
+) -> Result<()> {
+    let compress = || -> io::Result<u64> {
+        let mut encoder = GzEncoder::new(mk_file(file_path)?, Compression::default());
+        encoder.write_all(bytes.as_ref())?;
+        let mut file = encoder.finish()?;
+
+        return file.stream_position();
+    };
+    metered("write_bytes_gz", |written| (0, *written), compress)
+        .map(|_| ())
+        .context("write_bytes_gz", file_path)
+}
+
+/// Compresses text with gzip and writes it to a file. This function will create the file
+/// **and its full directory path** if they don't exist, and will entirely replace the contents.
+/// **Requires the `gzip` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "write_string_gz_example/app.log.gz";
+///         file_access::write_string_gz(&file_path, &"Hello, World!")?;
+///
+///         // Clean-up:
+///         file_access::delete(&"write_string_gz_example")?;
+///     })
+/// }
+/// ```
+pub fn write_string_gz<Path: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    text: &Text,
+) -> Result<()> {
+    write_bytes_gz(file_path, text.as_ref().as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn gzip() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "gzip/logs/app.log.gz";
+            let text = "line\n".repeat(10_000);
+
+            // Action
+            write_string_gz(&file, &text)?;
+            let mut concatenated = read_bytes(&file)?;
+            concatenated.extend(read_bytes(&file)?);
+            write_bytes(&"gzip/concatenated.gz", &concatenated)?;
+            write_string(&"gzip/plain.txt", &text)?;
+
+            // Assert
+            assert!(read_bytes(&file)?.len() < text.len() / 10);
+            assert_eq!(read_string_gz(&file)?, text);
+            assert_eq!(read_string_gz(&"gzip/concatenated.gz")?, text.repeat(2));
+            assert!(read_string_gz(&"gzip/plain.txt").is_err());
+            let gunzip = ReadOptions::new().gunzip(true);
+            assert_eq!(read_string_with(&file, gunzip)?, text);
+            assert_eq!(read_string_with(&"gzip/plain.txt", gunzip)?, text);
+
+            // Clean-up
+            delete(&"gzip")?;
+        })
+    }
+}
@@ -0,0 +1,79 @@
+use crate::*;
+
+/// Creates a hard link at `to` for the file at `from`: another name for the same contents, taking no extra space,
+/// such as to deduplicate identical files or to publish a finished file under its final name in one step.
+/// Both must be on the same filesystem. This function will create the destination's full directory path
+/// if it doesn't exist, and fails with `ErrorKind::AlreadyExists` if something is already at `to`.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"hard_link_example/staging/report.csv", &"a,b")?;
+///
+///         file_access::hard_link(&"hard_link_example/staging/report.csv", &"hard_link_example/public/report.csv")?;
+///         assert_eq!(file_access::link_count(&"hard_link_example/public/report.csv")?, 2);
+///
+///         // Clean-up:
+///         file_access::delete(&"hard_link_example")?;
+///     })
+/// }
+/// ```
+pub fn hard_link<From: AsPath + ?Sized, To: AsPath + ?Sized>(from: &From, to: &To) -> Result<()> {
+    if let Some(dir) = path_of(to).parent() {
+        fs::create_dir_all(dir).context("hard_link", to)?;
+    }
+    let linked = fs::hard_link(from.as_path(), to.as_path());
+    return context_either(linked, "hard_link", from, to, |from| {
+        from.symlink_metadata().is_ok()
+    });
+}
+
+/// Counts the hard links to a file, which is how many names its contents go by: `1` for an ordinary file,
+/// more once it's been hard linked. Deleting a name only frees the contents once the count drops to `0`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`u64`>
+pub fn link_count<Path: AsPath + ?Sized>(file_path: &Path) -> Result<u64> {
+    internal::sys::link_count(file_path.as_path()).context("link_count", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn hard_link() -> Result<()> {
+        Ok({
+            // Arrange
+            let (file, link) = ("hard_link/a.txt", "hard_link/b/a.txt");
+            write_string(&file, &"a")?;
+
+            // Action
+            super::hard_link(&file, &link)?;
+            append_string(&link, &"b")?;
+
+            // Assert
+            assert_eq!(read_string(&file)?, "ab");
+            assert_eq!(link_count(&file)?, 2);
+            let exists = super::hard_link(&file, &link).unwrap_err();
+            assert_eq!(exists.kind(), ErrorKind::AlreadyExists);
+            delete(&link)?;
+            assert_eq!(link_count(&file)?, 1);
+
+            // Clean-up
+            delete(&"hard_link")?;
+        })
+    }
+}
@@ -0,0 +1,360 @@
+use crate::*;
+#[cfg(any(
+    feature = "hash",
+    feature = "md5",
+    feature = "sha1",
+    feature = "crc32",
+    feature = "blake3"
+))]
+use crate::{internal::sha256, progress::Tracker};
+use std::io::BufReader;
+
+// How much of a file is read at a time when hashing or comparing it
+const CHUNK: usize = 64 * 1024;
+
+/// The algorithm `hash` digests a file with. Each needs the feature of its name, or `hash` for SHA-256.
+#[cfg(any(
+    feature = "hash",
+    feature = "md5",
+    feature = "sha1",
+    feature = "crc32",
+    feature = "blake3"
+))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    /// **Requires the `hash` feature**, which is on by default.
+    #[cfg(feature = "hash")]
+    Sha256,
+    /// **Requires the `md5` feature**. Only suited to detecting accidental changes, not tampering.
+    #[cfg(feature = "md5")]
+    Md5,
+    /// **Requires the `sha1` feature**. Only suited to detecting accidental changes, not tampering.
+    #[cfg(feature = "sha1")]
+    Sha1,
+    /// **Requires the `crc32` feature**. The IEEE CRC-32 used by zip and gzip; only detects accidental changes.
+    #[cfg(feature = "crc32")]
+    Crc32,
+    /// **Requires the `blake3` feature**. A fast cryptographic hash, suited to large files.
+    #[cfg(feature = "blake3")]
+    Blake3,
+}
+
+// The running state of a `HashAlgorithm`
+#[cfg(any(
+    feature = "hash",
+    feature = "md5",
+    feature = "sha1",
+    feature = "crc32",
+    feature = "blake3"
+))]
+enum Hasher {
+    #[cfg(feature = "hash")]
+    Sha256(sha2::Sha256),
+    #[cfg(feature = "md5")]
+    Md5(md5::Md5),
+    #[cfg(feature = "sha1")]
+    Sha1(sha1::Sha1),
+    #[cfg(feature = "crc32")]
+    Crc32(crc32fast::Hasher),
+    #[cfg(feature = "blake3")]
+    Blake3(Box<blake3::Hasher>),
+}
+
+#[cfg(any(
+    feature = "hash",
+    feature = "md5",
+    feature = "sha1",
+    feature = "crc32",
+    feature = "blake3"
+))]
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            #[cfg(feature = "hash")]
+            HashAlgorithm::Sha256 => Self::Sha256(sha2::Digest::new()),
+            #[cfg(feature = "md5")]
+            HashAlgorithm::Md5 => Self::Md5(md5::Digest::new()),
+            #[cfg(feature = "sha1")]
+            HashAlgorithm::Sha1 => Self::Sha1(sha1::Digest::new()),
+            #[cfg(feature = "crc32")]
+            HashAlgorithm::Crc32 => Self::Crc32(crc32fast::Hasher::new()),
+            #[cfg(feature = "blake3")]
+            HashAlgorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            #[cfg(feature = "hash")]
+            Self::Sha256(hasher) => sha2::Digest::update(hasher, bytes),
+            #[cfg(feature = "md5")]
+            Self::Md5(hasher) => md5::Digest::update(hasher, bytes),
+            #[cfg(feature = "sha1")]
+            Self::Sha1(hasher) => sha1::Digest::update(hasher, bytes),
+            #[cfg(feature = "crc32")]
+            Self::Crc32(hasher) => hasher.update(bytes),
+            #[cfg(feature = "blake3")]
+            Self::Blake3(hasher) => {
+                hasher.update(bytes);
+            }
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        match self {
+            #[cfg(feature = "hash")]
+            Self::Sha256(hasher) => sha2::Digest::finalize(hasher).to_vec(),
+            #[cfg(feature = "md5")]
+            Self::Md5(hasher) => md5::Digest::finalize(hasher).to_vec(),
+            #[cfg(feature = "sha1")]
+            Self::Sha1(hasher) => sha1::Digest::finalize(hasher).to_vec(),
+            #[cfg(feature = "crc32")]
+            Self::Crc32(hasher) => hasher.finalize().to_be_bytes().to_vec(),
+            #[cfg(feature = "blake3")]
+            Self::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Computes the digest of a file with an algorithm, reading it in chunks so it's never fully in memory.
+/// **Requires the `hash`, `md5`, `sha1`, `crc32` or `blake3` feature**, for the algorithms to choose from.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `algorithm`: `HashAlgorithm`, such as `HashAlgorithm::Sha256`
+///
+/// # Returns
+/// Result<`String`>, the digest as lowercase hex, such as `sha256sum` prints it
+///
+/// # Examples
+/// ```
+/// use file_access::HashAlgorithm;
+///
+/// # #[cfg(feature = "hash")]
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "hash_example/release.tar";
+///         file_access::write_string(&file_path, &"abc")?;
+///
+///         let digest = file_access::hash(&file_path, HashAlgorithm::Sha256)?;
+///         assert_eq!(digest, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+///
+///         // Clean-up:
+///         file_access::delete(&"hash_example")?;
+///     })
+/// }
+/// # #[cfg(not(feature = "hash"))]
+/// # fn main() {}
+/// ```
+#[cfg(any(
+    feature = "hash",
+    feature = "md5",
+    feature = "sha1",
+    feature = "crc32",
+    feature = "blake3"
+))]
+pub fn hash<Path: AsPath + ?Sized>(file_path: &Path, algorithm: HashAlgorithm) -> Result<String> {
+    let digest = || -> io::Result<(String, u64)> {
+        let mut file = get_file(file_path)?;
+        let mut hasher = Hasher::new(algorithm);
+        let mut buffer = vec![0; CHUNK];
+        let mut read = 0;
+        loop {
+            match file.read(&mut buffer)? {
+                0 => return Ok((sha256::hex(&hasher.finish()), read)),
+                n => {
+                    hasher.update(&buffer[..n]);
+                    read += n as u64;
+                }
+            }
+        }
+    };
+    metered("hash", |(_, read)| (*read, 0), digest)
+        .map(|(digest, _)| digest)
+        .context("hash", file_path)
+}
+
+/// Computes the digest of a file like `hash`, calling `progress` after each chunk read,
+/// such as to show a progress bar while verifying a multi-gigabyte download.
+/// **Requires the `hash`, `md5`, `sha1`, `crc32` or `blake3` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `algorithm`: `HashAlgorithm`, such as `HashAlgorithm::Sha256`
+/// - `progress`: **mutably borrowed** `Progress` such as a `FnMut(&ProgressUpdate)` closure
+///
+/// # Returns
+/// Result<`String`>, the digest as lowercase hex
+///
+/// # Examples
+/// ```
+/// use file_access::{HashAlgorithm, ProgressUpdate};
+///
+/// # #[cfg(feature = "hash")]
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let mut hashed = 0;
+///         let digest = file_access::hash_with_progress(&"Cargo.toml", HashAlgorithm::Sha256, &mut |update: &ProgressUpdate| {
+///             hashed = update.bytes;
+///         })?;
+///         assert_eq!(digest, file_access::hash(&"Cargo.toml", HashAlgorithm::Sha256)?);
+///         assert_eq!(hashed, file_access::stat(&"Cargo.toml")?.len());
+///     })
+/// }
+/// # #[cfg(not(feature = "hash"))]
+/// # fn main() {}
+/// ```
+#[cfg(any(
+    feature = "hash",
+    feature = "md5",
+    feature = "sha1",
+    feature = "crc32",
+    feature = "blake3"
+))]
+pub fn hash_with_progress<Path: AsPath + ?Sized, P: Progress>(
+    file_path: &Path,
+    algorithm: HashAlgorithm,
+    progress: &mut P,
+) -> Result<String> {
+    let digest = || -> io::Result<(String, u64)> {
+        let mut file = get_file(file_path)?;
+        let mut tracker = Tracker::new(progress, Some(file.metadata()?.len()));
+        let mut hasher = Hasher::new(algorithm);
+        let read = tracker.pump(file_path.as_path(), &mut file, |chunk| {
+            hasher.update(chunk);
+            return Ok(());
+        })?;
+        tracker.done(file_path.as_path());
+        return Ok((sha256::hex(&hasher.finish()), read));
+    };
+    metered("hash_with_progress", |(_, read)| (*read, 0), digest)
+        .map(|(digest, _)| digest)
+        .context("hash_with_progress", file_path)
+}
+
+/// Checks whether two files have the same contents, comparing them chunk by chunk and stopping at the first difference.
+/// Files of different sizes are told apart without reading them.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `other_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`bool`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"same_content_example/a.txt", &"Hello, World!")?;
+///         file_access::write_string(&"same_content_example/b.txt", &"Hello, World!")?;
+///
+///         assert!(file_access::same_content(&"same_content_example/a.txt", &"same_content_example/b.txt")?);
+///
+///         // Clean-up:
+///         file_access::delete(&"same_content_example")?;
+///     })
+/// }
+/// ```
+pub fn same_content<Path: AsPath + ?Sized, Other: AsPath + ?Sized>(
+    file_path: &Path,
+    other_path: &Other,
+) -> Result<bool> {
+    let file = get_file(file_path).context("same_content", file_path)?;
+    let other = get_file(other_path).context("same_content", other_path)?;
+    let compare = || -> io::Result<bool> {
+        if file.metadata()?.len() != other.metadata()?.len() {
+            return Ok(false);
+        }
+        let (mut file, mut other) = (
+            BufReader::with_capacity(CHUNK, file),
+            BufReader::with_capacity(CHUNK, other),
+        );
+        loop {
+            let (chunk, other_chunk) = (file.fill_buf()?, other.fill_buf()?);
+            let n = chunk.len().min(other_chunk.len());
+            if chunk[..n] != other_chunk[..n] {
+                return Ok(false);
+            }
+            if n == 0 {
+                // Both ended, unless one of them changed size while being read
+                return Ok(chunk.len() == other_chunk.len());
+            }
+            file.consume(n);
+            other.consume(n);
+        }
+    };
+    compare().context("same_content", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    #[cfg(feature = "hash")]
+    fn hash() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "hash/file.txt";
+            write_string(&file, &"123456789")?;
+
+            // Action
+            let sha256 = super::hash(&file, HashAlgorithm::Sha256)?;
+            let missing = super::hash(&"hash/missing.txt", HashAlgorithm::Sha256);
+
+            // Assert
+            assert_eq!(
+                sha256,
+                "15e2b0d3c33891ebb0f1ef609ec419420c20e320ce94c65fbc8c3312448eb225"
+            );
+            assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+            #[cfg(feature = "md5")]
+            assert_eq!(
+                super::hash(&file, HashAlgorithm::Md5)?,
+                "25f9e794323b453885f5181f1b624d0b"
+            );
+            #[cfg(feature = "sha1")]
+            assert_eq!(
+                super::hash(&file, HashAlgorithm::Sha1)?,
+                "f7c3bc1d808e04732adf679965ccc34ca7ae3441"
+            );
+            #[cfg(feature = "crc32")]
+            assert_eq!(super::hash(&file, HashAlgorithm::Crc32)?, "cbf43926");
+            #[cfg(feature = "blake3")]
+            assert_eq!(
+                super::hash(&file, HashAlgorithm::Blake3)?,
+                "b7d65b48420d1033cb2595293263b6f72eabee20d55e699d0df1973b3c9deed1"
+            );
+
+            // Clean-up
+            delete(&"hash")?;
+        })
+    }
+
+    #[test]
+    fn same_content() -> Result<()> {
+        Ok({
+            // Arrange
+            let big = "x".repeat(CHUNK * 2 + 7);
+            write_string(&"same_content/a.txt", &big)?;
+            write_string(&"same_content/b.txt", &big)?;
+            write_string(&"same_content/c.txt", &format!("{}y", &big[1..]))?;
+            write_string(&"same_content/d.txt", &"short")?;
+
+            // Action
+            let same = super::same_content(&"same_content/a.txt", &"same_content/b.txt")?;
+            let differ = super::same_content(&"same_content/a.txt", &"same_content/c.txt")?;
+            let sizes = super::same_content(&"same_content/a.txt", &"same_content/d.txt")?;
+
+            // Assert
+            assert!(same);
+            assert!(!differ);
+            assert!(!sizes);
+
+            // Clean-up
+            delete(&"same_content")?;
+        })
+    }
+}
@@ -0,0 +1,473 @@
+use crate::{
+    internal::{fingerprint::fingerprint, glob::glob_match, paths},
+    *,
+};
+use std::{
+    collections::BTreeMap,
+    ffi::{OsStr, OsString},
+    ops::RangeBounds,
+    path::Path as StdPath,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+// The first line of a saved index, followed by the root it was scanned from
+const HEADER: &str = "file_access index v1";
+
+/// A file recorded in an `Index`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexEntry {
+    /// The path relative to the index root.
+    pub path: PathBuf,
+    /// The size, in bytes.
+    pub size: u64,
+    /// The last modification time.
+    pub modified: SystemTime,
+    /// A 64-bit hash of the contents, stable across runs and machines.
+    pub fingerprint: u64,
+}
+
+/// The paths that changed between two scans, as returned by `Index::refresh`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IndexChanges {
+    pub added: Vec<PathBuf>,
+    pub modified: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+}
+
+impl IndexChanges {
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// An index of every file below a root directory, mapping each relative path to its size,
+/// modification time and content fingerprint. It can be saved to and loaded from a file,
+/// and kept up to date with `refresh`, which only re-reads files whose size or modification time changed.
+/// There's no file system watcher behind it: call `refresh` whenever the index should catch up, such as on a timer.
+/// Symbolic links are not followed.
+///
+/// # Examples
+/// ```
+/// use file_access::Index;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let index = Index::scan(&"src")?;
+///         for entry in index.by_extension(&"rs") {
+///             println!("{} ({} bytes)", entry.path.display(), entry.size);
+///         }
+///
+///         index.save(&"index_example/src.index")?;
+///         let loaded = Index::load(&"index_example/src.index")?;
+///         assert_eq!(loaded.len(), index.len());
+///
+///         // Clean-up:
+///         file_access::delete(&"index_example")?;
+///     })
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Index {
+    root: PathBuf,
+    entries: BTreeMap<PathBuf, IndexEntry>,
+}
+
+// Escapes the characters used as separators in a saved index, and the bytes that aren't valid UTF-8 as `\xHH`
+fn escape(path: &OsStr) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for chunk in path.as_encoded_bytes().utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '\\' => escaped.push_str("\\\\"),
+                '\t' => escaped.push_str("\\t"),
+                '\n' => escaped.push_str("\\n"),
+                c => escaped.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            escaped.push_str(&format!("\\x{byte:02x}"));
+        }
+    }
+    return escaped;
+}
+
+fn unescape(path: &str) -> Result<PathBuf> {
+    let mut unescaped = Vec::with_capacity(path.len());
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        let c = match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('t') => '\t',
+                Some('n') => '\n',
+                Some('x') => {
+                    let hex: String = chars.by_ref().take(2).collect();
+                    let byte = u8::from_str_radix(&hex, 16).map_err(|_| invalid(path))?;
+                    unescaped.push(byte);
+                    continue;
+                }
+                Some(c) => c,
+                None => '\\',
+            },
+            (c, false) => c,
+        };
+        unescaped.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+    return Ok(paths::from_bytes(unescaped)?);
+}
+
+// Joins the components of a relative path with `/`, as it's saved and matched against glob patterns
+fn slashed(relative: &StdPath) -> OsString {
+    let mut slashed = OsString::new();
+    for (i, component) in relative.components().enumerate() {
+        if i > 0 {
+            slashed.push("/");
+        }
+        slashed.push(component);
+    }
+    return slashed;
+}
+
+fn invalid<Line: AsRef<str>>(line: Line) -> Error {
+    Error::new(ErrorKind::InvalidData, line.as_ref())
+}
+
+// Collects the metadata of every regular file below `dir`, keyed by its path relative to `root`
+fn walk(root: &StdPath, dir: &StdPath, files: &mut BTreeMap<PathBuf, Metadata>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?; // doesn't follow symlinks
+        if metadata.is_dir() {
+            walk(root, &entry.path(), files)?;
+        } else if metadata.is_file() {
+            let path = entry.path();
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            files.insert(relative.to_path_buf(), metadata);
+        }
+    }
+    return Ok(());
+}
+
+impl Index {
+    /// Scans every file below a root directory, fingerprinting their contents.
+    ///
+    /// # Parameters
+    /// - `root`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`Index`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::Index;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         let index = Index::scan(&"src")?;
+    ///         println!("{} files", index.len());
+    ///     })
+    /// }
+    /// ```
+    pub fn scan<Path: AsPath + ?Sized>(root: &Path) -> Result<Self> {
+        let mut index = Self {
+            root: path_of(root),
+            entries: BTreeMap::new(),
+        };
+        index.refresh()?;
+
+        return Ok(index);
+    }
+
+    /// Loads an index previously written by `save`.
+    ///
+    /// # Parameters
+    /// - `index_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`Index`>
+    pub fn load<Path: AsPath + ?Sized>(index_path: &Path) -> Result<Self> {
+        let text = read_string(index_path)?;
+        let parse = || -> Result<Self> {
+            let mut lines = text.lines();
+            if lines.next() != Some(HEADER) {
+                return Err(invalid("not an index file"));
+            }
+            let root = unescape(
+                lines
+                    .next()
+                    .ok_or_else(|| invalid("the index has no root"))?,
+            )?;
+
+            let mut entries = BTreeMap::new();
+            for line in lines {
+                let fields: Vec<&str> = line.splitn(5, '\t').collect();
+                let [fingerprint, size, secs, nanos, path] = fields[..] else {
+                    return Err(invalid(line));
+                };
+                let number = |field: &str| field.parse::<u64>().map_err(|_| invalid(line));
+                let entry = IndexEntry {
+                    path: unescape(path)?,
+                    size: number(size)?,
+                    modified: UNIX_EPOCH + Duration::new(number(secs)?, number(nanos)? as u32),
+                    fingerprint: u64::from_str_radix(fingerprint, 16).map_err(|_| invalid(line))?,
+                };
+                entries.insert(entry.path.clone(), entry);
+            }
+
+            return Ok(Self { root, entries });
+        };
+        parse().context("Index::load", index_path)
+    }
+
+    /// Saves the index to a file. This function will create the file **and its full directory path** if they don't exist,
+    /// and will entirely replace the contents.
+    ///
+    /// # Parameters
+    /// - `index_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    pub fn save<Path: AsPath + ?Sized>(&self, index_path: &Path) -> Result<()> {
+        let mut lines = vec![HEADER.to_string(), escape(self.root.as_os_str())];
+        for entry in self.entries.values() {
+            let modified = entry
+                .modified
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            lines.push(format!(
+                "{:016x}\t{}\t{}\t{}\t{}",
+                entry.fingerprint,
+                entry.size,
+                modified.as_secs(),
+                modified.subsec_nanos(),
+                escape(&slashed(&entry.path))
+            ));
+        }
+        return write_lines(index_path, &lines);
+    }
+
+    /// Rescans the root directory and updates the index in place.
+    /// Only new files, and files whose size or modification time changed, are read again.
+    ///
+    /// # Returns
+    /// Result<`IndexChanges`>
+    ///
+    /// # Examples
+    /// ```
+    /// use file_access::Index;
+    /// use std::path::PathBuf;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     Ok({
+    ///         file_access::write_string(&"index_example_refresh/a.txt", &"a")?;
+    ///         let mut index = Index::scan(&"index_example_refresh")?;
+    ///
+    ///         file_access::write_string(&"index_example_refresh/b.txt", &"b")?;
+    ///         let changes = index.refresh()?;
+    ///         assert_eq!(changes.added, [PathBuf::from("b.txt")]);
+    ///
+    ///         // Clean-up:
+    ///         file_access::delete(&"index_example_refresh")?;
+    ///     })
+    /// }
+    /// ```
+    pub fn refresh(&mut self) -> Result<IndexChanges> {
+        let root = path_of(&self.root);
+        let mut files = BTreeMap::new();
+        walk(&root, &root, &mut files).context("Index::refresh", &self.root)?;
+
+        let mut changes = IndexChanges {
+            removed: self
+                .entries
+                .keys()
+                .filter(|path| !files.contains_key(*path))
+                .cloned()
+                .collect(),
+            ..Default::default()
+        };
+        for path in &changes.removed {
+            self.entries.remove(path);
+        }
+
+        for (path, metadata) in files {
+            let full_path = root.join(&path);
+            let modified = metadata
+                .modified()
+                .context("Index::refresh", &*full_path.to_string_lossy())?;
+            let size = metadata.len();
+            let changed = match self.entries.get(&path) {
+                Some(entry) if entry.size == size && entry.modified == modified => continue,
+                Some(_) => &mut changes.modified,
+                None => &mut changes.added,
+            };
+            let entry = IndexEntry {
+                fingerprint: fingerprint(&full_path)
+                    .context("Index::refresh", &*full_path.to_string_lossy())?,
+                path: path.clone(),
+                size,
+                modified,
+            };
+            self.entries.insert(path.clone(), entry);
+            changed.push(path);
+        }
+
+        return Ok(changes);
+    }
+
+    /// The root directory the index was scanned from.
+    pub fn root(&self) -> &StdPath {
+        &self.root
+    }
+
+    /// The number of files in the index.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the index contains no files.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over every file in the index, ordered by path.
+    pub fn entries(&self) -> impl Iterator<Item = &IndexEntry> {
+        self.entries.values()
+    }
+
+    /// Looks up a file by its path relative to the root.
+    pub fn get<Path: AsPath + ?Sized>(&self, path: &Path) -> Option<&IndexEntry> {
+        self.entries.get(path.as_path())
+    }
+
+    /// Finds the files with the given extension, such as `"rs"`.
+    pub fn by_extension<Ext: AsRef<str> + ?Sized>(&self, extension: &Ext) -> Vec<&IndexEntry> {
+        let extension = extension.as_ref().trim_start_matches('.');
+        self.entries()
+            .filter(|entry| entry.path.extension().is_some_and(|ext| ext == extension))
+            .collect()
+    }
+
+    /// Finds the files whose size, in bytes, is within a range such as `1024..` or `..=4096`.
+    pub fn by_size<Range: RangeBounds<u64>>(&self, range: Range) -> Vec<&IndexEntry> {
+        self.entries()
+            .filter(|entry| range.contains(&entry.size))
+            .collect()
+    }
+
+    /// Finds the files whose relative path matches a glob pattern, such as `"**/*.log"`,
+    /// where `*` matches within a path segment, `?` matches one character, `[a-z]` matches a character class,
+    /// and `**` matches any number of directories.
+    pub fn matching<Pattern: AsRef<str> + ?Sized>(&self, pattern: &Pattern) -> Vec<&IndexEntry> {
+        self.entries()
+            .filter(|entry| glob_match(pattern.as_ref(), &slashed(&entry.path).to_string_lossy()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn scan_and_query() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"index_scan/a.txt", &"hello")?;
+            write_string(&"index_scan/logs/b.log", &"hello, world")?;
+            write_string(&"index_scan/logs/old/c.log", &"hello")?;
+
+            // Action
+            let index = Index::scan(&"index_scan")?;
+
+            // Assert
+            assert_eq!(index.len(), 3);
+            assert_eq!(index.by_extension(&"log").len(), 2);
+            assert_eq!(index.by_size(10..).len(), 1);
+            assert_eq!(index.matching(&"logs/**/*.log").len(), 2);
+            assert_eq!(index.matching(&"*.txt").len(), 1);
+            assert_eq!(
+                index.get(&"a.txt").unwrap().fingerprint,
+                index.get(&"logs/old/c.log").unwrap().fingerprint,
+                "same contents should have the same fingerprint"
+            );
+
+            // Clean-up
+            delete(&"index_scan")?;
+        })
+    }
+
+    #[test]
+    fn save_and_load() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"index_save/dir/tab\tname.txt", &"hello")?;
+            let index = Index::scan(&"index_save/dir")?;
+
+            // Action
+            index.save(&"index_save/dir.index")?;
+            let loaded = Index::load(&"index_save/dir.index")?;
+
+            // Assert
+            assert_eq!(loaded, index);
+            append_line(&"index_save/dir.index", &"not\tan\tentry")?;
+            let error = Index::load(&"index_save/dir.index").unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+            assert_eq!(error.operation, Some("Index::load"));
+            assert_eq!(error.path, Some(PathBuf::from("index_save/dir.index")));
+
+            // Clean-up
+            delete(&"index_save")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn save_and_load_non_utf8() -> Result<()> {
+        Ok({
+            // Arrange
+            use std::os::unix::ffi::OsStrExt;
+            let name = OsStr::from_bytes(b"caf\xe9\\x.txt");
+            write_string(&PathBuf::from("index_non_utf8/dir").join(name), &"hello")?;
+            let index = Index::scan(&"index_non_utf8/dir")?;
+
+            // Action
+            index.save(&"index_non_utf8/dir.index")?;
+            let loaded = Index::load(&"index_non_utf8/dir.index")?;
+
+            // Assert
+            assert_eq!(loaded, index);
+            assert!(loaded.get(name).is_some());
+            assert_eq!(loaded.matching(&"caf*.txt").len(), 1);
+
+            // Clean-up
+            delete(&"index_non_utf8")?;
+        })
+    }
+
+    #[test]
+    fn refresh() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"index_refresh/keep.txt", &"keep")?;
+            write_string(&"index_refresh/change.txt", &"before")?;
+            write_string(&"index_refresh/remove.txt", &"remove")?;
+            let mut index = Index::scan(&"index_refresh")?;
+
+            // Action
+            write_string(&"index_refresh/change.txt", &"after, and longer")?;
+            delete(&"index_refresh/remove.txt")?;
+            write_string(&"index_refresh/add.txt", &"add")?;
+            let changes = index.refresh()?;
+
+            // Assert
+            assert_eq!(changes.added, [PathBuf::from("add.txt")]);
+            assert_eq!(changes.modified, [PathBuf::from("change.txt")]);
+            assert_eq!(changes.removed, [PathBuf::from("remove.txt")]);
+            assert!(index.refresh()?.is_empty());
+
+            // Clean-up
+            delete(&"index_refresh")?;
+        })
+    }
+}
@@ -0,0 +1,150 @@
+// Matches a '/'-separated path against a glob pattern, where
+// `*` matches within a path segment, `?` matches one character,
+// `[abc]`/`[a-z]`/`[!abc]` match a character class, and `**` matches any number of whole segments
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    return match_segments(&pattern, &path);
+}
+
+// Matches with two pointers, backtracking only to just after the last `**`: each other segment
+// matches exactly one path segment, so retrying from there is enough, and no input takes more than
+// quadratic time
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    let (mut p, mut s) = (0, 0);
+    let mut star = None;
+    while s < path.len() {
+        if pattern.get(p) == Some(&"**") {
+            star = Some((p + 1, s));
+            p += 1;
+        } else if p < pattern.len() && match_chars(pattern[p], path[s]) {
+            p += 1;
+            s += 1;
+        } else if let Some((after, from)) = star {
+            star = Some((after, from + 1));
+            (p, s) = (after, from + 1);
+        } else {
+            return false;
+        }
+    }
+    return pattern[p..].iter().all(|segment| *segment == "**");
+}
+
+enum Token {
+    Star,
+    Any,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+    Char(char),
+}
+
+fn tokenize(pattern: &str) -> Vec<Token> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let end = chars[i + 1..].iter().position(|c| *c == ']');
+        match (chars[i], end) {
+            ('*', _) => tokens.push(Token::Star),
+            ('?', _) => tokens.push(Token::Any),
+            ('[', Some(end)) => {
+                let class = &chars[i + 1..i + 1 + end];
+                let (negated, class) = match class.first() {
+                    Some('!') | Some('^') => (true, &class[1..]),
+                    _ => (false, class),
+                };
+                let mut ranges = vec![];
+                let mut j = 0;
+                while j < class.len() {
+                    if j + 2 < class.len() && class[j + 1] == '-' {
+                        ranges.push((class[j], class[j + 2]));
+                        j += 3;
+                    } else {
+                        ranges.push((class[j], class[j]));
+                        j += 1;
+                    }
+                }
+                tokens.push(Token::Class { negated, ranges });
+                i += end + 1;
+            }
+            // an unterminated class is just a literal '['
+            (c, _) => tokens.push(Token::Char(c)),
+        }
+        i += 1;
+    }
+    return tokens;
+}
+
+impl Token {
+    // Whether a token other than `*` matches a character
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Token::Star | Token::Any => true,
+            Token::Class { negated, ranges } => {
+                ranges.iter().any(|(low, high)| *low <= c && c <= *high) != *negated
+            }
+            Token::Char(p) => *p == c,
+        }
+    }
+}
+
+// Matches a segment the same way as `match_segments`, with `*` in the place of `**`
+fn match_chars(pattern: &str, text: &str) -> bool {
+    let (pattern, text): (Vec<Token>, Vec<char>) = (tokenize(pattern), text.chars().collect());
+    let (mut p, mut t) = (0, 0);
+    let mut star = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(Token::Star) => {
+                star = Some((p + 1, t));
+                p += 1;
+            }
+            Some(token) if token.matches(text[t]) => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((after, from)) => {
+                    star = Some((after, from + 1));
+                    (p, t) = (after, from + 1);
+                }
+                None => return false,
+            },
+        }
+    }
+    return pattern[p..]
+        .iter()
+        .all(|token| matches!(token, Token::Star));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Result;
+
+    #[test]
+    fn glob_match() -> Result<()> {
+        Ok({
+            // Assert
+            assert!(super::glob_match("*.log", "app.log"));
+            assert!(!super::glob_match("*.log", "logs/app.log"));
+            assert!(super::glob_match("logs/**/*.log", "logs/app.log"));
+            assert!(super::glob_match("logs/**/*.log", "logs/2024/01/app.log"));
+            assert!(super::glob_match("**", "any/thing"));
+            assert!(super::glob_match("file.?", "file.1"));
+            assert!(super::glob_match("file.[0-9]", "file.7"));
+            assert!(!super::glob_match("file.[!0-9]", "file.7"));
+            assert!(!super::glob_match("src/*.rs", "src/internal/mod.rs"));
+            assert!(super::glob_match("a*b*c", "aXbYbZc"));
+            assert!(super::glob_match("[ab", "[ab"));
+            assert!(super::glob_match("**/b/**/c", "a/b/x/b/y/c"));
+            assert!(!super::glob_match("**/b/c", "a/b/x/c"));
+            let long = "a".repeat(100);
+            assert!(!super::glob_match(&"*a".repeat(30), &format!("{long}b")));
+            let path = format!("{}b", "a/".repeat(100));
+            assert!(!super::glob_match(&"**/a/".repeat(30), &path));
+        })
+    }
+}
@@ -0,0 +1,14 @@
+use std::{fs::File, io::Result, path::Path};
+
+// Opens (creating if needed) and exclusively locks a lock file, across threads and processes;
+// the lock is released when the returned file is closed
+pub fn exclusive(lock_path: &Path) -> Result<File> {
+    let lock = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path)?;
+    lock.lock()?;
+
+    return Ok(lock);
+}
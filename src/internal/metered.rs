@@ -0,0 +1,64 @@
+use std::io;
+#[cfg(feature = "metrics")]
+use std::io::ErrorKind;
+
+// The errors an operation can fail with, by the kind they're counted under
+#[cfg(feature = "metrics")]
+pub trait Kind {
+    fn kind(&self) -> ErrorKind;
+}
+
+#[cfg(feature = "metrics")]
+impl Kind for io::Error {
+    fn kind(&self) -> ErrorKind {
+        io::Error::kind(self)
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl Kind for crate::Error {
+    fn kind(&self) -> ErrorKind {
+        crate::Error::kind(self)
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn run<T, E: Kind>(
+    operation: &'static str,
+    bytes: impl FnOnce(&T) -> (u64, u64),
+    f: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+    let start = std::time::Instant::now();
+    let result = f();
+    let outcome = result.as_ref().map(bytes).map_err(Kind::kind);
+    crate::metrics::record(operation, start.elapsed(), outcome);
+
+    return result;
+}
+
+#[cfg(not(feature = "metrics"))]
+fn run<T, E>(
+    _operation: &'static str,
+    _bytes: impl FnOnce(&T) -> (u64, u64),
+    f: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+    f()
+}
+
+// Runs an operation, recording it with the `metrics` feature; `bytes` tells how many bytes a success read and wrote
+pub fn metered<T>(
+    operation: &'static str,
+    bytes: impl FnOnce(&T) -> (u64, u64),
+    f: impl FnOnce() -> io::Result<T>,
+) -> io::Result<T> {
+    run(operation, bytes, f)
+}
+
+// Like `metered`, for an operation whose errors already name the path they occurred on
+pub fn metered_with_context<T>(
+    operation: &'static str,
+    bytes: impl FnOnce(&T) -> (u64, u64),
+    f: impl FnOnce() -> crate::Result<T>,
+) -> crate::Result<T> {
+    run(operation, bytes, f)
+}
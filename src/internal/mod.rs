@@ -1,2 +1,18 @@
+pub mod fingerprint;
+pub mod glob;
+pub mod lock;
+pub mod metered;
+pub mod parallel;
+pub mod paths;
+#[cfg(any(
+    feature = "hash",
+    feature = "md5",
+    feature = "sha1",
+    feature = "crc32",
+    feature = "blake3"
+))]
+pub mod sha256;
+pub mod sys;
 pub mod traits;
 pub mod types;
+pub mod uri;
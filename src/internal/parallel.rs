@@ -0,0 +1,42 @@
+use std::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+// File operations are I/O bound, so it pays to have more requests in flight than there are cores
+const THREADS_PER_CORE: usize = 4;
+
+// Applies `f` to every item on a pool of scoped threads, returning the results in input order
+pub fn map<Item: Sync, Output: Send>(
+    items: &[Item],
+    f: impl Fn(&Item) -> Output + Sync,
+) -> Vec<Output> {
+    let cores = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let threads = (cores * THREADS_PER_CORE).min(items.len());
+    let next = AtomicUsize::new(0);
+
+    let mut results: Vec<(usize, Output)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = vec![];
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        match items.get(i) {
+                            Some(item) => results.push((i, f(item))),
+                            None => return results,
+                        }
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+    results.sort_unstable_by_key(|(i, _)| *i);
+
+    return results.into_iter().map(|(_, output)| output).collect();
+}
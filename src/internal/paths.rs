@@ -0,0 +1,152 @@
+#[cfg(windows)]
+use std::borrow::Cow;
+use std::path::{Component, Path, PathBuf};
+
+// Paths at least this long need the `\\?\` prefix on Windows; directories are limited to MAX_PATH (260)
+// minus room for an 8.3 file name, and so are the Win32 calls that take them
+#[cfg(windows)]
+const LONG_PATH: usize = 248;
+// The longest path Windows takes without the `\\?\` prefix
+const MAX_PATH: usize = 260;
+
+// Collapses `.` and `..` segments and redundant separators, without touching the disk
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            component => normalized.push(component),
+        }
+    }
+    if normalized.as_os_str().is_empty() {
+        normalized.push(".");
+    }
+    return normalized;
+}
+
+// Adds the `\\?\` prefix to a normalized absolute Windows path: `C:\a` becomes `\\?\C:\a`,
+// and `\\server\share\a` becomes `\\?\UNC\server\share\a`; other paths are left as they are
+fn add_prefix(path: &str) -> Option<String> {
+    if path.starts_with(r"\\?\") || path.starts_with(r"\\.\") {
+        return None;
+    }
+    if let Some(unc) = path.strip_prefix(r"\\") {
+        return Some(format!(r"\\?\UNC\{unc}"));
+    }
+    let drive = path.as_bytes();
+    return match drive.len() >= 3 && drive[0].is_ascii_alphabetic() && &drive[1..3] == br":\" {
+        true => Some(format!(r"\\?\{path}")),
+        false => None,
+    };
+}
+
+// Removes the `\\?\` prefix that `add_prefix` adds, and that `fs::canonicalize` returns on Windows
+pub fn strip_prefix(path: &str) -> Option<String> {
+    if let Some(unc) = path.strip_prefix(r"\\?\UNC\") {
+        return Some(format!(r"\\{unc}"));
+    }
+    let drive = path.strip_prefix(r"\\?\")?;
+    return add_prefix(drive).map(|_| drive.to_string());
+}
+
+// Gets the `\\?\` form of a path too long for Win32 calls that take raw paths; std does this itself for its own calls.
+// `.` and `..` aren't resolved inside the `\\?\` form, so the path is made absolute and normalized first
+#[cfg(windows)]
+pub fn extended(path: &Path) -> Cow<'_, Path> {
+    if path.as_os_str().len() < LONG_PATH {
+        return Cow::Borrowed(path);
+    }
+    let absolute = match path.is_absolute() {
+        true => normalize(path),
+        false => match std::env::current_dir() {
+            Ok(dir) => normalize(&dir.join(path)),
+            Err(_) => return Cow::Borrowed(path),
+        },
+    };
+    return match absolute.to_str().and_then(add_prefix) {
+        Some(prefixed) => Cow::Owned(PathBuf::from(prefixed)),
+        None => Cow::Borrowed(path),
+    };
+}
+
+// Removes the `\\?\` prefix from a path, such as one from `fs::canonicalize`, when it fits without it,
+// so it reads like the paths users type, including `\\server\share` for UNC shares
+pub fn simplified(path: PathBuf) -> PathBuf {
+    if !cfg!(windows) {
+        return path;
+    }
+    return match path.to_str().and_then(strip_prefix) {
+        Some(stripped) if stripped.len() < MAX_PATH => PathBuf::from(stripped),
+        _ => path,
+    };
+}
+
+// Turns the bytes of a path back into it: any bytes on Unix, and only UTF-8 elsewhere
+#[cfg(unix)]
+pub fn from_bytes(bytes: Vec<u8>) -> std::io::Result<PathBuf> {
+    use std::os::unix::ffi::OsStringExt;
+    Ok(PathBuf::from(std::ffi::OsString::from_vec(bytes)))
+}
+
+#[cfg(not(unix))]
+pub fn from_bytes(bytes: Vec<u8>) -> std::io::Result<PathBuf> {
+    String::from_utf8(bytes).map(PathBuf::from).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "the path isn't valid Unicode",
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefixes() {
+        // Arrange
+        let paths = [
+            r"C:\a\b",
+            r"\\server\share\a",
+            r"\\?\C:\a",
+            r"\\.\pipe\a",
+            r"a\b",
+        ];
+
+        // Action
+        let added = paths.map(add_prefix);
+        let stripped = added
+            .clone()
+            .map(|path| path.and_then(|p| strip_prefix(&p)));
+
+        // Assert
+        assert_eq!(
+            added,
+            [
+                Some(r"\\?\C:\a\b".to_string()),
+                Some(r"\\?\UNC\server\share\a".to_string()),
+                None,
+                None,
+                None,
+            ]
+        );
+        assert_eq!(
+            stripped,
+            [
+                Some(r"C:\a\b".to_string()),
+                Some(r"\\server\share\a".to_string()),
+                None,
+                None,
+                None,
+            ]
+        );
+        assert_eq!(strip_prefix(r"\\?\Volume{1234}\a"), None);
+    }
+}
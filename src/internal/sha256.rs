@@ -0,0 +1,57 @@
+#[cfg(feature = "hash")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "hash")]
+use std::{
+    fs::File,
+    io::{Read, Result},
+    path::Path,
+};
+
+// Hashes the contents of a file without reading it into memory all at once
+#[cfg(feature = "hash")]
+pub fn digest_file(path: &Path) -> Result<[u8; 32]> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        match file.read(&mut buffer)? {
+            0 => return Ok(hasher.finalize().into()),
+            n => hasher.update(&buffer[..n]),
+        }
+    }
+}
+
+// Formats a digest as lowercase hex
+pub fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+#[cfg(feature = "hash")]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    fn sha256(bytes: &[u8]) -> String {
+        hex(&Sha256::digest(bytes))
+    }
+
+    #[test]
+    fn known_digests() -> Result<()> {
+        Ok({
+            // Assert
+            assert_eq!(
+                sha256(b""),
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+            );
+            assert_eq!(
+                sha256(b"abc"),
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            );
+            assert_eq!(
+                sha256(&[b'a'; 1_000]),
+                "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3"
+            );
+        })
+    }
+}
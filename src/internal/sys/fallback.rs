@@ -0,0 +1,72 @@
+use crate::preserve::*;
+use std::{
+    io::{Error, ErrorKind},
+    path::Path,
+};
+
+// Neither xattrs nor ACLs can be carried over on this platform
+// (file flags and SELinux contexts are documented as ignored outside macOS/Linux)
+pub fn preserve(
+    _from: &Path,
+    _to: &Path,
+    preserve: Preserve,
+) -> std::io::Result<Vec<PreserveWarning>> {
+    Ok([(Preserve::XATTRS, "xattrs"), (Preserve::ACLS, "acls")]
+        .into_iter()
+        .filter(|(item, _)| preserve.contains(*item))
+        .map(|(_, item)| PreserveWarning::new(item, Error::from(ErrorKind::Unsupported)))
+        .collect())
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn filesystem_info(_path: &Path) -> std::io::Result<crate::filesystem::FilesystemInfo> {
+    Err(Error::from(ErrorKind::Unsupported))
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn device_id(_path: &Path) -> std::io::Result<u64> {
+    Err(Error::from(ErrorKind::Unsupported))
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn file_id(_file: &std::fs::File) -> std::io::Result<(u64, u64)> {
+    Err(Error::from(ErrorKind::Unsupported))
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn link_count(_path: &Path) -> std::io::Result<u64> {
+    Err(Error::from(ErrorKind::Unsupported))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+pub fn volume_kind(_path: &Path) -> std::io::Result<crate::filesystem::VolumeKind> {
+    Ok(crate::filesystem::VolumeKind::Unknown)
+}
+
+// Local time zones aren't looked up on this platform, so times are shown in UTC
+#[cfg(not(any(unix, windows)))]
+pub fn utc_offset(_secs: i64) -> i64 {
+    0
+}
+
+// Processes can't be looked up on this platform, so they're taken to be running
+#[cfg(not(any(unix, windows)))]
+pub fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+// Holes can't be found on this platform, so the contents are copied whole
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn copy_sparse(from: &mut std::fs::File, to: &mut std::fs::File) -> std::io::Result<u64> {
+    std::io::copy(from, to)
+}
+
+// Space can't be reserved on its own on this platform, so the file is only extended with zeros
+// (which NTFS allocates, unless the file is marked sparse)
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn allocate(file: &std::fs::File, len: u64) -> std::io::Result<()> {
+    if file.metadata()?.len() < len {
+        file.set_len(len)?;
+    }
+    return Ok(());
+}
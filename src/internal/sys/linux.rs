@@ -0,0 +1,292 @@
+use crate::{filesystem::*, internal::sys::unix::c_path, preserve::*};
+use std::{
+    ffi::{CStr, CString},
+    fs::{self, File},
+    io::{Error, ErrorKind, Result, Write},
+    os::fd::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd},
+    path::Path,
+};
+
+// POSIX ACLs are stored by the kernel as xattrs under these names
+const ACL_PREFIX: &[u8] = b"system.posix_acl_";
+// and so is the SELinux security context
+const SELINUX: &CStr = c"security.selinux";
+
+// Lists the names of the extended attributes set on a file
+pub fn xattr_names(path: &Path) -> Result<Vec<CString>> {
+    let path = c_path(path)?;
+    let size = unsafe { libc::listxattr(path.as_ptr(), std::ptr::null_mut(), 0) };
+    if size < 0 {
+        return Err(Error::last_os_error());
+    }
+    let mut buf = vec![0u8; size as usize];
+    let size = unsafe { libc::listxattr(path.as_ptr(), buf.as_mut_ptr().cast(), buf.len()) };
+    if size < 0 {
+        return Err(Error::last_os_error());
+    }
+    buf.truncate(size as usize);
+
+    return Ok(buf
+        .split_inclusive(|b| *b == 0)
+        .filter_map(|name| CStr::from_bytes_with_nul(name).ok())
+        .map(CStr::to_owned)
+        .collect());
+}
+
+// Gets the value of an extended attribute
+pub fn get_xattr(path: &Path, name: &CStr) -> Result<Vec<u8>> {
+    let path = c_path(path)?;
+    let size = unsafe { libc::getxattr(path.as_ptr(), name.as_ptr(), std::ptr::null_mut(), 0) };
+    if size < 0 {
+        return Err(Error::last_os_error());
+    }
+    let mut buf = vec![0u8; size as usize];
+    let size = unsafe {
+        libc::getxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            buf.as_mut_ptr().cast(),
+            buf.len(),
+        )
+    };
+    if size < 0 {
+        return Err(Error::last_os_error());
+    }
+    buf.truncate(size as usize);
+
+    return Ok(buf);
+}
+
+// Sets (creating or replacing) the value of an extended attribute
+pub fn set_xattr(path: &Path, name: &CStr, value: &[u8]) -> Result<()> {
+    let path = c_path(path)?;
+    match unsafe {
+        libc::setxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            value.as_ptr().cast(),
+            value.len(),
+            0,
+        )
+    } {
+        0 => Ok(()),
+        _ => Err(Error::last_os_error()),
+    }
+}
+
+// Removes an extended attribute
+#[cfg(feature = "acl")]
+pub fn remove_xattr(path: &Path, name: &CStr) -> Result<()> {
+    let path = c_path(path)?;
+    match unsafe { libc::removexattr(path.as_ptr(), name.as_ptr()) } {
+        0 => Ok(()),
+        _ => Err(Error::last_os_error()),
+    }
+}
+
+// Writes the contents to an anonymous O_TMPFILE in the file's directory, then links it into place,
+// keeping the permissions (and where allowed the owner) of the file it replaces like `write_via_rename`;
+// fails with ErrorKind::Unsupported if the filesystem can't do that
+pub fn write_anon_then_link(path: &Path, contents: &[u8]) -> Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let flags = libc::O_TMPFILE | libc::O_WRONLY | libc::O_CLOEXEC;
+    let fd = unsafe { libc::open(c_path(dir)?.as_ptr(), flags, 0o666) };
+    if fd < 0 {
+        let x = Error::last_os_error();
+        return Err(match x.raw_os_error() {
+            Some(libc::EOPNOTSUPP | libc::EISDIR | libc::EINVAL) => {
+                Error::new(ErrorKind::Unsupported, x)
+            }
+            _ => x,
+        });
+    }
+    let mut file = unsafe { File::from_raw_fd(fd) };
+    crate::keep_permissions(path, &file)?;
+    file.write_all(contents)?;
+
+    // linking through /proc doesn't need CAP_DAC_READ_SEARCH, unlike AT_EMPTY_PATH
+    let proc = c_path(Path::new(&format!("/proc/self/fd/{}", file.as_raw_fd())))?;
+    let link = |to: &Path| -> Result<()> {
+        let to = c_path(to)?;
+        let flags = libc::AT_SYMLINK_FOLLOW;
+        match unsafe {
+            libc::linkat(
+                libc::AT_FDCWD,
+                proc.as_ptr(),
+                libc::AT_FDCWD,
+                to.as_ptr(),
+                flags,
+            )
+        } {
+            0 => Ok(()),
+            _ => Err(Error::last_os_error()),
+        }
+    };
+
+    return match link(path) {
+        // linkat(2) never replaces, so link next to an existing file and rename over it
+        Err(x) if x.kind() == ErrorKind::AlreadyExists => {
+            let temp = crate::temp_sibling(path);
+            link(&temp)?;
+            fs::rename(&temp, path).inspect_err(|_| _ = fs::remove_file(&temp))
+        }
+        result => result,
+    };
+}
+
+// Names the filesystem type behind a statfs(2) magic number
+fn fs_type_name(magic: u32) -> String {
+    match magic {
+        0xEF53 => "ext4",
+        0x58465342 => "xfs",
+        0x9123683E => "btrfs",
+        0x2FC12FC1 => "zfs",
+        0xF2F52010 => "f2fs",
+        0x01021994 => "tmpfs",
+        0x858458F6 => "ramfs",
+        0x794C7630 => "overlay",
+        0x6969 => "nfs",
+        0xFF534D42 => "cifs",
+        0xFE534D42 => "smb2",
+        0x517B => "smb",
+        0x01021997 => "9p",
+        0x65735546 => "fuse",
+        0x4D44 => "vfat",
+        0x2011BAB0 => "exfat",
+        0x5346544E => "ntfs",
+        0x9660 => "iso9660",
+        0x73717368 => "squashfs",
+        0x9FA0 => "proc",
+        0x62656572 => "sysfs",
+        _ => return format!("0x{magic:x}"),
+    }
+    .to_string()
+}
+
+// Queries the statfs(2) of the filesystem containing a path
+fn statfs(path: &Path) -> Result<libc::statfs> {
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    match unsafe { libc::statfs(c_path(path)?.as_ptr(), &mut stat) } {
+        0 => Ok(stat),
+        _ => Err(Error::last_os_error()),
+    }
+}
+
+// Gets the type, space and block size of the filesystem containing a path
+pub fn filesystem_info(path: &Path) -> Result<FilesystemInfo> {
+    return Ok(FilesystemInfo {
+        fs_type: fs_type_name(statfs(path)?.f_type as u32),
+        ..crate::internal::sys::unix::filesystem_info(path)?
+    });
+}
+
+// Tells network and memory-backed filesystems apart by their type,
+// and removable media by what sysfs reports for the underlying block device
+pub fn volume_kind(path: &Path) -> Result<VolumeKind> {
+    let kind = match fs_type_name(statfs(path)?.f_type as u32).as_str() {
+        "nfs" | "cifs" | "smb2" | "smb" | "9p" => VolumeKind::Network,
+        "tmpfs" | "ramfs" => VolumeKind::Ramdisk,
+        _ => {
+            use std::os::unix::fs::MetadataExt;
+            let dev = fs::metadata(path)?.dev();
+            let sysfs = format!("/sys/dev/block/{}:{}", libc::major(dev), libc::minor(dev));
+            // partitions don't have the flag themselves, their parent disk does
+            let removable = fs::read_to_string(format!("{sysfs}/removable"))
+                .or_else(|_| fs::read_to_string(format!("{sysfs}/../removable")));
+            match removable {
+                Ok(flag) if flag.trim() == "1" => VolumeKind::Removable,
+                Ok(_) => VolumeKind::Local,
+                Err(_) => VolumeKind::Unknown,
+            }
+        }
+    };
+    return Ok(kind);
+}
+
+// Gets the SELinux security context of a file, if it has one
+pub fn selinux_context(path: &Path) -> Result<Option<String>> {
+    match get_xattr(path, SELINUX) {
+        Ok(context) => Ok(Some(
+            String::from_utf8_lossy(&context)
+                .trim_end_matches('\0')
+                .to_string(),
+        )),
+        Err(x) if x.raw_os_error() == Some(libc::ENODATA) => Ok(None),
+        Err(x) => Err(x),
+    }
+}
+
+// Sets the SELinux security context of a file
+pub fn set_selinux_context(path: &Path, context: &str) -> Result<()> {
+    set_xattr(path, SELINUX, context.as_bytes())
+}
+
+pub fn preserve(from: &Path, to: &Path, preserve: Preserve) -> Result<Vec<PreserveWarning>> {
+    let mut warnings = vec![];
+
+    // file flags are a BSD/macOS concept, so only xattrs and ACLs apply here;
+    // a source on a filesystem without xattr support simply has nothing to carry over
+    let names = match xattr_names(from) {
+        Err(x) if x.raw_os_error() == Some(libc::ENOTSUP) => vec![],
+        names => names?,
+    };
+    for name in names {
+        let wanted = match name.to_bytes() {
+            acl if acl.starts_with(ACL_PREFIX) => preserve.contains(Preserve::ACLS),
+            context if context == SELINUX.to_bytes() => preserve.contains(Preserve::SELINUX),
+            _ => preserve.contains(Preserve::XATTRS),
+        };
+        if !wanted {
+            continue;
+        }
+        // failing to read the source is an error, while the destination may not support the attribute
+        let value = match get_xattr(from, &name) {
+            Err(x) if x.raw_os_error() == Some(libc::ENODATA) => continue, // removed since it was listed
+            value => value?,
+        };
+        if let Err(x) = set_xattr(to, &name, &value) {
+            let item = format!("xattr {}", name.to_string_lossy());
+            warnings.push(PreserveWarning::new(item, x));
+        }
+    }
+    return Ok(warnings);
+}
+
+// Reserves disk space for a file up to `len` bytes, extending it with zeros if it's shorter
+pub fn allocate(file: &File, len: u64) -> Result<()> {
+    let len = libc::off_t::try_from(len).map_err(|x| Error::new(ErrorKind::InvalidInput, x))?;
+    match unsafe { libc::posix_fallocate(file.as_raw_fd(), 0, len) } {
+        0 => Ok(()),
+        errno => Err(Error::from_raw_os_error(errno)),
+    }
+}
+
+// Opens a relative path beneath a directory with openat2(2), which follows symlinks as long as they stay beneath it
+// and fails with `EXDEV` on the ones that would leave it; without openat2 (before Linux 5.6, or when a seccomp
+// filter refuses it), falls back to walking the path without following any symlink
+pub fn open_beneath(dir: BorrowedFd, path: &Path, flags: libc::c_int) -> Result<OwnedFd> {
+    let mut how: libc::open_how = unsafe { std::mem::zeroed() };
+    how.flags = (flags | libc::O_CLOEXEC) as u64;
+    if flags & libc::O_CREAT != 0 {
+        how.mode = 0o666;
+    }
+    how.resolve = libc::RESOLVE_BENEATH | libc::RESOLVE_NO_MAGICLINKS;
+    let name = match path.as_os_str().is_empty() {
+        true => c".".to_owned(),
+        false => c_path(path)?,
+    };
+    let size = std::mem::size_of::<libc::open_how>();
+    let how = &how as *const libc::open_how;
+    let fd = unsafe { libc::syscall(libc::SYS_openat2, dir.as_raw_fd(), name.as_ptr(), how, size) };
+    if fd >= 0 {
+        return Ok(unsafe { OwnedFd::from_raw_fd(fd as libc::c_int) });
+    }
+    let error = Error::last_os_error();
+    return match error.raw_os_error() {
+        Some(libc::ENOSYS | libc::EPERM) => super::unix::open_beneath(dir, path, flags),
+        _ => Err(error),
+    };
+}
@@ -0,0 +1,105 @@
+use crate::{filesystem::*, internal::sys::unix::c_path, preserve::*};
+use std::{
+    ffi::CStr,
+    io::{Error, Result},
+    os::macos::fs::MetadataExt,
+    path::Path,
+    ptr,
+};
+
+// Creates a copy-on-write clone of `from` at `to` via clonefile(2);
+// `to` must not exist yet
+pub fn clonefile(from: &Path, to: &Path) -> Result<()> {
+    let (from, to) = (c_path(from)?, c_path(to)?);
+    match unsafe { libc::clonefile(from.as_ptr(), to.as_ptr(), 0) } {
+        0 => Ok(()),
+        _ => Err(Error::last_os_error()),
+    }
+}
+
+// Copies the selected metadata of `from` onto `to` via copyfile(3) and chflags(2)
+fn copyfile(from: &Path, to: &Path, flags: libc::copyfile_flags_t) -> Result<()> {
+    let (from, to) = (c_path(from)?, c_path(to)?);
+    match unsafe { libc::copyfile(from.as_ptr(), to.as_ptr(), ptr::null_mut(), flags) } {
+        0 => Ok(()),
+        _ => Err(Error::last_os_error()),
+    }
+}
+
+fn chflags(from: &Path, to: &Path) -> Result<()> {
+    let flags = from.metadata()?.st_flags();
+    match unsafe { libc::chflags(c_path(to)?.as_ptr(), flags) } {
+        0 => Ok(()),
+        _ => Err(Error::last_os_error()),
+    }
+}
+
+// Queries the statfs(2) of the filesystem containing a path
+fn statfs(path: &Path) -> Result<libc::statfs> {
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    match unsafe { libc::statfs(c_path(path)?.as_ptr(), &mut stat) } {
+        0 => Ok(stat),
+        _ => Err(Error::last_os_error()),
+    }
+}
+
+// Gets the type, space and block size of the filesystem containing a path
+pub fn filesystem_info(path: &Path) -> Result<FilesystemInfo> {
+    let stat = statfs(path)?;
+    let fs_type = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+
+    return Ok(FilesystemInfo {
+        fs_type: fs_type.to_string_lossy().into_owned(),
+        ..crate::internal::sys::unix::filesystem_info(path)?
+    });
+}
+
+// Only tells local and network volumes apart; removable media would need DiskArbitration
+pub fn volume_kind(path: &Path) -> Result<VolumeKind> {
+    Ok(match statfs(path)?.f_flags & libc::MNT_LOCAL as u32 {
+        0 => VolumeKind::Network,
+        _ => VolumeKind::Local,
+    })
+}
+
+pub fn preserve(from: &Path, to: &Path, preserve: Preserve) -> Result<Vec<PreserveWarning>> {
+    let mut warnings = vec![];
+    if preserve.contains(Preserve::XATTRS) {
+        if let Err(x) = copyfile(from, to, libc::COPYFILE_XATTR) {
+            warnings.push(PreserveWarning::new("xattrs", x));
+        }
+    }
+    if preserve.contains(Preserve::ACLS) {
+        if let Err(x) = copyfile(from, to, libc::COPYFILE_ACL) {
+            warnings.push(PreserveWarning::new("acls", x));
+        }
+    }
+    // flags go last, since e.g. `uchg` would lock the destination against the steps above
+    if preserve.contains(Preserve::FLAGS) {
+        if let Err(x) = chflags(from, to) {
+            warnings.push(PreserveWarning::new("flags", x));
+        }
+    }
+    return Ok(warnings);
+}
+
+// Reserves disk space for a file up to `len` bytes via F_PREALLOCATE, then extends it with zeros if it's shorter
+pub fn allocate(file: &std::fs::File, len: u64) -> Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let current = file.metadata()?.len();
+    if len <= current {
+        return Ok(());
+    }
+    let mut store = libc::fstore_t {
+        fst_flags: libc::F_ALLOCATEALL,
+        fst_posmode: libc::F_PEOFPOSMODE,
+        fst_offset: 0,
+        fst_length: (len - current) as libc::off_t,
+        fst_bytesalloc: 0,
+    };
+    if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_PREALLOCATE, &mut store) } == -1 {
+        return Err(Error::last_os_error());
+    }
+    return file.set_len(len);
+}
@@ -0,0 +1,247 @@
+use crate::{
+    acl::*,
+    internal::sys::{unix::c_path, users::*},
+};
+use std::{
+    ffi::{c_char, c_int, c_void},
+    fs,
+    io::{Error, ErrorKind, Result},
+    os::unix::fs::{MetadataExt, PermissionsExt},
+    path::Path,
+    ptr,
+};
+
+// The parts of acl(3) and membership(3) used here, which the libc crate doesn't declare
+type Acl = *mut c_void;
+type AclEntryT = *mut c_void;
+type AclPermset = *mut c_void;
+type Uuid = [u8; 16];
+
+const ACL_TYPE_EXTENDED: c_int = 0x100;
+const ACL_FIRST_ENTRY: c_int = 0;
+const ACL_NEXT_ENTRY: c_int = -1;
+const ACL_EXTENDED_ALLOW: c_int = 1;
+const ACL_EXTENDED_DENY: c_int = 2;
+const ACL_READ_DATA: c_int = 1 << 1;
+const ACL_WRITE_DATA: c_int = 1 << 2;
+const ACL_EXECUTE: c_int = 1 << 3;
+const ID_TYPE_UID: c_int = 0;
+const ID_TYPE_GID: c_int = 1;
+
+extern "C" {
+    fn acl_get_file(path: *const c_char, kind: c_int) -> Acl;
+    fn acl_set_file(path: *const c_char, kind: c_int, acl: Acl) -> c_int;
+    fn acl_init(count: c_int) -> Acl;
+    fn acl_free(object: *mut c_void) -> c_int;
+    fn acl_get_entry(acl: Acl, id: c_int, entry: *mut AclEntryT) -> c_int;
+    fn acl_create_entry(acl: *mut Acl, entry: *mut AclEntryT) -> c_int;
+    fn acl_get_tag_type(entry: AclEntryT, tag: *mut c_int) -> c_int;
+    fn acl_set_tag_type(entry: AclEntryT, tag: c_int) -> c_int;
+    fn acl_get_qualifier(entry: AclEntryT) -> *mut c_void;
+    fn acl_set_qualifier(entry: AclEntryT, qualifier: *const c_void) -> c_int;
+    fn acl_get_permset(entry: AclEntryT, permset: *mut AclPermset) -> c_int;
+    fn acl_get_perm_np(permset: AclPermset, perm: c_int) -> c_int;
+    fn acl_add_perm(permset: AclPermset, perm: c_int) -> c_int;
+    fn acl_clear_perms(permset: AclPermset) -> c_int;
+    fn acl_set_permset(entry: AclEntryT, permset: AclPermset) -> c_int;
+    fn mbr_uuid_to_id(uuid: *const u8, id: *mut libc::id_t, kind: *mut c_int) -> c_int;
+    fn mbr_uid_to_uuid(uid: libc::uid_t, uuid: *mut u8) -> c_int;
+    fn mbr_gid_to_uuid(gid: libc::gid_t, uuid: *mut u8) -> c_int;
+}
+
+fn check(ret: c_int) -> Result<()> {
+    match ret {
+        0 => Ok(()),
+        _ => Err(Error::last_os_error()),
+    }
+}
+
+// The membership calls return an errno instead of setting it
+fn check_mbr(ret: c_int) -> Result<()> {
+    match ret {
+        0 => Ok(()),
+        _ => Err(Error::from_raw_os_error(ret)),
+    }
+}
+
+// An ACL from acl(3), freed on drop
+struct OwnedAcl(Acl);
+
+impl Drop for OwnedAcl {
+    fn drop(&mut self) {
+        unsafe { acl_free(self.0) };
+    }
+}
+
+fn from_bits(principal: Principal, bits: u32) -> AclEntry {
+    AclEntry {
+        principal,
+        kind: AclKind::Allow,
+        read: bits & 4 != 0,
+        write: bits & 2 != 0,
+        execute: bits & 1 != 0,
+    }
+}
+
+fn to_bits(entry: &AclEntry) -> u32 {
+    (entry.read as u32) << 2 | (entry.write as u32) << 1 | entry.execute as u32
+}
+
+fn not_found(kind: &str, name: &str) -> Error {
+    Error::new(ErrorKind::NotFound, format!("no such {kind}: {name}"))
+}
+
+// Names the user or group an extended entry applies to, by numeric id when it has no name
+fn principal(uuid: &Uuid) -> Result<Principal> {
+    let (mut id, mut kind) = (0, 0);
+    check_mbr(unsafe { mbr_uuid_to_id(uuid.as_ptr(), &mut id, &mut kind) })?;
+    return Ok(match kind {
+        ID_TYPE_UID => Principal::User(user_name(id).unwrap_or_else(|| id.to_string())),
+        ID_TYPE_GID => Principal::Group(group_name(id).unwrap_or_else(|| id.to_string())),
+        _ => return Err(Error::new(ErrorKind::InvalidData, "unknown ACL qualifier")),
+    });
+}
+
+// Resolves a named user to the UUID extended entries refer to it by
+fn user_uuid(name: &str) -> Result<Uuid> {
+    let uid = match name.parse() {
+        Ok(uid) => uid,
+        Err(_) => user_id(name).ok_or_else(|| not_found("user", name))?,
+    };
+    let mut uuid = [0; 16];
+    check_mbr(unsafe { mbr_uid_to_uuid(uid, uuid.as_mut_ptr()) })?;
+    return Ok(uuid);
+}
+
+// Resolves a named group to the UUID extended entries refer to it by
+fn group_uuid(name: &str) -> Result<Uuid> {
+    let gid = match name.parse() {
+        Ok(gid) => gid,
+        Err(_) => group_id(name).ok_or_else(|| not_found("group", name))?,
+    };
+    let mut uuid = [0; 16];
+    check_mbr(unsafe { mbr_gid_to_uuid(gid, uuid.as_mut_ptr()) })?;
+    return Ok(uuid);
+}
+
+pub fn get_acl(path: &Path) -> Result<Vec<AclEntry>> {
+    // the owner, owning group and everyone else are always in the permission bits
+    let mode = fs::metadata(path)?.mode() as u32;
+    let mut entries = vec![
+        from_bits(Principal::Owner, mode >> 6 & 7),
+        from_bits(Principal::OwningGroup, mode >> 3 & 7),
+        from_bits(Principal::Everyone, mode & 7),
+    ];
+
+    let acl = unsafe { acl_get_file(c_path(path)?.as_ptr(), ACL_TYPE_EXTENDED) };
+    if acl.is_null() {
+        let error = Error::last_os_error();
+        // without an extended ACL, the permission bits are the whole story
+        return match error.raw_os_error() {
+            Some(libc::ENOENT) => Ok(entries),
+            _ => Err(error),
+        };
+    }
+    let acl = OwnedAcl(acl);
+    let mut entry = ptr::null_mut();
+    let mut id = ACL_FIRST_ENTRY;
+    while unsafe { acl_get_entry(acl.0, id, &mut entry) } == 0 {
+        id = ACL_NEXT_ENTRY;
+        let mut tag = 0;
+        check(unsafe { acl_get_tag_type(entry, &mut tag) })?;
+        let kind = match tag {
+            ACL_EXTENDED_ALLOW => AclKind::Allow,
+            ACL_EXTENDED_DENY => AclKind::Deny,
+            _ => continue,
+        };
+        let qualifier = unsafe { acl_get_qualifier(entry) };
+        if qualifier.is_null() {
+            return Err(Error::last_os_error());
+        }
+        let uuid = unsafe { *(qualifier as *const Uuid) };
+        unsafe { acl_free(qualifier) };
+
+        let mut permset = ptr::null_mut();
+        check(unsafe { acl_get_permset(entry, &mut permset) })?;
+        let has = |perm| unsafe { acl_get_perm_np(permset, perm) } == 1;
+        entries.push(AclEntry {
+            principal: principal(&uuid)?,
+            kind,
+            read: has(ACL_READ_DATA),
+            write: has(ACL_WRITE_DATA),
+            execute: has(ACL_EXECUTE),
+        });
+    }
+    return Ok(entries);
+}
+
+pub fn set_acl(path: &Path, entries: &[AclEntry]) -> Result<()> {
+    // the owner, owning group and everyone else go in the permission bits, the rest in the extended ACL,
+    // where entries for the same principal and kind are merged
+    let (mut owner, mut group, mut other) = (0, 0, 0);
+    let mut extended: Vec<(Uuid, AclKind, u32)> = vec![];
+    for entry in entries {
+        let bits = match (&entry.principal, entry.kind) {
+            (Principal::Owner, AclKind::Allow) => &mut owner,
+            (Principal::OwningGroup, AclKind::Allow) => &mut group,
+            (Principal::Everyone, AclKind::Allow) => &mut other,
+            (Principal::User(name) | Principal::Group(name), kind) => {
+                let uuid = match entry.principal {
+                    Principal::User(_) => user_uuid(name)?,
+                    _ => group_uuid(name)?,
+                };
+                match extended
+                    .iter_mut()
+                    .find(|(u, k, _)| *u == uuid && *k == kind)
+                {
+                    Some((_, _, merged)) => *merged |= to_bits(entry),
+                    None => extended.push((uuid, kind, to_bits(entry))),
+                }
+                continue;
+            }
+            (_, AclKind::Deny) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "only named users and groups can be denied on macOS",
+                ))
+            }
+        };
+        *bits |= to_bits(entry);
+    }
+    // macOS checks entries in order, so deny ones go first
+    extended.sort_by_key(|(_, kind, _)| *kind == AclKind::Allow);
+
+    let mut acl = OwnedAcl(unsafe { acl_init(extended.len() as c_int) });
+    if acl.0.is_null() {
+        return Err(Error::last_os_error());
+    }
+    for (uuid, kind, bits) in &extended {
+        let (mut entry, mut permset) = (ptr::null_mut(), ptr::null_mut());
+        check(unsafe { acl_create_entry(&mut acl.0, &mut entry) })?;
+        check(unsafe {
+            acl_set_tag_type(
+                entry,
+                match kind {
+                    AclKind::Allow => ACL_EXTENDED_ALLOW,
+                    AclKind::Deny => ACL_EXTENDED_DENY,
+                },
+            )
+        })?;
+        check(unsafe { acl_set_qualifier(entry, uuid.as_ptr() as *const c_void) })?;
+        check(unsafe { acl_get_permset(entry, &mut permset) })?;
+        check(unsafe { acl_clear_perms(permset) })?;
+        for (bit, perm) in [(4, ACL_READ_DATA), (2, ACL_WRITE_DATA), (1, ACL_EXECUTE)] {
+            if bits & bit != 0 {
+                check(unsafe { acl_add_perm(permset, perm) })?;
+            }
+        }
+        check(unsafe { acl_set_permset(entry, permset) })?;
+    }
+    // an empty ACL removes any extended entries the file had
+    check(unsafe { acl_set_file(c_path(path)?.as_ptr(), ACL_TYPE_EXTENDED, acl.0) })?;
+
+    // the setuid, setgid and sticky bits aren't part of the ACL, and are kept
+    let special = fs::metadata(path)?.permissions().mode() & 0o7000;
+    let mode = special | owner << 6 | group << 3 | other;
+    return fs::set_permissions(path, fs::Permissions::from_mode(mode));
+}
@@ -0,0 +1,94 @@
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub mod fallback;
+#[cfg(target_os = "linux")]
+pub mod linux;
+#[cfg(target_os = "macos")]
+pub mod macos;
+#[cfg(all(target_os = "macos", feature = "acl"))]
+pub mod macos_acl;
+#[cfg(all(target_os = "linux", feature = "acl"))]
+pub mod posix_acl;
+#[cfg(unix)]
+pub mod unix;
+#[cfg(unix)]
+pub mod users;
+#[cfg(windows)]
+pub mod windows;
+#[cfg(all(windows, feature = "acl"))]
+pub mod windows_acl;
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub use fallback::preserve;
+#[cfg(target_os = "linux")]
+pub use linux::preserve;
+#[cfg(target_os = "macos")]
+pub use macos::preserve;
+
+#[cfg(not(any(unix, windows)))]
+pub use fallback::filesystem_info;
+#[cfg(target_os = "linux")]
+pub use linux::filesystem_info;
+#[cfg(target_os = "macos")]
+pub use macos::filesystem_info;
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "macos"))))]
+pub use unix::filesystem_info;
+#[cfg(windows)]
+pub use windows::filesystem_info;
+
+#[cfg(not(any(unix, windows)))]
+pub use fallback::device_id;
+#[cfg(unix)]
+pub use unix::device_id;
+#[cfg(windows)]
+pub use windows::device_id;
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub use fallback::{allocate, copy_sparse};
+#[cfg(target_os = "linux")]
+pub use linux::allocate;
+#[cfg(target_os = "macos")]
+pub use macos::allocate;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub use unix::copy_sparse;
+
+#[cfg(not(any(unix, windows)))]
+pub use fallback::file_id;
+#[cfg(unix)]
+pub use unix::file_id;
+#[cfg(windows)]
+pub use windows::file_id;
+
+#[cfg(not(any(unix, windows)))]
+pub use fallback::link_count;
+#[cfg(unix)]
+pub use unix::link_count;
+#[cfg(windows)]
+pub use windows::link_count;
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+pub use fallback::volume_kind;
+#[cfg(target_os = "linux")]
+pub use linux::volume_kind;
+#[cfg(target_os = "macos")]
+pub use macos::volume_kind;
+#[cfg(windows)]
+pub use windows::volume_kind;
+
+#[cfg(not(any(unix, windows)))]
+pub use fallback::process_alive;
+#[cfg(unix)]
+pub use unix::process_alive;
+#[cfg(windows)]
+pub use windows::process_alive;
+
+#[cfg(not(any(unix, windows)))]
+pub use fallback::utc_offset;
+#[cfg(unix)]
+pub use unix::utc_offset;
+#[cfg(windows)]
+pub use windows::utc_offset;
+
+#[cfg(target_os = "linux")]
+pub use linux::open_beneath;
+#[cfg(all(unix, not(target_os = "linux")))]
+pub use unix::open_beneath;
@@ -0,0 +1,173 @@
+use crate::{
+    acl::*,
+    internal::sys::{linux::*, users::*},
+};
+use std::{
+    collections::BTreeMap,
+    ffi::CStr,
+    fs,
+    io::{Error, ErrorKind, Result},
+    os::unix::fs::{MetadataExt, PermissionsExt},
+    path::Path,
+};
+
+// The POSIX ACL of a file, as stored by the kernel in this xattr
+const ACL_ACCESS: &CStr = c"system.posix_acl_access";
+const ACL_VERSION: u32 = 2;
+const ACL_UNDEFINED_ID: u32 = u32::MAX;
+
+// Entry tags, ordered the way the kernel expects them
+const ACL_USER_OBJ: u16 = 0x01;
+const ACL_USER: u16 = 0x02;
+const ACL_GROUP_OBJ: u16 = 0x04;
+const ACL_GROUP: u16 = 0x08;
+const ACL_MASK: u16 = 0x10;
+const ACL_OTHER: u16 = 0x20;
+
+fn to_bits(entry: &AclEntry) -> u16 {
+    (entry.read as u16) << 2 | (entry.write as u16) << 1 | entry.execute as u16
+}
+
+fn from_bits(principal: Principal, bits: u16) -> AclEntry {
+    AclEntry {
+        principal,
+        kind: AclKind::Allow,
+        read: bits & 4 != 0,
+        write: bits & 2 != 0,
+        execute: bits & 1 != 0,
+    }
+}
+
+fn user(uid: u32) -> Principal {
+    Principal::User(user_name(uid).unwrap_or_else(|| uid.to_string()))
+}
+
+fn group(gid: u32) -> Principal {
+    Principal::Group(group_name(gid).unwrap_or_else(|| gid.to_string()))
+}
+
+fn not_found(kind: &str, name: &str) -> Error {
+    Error::new(ErrorKind::NotFound, format!("no such {kind}: {name}"))
+}
+
+// Maps a Principal to its (tag, qualifier) pair
+fn tag_of(principal: &Principal) -> Result<(u16, u32)> {
+    Ok(match principal {
+        Principal::Owner => (ACL_USER_OBJ, ACL_UNDEFINED_ID),
+        Principal::OwningGroup => (ACL_GROUP_OBJ, ACL_UNDEFINED_ID),
+        Principal::Everyone => (ACL_OTHER, ACL_UNDEFINED_ID),
+        Principal::User(name) => match name.parse() {
+            Ok(uid) => (ACL_USER, uid),
+            Err(_) => (
+                ACL_USER,
+                user_id(name).ok_or_else(|| not_found("user", name))?,
+            ),
+        },
+        Principal::Group(name) => match name.parse() {
+            Ok(gid) => (ACL_GROUP, gid),
+            Err(_) => (
+                ACL_GROUP,
+                group_id(name).ok_or_else(|| not_found("group", name))?,
+            ),
+        },
+    })
+}
+
+pub fn get_acl(path: &Path) -> Result<Vec<AclEntry>> {
+    let xattr = match get_xattr(path, ACL_ACCESS) {
+        Ok(xattr) => xattr,
+        // without an extended ACL, the permission bits are the whole story
+        Err(x) if x.raw_os_error() == Some(libc::ENODATA) => {
+            let mode = fs::metadata(path)?.mode() as u16;
+            return Ok(vec![
+                from_bits(Principal::Owner, mode >> 6 & 7),
+                from_bits(Principal::OwningGroup, mode >> 3 & 7),
+                from_bits(Principal::Everyone, mode & 7),
+            ]);
+        }
+        Err(x) => return Err(x),
+    };
+
+    let invalid = || Error::new(ErrorKind::InvalidData, "malformed POSIX ACL");
+    let (header, body) = xattr.split_at_checked(4).ok_or_else(invalid)?;
+    if u32::from_le_bytes(header.try_into().unwrap()) != ACL_VERSION || body.len() % 8 != 0 {
+        return Err(invalid());
+    }
+    let raw: Vec<(u16, u16, u32)> = body
+        .chunks_exact(8)
+        .map(|e| {
+            (
+                u16::from_le_bytes([e[0], e[1]]),
+                u16::from_le_bytes([e[2], e[3]]),
+                u32::from_le_bytes([e[4], e[5], e[6], e[7]]),
+            )
+        })
+        .collect();
+
+    // report effective permissions, so the mask doesn't need to be part of the model
+    let mask = raw
+        .iter()
+        .find(|(tag, ..)| *tag == ACL_MASK)
+        .map_or(7, |(_, perm, _)| *perm);
+    return Ok(raw
+        .into_iter()
+        .filter_map(|(tag, perm, id)| match tag {
+            ACL_USER_OBJ => Some(from_bits(Principal::Owner, perm)),
+            ACL_USER => Some(from_bits(user(id), perm & mask)),
+            ACL_GROUP_OBJ => Some(from_bits(Principal::OwningGroup, perm & mask)),
+            ACL_GROUP => Some(from_bits(group(id), perm & mask)),
+            ACL_OTHER => Some(from_bits(Principal::Everyone, perm)),
+            _ => None,
+        })
+        .collect());
+}
+
+pub fn set_acl(path: &Path, entries: &[AclEntry]) -> Result<()> {
+    // the owner, owning group and everyone else must always be present
+    let mut tags = BTreeMap::from([
+        ((ACL_USER_OBJ, ACL_UNDEFINED_ID), 0),
+        ((ACL_GROUP_OBJ, ACL_UNDEFINED_ID), 0),
+        ((ACL_OTHER, ACL_UNDEFINED_ID), 0),
+    ]);
+    for entry in entries {
+        if entry.kind == AclKind::Deny {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "POSIX ACLs cannot express deny entries",
+            ));
+        }
+        *tags.entry(tag_of(&entry.principal)?).or_default() |= to_bits(entry);
+    }
+
+    // without named entries the ACL is fully expressed by the permission bits
+    if !tags
+        .keys()
+        .any(|(tag, _)| *tag == ACL_USER || *tag == ACL_GROUP)
+    {
+        if let Err(x) = remove_xattr(path, ACL_ACCESS) {
+            if x.raw_os_error() != Some(libc::ENODATA) {
+                return Err(x);
+            }
+        }
+        let bits = |tag| u32::from(tags[&(tag, ACL_UNDEFINED_ID)]);
+        // the setuid, setgid and sticky bits aren't part of the ACL, and are kept
+        let special = fs::metadata(path)?.permissions().mode() & 0o7000;
+        let mode = special | bits(ACL_USER_OBJ) << 6 | bits(ACL_GROUP_OBJ) << 3 | bits(ACL_OTHER);
+        return fs::set_permissions(path, fs::Permissions::from_mode(mode));
+    }
+
+    // named entries are capped by a mask, which has to let all of them through
+    let mask = tags
+        .iter()
+        .filter(|((tag, _), _)| ![ACL_USER_OBJ, ACL_OTHER].contains(tag))
+        .fold(0, |mask, (_, perm)| mask | perm);
+    tags.insert((ACL_MASK, ACL_UNDEFINED_ID), mask);
+
+    let mut xattr = ACL_VERSION.to_le_bytes().to_vec();
+    for ((tag, id), perm) in tags {
+        xattr.extend_from_slice(&tag.to_le_bytes());
+        xattr.extend_from_slice(&perm.to_le_bytes());
+        xattr.extend_from_slice(&id.to_le_bytes());
+    }
+    return set_xattr(path, ACL_ACCESS, &xattr);
+}
@@ -0,0 +1,148 @@
+use crate::filesystem::*;
+use std::{
+    ffi::{CStr, CString},
+    io::{Error, ErrorKind, Result},
+    os::{
+        fd::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd},
+        unix::ffi::OsStrExt,
+    },
+    path::{Component, Path},
+};
+
+// Converts a Path into a NUL-terminated C string
+pub fn c_path(path: &Path) -> Result<CString> {
+    CString::new(path.as_os_str().as_bytes()).map_err(|x| Error::new(ErrorKind::InvalidInput, x))
+}
+
+// Queries the statvfs(3) of the filesystem containing a path
+pub fn statvfs(path: &Path) -> Result<libc::statvfs> {
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    match unsafe { libc::statvfs(c_path(path)?.as_ptr(), &mut stat) } {
+        0 => Ok(stat),
+        _ => Err(Error::last_os_error()),
+    }
+}
+
+// Gets the space and block size of the filesystem containing a path;
+// the filesystem type is left for the platform to fill in
+pub fn filesystem_info(path: &Path) -> Result<FilesystemInfo> {
+    let stat = statvfs(path)?;
+    let fragment = stat.f_frsize as u64;
+
+    return Ok(FilesystemInfo {
+        fs_type: String::new(),
+        total_space: stat.f_blocks as u64 * fragment,
+        free_space: stat.f_bfree as u64 * fragment,
+        available_space: stat.f_bavail as u64 * fragment,
+        block_size: stat.f_bsize as u64,
+    });
+}
+
+// Gets the id of the device (filesystem) containing a path
+pub fn device_id(path: &Path) -> Result<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Ok(std::fs::metadata(path)?.dev())
+}
+
+// Identifies an open file by its device and inode, to tell whether a path still names it
+pub fn file_id(file: &std::fs::File) -> Result<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = file.metadata()?;
+    return Ok((metadata.dev(), metadata.ino()));
+}
+
+// Counts the hard links to a file or directory
+pub fn link_count(path: &Path) -> Result<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Ok(std::fs::metadata(path)?.nlink())
+}
+
+// Gets the offset of the local time zone from UTC, in seconds, at a given Unix time
+pub fn utc_offset(secs: i64) -> i64 {
+    let time = secs as libc::time_t;
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    match unsafe { libc::localtime_r(&time, &mut tm) }.is_null() {
+        true => 0,
+        false => tm.tm_gmtoff as i64,
+    }
+}
+
+// Tells whether a process is running; one owned by another user, which can't be signaled, still is
+pub fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    match unsafe { libc::kill(pid, 0) } {
+        0 => true,
+        _ => Error::last_os_error().raw_os_error() == Some(libc::EPERM),
+    }
+}
+
+// Copies the contents of `from` into the empty `to` segment by segment, found with SEEK_DATA/SEEK_HOLE,
+// seeking over the holes so they stay unallocated; filesystems without holes report one segment
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub fn copy_sparse(from: &mut std::fs::File, to: &mut std::fs::File) -> Result<u64> {
+    use std::{
+        io::{Read, Seek, SeekFrom},
+        os::fd::AsRawFd,
+    };
+
+    let len = from.metadata()?.len();
+    let mut offset = 0;
+    while offset < len {
+        let data = unsafe { libc::lseek(from.as_raw_fd(), offset as libc::off_t, libc::SEEK_DATA) };
+        if data < 0 {
+            let x = Error::last_os_error();
+            match x.raw_os_error() {
+                Some(libc::ENXIO) => break, // only a hole is left
+                _ => return Err(x),
+            }
+        }
+        let hole = unsafe { libc::lseek(from.as_raw_fd(), data, libc::SEEK_HOLE) };
+        if hole < 0 {
+            return Err(Error::last_os_error());
+        }
+        from.seek(SeekFrom::Start(data as u64))?;
+        to.seek(SeekFrom::Start(data as u64))?;
+        std::io::copy(&mut Read::by_ref(from).take((hole - data) as u64), to)?;
+        offset = hole as u64;
+    }
+    to.set_len(len)?; // a trailing hole
+
+    return Ok(len);
+}
+
+fn openat(dir: RawFd, name: &CStr, flags: libc::c_int) -> Result<OwnedFd> {
+    let flags = flags | libc::O_CLOEXEC;
+    match unsafe { libc::openat(dir, name.as_ptr(), flags, 0o666) } {
+        -1 => Err(Error::last_os_error()),
+        fd => Ok(unsafe { OwnedFd::from_raw_fd(fd) }),
+    }
+}
+
+// Opens a relative path beneath a directory one component at a time, without following any symlink on the way,
+// so that nothing outside of the directory can be reached; `..` and absolute paths fail with `EXDEV`
+pub fn open_beneath(dir: BorrowedFd, path: &Path, flags: libc::c_int) -> Result<OwnedFd> {
+    let mut names = vec![];
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => names.push(c_path(Path::new(name))?),
+            Component::CurDir => {}
+            _ => return Err(Error::from_raw_os_error(libc::EXDEV)),
+        }
+    }
+    let leaf = names.pop().unwrap_or_else(|| c".".to_owned());
+
+    let mut current: Option<OwnedFd> = None;
+    for name in names {
+        let at = current
+            .as_ref()
+            .map_or(dir.as_raw_fd(), |fd| fd.as_raw_fd());
+        let flags = libc::O_RDONLY | libc::O_DIRECTORY | libc::O_NOFOLLOW;
+        current = Some(openat(at, &name, flags)?);
+    }
+    let at = current
+        .as_ref()
+        .map_or(dir.as_raw_fd(), |fd| fd.as_raw_fd());
+    return openat(at, &leaf, flags | libc::O_NOFOLLOW);
+}
@@ -0,0 +1,77 @@
+#[cfg(any(feature = "acl", feature = "unix"))]
+use std::ffi::CString;
+use std::{ffi::CStr, mem, ptr};
+
+// The buffer the lookups start with, and the most they grow it to for a record that doesn't fit,
+// such as a group with thousands of members
+const INITIAL_BUF: usize = 16 * 1024;
+const MAX_BUF: usize = 16 * 1024 * 1024;
+
+// Reads a NUL-terminated C string owned by a passwd/group record
+fn owned(name: *const libc::c_char) -> String {
+    unsafe { CStr::from_ptr(name) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+// Runs a reentrant lookup such as `getpwnam_r` into `record`, doubling its buffer for as long as it fails with
+// `ERANGE`, and reads what's needed from the record while the buffer its strings point into is still alive
+fn lookup<T, R>(
+    record: &mut T,
+    call: impl Fn(*mut T, *mut libc::c_char, usize, *mut *mut T) -> libc::c_int,
+    read: impl FnOnce(&T) -> R,
+) -> Option<R> {
+    let mut buf = vec![0 as libc::c_char; INITIAL_BUF];
+    loop {
+        let mut found = ptr::null_mut();
+        match call(record, buf.as_mut_ptr(), buf.len(), &mut found) {
+            libc::ERANGE if buf.len() < MAX_BUF => buf.resize(buf.len() * 2, 0),
+            _ if found.is_null() => return None,
+            _ => return Some(read(record)),
+        }
+    }
+}
+
+// Looks up the name of a user id
+pub fn user_name(uid: u32) -> Option<String> {
+    let mut pwd: libc::passwd = unsafe { mem::zeroed() };
+    lookup(
+        &mut pwd,
+        |pwd, buf, len, found| unsafe { libc::getpwuid_r(uid, pwd, buf, len, found) },
+        |pwd| owned(pwd.pw_name),
+    )
+}
+
+// Looks up the id of a user name
+#[cfg(any(feature = "acl", feature = "unix"))]
+pub fn user_id(name: &str) -> Option<u32> {
+    let name = CString::new(name).ok()?;
+    let mut pwd: libc::passwd = unsafe { mem::zeroed() };
+    lookup(
+        &mut pwd,
+        |pwd, buf, len, found| unsafe { libc::getpwnam_r(name.as_ptr(), pwd, buf, len, found) },
+        |pwd| pwd.pw_uid,
+    )
+}
+
+// Looks up the name of a group id
+pub fn group_name(gid: u32) -> Option<String> {
+    let mut grp: libc::group = unsafe { mem::zeroed() };
+    lookup(
+        &mut grp,
+        |grp, buf, len, found| unsafe { libc::getgrgid_r(gid, grp, buf, len, found) },
+        |grp| owned(grp.gr_name),
+    )
+}
+
+// Looks up the id of a group name
+#[cfg(any(feature = "acl", feature = "unix"))]
+pub fn group_id(name: &str) -> Option<u32> {
+    let name = CString::new(name).ok()?;
+    let mut grp: libc::group = unsafe { mem::zeroed() };
+    lookup(
+        &mut grp,
+        |grp, buf, len, found| unsafe { libc::getgrnam_r(name.as_ptr(), grp, buf, len, found) },
+        |grp| grp.gr_gid,
+    )
+}
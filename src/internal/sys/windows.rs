@@ -0,0 +1,208 @@
+use crate::filesystem::*;
+use std::{
+    io::{Error, Result},
+    os::windows::ffi::{OsStrExt, OsStringExt},
+    path::Path,
+    ptr,
+};
+use windows_sys::Win32::Storage::FileSystem::{
+    GetDiskFreeSpaceExW, GetDiskFreeSpaceW, GetDriveTypeW, GetFileInformationByHandle,
+    GetVolumeInformationW, GetVolumePathNameW, BY_HANDLE_FILE_INFORMATION,
+    FILE_FLAG_BACKUP_SEMANTICS,
+};
+
+// Drive types returned by GetDriveTypeW
+const DRIVE_REMOVABLE: u32 = 2;
+const DRIVE_FIXED: u32 = 3;
+const DRIVE_REMOTE: u32 = 4;
+const DRIVE_CDROM: u32 = 5;
+const DRIVE_RAMDISK: u32 = 6;
+
+// Converts a Path into a NUL-terminated wide string, with the `\\?\` prefix if it's too long for Win32 calls
+pub fn wide(path: &Path) -> Vec<u16> {
+    let path = crate::internal::paths::extended(path);
+    path.as_os_str().encode_wide().chain(Some(0)).collect()
+}
+
+// Converts a NUL-terminated wide string buffer back into a String
+pub fn from_wide(buf: &[u16]) -> String {
+    let len = buf.iter().position(|c| *c == 0).unwrap_or(buf.len());
+    std::ffi::OsString::from_wide(&buf[..len])
+        .to_string_lossy()
+        .into_owned()
+}
+
+// Gets the root of the volume containing a path, e.g. `C:\` or `\\server\share\`
+pub fn volume_root(path: &Path) -> Result<Vec<u16>> {
+    let mut root = vec![0u16; 1024];
+    match unsafe { GetVolumePathNameW(wide(path).as_ptr(), root.as_mut_ptr(), root.len() as u32) } {
+        0 => Err(Error::last_os_error()),
+        _ => Ok(root),
+    }
+}
+
+// Gets the type, space and block size of the volume containing a path
+pub fn filesystem_info(path: &Path) -> Result<FilesystemInfo> {
+    let root = volume_root(path)?;
+
+    let (mut available, mut total, mut free) = (0u64, 0u64, 0u64);
+    if unsafe { GetDiskFreeSpaceExW(root.as_ptr(), &mut available, &mut total, &mut free) } == 0 {
+        return Err(Error::last_os_error());
+    }
+
+    let (mut sectors, mut bytes, mut free_clusters, mut clusters) = (0u32, 0u32, 0u32, 0u32);
+    if unsafe {
+        GetDiskFreeSpaceW(
+            root.as_ptr(),
+            &mut sectors,
+            &mut bytes,
+            &mut free_clusters,
+            &mut clusters,
+        )
+    } == 0
+    {
+        return Err(Error::last_os_error());
+    }
+
+    let mut fs_type = vec![0u16; 64];
+    if unsafe {
+        GetVolumeInformationW(
+            root.as_ptr(),
+            ptr::null_mut(),
+            0,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            fs_type.as_mut_ptr(),
+            fs_type.len() as u32,
+        )
+    } == 0
+    {
+        return Err(Error::last_os_error());
+    }
+
+    return Ok(FilesystemInfo {
+        fs_type: from_wide(&fs_type),
+        total_space: total,
+        free_space: free,
+        available_space: available,
+        block_size: sectors as u64 * bytes as u64,
+    });
+}
+
+// Gets the serial number of the volume containing a path
+pub fn device_id(path: &Path) -> Result<u64> {
+    let root = volume_root(path)?;
+    let mut serial = 0u32;
+    if unsafe {
+        GetVolumeInformationW(
+            root.as_ptr(),
+            ptr::null_mut(),
+            0,
+            &mut serial,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            0,
+        )
+    } == 0
+    {
+        return Err(Error::last_os_error());
+    }
+    return Ok(serial as u64);
+}
+
+// Counts the hard links to a file or directory, opened with backup semantics so directories can be too
+pub fn link_count(path: &Path) -> Result<u64> {
+    use std::os::windows::{fs::OpenOptionsExt, io::AsRawHandle};
+
+    let file = std::fs::File::options()
+        .read(true)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(path)?;
+    let mut info: BY_HANDLE_FILE_INFORMATION = unsafe { std::mem::zeroed() };
+    if unsafe { GetFileInformationByHandle(file.as_raw_handle(), &mut info) } == 0 {
+        return Err(Error::last_os_error());
+    }
+    return Ok(info.nNumberOfLinks as u64);
+}
+
+// Identifies an open file by its volume serial number and file index, to tell whether a path still names it
+pub fn file_id(file: &std::fs::File) -> Result<(u64, u64)> {
+    use std::os::windows::io::AsRawHandle;
+
+    let mut info: BY_HANDLE_FILE_INFORMATION = unsafe { std::mem::zeroed() };
+    if unsafe { GetFileInformationByHandle(file.as_raw_handle(), &mut info) } == 0 {
+        return Err(Error::last_os_error());
+    }
+    let index = (info.nFileIndexHigh as u64) << 32 | info.nFileIndexLow as u64;
+    return Ok((info.dwVolumeSerialNumber as u64, index));
+}
+
+// Classifies the volume containing a path by its drive type
+pub fn volume_kind(path: &Path) -> Result<VolumeKind> {
+    let root = volume_root(path)?;
+    return Ok(match unsafe { GetDriveTypeW(root.as_ptr()) } {
+        DRIVE_FIXED => VolumeKind::Local,
+        DRIVE_REMOTE => VolumeKind::Network,
+        DRIVE_REMOVABLE | DRIVE_CDROM => VolumeKind::Removable,
+        DRIVE_RAMDISK => VolumeKind::Ramdisk,
+        _ => VolumeKind::Unknown,
+    });
+}
+
+// Tells whether a process is running; one that can't be queried for lack of rights still is
+pub fn process_alive(pid: u32) -> bool {
+    use windows_sys::Win32::{
+        Foundation::{CloseHandle, ERROR_ACCESS_DENIED, STILL_ACTIVE},
+        System::Threading::{GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION},
+    };
+    let process = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    if process.is_null() {
+        return Error::last_os_error().raw_os_error() == Some(ERROR_ACCESS_DENIED as i32);
+    }
+    let mut code = 0;
+    let alive =
+        unsafe { GetExitCodeProcess(process, &mut code) } != 0 && code == STILL_ACTIVE as u32;
+    unsafe { CloseHandle(process) };
+
+    return alive;
+}
+
+// Gets the local time zone's offset from UTC in seconds at a Unix time, daylight saving included
+pub fn utc_offset(secs: i64) -> i64 {
+    use windows_sys::Win32::{
+        Foundation::{FILETIME, SYSTEMTIME},
+        System::Time::{
+            FileTimeToSystemTime, SystemTimeToFileTime, SystemTimeToTzSpecificLocalTime,
+        },
+    };
+    // FILETIMEs count 100ns ticks since 1601-01-01
+    let Some(ticks) = secs
+        .checked_add(11_644_473_600)
+        .and_then(|secs| u64::try_from(secs).ok())
+        .and_then(|secs| secs.checked_mul(10_000_000))
+    else {
+        return 0;
+    };
+    let to_filetime = |ticks: u64| FILETIME {
+        dwLowDateTime: ticks as u32,
+        dwHighDateTime: (ticks >> 32) as u32,
+    };
+    let to_ticks =
+        |time: &FILETIME| ((time.dwHighDateTime as u64) << 32) | time.dwLowDateTime as u64;
+
+    let utc = to_filetime(ticks);
+    let mut utc_system: SYSTEMTIME = unsafe { std::mem::zeroed() };
+    let mut local_system: SYSTEMTIME = unsafe { std::mem::zeroed() };
+    let mut local = to_filetime(0);
+    let converted = unsafe {
+        FileTimeToSystemTime(&utc, &mut utc_system) != 0
+            && SystemTimeToTzSpecificLocalTime(ptr::null(), &utc_system, &mut local_system) != 0
+            && SystemTimeToFileTime(&local_system, &mut local) != 0
+    };
+    if !converted {
+        return 0;
+    }
+    return (to_ticks(&local) as i64 - to_ticks(&utc) as i64) / 10_000_000;
+}
@@ -0,0 +1,336 @@
+use crate::{
+    acl::*,
+    internal::sys::windows::{from_wide, wide},
+};
+use std::{
+    io::{Error, ErrorKind, Result},
+    path::Path,
+    ptr, slice,
+};
+use windows_sys::Win32::{
+    Foundation::{
+        LocalFree, ERROR_INSUFFICIENT_BUFFER, GENERIC_ALL, GENERIC_EXECUTE, GENERIC_READ,
+        GENERIC_WRITE,
+    },
+    Security::{
+        Authorization::{
+            ConvertSidToStringSidW, ConvertStringSidToSidW, GetNamedSecurityInfoW,
+            SetEntriesInAclW, SetNamedSecurityInfoW, DENY_ACCESS, EXPLICIT_ACCESS_W, GRANT_ACCESS,
+            NO_MULTIPLE_TRUSTEE, SE_FILE_OBJECT, TRUSTEE_IS_SID, TRUSTEE_IS_UNKNOWN, TRUSTEE_W,
+        },
+        CreateWellKnownSid, EqualSid, GetAce, GetLengthSid, LookupAccountNameW, LookupAccountSidW,
+        SidTypeUser, WinWorldSid, ACCESS_ALLOWED_ACE, ACL, DACL_SECURITY_INFORMATION,
+        GROUP_SECURITY_INFORMATION, INHERIT_ONLY_ACE, NO_INHERITANCE, OWNER_SECURITY_INFORMATION,
+        PROTECTED_DACL_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR, PSID, SECURITY_MAX_SID_SIZE,
+    },
+    Storage::FileSystem::{
+        FILE_APPEND_DATA, FILE_EXECUTE, FILE_GENERIC_EXECUTE, FILE_GENERIC_READ,
+        FILE_GENERIC_WRITE, FILE_READ_DATA, FILE_WRITE_DATA,
+    },
+};
+
+// ACE types of the ACEs that are mapped; object and callback ACEs aren't
+const ACCESS_ALLOWED_ACE_TYPE: u8 = 0;
+const ACCESS_DENIED_ACE_TYPE: u8 = 1;
+
+// The rights, specific or generic, that grant each permission
+const READ: u32 = FILE_READ_DATA | GENERIC_READ | GENERIC_ALL;
+const WRITE: u32 = FILE_WRITE_DATA | GENERIC_WRITE | GENERIC_ALL;
+const EXECUTE: u32 = FILE_EXECUTE | GENERIC_EXECUTE | GENERIC_ALL;
+
+// A SID copied out of the buffer it came in, as u32s since SIDs are 4-byte aligned; copies of the same SID are equal
+type Sid = Vec<u32>;
+
+fn as_psid(sid: &Sid) -> PSID {
+    sid.as_ptr() as PSID
+}
+
+fn copy_sid(sid: PSID) -> Sid {
+    let len = unsafe { GetLengthSid(sid) } as usize;
+    let mut copy = vec![0u32; len.div_ceil(4)];
+    unsafe { ptr::copy_nonoverlapping(sid as *const u8, copy.as_mut_ptr() as *mut u8, len) };
+    return copy;
+}
+
+fn check(ok: i32) -> Result<()> {
+    match ok {
+        0 => Err(Error::last_os_error()),
+        _ => Ok(()),
+    }
+}
+
+fn check_win32(error: u32) -> Result<()> {
+    match error {
+        0 => Ok(()),
+        _ => Err(Error::from_raw_os_error(error as i32)),
+    }
+}
+
+// The owner, group and DACL of a file, pointing into the security descriptor they came in, which is freed on drop
+struct SecurityInfo {
+    owner: PSID,
+    group: PSID,
+    dacl: *mut ACL,
+    descriptor: PSECURITY_DESCRIPTOR,
+}
+
+impl SecurityInfo {
+    fn of(path: &Path) -> Result<Self> {
+        let mut info = Self {
+            owner: ptr::null_mut(),
+            group: ptr::null_mut(),
+            dacl: ptr::null_mut(),
+            descriptor: ptr::null_mut(),
+        };
+        check_win32(unsafe {
+            GetNamedSecurityInfoW(
+                wide(path).as_ptr(),
+                SE_FILE_OBJECT,
+                OWNER_SECURITY_INFORMATION | GROUP_SECURITY_INFORMATION | DACL_SECURITY_INFORMATION,
+                &mut info.owner,
+                &mut info.group,
+                &mut info.dacl,
+                ptr::null_mut(),
+                &mut info.descriptor,
+            )
+        })?;
+        return Ok(info);
+    }
+}
+
+impl Drop for SecurityInfo {
+    fn drop(&mut self) {
+        unsafe { LocalFree(self.descriptor) };
+    }
+}
+
+fn everyone() -> Result<Sid> {
+    let mut sid = vec![0u32; SECURITY_MAX_SID_SIZE.div_ceil(4) as usize];
+    let mut len = SECURITY_MAX_SID_SIZE;
+    check(unsafe {
+        CreateWellKnownSid(
+            WinWorldSid,
+            ptr::null_mut(),
+            sid.as_mut_ptr().cast(),
+            &mut len,
+        )
+    })?;
+    return Ok(copy_sid(as_psid(&sid)));
+}
+
+// Formats a SID as `S-1-5-…`, for accounts that have no name
+fn sid_string(sid: PSID) -> Result<String> {
+    let mut string = ptr::null_mut();
+    check(unsafe { ConvertSidToStringSidW(sid, &mut string) })?;
+    let len = (0..)
+        .take_while(|&i| unsafe { *string.add(i) } != 0)
+        .count();
+    let text = from_wide(unsafe { slice::from_raw_parts(string, len) });
+    unsafe { LocalFree(string as _) };
+
+    return Ok(text);
+}
+
+// Names the account of a SID as `DOMAIN\name`, or by its SID string when it has no name
+fn principal(sid: PSID) -> Result<Principal> {
+    let (mut name, mut domain) = (vec![0u16; 256], vec![0u16; 256]);
+    loop {
+        let (mut name_len, mut domain_len, mut kind) = (name.len() as u32, domain.len() as u32, 0);
+        let found = unsafe {
+            LookupAccountSidW(
+                ptr::null(),
+                sid,
+                name.as_mut_ptr(),
+                &mut name_len,
+                domain.as_mut_ptr(),
+                &mut domain_len,
+                &mut kind,
+            )
+        };
+        if found == 0 {
+            let error = Error::last_os_error();
+            if error.raw_os_error() == Some(ERROR_INSUFFICIENT_BUFFER as i32) {
+                name.resize(name_len as usize, 0);
+                domain.resize(domain_len as usize, 0);
+                continue;
+            }
+            return Ok(Principal::User(sid_string(sid)?));
+        }
+        let (name, domain) = (from_wide(&name), from_wide(&domain));
+        let name = match domain.is_empty() {
+            true => name,
+            false => format!("{domain}\\{name}"),
+        };
+        return Ok(match kind == SidTypeUser {
+            true => Principal::User(name),
+            false => Principal::Group(name),
+        });
+    }
+}
+
+// Resolves a user or group, given as `S-1-5-…` or an account name, to its SID
+fn lookup(name: &str) -> Result<Sid> {
+    let account: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+    if name.starts_with("S-") {
+        let mut sid = ptr::null_mut();
+        if unsafe { ConvertStringSidToSidW(account.as_ptr(), &mut sid) } != 0 {
+            let copy = copy_sid(sid);
+            unsafe { LocalFree(sid) };
+            return Ok(copy);
+        }
+    }
+    let mut sid = vec![0u32; SECURITY_MAX_SID_SIZE.div_ceil(4) as usize];
+    let mut domain = vec![0u16; 256];
+    loop {
+        let (mut sid_len, mut domain_len, mut kind) =
+            (SECURITY_MAX_SID_SIZE, domain.len() as u32, 0);
+        let found = unsafe {
+            LookupAccountNameW(
+                ptr::null(),
+                account.as_ptr(),
+                sid.as_mut_ptr().cast(),
+                &mut sid_len,
+                domain.as_mut_ptr(),
+                &mut domain_len,
+                &mut kind,
+            )
+        };
+        if found != 0 {
+            return Ok(copy_sid(as_psid(&sid)));
+        }
+        let error = Error::last_os_error();
+        if error.raw_os_error() == Some(ERROR_INSUFFICIENT_BUFFER as i32) {
+            domain.resize(domain_len as usize, 0);
+            continue;
+        }
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("no such account: {name}"),
+        ));
+    }
+}
+
+pub fn get_acl(path: &Path) -> Result<Vec<AclEntry>> {
+    let info = SecurityInfo::of(path)?;
+    // a missing DACL grants everyone everything
+    if info.dacl.is_null() {
+        return Ok(vec![AclEntry::allow(Principal::Everyone)
+            .read()
+            .write()
+            .execute()]);
+    }
+    let everyone = everyone()?;
+    let mut entries = vec![];
+    for index in 0..unsafe { (*info.dacl).AceCount } as u32 {
+        let mut ace = ptr::null_mut();
+        check(unsafe { GetAce(info.dacl, index, &mut ace) })?;
+        // allowed and denied ACEs share a layout
+        let ace = unsafe { &*(ace as *const ACCESS_ALLOWED_ACE) };
+        let kind = match ace.Header.AceType {
+            ACCESS_ALLOWED_ACE_TYPE => AclKind::Allow,
+            ACCESS_DENIED_ACE_TYPE => AclKind::Deny,
+            _ => continue,
+        };
+        if u32::from(ace.Header.AceFlags) & INHERIT_ONLY_ACE != 0 {
+            continue; // only applies to what's created inside a directory
+        }
+        let sid = &ace.SidStart as *const u32 as PSID;
+        let principal = if unsafe { EqualSid(sid, info.owner) } != 0 {
+            Principal::Owner
+        } else if unsafe { EqualSid(sid, info.group) } != 0 {
+            Principal::OwningGroup
+        } else if unsafe { EqualSid(sid, as_psid(&everyone)) } != 0 {
+            Principal::Everyone
+        } else {
+            principal(sid)?
+        };
+        entries.push(AclEntry {
+            principal,
+            kind,
+            read: ace.Mask & READ != 0,
+            write: ace.Mask & WRITE != 0,
+            execute: ace.Mask & EXECUTE != 0,
+        });
+    }
+    return Ok(entries);
+}
+
+pub fn set_acl(path: &Path, entries: &[AclEntry]) -> Result<()> {
+    let info = SecurityInfo::of(path)?;
+    // entries for the same principal and kind are merged into one ACE
+    let mut aces: Vec<(Sid, AclKind, u32)> = vec![];
+    for entry in entries {
+        let sid = match &entry.principal {
+            Principal::Owner => copy_sid(info.owner),
+            Principal::OwningGroup => copy_sid(info.group),
+            Principal::Everyone => everyone()?,
+            Principal::User(name) | Principal::Group(name) => lookup(name)?,
+        };
+        // the generic rights also carry the ones every handle needs, such as SYNCHRONIZE, which mustn't be denied
+        let rights = match entry.kind {
+            AclKind::Allow => [FILE_GENERIC_READ, FILE_GENERIC_WRITE, FILE_GENERIC_EXECUTE],
+            AclKind::Deny => [
+                FILE_READ_DATA,
+                FILE_WRITE_DATA | FILE_APPEND_DATA,
+                FILE_EXECUTE,
+            ],
+        };
+        let rights = [entry.read, entry.write, entry.execute]
+            .into_iter()
+            .zip(rights)
+            .filter(|(granted, _)| *granted)
+            .fold(0, |rights, (_, right)| rights | right);
+        match aces
+            .iter_mut()
+            .find(|(s, k, _)| *s == sid && *k == entry.kind)
+        {
+            Some((_, _, merged)) => *merged |= rights,
+            None => aces.push((sid, entry.kind, rights)),
+        }
+    }
+
+    let explicit: Vec<EXPLICIT_ACCESS_W> = aces
+        .iter()
+        .filter(|(_, _, rights)| *rights != 0)
+        .map(|(sid, kind, rights)| EXPLICIT_ACCESS_W {
+            grfAccessPermissions: *rights,
+            grfAccessMode: match kind {
+                AclKind::Allow => GRANT_ACCESS,
+                AclKind::Deny => DENY_ACCESS,
+            },
+            grfInheritance: NO_INHERITANCE,
+            Trustee: TRUSTEE_W {
+                pMultipleTrustee: ptr::null_mut(),
+                MultipleTrusteeOperation: NO_MULTIPLE_TRUSTEE,
+                TrusteeForm: TRUSTEE_IS_SID,
+                TrusteeType: TRUSTEE_IS_UNKNOWN,
+                ptstrName: as_psid(sid) as _,
+            },
+        })
+        .collect();
+    // built from scratch, so deny ACEs are put before allow ones
+    let mut dacl = ptr::null_mut();
+    check_win32(unsafe {
+        SetEntriesInAclW(
+            explicit.len() as u32,
+            explicit.as_ptr(),
+            ptr::null(),
+            &mut dacl,
+        )
+    })?;
+    // protected, so the ACEs inherited from the parent directory don't add to it
+    let set = check_win32(unsafe {
+        SetNamedSecurityInfoW(
+            wide(path).as_ptr(),
+            SE_FILE_OBJECT,
+            DACL_SECURITY_INFORMATION | PROTECTED_DACL_SECURITY_INFORMATION,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            dacl,
+            ptr::null(),
+        )
+    });
+    unsafe { LocalFree(dacl as _) };
+
+    return set;
+}
@@ -0,0 +1,6 @@
+use crate::{AsPath, Result};
+
+pub trait Context<T> {
+    // Names the operation and path in the error, unless an inner operation already did
+    fn context<Path: AsPath + ?Sized>(self, operation: &'static str, path: &Path) -> Result<T>;
+}
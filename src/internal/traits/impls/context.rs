@@ -0,0 +1,27 @@
+use crate::{internal::traits::context::*, AsPath, Error, Result};
+
+impl<T, E: Into<Error>> Context<T> for std::result::Result<T, E> {
+    fn context<Path: AsPath + ?Sized>(self, operation: &'static str, path: &Path) -> Result<T> {
+        self.map_err(|error| error.into().context(operation, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{self, ErrorKind, Result};
+
+    #[test]
+    fn io_result_context() -> Result<()> {
+        Ok({
+            // Arrange
+            let result: io::Result<()> = Err(ErrorKind::NotFound.into());
+
+            // Action
+            let error = result.context("delete", "a.txt").unwrap_err();
+
+            // Assert
+            assert_eq!(error.to_string(), "delete a.txt: entity not found");
+        })
+    }
+}
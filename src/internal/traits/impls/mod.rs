@@ -1 +1,2 @@
+pub mod context;
 pub mod to_vec_string;
@@ -1,3 +1,4 @@
 mod impls;
 
+pub mod context;
 pub mod to_vec_string;
@@ -0,0 +1,167 @@
+use crate::internal::paths;
+use std::{
+    env,
+    io::{Error, ErrorKind, Result},
+    path::{Path, PathBuf},
+};
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
+
+fn not_unicode() -> Error {
+    Error::new(ErrorKind::InvalidData, "the path isn't valid Unicode")
+}
+
+// The bytes kept as they are in the path of a URI: unreserved characters, sub-delimiters, `:`, `@` and `/`
+fn is_kept(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@/".contains(&byte)
+}
+
+fn encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len());
+    for &byte in bytes {
+        match is_kept(byte) {
+            true => encoded.push(byte as char),
+            false => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    return encoded;
+}
+
+fn decode(text: &str) -> Result<Vec<u8>> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'%' {
+            decoded.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        let hex = text
+            .get(i + 1..i + 3)
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| invalid("a `%` isn't followed by two hex digits"))?;
+        decoded.push(u8::from_str_radix(hex, 16).unwrap());
+        i += 3;
+    }
+    return Ok(decoded);
+}
+
+// Splits an absolute Windows path into the host and the path of its URI:
+// `C:\a` is `/C:/a` on no host, and `\\server\share\a` is `/share/a` on `server`
+fn windows_to_uri(path: &str) -> (String, String) {
+    let path = paths::strip_prefix(path).unwrap_or_else(|| path.to_string());
+    return match path.strip_prefix(r"\\") {
+        Some(unc) => {
+            let (host, rest) = unc.split_once('\\').unwrap_or((unc, ""));
+            (host.to_string(), format!("/{}", rest.replace('\\', "/")))
+        }
+        None => (String::new(), format!("/{}", path.replace('\\', "/"))),
+    };
+}
+
+// Joins the host and the decoded path of a URI back into a Windows path
+fn uri_to_windows(host: &str, path: &str) -> String {
+    if !host.is_empty() {
+        return format!(r"\\{host}{}", path.replace('/', "\\"));
+    }
+    let drive = path.as_bytes();
+    let path = match drive.len() >= 3 && drive[1].is_ascii_alphabetic() && drive[2] == b':' {
+        true => &path[1..],
+        false => path,
+    };
+    return path.replace('/', "\\");
+}
+
+// Converts a path, made absolute against the current directory, into a `file://` URI
+pub fn to_uri(path: &Path) -> Result<String> {
+    let absolute = match path.is_absolute() {
+        true => paths::normalize(path),
+        false => paths::normalize(&env::current_dir()?.join(path)),
+    };
+    if !cfg!(windows) {
+        return Ok(format!(
+            "file://{}",
+            encode(absolute.as_os_str().as_encoded_bytes())
+        ));
+    }
+    let (host, path) = windows_to_uri(absolute.to_str().ok_or_else(not_unicode)?);
+    return Ok(format!(
+        "file://{}{}",
+        encode(host.as_bytes()),
+        encode(path.as_bytes())
+    ));
+}
+
+// Converts a `file:` URI into a path; the query and fragment, if any, are ignored
+pub fn from_uri(uri: &str) -> Result<PathBuf> {
+    let rest = match uri.get(..5) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("file:") => &uri[5..],
+        _ => return Err(invalid("the URI doesn't start with `file:`")),
+    };
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (host, path) = match rest.strip_prefix("//") {
+        Some(authority) => authority.split_at(authority.find('/').unwrap_or(authority.len())),
+        None => ("", rest),
+    };
+    if !path.starts_with('/') {
+        return Err(invalid("the path of the URI isn't absolute"));
+    }
+    let host = match host.eq_ignore_ascii_case("localhost") {
+        true => "",
+        false => host,
+    };
+    let path = decode(path)?;
+    if !cfg!(windows) {
+        if !host.is_empty() {
+            return Err(invalid("the URI points to another host"));
+        }
+        return paths::from_bytes(path);
+    }
+    let host = String::from_utf8(decode(host)?).map_err(|_| not_unicode())?;
+    let path = String::from_utf8(path).map_err(|_| not_unicode())?;
+    return Ok(PathBuf::from(uri_to_windows(&host, &path)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_uris() -> Result<()> {
+        Ok({
+            // Arrange
+            let paths = [
+                r"C:\Program Files\a#1.txt",
+                r"\\?\C:\a",
+                r"\\server\share\a b",
+                r"\\?\UNC\server\share\a",
+            ];
+
+            // Action
+            let uris = paths.map(|path| {
+                let (host, path) = windows_to_uri(path);
+                format!("file://{host}{}", encode(path.as_bytes()))
+            });
+            let drive = String::from_utf8(decode("/c%3A/a%20b")?).unwrap();
+
+            // Assert
+            assert_eq!(
+                uris,
+                [
+                    "file:///C:/Program%20Files/a%231.txt",
+                    "file:///C:/a",
+                    "file://server/share/a%20b",
+                    "file://server/share/a",
+                ]
+            );
+            assert_eq!(uri_to_windows("", &drive), r"c:\a b");
+            assert_eq!(uri_to_windows("server", "/share/a"), r"\\server\share\a");
+            assert_eq!(uri_to_windows("", "/a"), r"\a");
+            assert!(decode("%2").is_err());
+            assert!(decode("%+1").is_err());
+        })
+    }
+}
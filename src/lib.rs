@@ -16,6 +16,7 @@
 //!                 println!("{}", line);
 //!             });
 //!
+//!         let _clean_up = ("file.1".as_file().delete_on_drop(), "file.2".as_file().delete_on_drop());
 //!         "file.1".as_file().write_string(&"Hello, World!")?;
 //!
 //!         let file = "file.1".as_file();
@@ -23,40 +24,303 @@
 //!         file.copy_to(&"file.2")?; // copies ./file.1 to ./file.2
 //!
 //!         "file.2".as_file().rename_to(&"file.1")?; // replace
-//!         "file.1".as_file().delete()?; // clean-up
 //!     })
 //! }
 //! ```
 
+#![allow(clippy::needless_return, clippy::unit_arg)]
+
+#[cfg(feature = "acl")]
+pub use acl::*; // re-export AclEntry
+pub use allocate::*; // re-export allocate
+#[cfg(any(feature = "tar", feature = "zip"))]
+pub use archive::*; // re-export zip, tar
 pub use as_file::*; // re-export AsFile
+pub use as_path::*; // re-export AsPath
+pub use cancel::*; // re-export CancelToken, Cancelled
+#[cfg(feature = "hash")]
+pub use checked::*; // re-export Corrupted
+pub use chunks::*; // re-export Chunks
+pub use describe::*; // re-export Description
+#[cfg(unix)]
+pub use dir::*; // re-export Dir
+pub use durable::*; // re-export sync_all, rename_durable
+#[cfg(feature = "encoding")]
+pub use encoding::*; // re-export Encoding
+pub use error::*; // re-export Error
+pub use executable::*; // re-export shebang
+#[cfg(feature = "expand")]
+pub use expand::*; // re-export expand_path
 pub use file_path::*; // re-export FilePath
-use internal::{traits::to_vec_string::*, types::*};
+pub use filesystem::*; // re-export FilesystemInfo
+pub use find::*; // re-export LineMatch
+pub use format::*; // re-export FormatRule
+pub use glob::*; // re-export matches_glob
+#[cfg(feature = "gzip")]
+pub use gzip::*; // re-export read_string_gz
+pub use hard_link::*; // re-export hard_link, link_count
+pub use hash::*; // re-export HashAlgorithm
+pub use index::*; // re-export Index
+use internal::{
+    metered::{metered, metered_with_context},
+    traits::{context::*, to_vec_string::*},
+    types::*,
+};
+pub use list::*; // re-export ListOptions
+pub use lock::*; // re-export FileLock
+pub use lockfile::*; // re-export Lockfile
+#[cfg(feature = "mmap")]
+pub use mmap::*; // re-export MappedFile
+pub use options::*; // re-export ReadOptions, WriteOptions, CopyOptions, PipeOptions
+#[cfg(all(unix, feature = "unix"))]
+pub use owner::*; // re-export set_owner
+#[cfg(feature = "rayon")]
+pub use par::*; // re-export par_for_each_file
+#[cfg(feature = "parity")]
+pub use parity::*; // re-export protect
+pub use permissions::*; // re-export set_readonly, set_mode
+pub use positional::*; // re-export read_bytes_at, write_bytes_at, read_range
+pub use preserve::*; // re-export Preserve
+pub use progress::*; // re-export Progress, copy_with_progress
+pub use prune::*; // re-export prune_empty_dirs
+pub use replace::*; // re-export replace_string
+pub use retry::*; // re-export RetryPolicy
+pub use rotate::*; // re-export RotationPolicy
+pub use sandbox::*; // re-export Sandbox
+pub use sanitize::*; // re-export sanitize_file_name
+#[cfg(feature = "sign")]
+pub use sign::*; // re-export SigningKey
+pub use size::*; // re-export format_size
 use std::{
     fs::{self, File, Metadata},
-    io::{Error, ErrorKind, Read, Result},
+    io::{self, BufRead, BufReader, BufWriter, ErrorKind, Read, Seek, Write},
+    ops::Range,
     path::PathBuf,
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
 };
+pub use stream::*; // re-export WriteMode, write_from, read_into
+pub use symlink::*; // re-export symlink, read_link, resolve
+pub use sync::*; // re-export SyncOptions, SyncReport
+pub use temp::*; // re-export TempFile, TempDir, DeleteOnDrop
+pub use text_profile::*; // re-export TextProfile
+pub use times::*; // re-export set_times
+pub use transaction::*; // re-export Transaction
+#[cfg(feature = "trash")]
+pub use trash::*; // re-export trash
+#[cfg(feature = "hash")]
+pub use tree::*; // re-export tree_hash
+pub use truncate::*; // re-export truncate, set_len
+pub use versions::*; // re-export restore_version
+pub use vfs::*; // re-export FileSystem, OsFileSystem, MemoryFileSystem
+pub use walk::*; // re-export Walk
+#[cfg(feature = "yaml")]
+pub use yaml::*; // re-export read_yaml
 
+#[cfg(feature = "acl")]
+pub mod acl;
+pub mod allocate;
+#[cfg(any(feature = "tar", feature = "zip"))]
+pub mod archive;
 pub mod as_file;
+pub mod as_path;
+pub mod batch;
+pub mod cancel;
+#[cfg(feature = "hash")]
+pub mod checked;
+pub mod chunks;
+pub mod counter;
+#[cfg(feature = "hash")]
+pub mod dedupe;
+pub mod describe;
+#[cfg(unix)]
+pub mod dir;
+pub mod durable;
+#[cfg(feature = "encoding")]
+pub mod encoding;
+pub mod error;
+pub mod executable;
+#[cfg(feature = "expand")]
+pub mod expand;
 pub mod file_path;
+pub mod filesystem;
+pub mod find;
+pub mod format;
+pub mod glob;
+#[cfg(feature = "gzip")]
+pub mod gzip;
+pub mod hard_link;
+pub mod hash;
+pub mod index;
 mod internal;
+pub mod list;
+pub mod lock;
+pub mod lockfile;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod options;
+#[cfg(all(unix, feature = "unix"))]
+pub mod owner;
+#[cfg(feature = "rayon")]
+pub mod par;
+#[cfg(feature = "parity")]
+pub mod parity;
+pub mod permissions;
+pub mod positional;
+pub mod preserve;
+pub mod progress;
+pub mod prune;
+pub mod queue;
+pub mod replace;
+pub mod retry;
+pub mod rotate;
+pub mod sandbox;
+pub mod sanitize;
+#[cfg(feature = "sign")]
+pub mod sign;
+pub mod size;
+pub mod stream;
+pub mod symlink;
+pub mod sync;
+pub mod temp;
+pub mod text_profile;
+pub mod times;
+pub mod transaction;
+#[cfg(feature = "trash")]
+pub mod trash;
+#[cfg(feature = "hash")]
+pub mod tree;
+pub mod truncate;
+pub mod versions;
+pub mod vfs;
+pub mod walk;
+#[cfg(feature = "yaml")]
+pub mod yaml;
+
+// Gets a File::open handle from AsPath such as String, &str or PathBuf
+fn get_file<Path: AsPath + ?Sized>(file_path: &Path) -> io::Result<File> {
+    File::open(file_path.as_path())
+}
 
-// Gets a File::open handle from AsRef<str> such as String or &str
-fn get_file<Path: AsRef<str>>(file_path: &Path) -> Result<File> {
-    File::open(file_path.as_ref())
+// Attaches `to` to an error from a two-path operation if the source is still usable, since the destination's side
+// must then have failed, and `from` otherwise
+fn context_either<T, From: AsPath + ?Sized, To: AsPath + ?Sized>(
+    result: io::Result<T>,
+    operation: &'static str,
+    from: &From,
+    to: &To,
+    source_usable: impl FnOnce(&std::path::Path) -> bool,
+) -> Result<T> {
+    return match result {
+        Err(x) if source_usable(from.as_path()) => Err(x).context(operation, to),
+        result => result.context(operation, from),
+    };
 }
 
-// Converts AsRef<str> such as String or &str to PathBuf
-fn path_of<Path: AsRef<str>>(file_path: &Path) -> PathBuf {
-    PathBuf::from(file_path.as_ref())
+// Converts AsPath such as String, &str or PathBuf to PathBuf
+fn path_of<Path: AsPath + ?Sized>(file_path: &Path) -> PathBuf {
+    file_path.as_path().to_path_buf()
 }
 
 // Creates a file and its full directory path if they don't exist
-fn mk_file<Path: AsRef<str>>(file_path: &Path) -> Result<File> {
+fn mk_file<Path: AsPath + ?Sized>(file_path: &Path) -> io::Result<File> {
     if let Some(path) = path_of(file_path).parent() {
         fs::create_dir_all(path)?;
     }
-    return File::create(file_path.as_ref());
+    return File::create(file_path.as_path());
+}
+
+// Gets a unique, hidden path next to a file, on the same filesystem, to stage writes at
+fn temp_sibling(path: &std::path::Path) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    return path.with_file_name(format!(".{name}.{}.{count}.tmp", process::id()));
+}
+
+// Reads the last byte of a file without reading the rest, or None if it's empty or doesn't exist
+fn last_byte<Path: AsPath + ?Sized>(file_path: &Path) -> io::Result<Option<u8>> {
+    let mut file = match get_file(file_path) {
+        Err(x) if x.kind() == ErrorKind::NotFound => return Ok(None),
+        file => file?,
+    };
+    if file.metadata()?.len() == 0 {
+        return Ok(None);
+    }
+    let mut byte = [0];
+    file.seek(std::io::SeekFrom::End(-1))?;
+    file.read_exact(&mut byte)?;
+
+    return Ok(Some(byte[0]));
+}
+
+// Writes the contents to a temporary sibling first, then renames it over the file;
+// with `sync`, the contents are flushed to disk before the rename, and the rename itself after it
+fn write_via_rename(path: &std::path::Path, contents: &[u8], sync: bool) -> io::Result<()> {
+    return replace_via_rename(path, sync, |mut file| {
+        file.write_all(contents)?;
+        return Ok((file, ()));
+    });
+}
+
+// Like `write_via_rename`, but the contents are written by a closure given the temporary sibling, which it hands back
+// along with its result; the sibling is removed instead of renamed if the closure fails
+fn replace_via_rename<T>(
+    path: &std::path::Path,
+    sync: bool,
+    write: impl FnOnce(File) -> io::Result<(File, T)>,
+) -> io::Result<T> {
+    let temp = temp_sibling(path);
+    let result = File::create(&temp)
+        .and_then(|file| {
+            keep_permissions(path, &file)?;
+            let (file, written) = write(file)?;
+            if sync {
+                file.sync_all()?;
+            }
+            return Ok(written);
+        })
+        .and_then(|written| fs::rename(&temp, path).map(|_| written));
+    if result.is_err() {
+        _ = fs::remove_file(&temp);
+    }
+    if sync && result.is_ok() {
+        sync_parent(path)?;
+    }
+    return result;
+}
+
+// Gives a temporary sibling the permissions of the file it's about to replace, if there is one, before anything is
+// written to it; on Unix it also tries to keep the owner and group, which only succeeds for root or the same owner
+fn keep_permissions(path: &std::path::Path, file: &File) -> io::Result<()> {
+    let metadata = match fs::metadata(path) {
+        Err(x) if x.kind() == ErrorKind::NotFound => return Ok(()),
+        metadata => metadata?,
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{fchown, MetadataExt};
+        _ = fchown(file, Some(metadata.uid()), Some(metadata.gid())); // before the mode, as it may clear setuid/setgid
+    }
+
+    return file.set_permissions(metadata.permissions());
+}
+
+// Flushes the directory holding a path to disk, so that a file created or renamed in it survives a power loss.
+// Windows can't open directories to sync them, so this only applies on Unix
+fn sync_parent(path: &std::path::Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()); // empty for the current directory
+        File::open(dir.unwrap_or(".".as_ref()))?.sync_all()?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+
+    return Ok(());
 }
 
 /// Reads the contents of a file.
@@ -76,11 +340,18 @@ fn mk_file<Path: AsRef<str>>(file_path: &Path) -> Result<File> {
 ///     })
 /// }
 /// ```
-pub fn read_string<Path: AsRef<str>>(file_path: &Path) -> Result<String> {
-    let mut buf = String::new();
-    get_file(file_path)?.read_to_string(&mut buf)?;
+pub fn read_string<Path: AsPath + ?Sized>(file_path: &Path) -> Result<String> {
+    metered(
+        "read_string",
+        |buf: &String| (buf.len() as u64, 0),
+        || {
+            let mut buf = String::new();
+            get_file(file_path)?.read_to_string(&mut buf)?;
 
-    return Ok(buf);
+            return Ok(buf);
+        },
+    )
+    .context("read_string", file_path)
 }
 
 /// Reads the contents of a file and returns it as lines.
@@ -100,18 +371,215 @@ pub fn read_string<Path: AsRef<str>>(file_path: &Path) -> Result<String> {
 ///     })
 /// }
 /// ```
-pub fn read_lines<Path: AsRef<str>>(file_path: &Path) -> Result<Lines> {
+pub fn read_lines<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Lines> {
     Ok(read_string(file_path)?
         .lines()
         .map(ToString::to_string)
         .collect())
 }
 
+/// Checks whether a file ends with a newline, such as to write the lines read by `read_lines` back the way they were
+/// with `write_lines_with`. Only the last byte is read. An empty file doesn't end with one.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`bool`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "ends_with_newline_example/file.txt";
+///         file_access::write_string(&file_path, &"Hello, World!\n")?;
+///
+///         assert!(file_access::ends_with_newline(&file_path)?);
+///
+///         // Clean-up:
+///         file_access::delete(&"ends_with_newline_example")?;
+///     })
+/// }
+/// ```
+pub fn ends_with_newline<Path: AsPath + ?Sized>(file_path: &Path) -> Result<bool> {
+    get_file(file_path).context("ends_with_newline", file_path)?;
+    Ok(last_byte(file_path).context("ends_with_newline", file_path)? == Some(b'\n'))
+}
+
+/// Lazily reads a file line by line through a buffer, so large files such as logs
+/// can be processed without loading them fully. Line endings (`\n` or `\r\n`) are stripped.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`impl Iterator<Item = Result<String>>`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         for line in file_access::lines(&file_path)? {
+///             println!("{}", line?);
+///         }
+///     })
+/// }
+/// ```
+pub fn lines<Path: AsPath + ?Sized>(
+    file_path: &Path,
+) -> Result<impl Iterator<Item = Result<String>>> {
+    let file = get_file(file_path).context("lines", file_path)?;
+    let path = path_of(file_path);
+
+    return Ok(BufReader::new(file)
+        .lines()
+        .map(move |line| line.context("lines", &path)));
+}
+
+/// Reads the first `n` lines of a file, stopping as soon as they were read. Line endings (`\n` or `\r\n`) are stripped.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `n`: how many lines to read, fewer being returned if the file is shorter
+///
+/// # Returns
+/// Result<`Vec<String>`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         let lines: Vec<String> = file_access::head(&file_path, 1)?;
+///         assert_eq!(lines, ["[package]"]);
+///     })
+/// }
+/// ```
+pub fn head<Path: AsPath + ?Sized>(file_path: &Path, n: usize) -> Result<Lines> {
+    get_file(file_path)
+        .and_then(|file| BufReader::new(file).lines().take(n).collect())
+        .context("head", file_path)
+}
+
+// How much of a file `tail` reads at a time, backwards from its end
+const TAIL_CHUNK: usize = 8 * 1024;
+
+/// Reads the last `n` lines of a file. The file is searched backwards from its end, a chunk at a time,
+/// so only about as much as the lines themselves is read, however large the file. Line endings (`\n` or `\r\n`) are stripped.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `n`: how many lines to read, fewer being returned if the file is shorter
+///
+/// # Returns
+/// Result<`Vec<String>`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "tail_example.log";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"started\nworking\nstopped\n")?;
+///
+///         let lines: Vec<String> = file_access::tail(&file_path, 2)?;
+///         assert_eq!(lines, ["working", "stopped"]);
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn tail<Path: AsPath + ?Sized>(file_path: &Path, n: usize) -> Result<Lines> {
+    (|| -> io::Result<Lines> {
+        if n == 0 {
+            return Ok(vec![]);
+        }
+        let mut file = get_file(file_path)?;
+        let len = file.metadata()?.len();
+
+        // Finds where the last `n` lines start: right after the `n`th newline from the end,
+        // not counting the one ending the last line
+        let (mut start, mut pos, mut newlines) = (0, len, 0);
+        let mut chunk = vec![0; TAIL_CHUNK];
+        'search: while pos > 0 {
+            let size = TAIL_CHUNK.min(pos as usize);
+            pos -= size as u64;
+            file.seek(io::SeekFrom::Start(pos))?;
+            file.read_exact(&mut chunk[..size])?;
+            for i in (0..size).rev() {
+                let offset = pos + i as u64;
+                if chunk[i] == b'\n' && offset != len - 1 {
+                    newlines += 1;
+                    if newlines == n {
+                        start = offset + 1;
+                        break 'search;
+                    }
+                }
+            }
+        }
+
+        let mut buf = String::new();
+        file.seek(io::SeekFrom::Start(start))?;
+        file.read_to_string(&mut buf)?;
+
+        return Ok(buf.lines().map(ToString::to_string).collect());
+    })()
+    .context("tail", file_path)
+}
+
+/// Reads the lines of a file within a range of line numbers, counted from `0`, such as a page of them.
+/// The file is streamed through a buffer, and reading stops once the range was read, so only the lines
+/// up to its end are read, however large the file. Line endings (`\n` or `\r\n`) are stripped.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `range`: the line numbers to read, such as `20..40`, fewer being returned if the file is shorter
+///
+/// # Returns
+/// Result<`Vec<String>`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "read_lines_range_example.txt";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_lines(&file_path, &vec!["a", "b", "c", "d"])?;
+///
+///         let page: Vec<String> = file_access::read_lines_range(&file_path, 1..3)?;
+///         assert_eq!(page, ["b", "c"]);
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn read_lines_range<Path: AsPath + ?Sized>(
+    file_path: &Path,
+    range: Range<usize>,
+) -> Result<Lines> {
+    get_file(file_path)
+        .and_then(|file| {
+            BufReader::new(file)
+                .lines()
+                .take(range.end)
+                .skip(range.start)
+                .collect()
+        })
+        .context("read_lines_range", file_path)
+}
+
 /// Writes text to a file. This function will create the file **and its full directory path** if they don't exist,
 /// and will entirely replace the contents.
 ///
 /// # Parameters
-/// - `file_path`: **borrowed** `AsRef<str>` such as `String` or `&str`
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
 /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
 ///
 /// # Returns
@@ -134,22 +602,32 @@ pub fn read_lines<Path: AsRef<str>>(file_path: &Path) -> Result<Lines> {
 ///     })
 /// }
 /// ```
-pub fn write_string<Path: AsRef<str>, Text: AsRef<str>>(
+pub fn write_string<Path: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
     file_path: &Path,
     text: &Text,
 ) -> Result<()> {
-    let path = path_of(file_path);
-    if !path.exists() {
-        mk_file(file_path)?;
-    }
-    return fs::write(path, text.as_ref());
+    let written = text.as_ref().len() as u64;
+    metered(
+        "write_string",
+        |_| (0, written),
+        || {
+            let path = path_of(file_path);
+            if !path.exists() {
+                mk_file(file_path)?;
+            }
+            return fs::write(path, text.as_ref());
+        },
+    )
+    .context("write_string", file_path)
 }
 
 /// Writes a list of text as lines to a file. This function will create the file **and its full directory path** if they don't exist,
 /// and will entirely replace the contents with the provided strings each on its own line.
+/// The last line isn't followed by a newline; for files that should end with one, such as `.gitignore`,
+/// use `write_lines_with` and `WriteOptions::trailing_newline`.
 ///
 /// # Parameters
-/// - `file_path`: **borrowed** `AsRef<str>` such as `String` or `&str`
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
 /// - `lines`: **borrowed** `Vec<AsRef<str>>` such as `Vec<String>` or `Vec<&str>`
 ///
 /// # Returns
@@ -166,24 +644,28 @@ pub fn write_string<Path: AsRef<str>, Text: AsRef<str>>(
 ///         let lines: Vec<String> = lines.iter().map(ToString::to_string).collect();
 ///
 ///         file_access::write_lines(&file_path, &lines)?;
+///         assert_eq!(file_access::read_string(&file_path)?, "Hello,\nWorld!"); // no trailing newline
 ///
 ///         // Clean-up:
 ///         file_access::delete(&"lines_to"); // ./lines_to/
 ///     })
 /// }
 /// ```
-pub fn write_lines<Path: AsRef<str>, Line: AsRef<str>>(
+pub fn write_lines<Path: AsPath + ?Sized, Line: AsRef<str>>(
     file_path: &Path,
     lines: &Vec<Line>,
 ) -> Result<()> {
     write_string(file_path, &lines.to_vec_string().join("\n"))
 }
 
-/// Appends text to a file. This function will append the contents of the file,
-/// or write a new one **and its full directory path** if they don't exist yet.
+/// Writes text to a file without it ever existing in an incomplete state under any name.
+/// On Linux the text is written to an anonymous `O_TMPFILE` first, which is then linked into place;
+/// elsewhere, or when the filesystem doesn't support it, it's written to a temporary sibling file which is then renamed.
+/// Either way an existing file keeps its permissions, and on Unix its owner and group where the process is allowed to set them.
+/// This function will create the file **and its full directory path** if they don't exist, and will entirely replace the contents.
 ///
 /// # Parameters
-/// - `file_path`: **borrowed** `AsRef<str>` such as `String` or `&str`
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
 /// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
 ///
 /// # Returns
@@ -193,38 +675,54 @@ pub fn write_lines<Path: AsRef<str>, Line: AsRef<str>>(
 /// ```
 /// fn main() -> std::io::Result<()> {
 ///     Ok({
-///         let file_path: &str = "append_to/absolute_or_relative.path";
+///         let file_path: &str = "anon_link_to/config.toml";
 ///         let file_path: String = String::from(file_path);
 ///
-///         let text: &str = "Hello, World!";
+///         let text: &str = "debug = true";
 ///         let text: String = String::from(text);
 ///
-///         file_access::append_string(&file_path, &text)?;
+///         file_access::write_string_anon_then_link(&file_path, &text)?;
 ///
 ///         // Clean-up:
-///         file_access::delete(&"append_to"); // ./append_to/
+///         file_access::delete(&"anon_link_to")?; // ./anon_link_to/
 ///     })
 /// }
 /// ```
-pub fn append_string<Path: AsRef<str>, Text: AsRef<str>>(
+pub fn write_string_anon_then_link<Path: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
     file_path: &Path,
     text: &Text,
 ) -> Result<()> {
-    write_string(
-        file_path,
-        &match read_string(file_path) {
-            Ok(file) => format!("{}{}", file, text.as_ref()),
-            Err(_) => text.as_ref().to_string(),
+    let written = text.as_ref().len() as u64;
+    metered(
+        "write_string_anon_then_link",
+        |_| (0, written),
+        || {
+            let path = path_of(file_path);
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+
+            #[cfg(target_os = "linux")]
+            match internal::sys::linux::write_anon_then_link(&path, text.as_ref().as_bytes()) {
+                Err(x) if x.kind() == ErrorKind::Unsupported => {}
+                result => return result,
+            }
+            return write_via_rename(&path, text.as_ref().as_bytes(), false);
         },
     )
+    .context("write_string_anon_then_link", file_path)
 }
 
-/// Appends a list of text as lines to a file. This function will append the contents of the file,
-/// or write a new one **and its full directory path** if they don't exist yet.
+/// Writes text to a file atomically: it's written to a temporary sibling file first, which is then renamed over the file,
+/// so a crash mid-write can never leave a truncated file behind; readers see either the old or the new contents.
+/// With `sync`, the contents and the rename are also flushed to disk before returning, so they survive a power loss.
+/// An existing file keeps its permissions, and on Unix its owner and group where the process is allowed to set them.
+/// This function will create the file **and its full directory path** if they don't exist, and will entirely replace the contents.
 ///
 /// # Parameters
-/// - `file_path`: **borrowed** `AsRef<str>` such as `String` or `&str`
-/// - `lines`: **borrowed** `Vec<AsRef<str>>` such as `Vec<String>` or `Vec<&str>`
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+/// - `sync`: `bool` whether to fsync before returning
 ///
 /// # Returns
 /// Result<`()`>
@@ -233,36 +731,36 @@ pub fn append_string<Path: AsRef<str>, Text: AsRef<str>>(
 /// ```
 /// fn main() -> std::io::Result<()> {
 ///     Ok({
-///         let file_path: &str = "append_lines_to/absolute_or_relative.path";
+///         let file_path: &str = "atomic_to/config.toml";
 ///         let file_path: String = String::from(file_path);
 ///
-///         let lines: Vec<&str> = "Hello, World!".split_whitespace().collect();
-///         let lines: Vec<String> = lines.iter().map(ToString::to_string).collect();
-///
-///         file_access::append_lines(&file_path, &lines)?;
+///         file_access::write_string_atomic(&file_path, &"debug = true", true)?;
 ///
 ///         // Clean-up:
-///         file_access::delete(&"append_lines_to"); // ./append_lines_to/
+///         file_access::delete(&"atomic_to")?; // ./atomic_to/
 ///     })
 /// }
 /// ```
-pub fn append_lines<Path: AsRef<str>, Line: AsRef<str>>(
+pub fn write_string_atomic<Path: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
     file_path: &Path,
-    lines: &Vec<Line>,
+    text: &Text,
+    sync: bool,
 ) -> Result<()> {
-    let mut file = match read_lines(file_path) {
-        Ok(lines) => lines,
-        Err(_) => vec![],
-    };
-    file.extend_from_slice(&lines.to_vec_string());
-
-    return write_lines(file_path, &file);
+    let path = path_of(file_path);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("write_string_atomic", file_path)?;
+    }
+    return write_via_rename(&path, text.as_ref().as_bytes(), sync)
+        .context("write_string_atomic", file_path);
 }
 
-/// Deletes a file, or a directory **recursively**.
+/// Edits the lines of a file in place: reads them, hands them to a closure to insert, remove or replace any of them,
+/// then writes them back joined with `\n` (keeping the file's trailing newline, if any). The file is replaced
+/// atomically, like `write_string_atomic`, so readers never see it half-edited, and it keeps its permissions.
 ///
 /// # Parameters
-/// - `file_path`: **borrowed** `AsRef<str>` such as `String` or `&str`
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `edit`: `FnOnce(&mut Vec<String>)` that edits the lines
 ///
 /// # Returns
 /// Result<`()`>
@@ -271,37 +769,42 @@ pub fn append_lines<Path: AsRef<str>, Line: AsRef<str>>(
 /// ```
 /// fn main() -> std::io::Result<()> {
 ///     Ok({
-///         let file_path: &str = "absolute_or_relative_path/to_a_file/or_a_directory";
+///         let file_path: &str = "edit_lines_example.txt";
 ///         let file_path: String = String::from(file_path);
+///         file_access::write_lines(&file_path, &vec!["a", "b", "c"])?;
 ///
-///         file_access::write_string(&file_path, &"Hello, World!");
-///         file_access::delete(&file_path)?; // delete file
+///         file_access::edit_lines(&file_path, |lines| {
+///             lines.retain(|line| line != "b");
+///             lines.insert(0, "first".to_string());
+///         })?;
+///         assert_eq!(file_access::read_lines(&file_path)?, ["first", "a", "c"]);
 ///
-///         // Delete directory:
-///         file_access::delete(&"absolute_or_relative_path")?;
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
 ///     })
 /// }
 /// ```
-pub fn delete<Path: AsRef<str>>(file_path: &Path) -> Result<()> {
-    let path = path_of(file_path);
-
-    if path.is_file() {
-        return fs::remove_file(path);
-    }
+pub fn edit_lines<Path: AsPath + ?Sized, Edit: FnOnce(&mut Lines)>(
+    file_path: &Path,
+    edit: Edit,
+) -> Result<()> {
+    let text = read_string(file_path)?;
+    let mut lines: Lines = text.lines().map(ToString::to_string).collect();
+    edit(&mut lines);
 
-    if path.is_dir() {
-        return fs::remove_dir_all(path);
+    let mut edited = lines.join("\n");
+    if text.ends_with('\n') {
+        edited.push('\n');
     }
-
-    return Err(Error::new(ErrorKind::InvalidInput, file_path.as_ref()));
+    return write_string_atomic(file_path, &edited, false);
 }
 
-/// Copies the contents of a file and write it to a destination.
-/// This function will entirely replace the contents of the destination if it already exists.
+/// Appends text to a file. This function will append the contents of the file,
+/// or write a new one **and its full directory path** if they don't exist yet.
 ///
 /// # Parameters
-/// - `from`: **borrowed** `AsRef<str>` such as `String` or `&str`
-/// - `to`: **borrowed** `AsRef<str>` such as `String` or `&str`
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
 ///
 /// # Returns
 /// Result<`()`>
@@ -310,29 +813,37 @@ pub fn delete<Path: AsRef<str>>(file_path: &Path) -> Result<()> {
 /// ```
 /// fn main() -> std::io::Result<()> {
 ///     Ok({
-///         let source: &str = "Cargo.toml";
-///         let source: String = String::from(source);
+///         let file_path: &str = "append_to/absolute_or_relative.path";
+///         let file_path: String = String::from(file_path);
 ///
-///         let destination: &str = "Cargo.toml.2";
-///         let destination: String = String::from(destination);
+///         let text: &str = "Hello, World!";
+///         let text: String = String::from(text);
 ///
-///         file_access::copy(&source, &destination)?;
+///         file_access::append_string(&file_path, &text)?;
 ///
-///         // Delete file:
-///         file_access::delete(&destination);
+///         // Clean-up:
+///         file_access::delete(&"append_to"); // ./append_to/
 ///     })
 /// }
 /// ```
-pub fn copy<From: AsRef<str>, To: AsRef<str>>(from: &From, to: &To) -> Result<()> {
-    write_string(to, &read_string(from)?)
+pub fn append_string<Path: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    text: &Text,
+) -> Result<()> {
+    append_bytes(file_path, text.as_ref().as_bytes())
 }
 
-/// Copies the contents of a file, writes it to a destination and then deletes the source.
-/// This function will entirely replace the contents of the destination if it already exists.
+/// Appends text to a file as one whole write, so that concurrent writers, across threads and processes,
+/// interleave entire entries rather than pieces of them, such as for a log shared by several processes.
+/// The file is opened in append mode (`O_APPEND`), and with `lock`, an exclusive advisory lock is held on it
+/// for the write, which also keeps out large writes the OS could split, and readers taking a shared lock.
+/// The text is flushed to disk before returning. This function will write a new file **and its full directory path**
+/// if they don't exist yet.
 ///
 /// # Parameters
-/// - `from`: **borrowed** `AsRef<str>` such as `String` or `&str`
-/// - `to`: **borrowed** `AsRef<str>` such as `String` or `&str`
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+/// - `lock`: **owned** `bool`, whether to lock the file for the write
 ///
 /// # Returns
 /// Result<`()`>
@@ -341,162 +852,1442 @@ pub fn copy<From: AsRef<str>, To: AsRef<str>>(from: &From, to: &To) -> Result<()
 /// ```
 /// fn main() -> std::io::Result<()> {
 ///     Ok({
-///         let source: &str = "file.1";
-///         let source: String = String::from(source);
-///
-///         let destination: &str = "file.2";
-///         let destination: String = String::from(destination);
+///         let file_path: &str = "append_string_sync_example/shared.log";
 ///
-///         file_access::write_string(&source, &"Hello, World!")?;
-///         file_access::rename(&source, &destination)?;
+///         file_access::append_string_sync(&file_path, &"worker 1: done\n", true)?;
 ///
 ///         // Clean-up:
-///         file_access::delete(&destination)?;
+///         file_access::delete(&"append_string_sync_example")?;
 ///     })
 /// }
 /// ```
-pub fn rename<From: AsRef<str>, To: AsRef<str>>(from: &From, to: &To) -> Result<()> {
-    copy(from, to)?;
+pub fn append_string_sync<Path: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    text: &Text,
+    lock: bool,
+) -> Result<()> {
+    let bytes = text.as_ref().as_bytes();
+    metered(
+        "append_string_sync",
+        |_| (0, bytes.len() as u64),
+        || {
+            if let Some(dir) = path_of(file_path).parent() {
+                fs::create_dir_all(dir)?;
+            }
+            // Read access too, which Windows needs to lock the file
+            let mut file = fs::OpenOptions::new()
+                .read(true)
+                .append(true)
+                .create(true)
+                .open(file_path.as_path())?;
+            if lock {
+                file.lock()?;
+            }
+            file.write_all(bytes)?;
 
-    return delete(from);
+            return file.sync_data();
+        },
+    )
+    .context("append_string_sync", file_path)
 }
 
-/// Queries metadata about the underlying file.
+/// Appends a list of text as lines to a file. This function will append the contents of the file,
+/// or write a new one **and its full directory path** if they don't exist yet.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `lines`: **borrowed** `Vec<AsRef<str>>` such as `Vec<String>` or `Vec<&str>`
 ///
 /// # Returns
-/// Result<`Metadata`>
+/// Result<`()`>
 ///
 /// # Examples
 /// ```
 /// fn main() -> std::io::Result<()> {
 ///     Ok({
-///         let file_path: &str = "Cargo.toml";
+///         let file_path: &str = "append_lines_to/absolute_or_relative.path";
 ///         let file_path: String = String::from(file_path);
 ///
-///         let metadata: std::fs::Metadata = file_access::get_metadata(&file_path)?;
-///         println!("{:#?}", metadata);
+///         let lines: Vec<&str> = "Hello, World!".split_whitespace().collect();
+///         let lines: Vec<String> = lines.iter().map(ToString::to_string).collect();
+///
+///         file_access::append_lines(&file_path, &lines)?;
+///
+///         // Clean-up:
+///         file_access::delete(&"append_lines_to"); // ./append_lines_to/
 ///     })
 /// }
 /// ```
-pub fn get_metadata<Path: AsRef<str>>(file_path: &Path) -> Result<Metadata> {
-    get_file(file_path)?.metadata()
+pub fn append_lines<Path: AsPath + ?Sized, Line: AsRef<str>>(
+    file_path: &Path,
+    lines: &Vec<Line>,
+) -> Result<()> {
+    let mut text = lines.to_vec_string().join("\n");
+    let last = last_byte(file_path).context("append_lines", file_path)?;
+    if !lines.is_empty() && matches!(last, Some(byte) if byte != b'\n') {
+        text.insert(0, '\n');
+    }
+    return append_bytes(file_path, &text);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Result;
-
-    // cargo test -- --show-output --test-threads=1
-    // cargo test <TESTNAME> --show-output
-
-    #[test]
-    fn read_string() -> Result<()> {
-        Ok({
-            // Arrange
-            let file = "Cargo.toml";
-
-            // Action
-            let text = super::read_string(&file)?;
-            println!("{text}");
-
+/// Appends a single line to a file, starting it on a new line unless the file is empty or already ends with a newline,
+/// where `append_string` would glue the text onto the last line. This function will write a new file
+/// **and its full directory path** if they don't exist yet.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "append_line_example.log";
+///         let file_path: String = String::from(file_path);
+///
+///         file_access::append_line(&file_path, &"started")?;
+///         file_access::append_line(&file_path, &"stopped")?;
+///         assert_eq!(file_access::read_string(&file_path)?, "started\nstopped");
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn append_line<Path: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    text: &Text,
+) -> Result<()> {
+    let mut line = String::new();
+    if matches!(last_byte(file_path).context("append_line", file_path)?, Some(byte) if byte != b'\n')
+    {
+        line.push('\n');
+    }
+    line.push_str(text.as_ref());
+
+    return append_bytes(file_path, &line);
+}
+
+/// Inserts text at the start of a file. The file is streamed into a temporary sibling after the text,
+/// which then replaces it atomically, keeping its permissions, so even huge files aren't loaded into memory.
+/// This function will write a new file **and its full directory path** if they don't exist yet.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "prepend_string_example.csv";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"1,2\n3,4")?;
+///
+///         file_access::prepend_string(&file_path, &"a,b\n")?;
+///         assert_eq!(file_access::read_string(&file_path)?, "a,b\n1,2\n3,4");
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn prepend_string<Path: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    text: &Text,
+) -> Result<()> {
+    if !path_of(file_path).exists() {
+        return write_string(file_path, text);
+    }
+    return pipe_with(
+        file_path,
+        file_path,
+        PipeOptions::new(),
+        |reader, writer| {
+            writer.write_all(text.as_ref().as_bytes())?;
+            io::copy(reader, writer)?;
+
+            return Ok(());
+        },
+    );
+}
+
+/// Inserts lines at the start of a file, each on its own line, before its first line. The file is streamed into
+/// a temporary sibling after the lines, which then replaces it atomically, keeping its permissions, so even huge files
+/// aren't loaded into memory.
+/// This function will write a new file **and its full directory path** if they don't exist yet.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `lines`: **borrowed** `Vec<AsRef<str>>` such as `Vec<String>` or `Vec<&str>`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "prepend_lines_example.rs";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"fn main() {}")?;
+///
+///         file_access::prepend_lines(&file_path, &vec!["// Copyright", "// License: Apache-2.0"])?;
+///         assert_eq!(file_access::read_lines(&file_path)?[2], "fn main() {}");
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn prepend_lines<Path: AsPath + ?Sized, Line: AsRef<str>>(
+    file_path: &Path,
+    lines: &Vec<Line>,
+) -> Result<()> {
+    let mut text = lines.to_vec_string().join("\n");
+    if !lines.is_empty()
+        && last_byte(file_path)
+            .context("prepend_lines", file_path)?
+            .is_some()
+    {
+        text.push('\n');
+    }
+    return prepend_string(file_path, &text);
+}
+
+/// Reads the contents of a file as bytes, such as an image or an archive.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Vec<u8>`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         let bytes: Vec<u8> = file_access::read_bytes(&file_path)?;
+///         println!("{} bytes", bytes.len());
+///     })
+/// }
+/// ```
+pub fn read_bytes<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Vec<u8>> {
+    metered(
+        "read_bytes",
+        |buf: &Vec<u8>| (buf.len() as u64, 0),
+        || fs::read(file_path.as_path()),
+    )
+    .context("read_bytes", file_path)
+}
+
+/// Writes bytes to a file. This function will create the file **and its full directory path** if they don't exist,
+/// and will entirely replace the contents.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `bytes`: **borrowed** `AsRef<[u8]>` such as `Vec<u8>`, `[u8; N]` or `&[u8]`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "bytes_to/image.png";
+///         let file_path: String = String::from(file_path);
+///
+///         let bytes: Vec<u8> = vec![0x89, b'P', b'N', b'G'];
+///
+///         file_access::write_bytes(&file_path, &bytes)?;
+///
+///         // Clean-up:
+///         file_access::delete(&"bytes_to")?; // ./bytes_to/
+///     })
+/// }
+/// ```
+pub fn write_bytes<Path: AsPath + ?Sized, Bytes: AsRef<[u8]> + ?Sized>(
+    file_path: &Path,
+    bytes: &Bytes,
+) -> Result<()> {
+    let written = bytes.as_ref().len() as u64;
+    metered(
+        "write_bytes",
+        |_| (0, written),
+        || mk_file(file_path)?.write_all(bytes.as_ref()),
+    )
+    .context("write_bytes", file_path)
+}
+
+/// Appends bytes to a file. This function will append the contents of the file,
+/// or write a new one **and its full directory path** if they don't exist yet.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `bytes`: **borrowed** `AsRef<[u8]>` such as `Vec<u8>`, `[u8; N]` or `&[u8]`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "append_bytes_to/data.bin";
+///         let file_path: String = String::from(file_path);
+///
+///         file_access::append_bytes(&file_path, &[0x00, 0xFF])?;
+///         file_access::append_bytes(&file_path, &[0xFE])?;
+///
+///         // Clean-up:
+///         file_access::delete(&"append_bytes_to")?; // ./append_bytes_to/
+///     })
+/// }
+/// ```
+pub fn append_bytes<Path: AsPath + ?Sized, Bytes: AsRef<[u8]> + ?Sized>(
+    file_path: &Path,
+    bytes: &Bytes,
+) -> Result<()> {
+    let written = bytes.as_ref().len() as u64;
+    metered(
+        "append_bytes",
+        |_| (0, written),
+        || {
+            if let Some(dir) = path_of(file_path).parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(file_path.as_path())?;
+
+            return file.write_all(bytes.as_ref());
+        },
+    )
+    .context("append_bytes", file_path)
+}
+
+/// Creates an empty directory. Its parent has to exist already; see `create_dir_all` to create it too.
+///
+/// # Parameters
+/// - `dir_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>, failing with `ErrorKind::AlreadyExists` if something is already at the path,
+/// or `ErrorKind::NotFound` if its parent doesn't exist
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::create_dir(&"create_dir_example")?;
+///         assert!(file_access::create_dir(&"create_dir_example").is_err());
+///
+///         // Clean-up:
+///         file_access::delete(&"create_dir_example")?;
+///     })
+/// }
+/// ```
+pub fn create_dir<Path: AsPath + ?Sized>(dir_path: &Path) -> Result<()> {
+    metered(
+        "create_dir",
+        |_| (0, 0),
+        || fs::create_dir(dir_path.as_path()),
+    )
+    .context("create_dir", dir_path)
+}
+
+/// Creates a directory along with its full directory path, doing nothing if it exists already.
+///
+/// # Parameters
+/// - `dir_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>, failing if a file is in the way
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::create_dir_all(&"create_dir_all_example/a/b")?;
+///         file_access::create_dir_all(&"create_dir_all_example/a/b")?; // already there
+///
+///         // Clean-up:
+///         file_access::delete(&"create_dir_all_example")?;
+///     })
+/// }
+/// ```
+pub fn create_dir_all<Path: AsPath + ?Sized>(dir_path: &Path) -> Result<()> {
+    metered(
+        "create_dir_all",
+        |_| (0, 0),
+        || fs::create_dir_all(dir_path.as_path()),
+    )
+    .context("create_dir_all", dir_path)
+}
+
+/// Creates the full directory path a file would go in, without creating the file, such as to hand the path over
+/// to a program that won't create it. Does nothing if the directory exists, or if the path has no parent.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::ensure_parent_exists(&"ensure_parent_example/logs/app.log")?;
+///         assert!(std::path::Path::new("ensure_parent_example/logs").is_dir());
+///
+///         // Clean-up:
+///         file_access::delete(&"ensure_parent_example")?;
+///     })
+/// }
+/// ```
+pub fn ensure_parent_exists<Path: AsPath + ?Sized>(file_path: &Path) -> Result<()> {
+    match file_path.as_path().parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => {
+            create_dir_all(parent).context("ensure_parent_exists", file_path)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Deletes a file, or a directory **recursively**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "absolute_or_relative_path/to_a_file/or_a_directory";
+///         let file_path: String = String::from(file_path);
+///
+///         file_access::write_string(&file_path, &"Hello, World!");
+///         file_access::delete(&file_path)?; // delete file
+///
+///         // Delete directory:
+///         file_access::delete(&"absolute_or_relative_path")?;
+///     })
+/// }
+/// ```
+pub fn delete<Path: AsPath + ?Sized>(file_path: &Path) -> Result<()> {
+    metered(
+        "delete",
+        |_| (0, 0),
+        || {
+            let path = path_of(file_path);
+
+            if path.is_file() {
+                return fs::remove_file(path);
+            }
+
+            if path.is_dir() {
+                return fs::remove_dir_all(path);
+            }
+
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                path.display().to_string(),
+            ));
+        },
+    )
+    .context("delete", file_path)
+}
+
+/// Copies the contents of a file and write it to a destination.
+/// The contents are streamed byte-for-byte, so binary and very large files copy in constant memory.
+/// This function will create the destination's full directory path if it doesn't exist,
+/// and will entirely replace the contents of the destination if it already exists.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let source: &str = "Cargo.toml";
+///         let source: String = String::from(source);
+///
+///         let destination: &str = "Cargo.toml.2";
+///         let destination: String = String::from(destination);
+///
+///         file_access::copy(&source, &destination)?;
+///
+///         // Delete file:
+///         file_access::delete(&destination);
+///     })
+/// }
+/// ```
+pub fn copy<From: AsPath + ?Sized, To: AsPath + ?Sized>(from: &From, to: &To) -> Result<()> {
+    let copied = metered(
+        "copy",
+        |copied: &u64| (*copied, *copied),
+        || {
+            if let Some(path) = path_of(to).parent() {
+                fs::create_dir_all(path)?;
+            }
+            return fs::copy(from.as_path(), to.as_path());
+        },
+    )
+    .map(|_| ());
+
+    return context_either(copied, "copy", from, to, |from| File::open(from).is_ok());
+}
+
+/// Copies a file like `copy`, then syncs the destination to disk and reads it back to compare it with the source,
+/// such as when copying onto a network mount or removable drive that may silently corrupt data.
+/// If they differ, the destination is removed and `ErrorKind::InvalidData` is returned.
+/// Note that reading back may be served from the OS cache rather than the device itself.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let source: &str = "Cargo.toml";
+///         let destination: &str = "copy_verified_example/Cargo.toml";
+///
+///         file_access::copy_verified(&source, &destination)?;
+///
+///         // Clean-up:
+///         file_access::delete(&"copy_verified_example")?;
+///     })
+/// }
+/// ```
+pub fn copy_verified<From: AsPath + ?Sized, To: AsPath + ?Sized>(
+    from: &From,
+    to: &To,
+) -> Result<()> {
+    copy(from, to)?;
+    File::open(to.as_path())
+        .and_then(|file| file.sync_all())
+        .context("copy_verified", to)?;
+    if !same_content(from, to)? {
+        _ = fs::remove_file(to.as_path());
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "the copy differs from the source",
+        ))
+        .context("copy_verified", to);
+    }
+    return Ok(());
+}
+
+/// Copies a file like `copy`, but only if the destination doesn't exist or was modified before the source,
+/// such as in incremental sync scripts; a destination that is as new as the source is left untouched, along with its timestamps.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`bool`>, whether the file was copied
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let destination: &str = "copy_if_newer_example/Cargo.toml";
+///
+///         assert!(file_access::copy_if_newer(&"Cargo.toml", &destination)?);
+///         assert!(!file_access::copy_if_newer(&"Cargo.toml", &destination)?);
+///
+///         // Clean-up:
+///         file_access::delete(&"copy_if_newer_example")?;
+///     })
+/// }
+/// ```
+pub fn copy_if_newer<From: AsPath + ?Sized, To: AsPath + ?Sized>(
+    from: &From,
+    to: &To,
+) -> Result<bool> {
+    if !from.as_path().exists() {
+        return Err(Error::from(ErrorKind::NotFound)).context("copy_if_newer", from);
+    }
+    if !is_newer_than(from, to)? {
+        return Ok(false);
+    }
+    copy(from, to)?;
+
+    return Ok(true);
+}
+
+/// Copies a file like `copy`, but only if the destination doesn't exist or its contents differ from the source's,
+/// such as in incremental sync scripts; a destination with the same contents is left untouched, along with its timestamps.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`bool`>, whether the file was copied
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let destination: &str = "copy_if_different_example/Cargo.toml";
+///
+///         assert!(file_access::copy_if_different(&"Cargo.toml", &destination)?);
+///         assert!(!file_access::copy_if_different(&"Cargo.toml", &destination)?);
+///
+///         // Clean-up:
+///         file_access::delete(&"copy_if_different_example")?;
+///     })
+/// }
+/// ```
+pub fn copy_if_different<From: AsPath + ?Sized, To: AsPath + ?Sized>(
+    from: &From,
+    to: &To,
+) -> Result<bool> {
+    if to.as_path().is_file() && same_content(from, to)? {
+        return Ok(false);
+    }
+    copy(from, to)?;
+
+    return Ok(true);
+}
+
+/// Copies a file to a destination together with the attributes selected by `preserve`,
+/// such as extended attributes, ACLs, SELinux contexts and (on macOS) file flags. The contents are copied byte-for-byte,
+/// along with the permissions. This function will create the destination's full directory path if it doesn't exist,
+/// and will entirely replace the contents of the destination if it already exists.
+///
+/// Attributes that cannot be carried over don't fail the copy; each of them is reported as a `PreserveWarning` instead.
+/// Failing to read the source's attributes, other than on a filesystem without support for them, is an error.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `preserve`: `Preserve` such as `Preserve::ALL` or `Preserve::XATTRS | Preserve::ACLS`
+///
+/// # Returns
+/// Result<`Vec<PreserveWarning>`>
+///
+/// # Examples
+/// ```
+/// use file_access::Preserve;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let source: &str = "Cargo.toml";
+///         let source: String = String::from(source);
+///
+///         let destination: &str = "backup_to/Cargo.toml";
+///         let destination: String = String::from(destination);
+///
+///         let warnings = file_access::copy_preserving(&source, &destination, Preserve::ALL)?;
+///         warnings.iter().for_each(|warning| println!("{}", warning));
+///
+///         // Clean-up:
+///         file_access::delete(&"backup_to")?; // ./backup_to/
+///     })
+/// }
+/// ```
+pub fn copy_preserving<From: AsPath + ?Sized, To: AsPath + ?Sized>(
+    from: &From,
+    to: &To,
+    preserve: Preserve,
+) -> Result<Vec<PreserveWarning>> {
+    let (source, dest) = (path_of(from), path_of(to));
+    if let Some(path) = dest.parent() {
+        fs::create_dir_all(path).context("copy_preserving", to)?;
+    }
+    let copied = fs::copy(&source, &dest);
+    context_either(copied, "copy_preserving", from, to, |from| {
+        File::open(from).is_ok()
+    })?;
+
+    return internal::sys::preserve(&source, &dest, preserve).context("copy_preserving", from);
+}
+
+/// Clones a file to a destination. On macOS this creates a copy-on-write clone via `clonefile(2)`,
+/// which is instantaneous on APFS; on other platforms, or when the volume doesn't support cloning,
+/// it falls back to a regular `copy`. This function will entirely replace the contents of the destination if it already exists.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let source: &str = "Cargo.toml";
+///         let source: String = String::from(source);
+///
+///         let destination: &str = "clone_to/Cargo.toml";
+///         let destination: String = String::from(destination);
+///
+///         file_access::clone(&source, &destination)?;
+///
+///         // Clean-up:
+///         file_access::delete(&"clone_to")?; // ./clone_to/
+///     })
+/// }
+/// ```
+pub fn clone<From: AsPath + ?Sized, To: AsPath + ?Sized>(from: &From, to: &To) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    {
+        let dest = path_of(to);
+        if let Some(path) = dest.parent() {
+            fs::create_dir_all(path).context("clone", to)?;
+        }
+        // clonefile(2) refuses to replace an existing file, so clone next to it first
+        let temp = temp_sibling(&dest);
+        if internal::sys::macos::clonefile(&path_of(from), &temp).is_ok() {
+            if let Err(err) = fs::rename(&temp, dest) {
+                _ = fs::remove_file(temp);
+                return Err(err).context("clone", to);
+            }
+            return Ok(());
+        }
+    }
+    return copy(from, to);
+}
+
+/// Streams a file through a transformation into a destination, such as to compress, encrypt or re-encode it.
+/// The closure is given a buffered reader of the source and a buffered writer, which writes to a temporary sibling
+/// of the destination that replaces it only if the closure succeeds. An existing destination keeps its permissions,
+/// and on Unix its owner and group where the process is allowed to set them. Both the new contents and the rename are
+/// synced to disk, so the replacement survives a power loss; `pipe_with` can skip the two fsyncs that costs,
+/// or the temporary sibling altogether.
+/// This function will create the destination's full directory path if it doesn't exist.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `transform`: **owned** `FnOnce(&mut BufReader<File>, &mut BufWriter<File>) -> std::io::Result<()>`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// use std::io::{BufRead, Write};
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let source: &str = "pipe_example.txt";
+///         let source: String = String::from(source);
+///
+///         let destination: &str = "pipe_example/upper.txt";
+///         let destination: String = String::from(destination);
+///
+///         file_access::write_lines(&source, &vec!["hello", "world"])?;
+///         file_access::pipe(&source, &destination, |reader, writer| {
+///             for line in reader.lines() {
+///                 writeln!(writer, "{}", line?.to_uppercase())?;
+///             }
+///             Ok(())
+///         })?;
+///         assert_eq!(file_access::read_lines(&destination)?, vec!["HELLO", "WORLD"]);
+///
+///         // Clean-up:
+///         file_access::delete(&source)?;
+///         file_access::delete(&"pipe_example")?;
+///     })
+/// }
+/// ```
+pub fn pipe<From: AsPath + ?Sized, To: AsPath + ?Sized>(
+    from: &From,
+    to: &To,
+    transform: impl FnOnce(&mut BufReader<File>, &mut BufWriter<File>) -> io::Result<()>,
+) -> Result<()> {
+    let options = PipeOptions::new().durable(true);
+    return options::pipe_as("pipe", from, to, options, transform);
+}
+
+/// Moves a file to a destination. This is an atomic `fs::rename` when both live on the same filesystem;
+/// across filesystems it falls back to copying the contents to the destination and then deleting the source.
+/// This function will create the destination's full directory path if it doesn't exist,
+/// and will entirely replace the contents of the destination if it already exists.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let source: &str = "file.1";
+///         let source: String = String::from(source);
+///
+///         let destination: &str = "file.2";
+///         let destination: String = String::from(destination);
+///
+///         file_access::write_string(&source, &"Hello, World!")?;
+///         file_access::rename(&source, &destination)?;
+///
+///         // Clean-up:
+///         file_access::delete(&destination)?;
+///     })
+/// }
+/// ```
+pub fn rename<From: AsPath + ?Sized, To: AsPath + ?Sized>(from: &From, to: &To) -> Result<()> {
+    if let Some(path) = path_of(to).parent() {
+        fs::create_dir_all(path).context("rename", to)?;
+    }
+    // across filesystems, the copy and delete it falls back to are recorded on their own
+    let renamed = metered(
+        "rename",
+        |_| (0, 0),
+        || fs::rename(from.as_path(), to.as_path()),
+    );
+    match renamed {
+        Err(x) if x.kind() == ErrorKind::CrossesDevices => {}
+        Ok(()) => return Ok(()),
+        result => {
+            return context_either(result, "rename", from, to, |from| {
+                from.symlink_metadata().is_ok()
+            })
+        }
+    }
+    copy(from, to)?;
+
+    return delete(from);
+}
+
+/// Queries metadata about the underlying file.
+///
+/// # Returns
+/// Result<`Metadata`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         let metadata: std::fs::Metadata = file_access::get_metadata(&file_path)?;
+///         println!("{:#?}", metadata);
+///     })
+/// }
+/// ```
+pub fn get_metadata<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Metadata> {
+    stat(file_path)
+}
+
+/// Queries metadata about a file or directory, following symlinks, without opening it:
+/// this works for files the process may stat but not open, and doesn't consume a file descriptor.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Metadata`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         let metadata: std::fs::Metadata = file_access::stat(&file_path)?;
+///         println!("{} bytes", metadata.len());
+///     })
+/// }
+/// ```
+pub fn stat<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Metadata> {
+    metered("stat", |_| (0, 0), || fs::metadata(file_path.as_path())).context("stat", file_path)
+}
+
+/// Like `stat`, but queries a symlink itself rather than what it points to.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Metadata`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         let metadata: std::fs::Metadata = file_access::lstat(&file_path)?;
+///         assert!(!metadata.file_type().is_symlink());
+///     })
+/// }
+/// ```
+pub fn lstat<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Metadata> {
+    fs::symlink_metadata(file_path.as_path()).context("lstat", file_path)
+}
+
+/// Gets the SELinux security context of a file or directory, such as `system_u:object_r:httpd_sys_content_t:s0`.
+/// Returns `None` when the file has no context, e.g. on systems without SELinux. **Linux only**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Option<String>`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         # #[cfg(target_os = "linux")]
+///         if let Some(context) = file_access::selinux_context(&file_path)? {
+///             println!("{}", context);
+///         }
+///     })
+/// }
+/// ```
+#[cfg(target_os = "linux")]
+pub fn selinux_context<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Option<String>> {
+    internal::sys::linux::selinux_context(&path_of(file_path)).context("selinux_context", file_path)
+}
+
+/// Sets the SELinux security context of a file or directory. **Linux only**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `context`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```no_run
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "/var/www/html/index.html";
+///         let file_path: String = String::from(file_path);
+///
+///         # #[cfg(target_os = "linux")]
+///         file_access::set_selinux_context(&file_path, &"system_u:object_r:httpd_sys_content_t:s0")?;
+///     })
+/// }
+/// ```
+#[cfg(target_os = "linux")]
+pub fn set_selinux_context<Path: AsPath + ?Sized, Context: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    context: &Context,
+) -> Result<()> {
+    internal::sys::linux::set_selinux_context(&path_of(file_path), context.as_ref())
+        .context("set_selinux_context", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::Result,
+        time::{Duration, SystemTime},
+    };
+
+    // cargo test -- --show-output --test-threads=1
+    // cargo test <TESTNAME> --show-output
+
+    #[test]
+    fn read_string() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "Cargo.toml";
+
+            // Action
+            let text = super::read_string(&file)?;
+            println!("{text}");
+
+            // Assert
+            assert_ne!(text.len(), 0);
+        })
+    }
+
+    #[test]
+    fn read_lines() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "Cargo.toml";
+
+            // Action
+            let lines = super::read_lines(&file)?;
+            for line in &lines {
+                println!("{line}");
+            }
+
+            // Assert
+            assert_ne!(lines.len(), 0);
+        })
+    }
+
+    #[test]
+    fn lines() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "lines/file_access.txt";
+            super::write_string(&file, &"1\r\n2\n\n3")?;
+
+            // Action
+            let lines = super::lines(&file)?.collect::<crate::Result<Vec<_>>>()?;
+
+            // Assert
+            assert_eq!(lines, vec!["1", "2", "", "3"]);
+            assert!(super::lines(&"lines/missing.txt").is_err());
+
+            // Clean-up
+            super::delete(&"lines")?;
+        })
+    }
+
+    #[test]
+    fn head_and_tail() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "head_and_tail/file_access.txt";
+            let text: Vec<String> = (0..5_000).map(|i| format!("line {i}")).collect();
+            super::write_lines(&file, &text)?;
+            super::write_string(&"head_and_tail/short.txt", &"a\r\nb")?;
+
+            // Action
+            let head = super::head(&file, 3)?;
+            let tail = super::tail(&file, 3)?;
+
+            // Assert
+            assert_eq!(head, ["line 0", "line 1", "line 2"]);
+            assert_eq!(tail, ["line 4997", "line 4998", "line 4999"]);
+            assert_eq!(super::tail(&file, 5_000)?, text);
+            assert_eq!(super::tail(&"head_and_tail/short.txt", 10)?, ["a", "b"]);
+            assert_eq!(super::head(&"head_and_tail/short.txt", 10)?, ["a", "b"]);
+            assert!(super::tail(&file, 0)?.is_empty());
+            assert!(super::tail(&"head_and_tail/missing.txt", 1).is_err());
+
+            // Clean-up
+            super::delete(&"head_and_tail")?;
+        })
+    }
+
+    #[test]
+    fn read_lines_range() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "read_lines_range/file_access.txt";
+            let text: Vec<String> = (0..1_000).map(|i| format!("line {i}")).collect();
+            super::write_lines(&file, &text)?;
+
+            // Action
+            let page = super::read_lines_range(&file, 500..503)?;
+
+            // Assert
+            assert_eq!(page, ["line 500", "line 501", "line 502"]);
+            assert_eq!(
+                super::read_lines_range(&file, 998..2_000)?,
+                ["line 998", "line 999"]
+            );
+            assert!(super::read_lines_range(&file, 2_000..3_000)?.is_empty());
+            #[allow(clippy::reversed_empty_ranges)]
+            let reversed = super::read_lines_range(&file, 3..1)?;
+            assert!(reversed.is_empty());
+
+            // Clean-up
+            super::delete(&"read_lines_range")?;
+        })
+    }
+
+    #[test]
+    fn edit_lines() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "edit_lines/file_access.txt";
+            super::write_string(&file, &"a\nb\nc\n")?;
+
+            // Action
+            super::edit_lines(&file, |lines| {
+                lines[1] = "B".to_string();
+                lines.push("d".to_string());
+            })?;
+
+            // Assert
+            assert_eq!(super::read_string(&file)?, "a\nB\nc\nd\n");
+            assert!(super::edit_lines(&"edit_lines/missing.txt", |_| {}).is_err());
+            assert!(super::stat(&"edit_lines/missing.txt").is_err());
+
+            // Clean-up
+            super::delete(&"edit_lines")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn edit_lines_keeps_permissions() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        Ok({
+            // Arrange
+            let file = "edit_lines_mode/script.sh";
+            super::write_string(&file, &"#!/bin/sh\necho a\n")?;
+            fs::set_permissions(file, fs::Permissions::from_mode(0o700))?;
+
+            // Action
+            super::edit_lines(&file, |lines| lines[1] = "echo b".to_string())?;
+
+            // Assert
+            assert_eq!(super::read_string(&file)?, "#!/bin/sh\necho b\n");
+            assert_eq!(fs::metadata(file)?.permissions().mode() & 0o7777, 0o700);
+
+            // Clean-up
+            super::delete(&"edit_lines_mode")?;
+        })
+    }
+
+    #[test]
+    fn write_string() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "write_string/file_access.txt";
+            let text = "Hello, World!";
+
+            // Action
+            super::write_string(&file, &text)?;
+
+            // Assert
+            assert_eq!(super::read_string(&file)?, text);
+
+            // Clean-up
+            super::delete(&"write_string")?;
+        })
+    }
+
+    #[test]
+    fn write_lines() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "write_lines/file_access.txt";
+            let lines = "Hello, World!"
+                .split_whitespace()
+                .map(ToString::to_string)
+                .collect();
+
+            // Action
+            super::write_lines(&file, &lines)?;
+
+            // Assert
+            assert_eq!(super::read_lines(&file)?, lines);
+
+            // Clean-up
+            super::delete(&"write_lines")?;
+        })
+    }
+
+    #[test]
+    fn write_string_anon_then_link() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "write_anon/file_access.txt";
+            super::write_string(&file, &"stale")?;
+            let text = "Hello, World!";
+
+            // Action
+            super::write_string_anon_then_link(&file, &text)?;
+
+            // Assert
+            assert_eq!(super::read_string(&file)?, text);
+            assert_eq!(
+                fs::read_dir("write_anon")?.count(),
+                1,
+                "no temporary file should be left behind"
+            );
+
+            // Clean-up
+            super::delete(&"write_anon")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn write_string_anon_then_link_keeps_permissions() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        Ok({
+            // Arrange
+            let secret = "write_anon_mode/secret.txt";
+            super::write_string(&secret, &"old")?;
+            fs::set_permissions(secret, fs::Permissions::from_mode(0o600))?;
+
+            // Action
+            super::write_string_anon_then_link(&secret, &"new")?;
+
+            // Assert
+            assert_eq!(super::read_string(&secret)?, "new");
+            assert_eq!(fs::metadata(secret)?.permissions().mode() & 0o7777, 0o600);
+
+            // Clean-up
+            super::delete(&"write_anon_mode")?;
+        })
+    }
+
+    #[test]
+    fn write_string_atomic() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "write_atomic/file_access.txt";
+            super::write_string(&file, &"old")?;
+
+            // Action
+            super::write_string_atomic(&file, &"new", false)?;
+            super::write_string_atomic(&"write_atomic/a/b.txt", &"synced", true)?;
+
+            // Assert
+            assert_eq!(super::read_string(&file)?, "new");
+            assert_eq!(super::read_string(&"write_atomic/a/b.txt")?, "synced");
+            assert_eq!(
+                fs::read_dir("write_atomic")?.count(),
+                2,
+                "no temporary files left"
+            );
+
+            // Clean-up
+            super::delete(&"write_atomic")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn write_string_atomic_keeps_permissions() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        Ok({
+            // Arrange
+            let secret = "write_atomic_mode/secret.txt";
+            let script = "write_atomic_mode/script.sh";
+            super::write_string(&secret, &"old")?;
+            super::write_string(&script, &"old")?;
+            fs::set_permissions(secret, fs::Permissions::from_mode(0o600))?;
+            fs::set_permissions(script, fs::Permissions::from_mode(0o755))?;
+
+            // Action
+            super::write_string_atomic(&secret, &"new", false)?;
+            super::write_string_atomic(&script, &"new", true)?;
+
+            // Assert
+            assert_eq!(super::read_string(&secret)?, "new");
+            assert_eq!(fs::metadata(secret)?.permissions().mode() & 0o7777, 0o600);
+            assert_eq!(fs::metadata(script)?.permissions().mode() & 0o7777, 0o755);
+
+            // Clean-up
+            super::delete(&"write_atomic_mode")?;
+        })
+    }
+
+    #[test]
+    fn append_string() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "append_string/file_access.txt";
+            let text = "Hello, World!";
+            super::write_string(&file, &text)?;
+
+            // Action
+            super::append_string(&file, &text)?;
+
+            // Assert
+            assert_eq!(super::read_string(&file)?, format!("{text}{text}"));
+
+            // Clean-up
+            super::delete(&"append_string")?;
+        })
+    }
+
+    #[test]
+    fn append_lines() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "append_lines/file_access.txt";
+            let lines1 = vec!["1", "2"]; // .to_vec_string();
+            super::write_lines(&file, &lines1)?;
+
+            // Action
+            let lines2 = vec!["3", "4"]; //.to_vec_string();
+            super::append_lines(&file, &lines2)?;
+
+            // Assert
+            assert_eq!(super::read_lines(&file)?, vec!["1", "2", "3", "4"]); // .to_vec_string());
+
+            // Clean-up
+            super::delete(&"append_lines")?;
+        })
+    }
+
+    #[test]
+    fn append_line() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "append_line/file_access.txt";
+            super::write_string(&file, &"1")?;
+            super::write_string(&"append_line/newline.txt", &"1\n")?;
+
+            // Action
+            super::append_line(&file, &"2")?;
+            super::append_line(&"append_line/newline.txt", &"2")?;
+            super::append_line(&"append_line/new.txt", &"1")?;
+
             // Assert
-            assert_ne!(text.len(), 0);
+            assert_eq!(super::read_string(&file)?, "1\n2");
+            assert_eq!(super::read_string(&"append_line/newline.txt")?, "1\n2");
+            assert_eq!(super::read_string(&"append_line/new.txt")?, "1");
+
+            // Clean-up
+            super::delete(&"append_line")?;
         })
     }
 
     #[test]
-    fn read_lines() -> Result<()> {
+    fn prepend() -> Result<()> {
         Ok({
             // Arrange
-            let file = "Cargo.toml";
+            let file = "prepend/file_access.txt";
+            super::write_lines(&file, &vec!["3", "4"])?;
 
             // Action
-            let lines = super::read_lines(&file)?;
-            for line in &lines {
-                println!("{line}");
-            }
+            super::prepend_lines(&file, &vec!["1", "2"])?;
+            super::prepend_string(&file, &"0\n")?;
+            super::prepend_lines(&"prepend/new.txt", &vec!["a", "b"])?;
 
             // Assert
-            assert_ne!(lines.len(), 0);
+            assert_eq!(super::read_lines(&file)?, vec!["0", "1", "2", "3", "4"]);
+            assert_eq!(super::read_string(&"prepend/new.txt")?, "a\nb");
+
+            // Clean-up
+            super::delete(&"prepend")?;
         })
     }
 
     #[test]
-    fn write_string() -> Result<()> {
+    #[cfg(unix)]
+    fn prepend_keeps_permissions() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
         Ok({
             // Arrange
-            let file = "write_string/file_access.txt";
-            let text = "Hello, World!";
+            let file = "prepend_mode/script.sh";
+            super::write_string(&file, &"echo a")?;
+            fs::set_permissions(file, fs::Permissions::from_mode(0o700))?;
 
             // Action
-            super::write_string(&file, &text)?;
+            super::prepend_lines(&file, &vec!["#!/bin/sh"])?;
+            super::prepend_string(&file, &"")?;
 
             // Assert
-            assert_eq!(super::read_string(&file)?, text);
+            assert_eq!(super::read_string(&file)?, "#!/bin/sh\necho a");
+            assert_eq!(fs::metadata(file)?.permissions().mode() & 0o7777, 0o700);
 
             // Clean-up
-            super::delete(&"write_string")?;
+            super::delete(&"prepend_mode")?;
         })
     }
 
     #[test]
-    fn write_lines() -> Result<()> {
+    fn append_lines_after_newline() -> Result<()> {
         Ok({
             // Arrange
-            let file = "write_lines/file_access.txt";
-            let lines = "Hello, World!"
-                .split_whitespace()
-                .map(ToString::to_string)
-                .collect();
+            let file = "append_lines_after_newline/file_access.txt";
+            super::write_string(&file, &"1\n")?;
 
             // Action
-            super::write_lines(&file, &lines)?;
+            super::append_lines(&file, &vec!["2", "3"])?;
+            super::append_lines(&file, &vec!["4"])?;
 
             // Assert
-            assert_eq!(super::read_lines(&file)?, lines);
+            assert_eq!(super::read_string(&file)?, "1\n2\n3\n4");
 
             // Clean-up
-            super::delete(&"write_lines")?;
+            super::delete(&"append_lines_after_newline")?;
         })
     }
 
     #[test]
-    fn append_string() -> Result<()> {
+    fn bytes() -> Result<()> {
         Ok({
             // Arrange
-            let file = "append_string/file_access.txt";
-            let text = "Hello, World!";
-            super::write_string(&file, &text)?;
+            let file = "bytes/file_access.bin";
+            let bytes = [0x00, 0xFF, 0xFE, 0x80];
 
             // Action
-            super::append_string(&file, &text)?;
+            super::write_bytes(&file, &bytes)?;
+            super::append_bytes(&file, &bytes[..2])?;
+            super::append_bytes(&"bytes/new/file_access.bin", &bytes)?;
 
             // Assert
-            assert_eq!(super::read_string(&file)?, format!("{text}{text}"));
+            assert_eq!(
+                super::read_bytes(&file)?,
+                [0x00, 0xFF, 0xFE, 0x80, 0x00, 0xFF]
+            );
+            assert_eq!(super::read_bytes(&"bytes/new/file_access.bin")?, bytes);
 
             // Clean-up
-            super::delete(&"append_string")?;
+            super::delete(&"bytes")?;
         })
     }
 
     #[test]
-    fn append_lines() -> Result<()> {
+    fn create_dir() -> Result<()> {
         Ok({
             // Arrange
-            let file = "append_lines/file_access.txt";
-            let lines1 = vec!["1", "2"]; // .to_vec_string();
-            super::write_lines(&file, &lines1)?;
+            let dir = "create_dir/a/b";
 
             // Action
-            let lines2 = vec!["3", "4"]; //.to_vec_string();
-            super::append_lines(&file, &lines2)?;
+            let orphan = super::create_dir(&dir);
+            super::create_dir_all(&dir)?;
+            super::create_dir_all(&dir)?;
+            let existing = super::create_dir(&dir);
+            super::ensure_parent_exists(&"create_dir/c/d/file_access.txt")?;
+            super::ensure_parent_exists(&"file_access.txt")?;
 
             // Assert
-            assert_eq!(super::read_lines(&file)?, vec!["1", "2", "3", "4"]); // .to_vec_string());
+            assert_eq!(orphan.unwrap_err().kind(), ErrorKind::NotFound);
+            assert_eq!(existing.unwrap_err().kind(), ErrorKind::AlreadyExists);
+            assert!(path_of(&dir).is_dir());
+            assert!(path_of(&"create_dir/c/d").is_dir());
+            assert!(!path_of(&"create_dir/c/d/file_access.txt").exists());
+            assert!(!path_of(&"file_access.txt").exists());
 
             // Clean-up
-            super::delete(&"append_lines")?;
+            super::delete(&"create_dir")?;
         })
     }
 
@@ -542,6 +2333,276 @@ mod tests {
         })
     }
 
+    #[test]
+    fn copy_verified() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "copy_verified/from.bin";
+            let to = "copy_verified/to/to.bin";
+            let bytes: Vec<u8> = (0..=255).cycle().take(200_000).collect();
+            super::write_bytes(&from, &bytes)?;
+
+            // Action
+            super::copy_verified(&from, &to)?;
+            let missing = super::copy_verified(&"copy_verified/missing.bin", &to);
+
+            // Assert
+            assert_eq!(super::read_bytes(&to)?, bytes);
+            assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+
+            // Clean-up
+            super::delete(&"copy_verified")?;
+        })
+    }
+
+    #[test]
+    fn copy_if_newer() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "copy_if_newer/from.txt";
+            let to = "copy_if_newer/to.txt";
+            super::write_string(&from, &"new")?;
+            super::write_string(&to, &"old")?;
+            let past = SystemTime::now() - Duration::from_secs(60);
+            set_modified(&to, past)?;
+
+            // Action
+            let copied = super::copy_if_newer(&from, &to)?;
+            set_modified(&from, past)?;
+            let skipped = super::copy_if_newer(&from, &to)?;
+            let missing = super::copy_if_newer(&"copy_if_newer/missing.txt", &to);
+
+            // Assert
+            assert!(copied && !skipped);
+            assert_eq!(super::read_string(&to)?, "new");
+            assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+
+            // Clean-up
+            super::delete(&"copy_if_newer")?;
+        })
+    }
+
+    #[test]
+    fn copy_if_different() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "copy_if_different/from.txt";
+            let to = "copy_if_different/to.txt";
+            super::write_string(&from, &"Hello, World!")?;
+            super::write_string(&to, &"Hello, World!")?;
+            let past = SystemTime::now() - Duration::from_secs(60);
+            set_modified(&to, past)?;
+
+            // Action
+            let skipped = super::copy_if_different(&from, &to)?;
+            let unchanged = modified(&to)?;
+            super::write_string(&from, &"Goodbye, World!")?;
+            let copied = super::copy_if_different(&from, &to)?;
+
+            // Assert
+            assert!(!skipped && copied);
+            assert_eq!(unchanged, past);
+            assert_eq!(super::read_string(&to)?, "Goodbye, World!");
+
+            // Clean-up
+            super::delete(&"copy_if_different")?;
+        })
+    }
+
+    #[test]
+    fn append_string_sync() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "append_string_sync/shared.log";
+            let line = |worker: usize| format!("{worker}:{}\n", "x".repeat(64 * 1024));
+
+            // Action
+            std::thread::scope(|scope| {
+                let workers: Vec<_> = (0..8)
+                    .map(|worker| {
+                        scope.spawn(move || super::append_string_sync(&file, &line(worker), true))
+                    })
+                    .collect();
+                workers
+                    .into_iter()
+                    .try_for_each(|worker| worker.join().unwrap())
+            })?;
+
+            // Assert
+            let mut lines: Vec<String> = super::read_lines(&file)?;
+            lines.sort();
+            let expected: Vec<String> = (0..8)
+                .map(|worker| line(worker).trim_end().to_string())
+                .collect();
+            assert_eq!(lines, expected);
+
+            // Clean-up
+            super::delete(&"append_string_sync")?;
+        })
+    }
+
+    #[test]
+    fn copy_binary() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "copy_binary/from.bin";
+            let to = "copy_binary/to/to.bin";
+            let bytes: Vec<u8> = (0..=255).cycle().take(100_000).collect();
+            super::write_bytes(&from, &bytes)?;
+
+            // Action
+            super::copy(&from, &to)?;
+
+            // Assert
+            assert_eq!(super::read_bytes(&to)?, bytes);
+
+            // Clean-up
+            super::delete(&"copy_binary")?;
+        })
+    }
+
+    #[test]
+    fn pipe() -> Result<()> {
+        Ok({
+            use std::io::BufRead;
+
+            // Arrange
+            let from = "pipe_from/file_access.txt";
+            let to = "pipe_to/file_access.txt";
+            let failed = "pipe_to/failed.txt";
+            super::write_lines(&from, &vec!["hello", "world"])?;
+
+            // Action
+            super::pipe(&from, &to, |reader, writer| {
+                for line in reader.lines() {
+                    writeln!(writer, "{}", line?.len())?;
+                }
+                Ok(())
+            })?;
+            let error = super::pipe(&from, &failed, |_, writer| {
+                writer.write_all(b"partial")?;
+                Err(io::Error::other("the transformation failed"))
+            });
+
+            // Assert
+            assert_eq!(super::read_lines(&to)?, vec!["5", "5"]);
+            assert!(error.is_err());
+            assert!(!path_of(&failed).exists(), "{failed} should not exist");
+            assert_eq!(
+                fs::read_dir("pipe_to")?.count(),
+                1,
+                "no temporary file should be left"
+            );
+
+            // Clean-up
+            super::delete(&"pipe_from")?;
+            super::delete(&"pipe_to")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn pipe_keeps_permissions() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        Ok({
+            // Arrange
+            let from = "pipe_mode/from.txt";
+            let to = "pipe_mode/key.pem";
+            super::write_string(&from, &"new")?;
+            super::write_string(&to, &"old")?;
+            fs::set_permissions(to, fs::Permissions::from_mode(0o600))?;
+
+            // Action
+            super::pipe(&from, &to, |reader, writer| {
+                io::copy(reader, writer).map(|_| ())
+            })?;
+
+            // Assert
+            assert_eq!(super::read_string(&to)?, "new");
+            assert_eq!(fs::metadata(to)?.permissions().mode() & 0o7777, 0o600);
+
+            // Clean-up
+            super::delete(&"pipe_mode")?;
+        })
+    }
+
+    #[test]
+    fn copy_preserving() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "copy_preserving_from/file_access.txt";
+            let to = "copy_preserving_to/file_access.txt";
+            super::write_string(&from, &"Hello, World!")?;
+
+            // Action
+            let warnings = super::copy_preserving(&from, &to, Preserve::ALL)?;
+
+            // Assert
+            assert_eq!(
+                super::read_string(&from)?,
+                super::read_string(&to)?,
+                "{from} and {to} should contain the same text"
+            );
+            assert!(warnings.is_empty(), "{warnings:?}");
+
+            // Clean-up
+            super::delete(&"copy_preserving_from")?;
+            super::delete(&"copy_preserving_to")?;
+        })
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn copy_preserving_xattrs() -> Result<()> {
+        use internal::sys::linux::*;
+        use std::ffi::CString;
+
+        Ok({
+            // Arrange
+            let from = "copy_xattrs_from.txt";
+            let to = "copy_xattrs_to.txt";
+            let name = CString::new("user.file_access").unwrap();
+            super::write_string(&from, &"Hello, World!")?;
+            set_xattr(&path_of(&from), &name, b"preserved")?;
+
+            // Action
+            let warnings = super::copy_preserving(&from, &to, Preserve::XATTRS)?;
+
+            // Assert
+            assert!(warnings.is_empty(), "{warnings:?}");
+            assert_eq!(get_xattr(&path_of(&to), &name)?, b"preserved");
+
+            // Clean-up
+            super::delete(&from)?;
+            super::delete(&to)?;
+        })
+    }
+
+    #[test]
+    fn clone() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "clone_from/file_access.txt";
+            let to = "clone_to/file_access.txt";
+            super::write_string(&from, &"Hello, World!")?;
+
+            // Action
+            super::clone(&from, &to)?;
+
+            // Assert
+            assert_eq!(
+                super::read_string(&from)?,
+                super::read_string(&to)?,
+                "{from} and {to} should contain the same text"
+            );
+
+            // Clean-up
+            super::delete(&"clone_from")?;
+            super::delete(&"clone_to")?;
+        })
+    }
+
     #[test]
     fn rename() -> Result<()> {
         Ok({
@@ -567,4 +2628,111 @@ mod tests {
             super::delete(&"rename_to")?;
         })
     }
+
+    #[test]
+    fn two_path_errors() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "two_path_errors/from.txt";
+            let blocked = "two_path_errors/from.txt/to.txt";
+            let missing = "two_path_errors/missing.txt";
+            let to = "two_path_errors/to.txt";
+            super::write_string(&from, &"Hello, World!")?;
+
+            // Action
+            let errors = [
+                super::copy(&from, &blocked).err().unwrap(),
+                super::copy(&missing, &to).err().unwrap(),
+                super::rename(&from, &blocked).err().unwrap(),
+                super::rename(&missing, &to).err().unwrap(),
+                super::pipe(&from, &blocked, |_, _| Ok(())).err().unwrap(),
+                super::pipe(&missing, &to, |_, _| Ok(())).err().unwrap(),
+            ];
+
+            // Assert
+            let paths = errors.map(|error| error.path.unwrap());
+            let expected = [blocked, missing, blocked, missing, blocked, missing];
+            assert_eq!(paths, expected.map(PathBuf::from));
+
+            // Clean-up
+            super::delete(&"two_path_errors")?;
+        })
+    }
+
+    #[test]
+    fn rename_dir() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "rename_dir_from";
+            let to = "rename_dir_to/moved";
+            super::write_string(&format!("{from}/a/file_access.txt"), &"Hello, World!")?;
+
+            // Action
+            super::rename(&from, &to)?;
+
+            // Assert
+            assert!(!path_of(&from).exists(), "{from} should no longer exist");
+            assert_eq!(
+                super::read_string(&format!("{to}/a/file_access.txt"))?,
+                "Hello, World!"
+            );
+
+            // Clean-up
+            super::delete(&"rename_dir_to")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn stat() -> Result<()> {
+        Ok({
+            use std::os::unix::fs::{symlink, PermissionsExt};
+
+            // Arrange
+            let file = "stat/unreadable.txt";
+            let link = "stat/link.txt";
+            super::write_string(&file, &"Hello, World!")?;
+            fs::set_permissions(file, fs::Permissions::from_mode(0o000))?;
+            symlink("unreadable.txt", link)?;
+
+            // Action
+            let metadata = super::stat(&link)?;
+            let link_metadata = super::lstat(&link)?;
+
+            // Assert
+            assert_eq!(
+                metadata.len(),
+                13,
+                "{file} should be stat-able without read access"
+            );
+            assert!(link_metadata.file_type().is_symlink());
+
+            // Clean-up
+            super::delete(&"stat")?;
+        })
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn selinux_context() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "selinux_context/file_access.txt";
+            super::write_string(&file, &"Hello, World!")?;
+
+            // Action
+            let context = super::selinux_context(&file)?;
+
+            // Assert
+            if let Some(context) = context {
+                assert!(
+                    !context.ends_with('\0'),
+                    "{context} should not be NUL-terminated"
+                );
+            }
+
+            // Clean-up
+            super::delete(&"selinux_context")?;
+        })
+    }
 }
@@ -0,0 +1,119 @@
+use crate::*;
+use std::fs::DirEntry;
+
+/// Which entries `list_dir` returns, and how their paths are written.
+///
+/// # Examples
+/// ```
+/// use file_access::ListOptions;
+///
+/// let options = ListOptions {
+///     hidden: true,
+///     ..ListOptions::default()
+/// };
+/// assert!(!options.absolute);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ListOptions {
+    /// Whether to include hidden entries: names starting with a `.`, or on Windows, entries with the hidden attribute.
+    pub hidden: bool,
+    /// Whether to return absolute paths, rather than paths joined onto the directory as given.
+    pub absolute: bool,
+}
+
+// Tells whether a directory entry is hidden on this platform
+fn is_hidden(entry: &DirEntry) -> bool {
+    if entry.file_name().as_encoded_bytes().starts_with(b".") {
+        return true;
+    }
+    #[cfg(windows)]
+    if let Ok(metadata) = entry.metadata() {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        return metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0;
+    }
+    return false;
+}
+
+/// Lists the entries (files, directories and links) of a directory, sorted by path, not recursively.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `options`: `ListOptions`, such as `ListOptions::default()` for the visible entries, with relative paths
+///
+/// # Returns
+/// Result<`Vec<FilePath>`>
+///
+/// # Examples
+/// ```
+/// use file_access::ListOptions;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let entries = file_access::list_dir(&"src", ListOptions::default())?;
+///         for entry in entries {
+///             println!("{}", entry.as_ref());
+///         }
+///     })
+/// }
+/// ```
+pub fn list_dir<Path: AsPath + ?Sized>(
+    file_path: &Path,
+    options: ListOptions,
+) -> Result<Vec<FilePath>> {
+    let dir = match options.absolute {
+        true => fs::canonicalize(file_path.as_path()).context("list_dir", file_path)?,
+        false => path_of(file_path),
+    };
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(&dir).context("list_dir", file_path)? {
+        let entry = entry.context("list_dir", file_path)?;
+        if !options.hidden && is_hidden(&entry) {
+            continue;
+        }
+        entries.push(FilePath::from(dir.join(entry.file_name())));
+    }
+    entries.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+
+    return Ok(entries);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn list_dir() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"list_dir/b.txt", &"b")?;
+            write_string(&"list_dir/a/c.txt", &"c")?;
+            write_string(&"list_dir/.hidden", &"")?;
+
+            // Action
+            let visible = super::list_dir(&"list_dir", ListOptions::default())?;
+            let all = super::list_dir(
+                &"list_dir",
+                ListOptions {
+                    hidden: true,
+                    absolute: true,
+                },
+            )?;
+
+            // Assert
+            let visible: Vec<&str> = visible.iter().map(|entry| entry.as_ref()).collect();
+            let expected: Vec<String> = ["a", "b.txt"]
+                .iter()
+                .map(|name| path_of("list_dir").join(name).display().to_string())
+                .collect();
+            assert_eq!(visible, expected);
+            assert_eq!(all.len(), 3);
+            assert!(all.iter().all(|entry| path_of(entry).is_absolute()));
+            assert!(all[0].as_ref().ends_with(".hidden"));
+
+            // Clean-up
+            delete(&"list_dir")?;
+        })
+    }
+}
@@ -0,0 +1,289 @@
+use crate::*;
+use std::{
+    fs::TryLockError,
+    path::Path as StdPath,
+    thread,
+    time::{Duration, Instant},
+};
+
+// How long to wait between attempts to take a lock before a timeout; it doubles up to the maximum
+const POLL: Duration = Duration::from_millis(5);
+const MAX_POLL: Duration = Duration::from_millis(100);
+
+/// An advisory lock held on a file, as taken by `lock_exclusive` or `lock_shared`, which is released when this guard is dropped.
+/// Advisory means the lock only keeps out others that lock the file too; on Windows, where locks are enforced on the
+/// locked file's contents, read and write it through `file` while holding the lock.
+#[derive(Debug)]
+pub struct FileLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl FileLock {
+    /// The path of the locked file.
+    pub fn path(&self) -> &StdPath {
+        &self.path
+    }
+
+    /// The handle the lock is held through, open for reading and (if permitted) writing.
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        _ = self.file.unlock();
+    }
+}
+
+// Opens a file to be locked, creating it (and its full directory path) if it doesn't exist
+fn open_for_lock(path: &StdPath) -> io::Result<File> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let open = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path);
+    match open {
+        Err(x) if x.kind() == ErrorKind::PermissionDenied => File::open(path),
+        open => open,
+    }
+}
+
+// Takes a lock, waiting for it if `wait` is `None`, for up to `wait` otherwise, returning `None` if it couldn't
+fn acquire(path: &StdPath, shared: bool, wait: Option<Duration>) -> io::Result<Option<FileLock>> {
+    let file = open_for_lock(path)?;
+    let lock = |file: &File| -> io::Result<bool> {
+        let result = match (shared, wait) {
+            (true, None) => return file.lock_shared().map(|_| true),
+            (false, None) => return file.lock().map(|_| true),
+            (true, Some(_)) => file.try_lock_shared(),
+            (false, Some(_)) => file.try_lock(),
+        };
+        match result {
+            Ok(()) => Ok(true),
+            Err(TryLockError::WouldBlock) => Ok(false),
+            Err(TryLockError::Error(x)) => Err(x),
+        }
+    };
+    let deadline = Instant::now() + wait.unwrap_or_default();
+    let mut poll = POLL;
+    while !lock(&file)? {
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        thread::sleep(poll.min(deadline - now));
+        poll = (poll * 2).min(MAX_POLL);
+    }
+    return Ok(Some(FileLock {
+        file,
+        path: path.to_path_buf(),
+    }));
+}
+
+/// Takes an exclusive advisory lock on a file, such as a shared state file, waiting until no other process or handle
+/// holds a lock on it; `flock` on Unix, `LockFileEx` on Windows. The file is created (with its full directory path)
+/// if it doesn't exist, and the lock is released when the returned guard is dropped.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`FileLock`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "lock_exclusive_example/state.json";
+///
+///         let lock = file_access::lock_exclusive(&file_path)?;
+///         assert!(file_access::try_lock_shared(&file_path)?.is_none());
+///         drop(lock);
+///
+///         // Clean-up:
+///         file_access::delete(&"lock_exclusive_example")?;
+///     })
+/// }
+/// ```
+pub fn lock_exclusive<Path: AsPath + ?Sized>(file_path: &Path) -> Result<FileLock> {
+    acquire(file_path.as_path(), false, None)
+        .map(|lock| lock.expect("waited for the lock"))
+        .context("lock_exclusive", file_path)
+}
+
+/// Takes a shared advisory lock on a file, waiting until no other process or handle holds an exclusive lock on it;
+/// several shared locks can be held at once, such as by readers. The file is created (with its full directory path)
+/// if it doesn't exist, and the lock is released when the returned guard is dropped.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`FileLock`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "lock_shared_example/state.json";
+///
+///         let reader = file_access::lock_shared(&file_path)?;
+///         let another = file_access::lock_shared(&file_path)?;
+///         assert!(file_access::try_lock_exclusive(&file_path)?.is_none());
+///
+///         // Clean-up:
+///         drop((reader, another));
+///         file_access::delete(&"lock_shared_example")?;
+///     })
+/// }
+/// ```
+pub fn lock_shared<Path: AsPath + ?Sized>(file_path: &Path) -> Result<FileLock> {
+    acquire(file_path.as_path(), true, None)
+        .map(|lock| lock.expect("waited for the lock"))
+        .context("lock_shared", file_path)
+}
+
+/// Takes an exclusive advisory lock on a file like `lock_exclusive`, without waiting if it's held.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Option<FileLock>`>, `None` if another lock is held
+pub fn try_lock_exclusive<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Option<FileLock>> {
+    acquire(file_path.as_path(), false, Some(Duration::ZERO))
+        .context("try_lock_exclusive", file_path)
+}
+
+/// Takes a shared advisory lock on a file like `lock_shared`, without waiting if an exclusive lock is held.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Option<FileLock>`>, `None` if an exclusive lock is held
+pub fn try_lock_shared<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Option<FileLock>> {
+    acquire(file_path.as_path(), true, Some(Duration::ZERO)).context("try_lock_shared", file_path)
+}
+
+/// Takes an exclusive advisory lock on a file like `lock_exclusive`, waiting for at most `timeout`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `timeout`: **owned** `Duration`
+///
+/// # Returns
+/// Result<`Option<FileLock>`>, `None` if the lock couldn't be taken in time
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "lock_exclusive_timeout_example/state.json";
+///
+///         let lock = file_access::lock_exclusive(&file_path)?;
+///         let timeout = Duration::from_millis(50);
+///         assert!(file_access::lock_exclusive_timeout(&file_path, timeout)?.is_none());
+///
+///         // Clean-up:
+///         drop(lock);
+///         file_access::delete(&"lock_exclusive_timeout_example")?;
+///     })
+/// }
+/// ```
+pub fn lock_exclusive_timeout<Path: AsPath + ?Sized>(
+    file_path: &Path,
+    timeout: Duration,
+) -> Result<Option<FileLock>> {
+    acquire(file_path.as_path(), false, Some(timeout)).context("lock_exclusive_timeout", file_path)
+}
+
+/// Takes a shared advisory lock on a file like `lock_shared`, waiting for at most `timeout`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `timeout`: **owned** `Duration`
+///
+/// # Returns
+/// Result<`Option<FileLock>`>, `None` if the lock couldn't be taken in time
+pub fn lock_shared_timeout<Path: AsPath + ?Sized>(
+    file_path: &Path,
+    timeout: Duration,
+) -> Result<Option<FileLock>> {
+    acquire(file_path.as_path(), true, Some(timeout)).context("lock_shared_timeout", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn locks() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "locks/state.txt";
+            let timeout = Duration::from_millis(20);
+
+            // Action
+            let shared = lock_shared(&file)?;
+            let another = try_lock_shared(&file)?;
+            let blocked = lock_exclusive_timeout(&file, timeout)?;
+            drop((shared, another));
+            let exclusive = try_lock_exclusive(&file)?;
+
+            // Assert
+            assert!(blocked.is_none());
+            let exclusive = exclusive.expect("nothing else holds a lock");
+            assert_eq!(exclusive.path(), StdPath::new(file));
+            assert!(lock_shared_timeout(&file, timeout)?.is_none());
+            drop(exclusive);
+            assert!(lock_shared_timeout(&file, timeout)?.is_some());
+
+            // Clean-up
+            delete(&"locks")?;
+        })
+    }
+
+    #[test]
+    fn lock_across_threads() -> Result<()> {
+        Ok({
+            // Arrange
+            let (file, lock) = (
+                "lock_across_threads/count.txt",
+                "lock_across_threads/count.lock",
+            );
+            write_string(&file, &"0")?;
+
+            // Action
+            thread::scope(|scope| {
+                let workers: Vec<_> = (0..8)
+                    .map(|_| {
+                        scope.spawn(|| -> Result<()> {
+                            let _lock = lock_exclusive(&lock)?;
+                            let count: u32 = read_string(&file)?.parse().unwrap();
+                            return Ok(write_string(&file, &(count + 1).to_string())?);
+                        })
+                    })
+                    .collect();
+                workers
+                    .into_iter()
+                    .try_for_each(|worker| worker.join().unwrap())
+            })?;
+
+            // Assert
+            assert_eq!(read_string(&file)?, "8");
+
+            // Clean-up
+            delete(&"lock_across_threads")?;
+        })
+    }
+}
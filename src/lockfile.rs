@@ -0,0 +1,254 @@
+use crate::*;
+use std::{
+    path::Path as StdPath,
+    time::{Duration, SystemTime},
+};
+
+// How old a lockfile without a PID must be to be taken as left behind by a crash, rather than being written
+const UNWRITTEN_STALE_AFTER: Duration = Duration::from_secs(5);
+
+/// A lockfile holding the PID of the process that acquired it, such as to keep a CLI tool or daemon single-instance,
+/// which is removed when this guard is dropped. A lockfile left behind by a process that is no longer running is stale,
+/// and is taken over rather than blocking forever.
+///
+/// # Examples
+/// ```
+/// use file_access::Lockfile;
+/// use std::io::ErrorKind;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let lock = Lockfile::acquire(&"lockfile_example/app.pid")?;
+///         assert_eq!(Lockfile::owner(&"lockfile_example/app.pid")?, Some(std::process::id()));
+///
+///         let error = Lockfile::acquire(&"lockfile_example/app.pid").unwrap_err();
+///         assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+///
+///         // Clean-up:
+///         drop(lock);
+///         file_access::delete(&"lockfile_example")?;
+///     })
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Lockfile {
+    path: PathBuf,
+}
+
+fn parse_pid(text: &str) -> Option<u32> {
+    text.trim().parse().ok()
+}
+
+// Reads the PID in a lockfile, `None` if it has none (yet), or the lockfile doesn't exist
+fn read_pid(path: &StdPath) -> io::Result<Option<u32>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(parse_pid(&text)),
+        Err(x) if x.kind() == ErrorKind::NotFound => Ok(None),
+        Err(x) => Err(x),
+    }
+}
+
+// Tells whether a lockfile was left behind: its process is gone, or it never got a PID written
+fn is_stale(pid: Option<u32>, modified: SystemTime) -> bool {
+    match pid {
+        Some(pid) => !internal::sys::process_alive(pid),
+        None => {
+            let age = SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or_default();
+            age >= UNWRITTEN_STALE_AFTER
+        }
+    }
+}
+
+// Removes a stale lockfile while holding an exclusive lock on it. Of several processes taking it over at once,
+// the first one removes it, and the others, once they get the lock, find that the path no longer names the file
+// they opened, and leave alone whatever lockfile has been created there since
+fn remove_stale(path: &StdPath) -> io::Result<()> {
+    let mut file = match File::options().read(true).write(true).open(path) {
+        Err(x) if x.kind() == ErrorKind::NotFound => return Ok(()),
+        file => file?,
+    };
+    file.lock()?;
+    let current = match File::open(path) {
+        Err(x) if x.kind() == ErrorKind::NotFound => return Ok(()),
+        current => current?,
+    };
+    if internal::sys::file_id(&current)? != internal::sys::file_id(&file)? {
+        return Ok(());
+    }
+    let mut text = String::new();
+    file.read_to_string(&mut text)?;
+    if is_stale(parse_pid(&text), file.metadata()?.modified()?) {
+        fs::remove_file(path)?;
+    }
+    return Ok(());
+}
+
+impl Lockfile {
+    /// Acquires a lockfile by creating it (and its full directory path) with the PID of this process,
+    /// replacing a stale one left behind by a process that is no longer running.
+    ///
+    /// # Parameters
+    /// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`Lockfile`>, failing with `ErrorKind::AlreadyExists` if a running process holds it
+    pub fn acquire<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Self> {
+        let acquire = || -> io::Result<Self> {
+            let path = file_path.as_path();
+            if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                fs::create_dir_all(dir)?;
+            }
+            loop {
+                match File::options().write(true).create_new(true).open(path) {
+                    Ok(mut file) => {
+                        writeln!(file, "{}", process::id())?;
+                        file.sync_all()?;
+                        return Ok(Self {
+                            path: path.to_path_buf(),
+                        });
+                    }
+                    Err(x) if x.kind() == ErrorKind::AlreadyExists => {
+                        let pid = read_pid(path)?;
+                        let modified = match fs::metadata(path) {
+                            Err(x) if x.kind() == ErrorKind::NotFound => continue,
+                            metadata => metadata?.modified()?,
+                        };
+                        if !is_stale(pid, modified) {
+                            return Err(io::Error::new(
+                                ErrorKind::AlreadyExists,
+                                match pid {
+                                    Some(pid) => format!("the lock is held by process {pid}"),
+                                    None => "the lock is held".to_string(),
+                                },
+                            ));
+                        }
+                        // Checked again under a lock, as another process may be taking it over too;
+                        // either way, the create is retried
+                        remove_stale(path)?;
+                    }
+                    Err(x) => return Err(x),
+                }
+            }
+        };
+        acquire().context("Lockfile::acquire", file_path)
+    }
+
+    /// Gets the PID of the running process holding a lockfile, if any, such as to report which instance is running.
+    ///
+    /// # Parameters
+    /// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`Option<u32>`>, `None` if it doesn't exist, or is stale
+    pub fn owner<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Option<u32>> {
+        let pid = read_pid(file_path.as_path()).context("Lockfile::owner", file_path)?;
+        return Ok(pid.filter(|pid| internal::sys::process_alive(*pid)));
+    }
+
+    /// The path of the lockfile.
+    pub fn path(&self) -> &StdPath {
+        &self.path
+    }
+}
+
+impl Drop for Lockfile {
+    fn drop(&mut self) {
+        // Only removes it if it's still ours, not taken over after being mistaken as stale
+        if read_pid(&self.path).is_ok_and(|pid| pid == Some(process::id())) {
+            _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn acquire_and_release() -> Result<()> {
+        Ok({
+            // Arrange
+            let path = "lockfile_acquire/app.pid";
+
+            // Action
+            let lock = Lockfile::acquire(&path)?;
+            let held = Lockfile::acquire(&path);
+            let owner = Lockfile::owner(&path)?;
+            drop(lock);
+
+            // Assert
+            assert_eq!(held.unwrap_err().kind(), ErrorKind::AlreadyExists);
+            assert_eq!(owner, Some(process::id()));
+            assert!(!PathBuf::from(path).exists());
+            assert_eq!(Lockfile::owner(&path)?, None);
+
+            // Clean-up
+            delete(&"lockfile_acquire")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn stale() -> Result<()> {
+        Ok({
+            // Arrange
+            let path = "lockfile_stale/app.pid";
+            let mut child = process::Command::new("true").spawn()?;
+            child.wait()?; // reaped, so its PID is no longer running
+            write_string(&path, &format!("{}\n", child.id()))?;
+
+            // Action
+            let lock = Lockfile::acquire(&path)?;
+
+            // Assert
+            assert_eq!(read_string(&path)?, format!("{}\n", process::id()));
+            drop(lock);
+
+            // Clean-up
+            delete(&"lockfile_stale")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn concurrent_takeover() -> Result<()> {
+        Ok({
+            // Arrange
+            let path = "lockfile_takeover/app.pid";
+            let mut child = process::Command::new("true").spawn()?;
+            child.wait()?; // reaped, so its PID is no longer running
+            write_string(&path, &format!("{}\n", child.id()))?;
+            let barrier = std::sync::Barrier::new(8);
+
+            // Action
+            let results: Vec<_> = std::thread::scope(|scope| {
+                let takers: Vec<_> = (0..8)
+                    .map(|_| {
+                        scope.spawn(|| {
+                            barrier.wait();
+                            Lockfile::acquire(&path)
+                        })
+                    })
+                    .collect();
+                takers.into_iter().map(|x| x.join().unwrap()).collect()
+            });
+            // A taker that judged the lockfile stale before it was taken over only gets to remove it now
+            remove_stale(PathBuf::from(path).as_path())?;
+
+            // Assert
+            let acquired = results.iter().filter(|x| x.is_ok()).count();
+            assert_eq!(acquired, 1, "only one taker should hold the lock");
+            for error in results.iter().filter_map(|x| x.as_ref().err()) {
+                assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+            }
+            assert_eq!(read_string(&path)?, format!("{}\n", process::id()));
+            drop(results);
+
+            // Clean-up
+            delete(&"lockfile_takeover")?;
+        })
+    }
+}
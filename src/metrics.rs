@@ -0,0 +1,214 @@
+use std::{collections::BTreeMap, io::ErrorKind, sync::Mutex, time::Duration};
+
+/// The upper bounds of the latency histogram buckets; a last bucket counts everything slower.
+pub const LATENCY_BUCKETS: [Duration; 6] = [
+    Duration::from_micros(10),
+    Duration::from_micros(100),
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+    Duration::from_millis(100),
+    Duration::from_secs(1),
+];
+
+/// What was recorded for one kind of operation, such as `read_string`, since the start of the process
+/// or the last `reset`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OperationMetrics {
+    /// How many times the operation ran, whether it succeeded or not.
+    pub calls: u64,
+    /// How many bytes successful calls read.
+    pub bytes_read: u64,
+    /// How many bytes successful calls wrote.
+    pub bytes_written: u64,
+    /// How many calls failed, by `ErrorKind` (such as `"NotFound"`).
+    pub errors: BTreeMap<String, u64>,
+    /// How many calls took at most each of the `LATENCY_BUCKETS`, followed by how many took longer.
+    pub latency: [u64; LATENCY_BUCKETS.len() + 1],
+    /// The time taken by all calls together.
+    pub total_latency: Duration,
+}
+
+impl OperationMetrics {
+    /// Counts the failed calls, of any kind.
+    pub fn error_count(&self) -> u64 {
+        self.errors.values().sum()
+    }
+}
+
+/// A copy of the metrics recorded so far, per operation; see `snapshot`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    pub operations: BTreeMap<&'static str, OperationMetrics>,
+}
+
+impl Snapshot {
+    /// Gets the metrics of one operation, if it ran.
+    pub fn get(&self, operation: &str) -> Option<&OperationMetrics> {
+        self.operations.get(operation)
+    }
+
+    /// Sums the bytes read by all operations.
+    pub fn bytes_read(&self) -> u64 {
+        self.operations.values().map(|op| op.bytes_read).sum()
+    }
+
+    /// Sums the bytes written by all operations.
+    pub fn bytes_written(&self) -> u64 {
+        self.operations.values().map(|op| op.bytes_written).sum()
+    }
+}
+
+static METRICS: Mutex<Snapshot> = Mutex::new(Snapshot {
+    operations: BTreeMap::new(),
+});
+
+// Records one call of an operation
+pub(crate) fn record(
+    operation: &'static str,
+    latency: Duration,
+    outcome: std::result::Result<(u64, u64), ErrorKind>,
+) {
+    let mut metrics = METRICS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let op = metrics.operations.entry(operation).or_default();
+    op.calls += 1;
+    match outcome {
+        Ok((read, written)) => {
+            op.bytes_read += read;
+            op.bytes_written += written;
+        }
+        Err(kind) => *op.errors.entry(format!("{kind:?}")).or_default() += 1,
+    }
+    let bucket = LATENCY_BUCKETS
+        .iter()
+        .position(|bound| latency <= *bound)
+        .unwrap_or(LATENCY_BUCKETS.len());
+    op.latency[bucket] += 1;
+    op.total_latency += latency;
+}
+
+/// Copies the metrics recorded so far: calls, bytes read and written, errors by kind and a latency histogram,
+/// per operation. **Requires the `metrics` feature**.
+///
+/// Operations are the primitives the others are built on, e.g. a `read_lines` shows up as a `read_string`
+/// and an `append_lines` as an `append_bytes`. These are all that are recorded:
+/// - reading and writing: `read_string`, `read_bytes`, `write_string`, `write_string_anon_then_link`, `write_bytes`,
+///   `append_bytes`, `append_string_sync`, `read_bytes_at`, `write_bytes_at`, `read_into`, `write_from`
+///   and `map_readonly`
+/// - copying and moving: `copy`, `copy_with`, `copy_with_progress`, `pipe`, `pipe_with` and `rename`, whose fallback across
+///   filesystems shows up as a `copy` and a `delete`
+/// - the file system: `create_dir`, `create_dir_all`, `delete` and `stat`
+/// - hashing: `hash`, `hash_with_progress` and `tree_hash`, which count the contents they read
+/// - compression: `read_bytes_gz` and `write_bytes_gz`, which count compressed bytes, and `zip`, `unzip`, `tar`,
+///   `tar_gz` and `untar`, which count the size of the archive
+/// - syncing: `sync_dir`, `sync_dir_with_progress` and `par_sync_dir`, which count no bytes, as comparing files
+///   reads an unknown part of them
+///
+/// # Returns
+/// `Snapshot`
+///
+/// # Examples
+/// ```
+/// use file_access::metrics;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::read_string(&"Cargo.toml")?;
+///
+///         let snapshot = metrics::snapshot();
+///         let reads = snapshot.get("read_string").unwrap();
+///         println!("{} reads, {} bytes, {} errors", reads.calls, reads.bytes_read, reads.error_count());
+///     })
+/// }
+/// ```
+pub fn snapshot() -> Snapshot {
+    METRICS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Forgets the metrics recorded so far, e.g. after exporting a `snapshot`. **Requires the `metrics` feature**.
+pub fn reset() {
+    METRICS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .operations
+        .clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use std::io::Result;
+
+    #[test]
+    fn snapshot() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "metrics_snapshot.txt";
+            let before = super::snapshot();
+
+            // Action
+            write_string(&file_path, &"Hello, World!")?;
+            read_string(&file_path)?;
+            _ = read_string(&"metrics_missing.txt");
+            let after = super::snapshot();
+
+            // Assert
+            let reads =
+                |snapshot: &Snapshot| snapshot.get("read_string").cloned().unwrap_or_default();
+            let (reads_before, reads_after) = (reads(&before), reads(&after));
+            assert!(reads_after.calls >= reads_before.calls + 2);
+            assert!(reads_after.bytes_read >= reads_before.bytes_read + 13);
+            assert!(
+                reads_after.errors["NotFound"]
+                    > reads_before.errors.get("NotFound").copied().unwrap_or(0)
+            );
+            assert_eq!(reads_after.latency.iter().sum::<u64>(), reads_after.calls);
+            assert!(after.bytes_written() >= before.bytes_written() + 13);
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+
+    #[test]
+    fn renames_and_hashes() -> Result<()> {
+        Ok({
+            // Arrange
+            let calls = |operation| {
+                super::snapshot()
+                    .get(operation)
+                    .map_or(0, |op: &OperationMetrics| op.calls)
+            };
+            let (renames, syncs) = (calls("rename"), calls("sync_dir"));
+            write_string(&"metrics_rename/a.txt", &"Hello, World!")?;
+
+            // Action
+            rename(&"metrics_rename/a.txt", &"metrics_rename/b.txt")?;
+            sync_dir(
+                &"metrics_rename",
+                &"metrics_rename_copy",
+                &SyncOptions::new(),
+            )?;
+
+            // Assert
+            assert!(calls("rename") > renames);
+            assert!(calls("sync_dir") > syncs);
+            #[cfg(feature = "hash")]
+            {
+                let before = super::snapshot().get("hash").cloned().unwrap_or_default();
+                hash(&"metrics_rename/b.txt", HashAlgorithm::Sha256)?;
+                let after = super::snapshot().get("hash").cloned().unwrap_or_default();
+                assert!(after.bytes_read >= before.bytes_read + 13);
+            }
+
+            // Clean-up
+            delete(&"metrics_rename")?;
+            delete(&"metrics_rename_copy")?;
+        })
+    }
+}
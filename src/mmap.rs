@@ -0,0 +1,111 @@
+use crate::*;
+use memmap2::Mmap;
+use std::{ops::Deref, path::Path as StdPath};
+
+/// A file mapped read-only into memory, as returned by `map_readonly`, which dereferences to its bytes (`&[u8]`)
+/// and is unmapped when dropped. The OS pages the contents in as they're touched, so a multi-GB file can be searched
+/// or parsed in place without copying it into a buffer.
+///
+/// The map shows the file as it is on disk: if another process truncates the file while it's mapped, touching the
+/// bytes past the new end crashes the process (`SIGBUS` on Unix), and other changes show through the map, behind the
+/// back of the `&[u8]` it hands out. This is why `map_readonly` is `unsafe`: only map files that aren't modified while
+/// in use; network shares are best avoided, see `volume_kind`.
+#[derive(Debug)]
+pub struct MappedFile {
+    map: Mmap,
+    path: PathBuf,
+}
+
+impl MappedFile {
+    /// The path of the mapped file.
+    pub fn path(&self) -> &StdPath {
+        &self.path
+    }
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+impl AsRef<[u8]> for MappedFile {
+    fn as_ref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+/// Maps a file read-only into memory, such as to search or parse a large file without reading it into a buffer.
+/// An empty file maps to no bytes. **Requires the `mmap` feature**.
+///
+/// # Safety
+/// The file must not be truncated or written to, by this process or any other, for as long as the `MappedFile` lives:
+/// the bytes it dereferences to would change underneath the borrow, and reading past a truncated end crashes the
+/// process (`SIGBUS` on Unix). This is the same contract as `memmap2::Mmap::map`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`MappedFile`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         // SAFETY: Cargo.toml isn't modified while mapped
+///         let map = unsafe { file_access::map_readonly(&"Cargo.toml")? };
+///         assert!(map.starts_with(b"[package]"));
+///         let lines = map.iter().filter(|b| **b == b'\n').count();
+///         println!("{lines} lines");
+///     })
+/// }
+/// ```
+pub unsafe fn map_readonly<Path: AsPath + ?Sized>(file_path: &Path) -> Result<MappedFile> {
+    metered(
+        "map_readonly",
+        |_| (0, 0),
+        || {
+            let file = File::open(file_path.as_path())?;
+            // SAFETY: the caller guarantees the file isn't changed while mapped
+            let map = unsafe { Mmap::map(&file)? };
+            return Ok(MappedFile {
+                map,
+                path: path_of(file_path),
+            });
+        },
+    )
+    .context("map_readonly", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn map_readonly() -> Result<()> {
+        Ok({
+            // Arrange
+            let (file, empty) = ("map_readonly/data.bin", "map_readonly/empty.bin");
+            write_bytes(&file, &b"Hello, World!")?;
+            write_bytes(&empty, &b"")?;
+
+            // Action
+            // SAFETY: the files aren't modified while mapped
+            let map = unsafe { super::map_readonly(&file)? };
+            let empty_map = unsafe { super::map_readonly(&empty)? };
+
+            // Assert
+            assert_eq!(&map[..], b"Hello, World!");
+            assert_eq!(map.path(), StdPath::new(file));
+            assert!(empty_map.is_empty());
+            drop((map, empty_map));
+
+            // Clean-up
+            delete(&"map_readonly")?;
+        })
+    }
+}
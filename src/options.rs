@@ -0,0 +1,805 @@
+use crate::*;
+use std::borrow::Cow;
+
+/// How `read_string_with` turns the bytes of a file into text: whether a UTF-8 byte order mark is stripped,
+/// whether invalid UTF-8 is replaced instead of failing, whether `\r\n` line endings become `\n`,
+/// and (with the `gzip` feature) whether compressed files are decompressed.
+/// Settings are chained onto it builder-style; by default, like `read_string`, none of them apply.
+///
+/// # Examples
+/// ```
+/// use file_access::ReadOptions;
+///
+/// let options = ReadOptions::new().strip_bom(true).lossy(true).normalize_newlines(true);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReadOptions {
+    strip_bom: bool,
+    lossy: bool,
+    normalize_newlines: bool,
+    #[cfg(feature = "gzip")]
+    gunzip: bool,
+}
+
+impl ReadOptions {
+    /// Creates the default options, which read the file as it is and fail on invalid UTF-8.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to drop a leading UTF-8 byte order mark (`EF BB BF`), as written by many Windows editors.
+    pub fn strip_bom(mut self, strip_bom: bool) -> Self {
+        self.strip_bom = strip_bom;
+        self
+    }
+
+    /// Whether to replace invalid UTF-8 with `U+FFFD`, instead of failing with `ErrorKind::InvalidData`.
+    pub fn lossy(mut self, lossy: bool) -> Self {
+        self.lossy = lossy;
+        self
+    }
+
+    /// Whether to turn `\r\n` line endings into `\n`.
+    pub fn normalize_newlines(mut self, normalize_newlines: bool) -> Self {
+        self.normalize_newlines = normalize_newlines;
+        self
+    }
+
+    /// Whether to decompress the file if it's gzip-compressed, which is told by its leading bytes
+    /// rather than its extension, so compressed and plain files can be read alike. **Requires the `gzip` feature**.
+    #[cfg(feature = "gzip")]
+    pub fn gunzip(mut self, gunzip: bool) -> Self {
+        self.gunzip = gunzip;
+        self
+    }
+}
+
+/// Reads the contents of a file and returns it as a string, as set by `ReadOptions`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `options`: `ReadOptions`, such as `ReadOptions::new().strip_bom(true)`
+///
+/// # Returns
+/// Result<`String`>, failing with `ErrorKind::InvalidData` if the file isn't valid UTF-8 and `lossy` isn't set
+///
+/// # Examples
+/// ```
+/// use file_access::ReadOptions;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "read_string_with_example/notes.txt";
+///         file_access::write_bytes(&file_path, &b"\xEF\xBB\xBFcaf\xE9\r\n")?;
+///
+///         let options = ReadOptions::new().strip_bom(true).lossy(true).normalize_newlines(true);
+///         assert_eq!(file_access::read_string_with(&file_path, options)?, "caf\u{FFFD}\n");
+///
+///         // Clean-up:
+///         file_access::delete(&"read_string_with_example")?;
+///     })
+/// }
+/// ```
+pub fn read_string_with<Path: AsPath + ?Sized>(
+    file_path: &Path,
+    options: ReadOptions,
+) -> Result<String> {
+    metered(
+        "read_string",
+        |text: &String| (text.len() as u64, 0),
+        || {
+            let mut bytes = vec![];
+            get_file(file_path)?.read_to_end(&mut bytes)?;
+            #[cfg(feature = "gzip")]
+            if options.gunzip && bytes.starts_with(gzip::GZIP_MAGIC) {
+                bytes = gzip::gunzip(&bytes)?;
+            }
+            let mut bytes = &bytes[..];
+            if options.strip_bom {
+                bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+            }
+
+            let text = match options.lossy {
+                true => String::from_utf8_lossy(bytes).into_owned(),
+                false => String::from_utf8(bytes.to_vec())
+                    .map_err(|x| io::Error::new(ErrorKind::InvalidData, x))?,
+            };
+            return Ok(match options.normalize_newlines {
+                true => text.replace("\r\n", "\n"),
+                false => text,
+            });
+        },
+    )
+    .context("read_string_with", file_path)
+}
+
+/// How `write_string_with` and `write_lines_with` write a file: whether it may replace an existing one,
+/// the permissions it's created with, whether it ends with a newline, whether it's flushed to disk,
+/// and how many previous versions are kept.
+/// Settings are chained onto it builder-style; by default, like `write_string`, the file is created or entirely replaced,
+/// with the default permissions, as given, and isn't synced.
+///
+/// # Examples
+/// ```
+/// use file_access::WriteOptions;
+///
+/// let options = WriteOptions::new().create_new(true).mode(0o600).trailing_newline(true);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WriteOptions {
+    create_new: bool,
+    mode: Option<u32>,
+    trailing_newline: bool,
+    sync: bool,
+    durable: bool,
+    versions: usize,
+}
+
+impl WriteOptions {
+    /// Creates the default options, which create or entirely replace the file.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to refuse to replace an existing file, failing with `ErrorKind::AlreadyExists` instead.
+    /// The check and the creation are one atomic step, so two writers can't both create the file.
+    pub fn create_new(mut self, create_new: bool) -> Self {
+        self.create_new = create_new;
+        self
+    }
+
+    /// The permission bits, such as `0o600`, that the file gets if it's created, less the process's umask.
+    /// The permissions of an existing file are left as they are. **Unix only**; ignored elsewhere.
+    pub fn mode(mut self, mode: u32) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Whether to end the file with `\n`, as POSIX text files and tools such as Git expect,
+    /// adding it unless the text already ends with one. An empty file is left empty.
+    pub fn trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
+    /// Whether to flush the contents to disk before returning, so they survive a power loss.
+    pub fn sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Whether to flush the file and the directory holding it to disk before returning, so that after a power loss
+    /// the file is found, and not just its contents kept; implies `sync`.
+    pub fn durable(mut self, durable: bool) -> Self {
+        self.durable = durable;
+        self
+    }
+
+    /// How many previous versions of the file to keep next to it, as `file.1` (the latest) up to `file.n`,
+    /// each write copying the current contents into `file.1` first; see `restore_version`. `0`, the default, keeps none.
+    pub fn versioned(mut self, versions: usize) -> Self {
+        self.versions = versions;
+        self
+    }
+}
+
+/// Writes text to a file as set by `WriteOptions`. This function will create the file **and its full directory path**
+/// if they don't exist, and unless `create_new` is set, will entirely replace the contents of an existing file.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `text`: **borrowed** `AsRef<str>` such as `String` or `&str`
+/// - `options`: `WriteOptions`, such as `WriteOptions::new().create_new(true)`
+///
+/// # Returns
+/// Result<`()`>, failing with `ErrorKind::AlreadyExists` if `create_new` is set and the file exists
+///
+/// # Examples
+/// ```
+/// use file_access::WriteOptions;
+/// use std::io::ErrorKind;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "write_string_with_example/secret.key";
+///         let file_path: String = String::from(file_path);
+///         let options = WriteOptions::new().create_new(true).mode(0o600);
+///
+///         file_access::write_string_with(&file_path, &"s3cr3t", options)?;
+///         let error = file_access::write_string_with(&file_path, &"other", options).unwrap_err();
+///         assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+///
+///         // Clean-up:
+///         file_access::delete(&"write_string_with_example")?;
+///     })
+/// }
+/// ```
+pub fn write_string_with<Path: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    text: &Text,
+    options: WriteOptions,
+) -> Result<()> {
+    let mut text = Cow::from(text.as_ref());
+    if options.trailing_newline && !text.is_empty() && !text.ends_with('\n') {
+        text.to_mut().push('\n');
+    }
+    let written = text.len() as u64;
+    metered(
+        "write_string",
+        |_| (0, written),
+        || {
+            if let Some(dir) = path_of(file_path).parent() {
+                fs::create_dir_all(dir)?;
+            }
+            if !options.create_new {
+                versions::rotate_versions(file_path.as_path(), options.versions)?;
+            }
+            let mut open = fs::OpenOptions::new();
+            match options.create_new {
+                true => open.write(true).create_new(true),
+                false => open.write(true).create(true).truncate(true),
+            };
+            #[cfg(unix)]
+            if let Some(mode) = options.mode {
+                use std::os::unix::fs::OpenOptionsExt;
+                open.mode(mode);
+            }
+
+            let mut file = open.open(file_path.as_path())?;
+            file.write_all(text.as_bytes())?;
+            if options.sync || options.durable {
+                file.sync_all()?;
+            }
+            return if options.durable {
+                sync_parent(file_path.as_path())
+            } else {
+                Ok(())
+            };
+        },
+    )
+    .context("write_string_with", file_path)
+}
+
+/// Writes a list of text as lines to a file as set by `WriteOptions`, such as ending the last line with a newline.
+/// This function will create the file **and its full directory path** if they don't exist,
+/// and unless `create_new` is set, will entirely replace the contents.
+///
+/// To write back lines read by `read_lines` the way they were, carry over whether the file ended with a newline,
+/// which `ends_with_newline` tells.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `lines`: **borrowed** `Vec<AsRef<str>>` such as `Vec<String>` or `Vec<&str>`
+/// - `options`: `WriteOptions`, such as `WriteOptions::new().trailing_newline(true)`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// use file_access::WriteOptions;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "write_lines_with_example/.gitignore";
+///         let lines: Vec<&str> = vec!["target", "*.log"];
+///
+///         file_access::write_lines_with(&file_path, &lines, WriteOptions::new().trailing_newline(true))?;
+///         assert_eq!(file_access::read_string(&file_path)?, "target\n*.log\n");
+///
+///         let lines = file_access::read_lines(&file_path)?;
+///         let options = WriteOptions::new().trailing_newline(file_access::ends_with_newline(&file_path)?);
+///         file_access::write_lines_with(&file_path, &lines, options)?;
+///         assert_eq!(file_access::read_string(&file_path)?, "target\n*.log\n");
+///
+///         // Clean-up:
+///         file_access::delete(&"write_lines_with_example")?;
+///     })
+/// }
+/// ```
+pub fn write_lines_with<Path: AsPath + ?Sized, Line: AsRef<str>>(
+    file_path: &Path,
+    lines: &Vec<Line>,
+    options: WriteOptions,
+) -> Result<()> {
+    let mut text = lines.to_vec_string().join("\n");
+    // Ends the last line even if it's empty, which joining alone doesn't tell apart
+    if options.trailing_newline && !lines.is_empty() {
+        text.push('\n');
+    }
+
+    return write_string_with(file_path, &text, options);
+}
+
+/// How `copy_with` copies a file: whether the destination gets the source's permissions and timestamps,
+/// whether a symlink is copied as a symlink rather than as the contents it points to,
+/// and whether the holes of a sparse file are kept.
+/// Settings are chained onto it builder-style; by default the copy is a fresh file with the default permissions
+/// of a new file and the current time, holding the contents of what a symlink points to.
+/// Extended attributes, ACLs and the like are carried over by `copy_preserving` instead.
+///
+/// # Examples
+/// ```
+/// use file_access::CopyOptions;
+///
+/// let backup = CopyOptions::new().preserve_permissions(true).preserve_times(true);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CopyOptions {
+    preserve_permissions: bool,
+    preserve_times: bool,
+    copy_symlinks: bool,
+    sparse: bool,
+}
+
+impl CopyOptions {
+    /// Creates the default options, which copy just the contents, following symlinks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to give the destination the source's permissions, such as its executable bits,
+    /// or on Windows its read-only attribute.
+    pub fn preserve_permissions(mut self, preserve_permissions: bool) -> Self {
+        self.preserve_permissions = preserve_permissions;
+        self
+    }
+
+    /// Whether to give the destination the source's access and modification times.
+    pub fn preserve_times(mut self, preserve_times: bool) -> Self {
+        self.preserve_times = preserve_times;
+        self
+    }
+
+    /// Whether to copy a symlink as a symlink with the same target, like `copy_symlink`, rather than following it.
+    /// Its permissions and timestamps are then left as created.
+    pub fn copy_symlinks(mut self, copy_symlinks: bool) -> Self {
+        self.copy_symlinks = copy_symlinks;
+        self
+    }
+
+    /// Whether to keep the holes of a sparse file, such as a VM image or database file, as holes in the copy
+    /// rather than writing them out as zeros, so the copy takes as little disk space as the source.
+    /// Holes are found with `SEEK_DATA`/`SEEK_HOLE` on Linux and macOS; elsewhere the contents are copied whole.
+    pub fn sparse(mut self, sparse: bool) -> Self {
+        self.sparse = sparse;
+        self
+    }
+}
+
+/// Copies a file to a destination as set by `CopyOptions`, such as keeping its permissions and timestamps for a backup.
+/// This function will create the destination's full directory path if it doesn't exist,
+/// and will entirely replace the contents of the destination if it already exists; unless `preserve_permissions`
+/// is set, an existing destination keeps its own permissions.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `options`: `CopyOptions`, such as `CopyOptions::new().preserve_times(true)`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// use file_access::CopyOptions;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let options = CopyOptions::new().preserve_permissions(true).preserve_times(true);
+///         file_access::copy_with(&"Cargo.toml", &"copy_with_example/Cargo.toml.bak", options)?;
+///
+///         let source = file_access::stat(&"Cargo.toml")?.modified()?;
+///         let backup = file_access::stat(&"copy_with_example/Cargo.toml.bak")?.modified()?;
+///         assert_eq!(source, backup);
+///
+///         // Clean-up:
+///         file_access::delete(&"copy_with_example")?;
+///     })
+/// }
+/// ```
+pub fn copy_with<From: AsPath + ?Sized, To: AsPath + ?Sized>(
+    from: &From,
+    to: &To,
+    options: CopyOptions,
+) -> Result<()> {
+    if options.copy_symlinks && path_of(from).is_symlink() {
+        return copy_symlink(from, to);
+    }
+    let copied = metered(
+        "copy_with",
+        |copied: &u64| (*copied, *copied),
+        || {
+            if let Some(path) = path_of(to).parent() {
+                fs::create_dir_all(path)?;
+            }
+            let mut source = File::open(from.as_path())?;
+            let metadata = source.metadata()?;
+            let mut dest = File::create(to.as_path())?;
+            let copied = match options.sparse {
+                true => internal::sys::copy_sparse(&mut source, &mut dest)?,
+                false => io::copy(&mut source, &mut dest)?,
+            };
+            if options.preserve_times {
+                let times = fs::FileTimes::new()
+                    .set_accessed(metadata.accessed()?)
+                    .set_modified(metadata.modified()?);
+                dest.set_times(times)?;
+            }
+            if options.preserve_permissions {
+                dest.set_permissions(metadata.permissions())?;
+            }
+            return Ok(copied);
+        },
+    )
+    .map(|_| ());
+
+    return context_either(copied, "copy_with", from, to, |from| {
+        File::open(from).is_ok()
+    });
+}
+
+/// How `pipe_with` writes its destination: whether through a temporary sibling that atomically replaces it,
+/// and whether the result is flushed to disk.
+/// Settings are chained onto it builder-style; by default the destination is replaced atomically but isn't synced,
+/// which is cheaper than `pipe`, whose replacement is always durable.
+///
+/// # Examples
+/// ```
+/// use file_access::PipeOptions;
+///
+/// let options = PipeOptions::new().atomic(false).sync(true);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PipeOptions {
+    atomic: bool,
+    sync: bool,
+    durable: bool,
+}
+
+impl Default for PipeOptions {
+    fn default() -> Self {
+        Self {
+            atomic: true,
+            sync: false,
+            durable: false,
+        }
+    }
+}
+
+impl PipeOptions {
+    /// Creates the default options, which replace the destination atomically without syncing it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to write into a temporary sibling that replaces the destination only if the transformation succeeds,
+    /// keeping an existing destination's permissions. Without it the destination is truncated and written in place,
+    /// so a failed transformation leaves it partly written; a file can't be piped into itself that way.
+    pub fn atomic(mut self, atomic: bool) -> Self {
+        self.atomic = atomic;
+        self
+    }
+
+    /// Whether to flush the contents to disk before returning, or before the rename when `atomic` is set.
+    pub fn sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Whether to also flush the directory holding the destination to disk, so that after a power loss
+    /// the destination, or its atomic replacement, is found; implies `sync`.
+    pub fn durable(mut self, durable: bool) -> Self {
+        self.durable = durable;
+        self
+    }
+}
+
+/// Streams a file through a transformation into a destination like `pipe`, as set by `PipeOptions`,
+/// such as to skip the fsyncs of a scratch file that doesn't need to survive a power loss.
+/// This function will create the destination's full directory path if it doesn't exist.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `options`: `PipeOptions`, such as `PipeOptions::new().sync(true)`
+/// - `transform`: **owned** `FnOnce(&mut BufReader<File>, &mut BufWriter<File>) -> std::io::Result<()>`
+///
+/// # Returns
+/// Result<`()`>, failing with `ErrorKind::InvalidInput` if `atomic` isn't set and the destination is the source
+///
+/// # Examples
+/// ```
+/// use file_access::PipeOptions;
+/// use std::io::Write;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let source: &str = "pipe_with_example/report.csv";
+///         let destination: &str = "pipe_with_example/report.csv.tmp";
+///         file_access::write_string(&source, &"1,2")?;
+///
+///         let options = PipeOptions::new().atomic(false);
+///         file_access::pipe_with(&source, &destination, options, |reader, writer| {
+///             writer.write_all(b"a,b\n")?;
+///             std::io::copy(reader, writer).map(|_| ())
+///         })?;
+///         assert_eq!(file_access::read_string(&destination)?, "a,b\n1,2");
+///
+///         // Clean-up:
+///         file_access::delete(&"pipe_with_example")?;
+///     })
+/// }
+/// ```
+pub fn pipe_with<From: AsPath + ?Sized, To: AsPath + ?Sized>(
+    from: &From,
+    to: &To,
+    options: PipeOptions,
+    transform: impl FnOnce(&mut BufReader<File>, &mut BufWriter<File>) -> io::Result<()>,
+) -> Result<()> {
+    pipe_as("pipe_with", from, to, options, transform)
+}
+
+// Carries out `pipe` and `pipe_with`, naming the operation in their metrics and errors
+pub(crate) fn pipe_as<From: AsPath + ?Sized, To: AsPath + ?Sized>(
+    operation: &'static str,
+    from: &From,
+    to: &To,
+    options: PipeOptions,
+    transform: impl FnOnce(&mut BufReader<File>, &mut BufWriter<File>) -> io::Result<()>,
+) -> Result<()> {
+    let piped = metered(
+        operation,
+        |&bytes| bytes,
+        || {
+            let mut reader = BufReader::new(get_file(from)?);
+            let dest = path_of(to);
+            if let Some(path) = dest.parent() {
+                fs::create_dir_all(path)?;
+            }
+            let write = |file: File| {
+                let mut writer = BufWriter::new(file);
+                transform(&mut reader, &mut writer)?;
+                let file = writer.into_inner()?;
+                if options.sync || options.durable {
+                    file.sync_all()?;
+                }
+                let bytes = (
+                    reader.into_inner().stream_position()?,
+                    file.metadata()?.len(),
+                );
+
+                return Ok((file, bytes));
+            };
+
+            let bytes = match options.atomic {
+                true => replace_via_rename(&dest, false, write)?,
+                // Truncating the destination would wipe out the source before it's read
+                false
+                    if dest.exists()
+                        && fs::canonicalize(from.as_path())? == fs::canonicalize(&dest)? =>
+                {
+                    return Err(io::Error::new(
+                        ErrorKind::InvalidInput,
+                        "a file can only be piped into itself atomically",
+                    ));
+                }
+                false => write(File::create(&dest)?)?.1,
+            };
+            if options.durable {
+                sync_parent(&dest)?;
+            }
+            return Ok(bytes);
+        },
+    )
+    .map(|_| ());
+
+    return context_either(piped, operation, from, to, |from| File::open(from).is_ok());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::Result,
+        time::{Duration, SystemTime},
+    };
+
+    #[test]
+    fn read_string_with() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "read_string_with/file_access.txt";
+            write_bytes(&file, &b"\xEF\xBB\xBFline\xFF\r\nend")?;
+
+            // Action
+            let raw = super::read_string_with(&file, ReadOptions::new());
+            let bom =
+                super::read_string_with(&file, ReadOptions::new().strip_bom(true).lossy(true))?;
+            let all = ReadOptions::new()
+                .strip_bom(true)
+                .lossy(true)
+                .normalize_newlines(true);
+            let normalized = super::read_string_with(&file, all)?;
+
+            // Assert
+            assert_eq!(raw.unwrap_err().kind(), ErrorKind::InvalidData);
+            assert_eq!(bom, "line\u{FFFD}\r\nend");
+            assert_eq!(normalized, "line\u{FFFD}\nend");
+
+            // Clean-up
+            delete(&"read_string_with")?;
+        })
+    }
+
+    #[test]
+    fn write_string_with() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "write_string_with/file_access.txt";
+            let create_new = WriteOptions::new().create_new(true).sync(true);
+
+            // Action
+            super::write_string_with(&file, &"first", create_new)?;
+            let clobber = super::write_string_with(&file, &"second", create_new);
+            super::write_string_with(&file, &"third", WriteOptions::new())?;
+
+            // Assert
+            assert_eq!(clobber.unwrap_err().kind(), ErrorKind::AlreadyExists);
+            assert_eq!(read_string(&file)?, "third");
+
+            // Clean-up
+            delete(&"write_string_with")?;
+        })
+    }
+
+    #[test]
+    fn write_lines_with() -> Result<()> {
+        Ok({
+            // Arrange
+            let dir = "write_lines_with";
+            let trailing = WriteOptions::new().trailing_newline(true);
+
+            // Action
+            super::write_lines_with(&"write_lines_with/a.txt", &vec!["a", "b"], trailing)?;
+            super::write_lines_with(&"write_lines_with/b.txt", &vec!["a", ""], trailing)?;
+            super::write_lines_with(&"write_lines_with/c.txt", &Vec::<&str>::new(), trailing)?;
+            super::write_string_with(&"write_lines_with/d.txt", &"a\n", trailing)?;
+            let lines = read_lines(&"write_lines_with/a.txt")?;
+            let options =
+                WriteOptions::new().trailing_newline(ends_with_newline(&"write_lines_with/a.txt")?);
+            super::write_lines_with(&"write_lines_with/a.txt", &lines, options)?;
+
+            // Assert
+            assert_eq!(read_string(&"write_lines_with/a.txt")?, "a\nb\n");
+            assert_eq!(read_string(&"write_lines_with/b.txt")?, "a\n\n");
+            assert_eq!(read_string(&"write_lines_with/c.txt")?, "");
+            assert_eq!(read_string(&"write_lines_with/d.txt")?, "a\n");
+
+            // Clean-up
+            delete(&dir)?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn write_string_with_mode() -> Result<()> {
+        Ok({
+            // Arrange
+            use std::os::unix::fs::PermissionsExt;
+            let file = "write_string_with_mode/file_access.txt";
+
+            // Action
+            super::write_string_with(&file, &"secret", WriteOptions::new().mode(0o600))?;
+
+            // Assert
+            assert_eq!(stat(&file)?.permissions().mode() & 0o777, 0o600);
+
+            // Clean-up
+            delete(&"write_string_with_mode")?;
+        })
+    }
+
+    #[test]
+    fn copy_with() -> Result<()> {
+        Ok({
+            // Arrange
+            let (from, plain, kept) = (
+                "copy_with/run.sh",
+                "copy_with/plain/run.sh",
+                "copy_with/kept/run.sh",
+            );
+            write_string(&from, &"#!/bin/sh")?;
+            let stamp = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+            set_times(&from, stamp, stamp)?;
+            #[cfg(unix)]
+            set_mode(&from, 0o750)?;
+            let all = CopyOptions::new()
+                .preserve_permissions(true)
+                .preserve_times(true);
+
+            // Action
+            super::copy_with(&from, &plain, CopyOptions::new())?;
+            super::copy_with(&from, &kept, all)?;
+
+            // Assert
+            assert_eq!(read_string(&kept)?, "#!/bin/sh");
+            assert_eq!(stat(&kept)?.modified()?, stamp);
+            assert_ne!(stat(&plain)?.modified()?, stamp);
+            #[cfg(unix)]
+            assert_eq!(mode(&kept)?, 0o750);
+
+            // Clean-up
+            delete(&"copy_with")?;
+        })
+    }
+
+    #[test]
+    fn copy_with_sparse() -> Result<()> {
+        Ok({
+            // Arrange
+            let (from, to) = ("copy_with_sparse/disk.img", "copy_with_sparse/copy.img");
+            write_string(&from, &"boot")?;
+            let file = fs::OpenOptions::new().write(true).open(from)?;
+            file.set_len(8 * 1024 * 1024)?; // a trailing hole
+            drop(file);
+            append_string(&from, &"end")?;
+
+            // Action
+            super::copy_with(&from, &to, CopyOptions::new().sparse(true))?;
+
+            // Assert
+            assert!(same_content(&from, &to)?);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::MetadataExt;
+                assert!(stat(&to)?.blocks() * 512 < 8 * 1024 * 1024);
+            }
+
+            // Clean-up
+            delete(&"copy_with_sparse")?;
+        })
+    }
+
+    #[test]
+    fn pipe_with() -> Result<()> {
+        Ok({
+            // Arrange
+            let from = "pipe_with/from.txt";
+            let to = "pipe_with/to.txt";
+            write_string(&from, &"new")?;
+            write_string(&to, &"old")?;
+            let copy = |reader: &mut BufReader<File>, writer: &mut BufWriter<File>| {
+                io::copy(reader, writer).map(|_| ())
+            };
+
+            // Action
+            let in_place = PipeOptions::new().atomic(false).sync(true);
+            super::pipe_with(&from, &to, in_place, copy)?;
+            let failed =
+                super::pipe_with(&from, &to, PipeOptions::new().atomic(false), |_, writer| {
+                    writer.write_all(b"partial")?;
+                    Err(io::Error::other("the transformation failed"))
+                });
+            let itself = super::pipe_with(&from, &from, PipeOptions::new().atomic(false), copy);
+            super::pipe_with(&from, &from, PipeOptions::new(), |reader, writer| {
+                writer.write_all(b"re")?;
+                io::copy(reader, writer).map(|_| ())
+            })?;
+
+            // Assert
+            assert!(failed.is_err());
+            assert_eq!(read_string(&to)?, "partial");
+            assert_eq!(itself.unwrap_err().kind(), ErrorKind::InvalidInput);
+            assert_eq!(read_string(&from)?, "renew");
+            assert_eq!(
+                fs::read_dir("pipe_with")?.count(),
+                2,
+                "no temporary file should be left"
+            );
+
+            // Clean-up
+            delete(&"pipe_with")?;
+        })
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{internal::sys::users, *};
+
+/// Changes the user and/or group owning a file or directory, following symlinks, like `chown`,
+/// such as to hand a file written as root over to a service account; `None` leaves that one as it is.
+/// Changing the user usually takes root privileges, while the owner may change the group to one they belong to.
+/// **Requires the `unix` feature**, Unix only.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `uid`: **owned** `Option<u32>`, such as from `lookup_user`
+/// - `gid`: **owned** `Option<u32>`, such as from `lookup_group`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```no_run
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "/etc/myapp/config.toml";
+///
+///         file_access::write_string(&file_path, &"port = 8080")?;
+///         let uid = file_access::lookup_user(&"myapp")?;
+///         let gid = file_access::lookup_group(&"myapp")?;
+///         file_access::set_owner(&file_path, Some(uid), Some(gid))?;
+///     })
+/// }
+/// ```
+pub fn set_owner<Path: AsPath + ?Sized>(
+    file_path: &Path,
+    uid: Option<u32>,
+    gid: Option<u32>,
+) -> Result<()> {
+    std::os::unix::fs::chown(file_path.as_path(), uid, gid).context("set_owner", file_path)
+}
+
+// The error for a user or group name that isn't known to the system
+fn unknown(what: &str, name: &str) -> Error {
+    Error::new(ErrorKind::NotFound, format!("no such {what}: {name}"))
+}
+
+/// Looks up the id of a user by name, such as `"www-data"`, in the system's user database.
+/// **Requires the `unix` feature**, Unix only.
+///
+/// # Parameters
+/// - `name`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// Result<`u32`>, failing with `ErrorKind::NotFound` if there's no such user
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         assert_eq!(file_access::lookup_user(&"root")?, 0);
+///     })
+/// }
+/// ```
+pub fn lookup_user<Name: AsRef<str> + ?Sized>(name: &Name) -> Result<u32> {
+    let name = name.as_ref();
+    users::user_id(name).ok_or_else(|| unknown("user", name))
+}
+
+/// Looks up the id of a group by name, such as `"www-data"`, in the system's group database.
+/// **Requires the `unix` feature**, Unix only.
+///
+/// # Parameters
+/// - `name`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// Result<`u32`>, failing with `ErrorKind::NotFound` if there's no such group
+pub fn lookup_group<Name: AsRef<str> + ?Sized>(name: &Name) -> Result<u32> {
+    let name = name.as_ref();
+    users::group_id(name).ok_or_else(|| unknown("group", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Result, os::unix::fs::MetadataExt};
+
+    #[test]
+    fn set_owner() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "set_owner.txt";
+            write_string(&file_path, &"")?;
+            let metadata = fs::metadata(file_path)?;
+
+            // Action
+            super::set_owner(&file_path, Some(metadata.uid()), Some(metadata.gid()))?;
+            super::set_owner(&file_path, None, None)?;
+
+            // Assert
+            let after = fs::metadata(file_path)?;
+            assert_eq!((after.uid(), after.gid()), (metadata.uid(), metadata.gid()));
+            assert_eq!(lookup_user(&"root")?, 0);
+            let missing = lookup_group(&"no-such-group-here").unwrap_err();
+            assert_eq!(missing.kind(), ErrorKind::NotFound);
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+}
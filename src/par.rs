@@ -0,0 +1,319 @@
+use crate::{batch::BatchReport, progress::plan_copy, sync::mirror, *};
+use rayon::prelude::*;
+
+// Applies `f` to every item on rayon's thread pool, recording each outcome under its path in input order
+fn par_each<Item: Sync, Path: AsPath + ?Sized>(
+    items: &[Item],
+    path: impl Fn(&Item) -> &Path + Sync,
+    f: impl Fn(&Item) -> Result<()> + Sync,
+) -> BatchReport {
+    let outcomes: Vec<_> = items
+        .par_iter()
+        .map(|item| (path(item).as_path().to_path_buf(), f(item)))
+        .collect();
+
+    let mut report = BatchReport::default();
+    for (path, outcome) in outcomes {
+        match outcome {
+            Ok(()) => report.succeeded.push(path),
+            Err(x) => report.failed.push((path, x)),
+        }
+    }
+    return report;
+}
+
+/// Applies `f` to many files concurrently, spread over rayon's thread pool, carrying on past the ones that fail,
+/// such as to convert, validate or upload thousands of files using every core.
+/// **Requires the `rayon` feature**.
+///
+/// # Parameters
+/// - `file_paths`: **borrowed** `[AsPath]` such as `[String]`, `[&str]` or `[PathBuf]`
+/// - `f`: `Fn(&FilePath) -> Result<()>`, called once per file, from any thread
+///
+/// # Returns
+/// `BatchReport`, in the same order as `file_paths`
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_paths = ["Cargo.toml", "src/lib.rs", "missing.txt"];
+///
+///         let report = file_access::par_for_each_file(&file_paths, |file| {
+///             file.read_string().map(|text| assert!(!text.is_empty()))
+///         });
+///         assert_eq!(report.succeeded.len(), 2);
+///         assert_eq!(report.failed.len(), 1);
+///     })
+/// }
+/// ```
+pub fn par_for_each_file<Path: AsPath + Sync>(
+    file_paths: &[Path],
+    f: impl Fn(&FilePath) -> Result<()> + Sync,
+) -> BatchReport {
+    par_each(file_paths, |path| path, |path| f(&FilePath::access(path)))
+}
+
+/// Applies `f` concurrently to every file matching a glob pattern, as found by `glob`, spread over rayon's thread pool
+/// and carrying on past the ones that fail. **Requires the `rayon` feature**.
+///
+/// # Parameters
+/// - `pattern`: **borrowed** `AsRef<str>` such as `String` or `&str`, such as `"logs/**/*.log"`
+/// - `f`: `Fn(&FilePath) -> Result<()>`, called once per file, from any thread
+///
+/// # Returns
+/// Result<`BatchReport`>, failing only if the pattern can't be walked
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"par_for_each_glob_example/a.log", &"a")?;
+///         file_access::write_string(&"par_for_each_glob_example/b.log", &"b")?;
+///
+///         let report = file_access::par_for_each_glob(&"par_for_each_glob_example/*.log", |file| {
+///             file.append_string(&"!")
+///         })?;
+///         assert!(report.is_ok());
+///         assert_eq!(file_access::read_string(&"par_for_each_glob_example/a.log")?, "a!");
+///
+///         // Clean-up:
+///         file_access::delete(&"par_for_each_glob_example")?;
+///     })
+/// }
+/// ```
+pub fn par_for_each_glob<Pattern: AsRef<str> + ?Sized>(
+    pattern: &Pattern,
+    f: impl Fn(&FilePath) -> Result<()> + Sync,
+) -> Result<BatchReport> {
+    let files = glob(pattern)?;
+    return Ok(par_each(&files, |file| file, |file| f(file)));
+}
+
+/// Copies many files, like `copy`, concurrently over rayon's thread pool, carrying on past the ones that fail.
+/// **Requires the `rayon` feature**.
+///
+/// # Parameters
+/// - `pairs`: **borrowed** `[(AsPath, AsPath)]` of sources and destinations, such as `[(&str, &str)]`
+///
+/// # Returns
+/// `BatchReport`, in the same order as `pairs`
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let pairs = [
+///             ("Cargo.toml", "par_copy_all_example/Cargo.toml"),
+///             ("src/lib.rs", "par_copy_all_example/lib.rs"),
+///         ];
+///
+///         let report = file_access::par_copy_all(&pairs);
+///         assert!(report.is_ok());
+///
+///         // Clean-up:
+///         file_access::delete(&"par_copy_all_example")?;
+///     })
+/// }
+/// ```
+pub fn par_copy_all<From: AsPath + Sync, To: AsPath + Sync>(pairs: &[(From, To)]) -> BatchReport {
+    par_each(pairs, |(from, _)| from, |(from, to)| copy(from, to))
+}
+
+/// Copies a directory recursively, copying its files concurrently over rayon's thread pool and carrying on past
+/// the ones that fail. The directories are created first, including the destination's full directory path.
+/// Symbolic links are copied as the files they point to, and aren't followed into directories.
+/// **Requires the `rayon` feature**.
+///
+/// # Parameters
+/// - `source`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `dest`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`BatchReport`> of the source files, failing if the directories can't be read or created
+///
+/// # Examples
+/// ```
+/// use std::path::PathBuf;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let report = file_access::par_copy_dir(&"src", &"par_copy_dir_example/src")?;
+///         assert!(report.is_ok());
+///         assert!(report.succeeded.contains(&PathBuf::from("src/lib.rs")));
+///
+///         // Clean-up:
+///         file_access::delete(&"par_copy_dir_example")?;
+///     })
+/// }
+/// ```
+pub fn par_copy_dir<Source: AsPath + ?Sized, Dest: AsPath + ?Sized>(
+    source: &Source,
+    dest: &Dest,
+) -> Result<BatchReport> {
+    let mut files = vec![];
+    plan_copy(source.as_path(), dest.as_path(), &mut files).context("par_copy_dir", source)?;
+
+    return Ok(par_each(
+        &files,
+        |(from, _)| from,
+        |(from, to)| fs::copy(from, to).map(|_| ()).context("par_copy_dir", from),
+    ));
+}
+
+/// Computes the digests of many files, like `hash`, concurrently over rayon's thread pool.
+/// **Requires the `rayon` feature**, and the `hash`, `md5`, `sha1`, `crc32` or `blake3` feature.
+///
+/// # Parameters
+/// - `file_paths`: **borrowed** `[AsPath]` such as `[String]`, `[&str]` or `[PathBuf]`
+/// - `algorithm`: `HashAlgorithm`, such as `HashAlgorithm::Sha256`
+///
+/// # Returns
+/// `Vec<Result<String>>` of lowercase hex digests, in the same order as `file_paths`
+///
+/// # Examples
+/// ```
+/// use file_access::HashAlgorithm;
+///
+/// # #[cfg(feature = "hash")]
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let hashes = file_access::par_hash_all(&["Cargo.toml", "missing.txt"], HashAlgorithm::Sha256);
+///         assert_eq!(hashes[0].as_ref().unwrap().len(), 64);
+///         assert!(hashes[1].is_err());
+///     })
+/// }
+/// # #[cfg(not(feature = "hash"))]
+/// # fn main() {}
+/// ```
+#[cfg(any(
+    feature = "hash",
+    feature = "md5",
+    feature = "sha1",
+    feature = "crc32",
+    feature = "blake3"
+))]
+pub fn par_hash_all<Path: AsPath + Sync>(
+    file_paths: &[Path],
+    algorithm: HashAlgorithm,
+) -> Vec<Result<String>> {
+    file_paths
+        .par_iter()
+        .map(|path| hash(path, algorithm))
+        .collect()
+}
+
+/// Makes a destination directory mirror a source directory like `sync_dir`, comparing and copying the files
+/// concurrently over rayon's thread pool once the directories are walked. It stops at the first error, like `sync_dir`,
+/// though the copies already in flight finish. **Requires the `rayon` feature**.
+///
+/// # Parameters
+/// - `source`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `dest`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `options`: **borrowed** `SyncOptions`, such as `&SyncOptions::new().delete_extraneous(true)`
+///
+/// # Returns
+/// Result<`SyncReport`> of the files added, updated, deleted and left unchanged
+///
+/// # Examples
+/// ```
+/// use file_access::SyncOptions;
+/// use std::path::PathBuf;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let options = SyncOptions::new().checksum(true);
+///
+///         let report = file_access::par_sync_dir(&"src", &"par_sync_dir_example/src", &options)?;
+///         assert!(report.added.contains(&PathBuf::from("lib.rs")));
+///
+///         // Clean-up:
+///         file_access::delete(&"par_sync_dir_example")?;
+///     })
+/// }
+/// ```
+pub fn par_sync_dir<Source: AsPath + ?Sized, Dest: AsPath + ?Sized>(
+    source: &Source,
+    dest: &Dest,
+    options: &SyncOptions,
+) -> Result<SyncReport> {
+    mirror("par_sync_dir", source, dest, options, true, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn par_for_each_file() -> Result<()> {
+        Ok({
+            // Arrange
+            let mut paths: Vec<_> = (0..50)
+                .map(|i| format!("par_for_each_file/{i}.txt"))
+                .collect();
+            for path in &paths {
+                write_string(path, &"x")?;
+            }
+            paths.insert(10, "par_for_each_file/missing.txt".to_string());
+
+            // Action
+            let report = super::par_for_each_file(&paths, |file| {
+                file.read_string()
+                    .and_then(|text| file.write_string(&(text + "y")))
+            });
+            #[cfg(feature = "hash")]
+            let hashes = par_hash_all(&paths[..2], HashAlgorithm::Sha256);
+
+            // Assert
+            assert_eq!(report.succeeded.len(), 50);
+            assert_eq!(
+                report.succeeded[10],
+                PathBuf::from("par_for_each_file/10.txt")
+            );
+            assert_eq!(
+                report.failed[0].0,
+                PathBuf::from("par_for_each_file/missing.txt")
+            );
+            assert_eq!(read_string(&"par_for_each_file/49.txt")?, "xy");
+            #[cfg(feature = "hash")]
+            assert_eq!(
+                hashes[0].as_ref().ok(),
+                Some(&hash(&paths[0], HashAlgorithm::Sha256)?)
+            );
+
+            // Clean-up
+            delete(&"par_for_each_file")?;
+        })
+    }
+
+    #[test]
+    fn par_copy_dir() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"par_copy_dir/src/a.txt", &"a")?;
+            write_string(&"par_copy_dir/src/sub/b.txt", &"b")?;
+            write_string(&"par_copy_dir/src/sub/deeper/c.txt", &"c")?;
+
+            // Action
+            let report = super::par_copy_dir(&"par_copy_dir/src", &"par_copy_dir/dest")?;
+            let synced = par_sync_dir(
+                &"par_copy_dir/src",
+                &"par_copy_dir/dest",
+                &SyncOptions::new(),
+            )?;
+            let missing = super::par_copy_dir(&"par_copy_dir/missing", &"par_copy_dir/dest");
+
+            // Assert
+            assert_eq!(report.succeeded.len(), 3);
+            assert!(report.is_ok());
+            assert_eq!(read_string(&"par_copy_dir/dest/sub/deeper/c.txt")?, "c");
+            assert!(synced.added.is_empty());
+            assert_eq!(synced.updated.len(), 3);
+            assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+
+            // Clean-up
+            delete(&"par_copy_dir")?;
+        })
+    }
+}
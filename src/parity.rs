@@ -0,0 +1,321 @@
+use crate::*;
+use reed_solomon_erasure::galois_8::ReedSolomon;
+use sha2::{Digest, Sha256};
+use std::io::{BufRead, BufReader, BufWriter, Seek, SeekFrom};
+
+// The start of a parity file's header, followed by the protected file's length, the block size,
+// and the numbers of data and parity blocks per stripe
+const HEADER: &str = "file_access parity v1";
+// The unit of damage that can be detected and repaired
+const BLOCK_SIZE: usize = 4096;
+// How many blocks of the file each stripe of parity blocks protects
+const DATA_BLOCKS: usize = 20;
+
+fn invalid<Message: AsRef<str>>(message: Message) -> Error {
+    Error::new(ErrorKind::InvalidData, message.as_ref())
+}
+
+// Gets the path of the parity file of a file
+fn parity_path_of(path: &std::path::Path) -> PathBuf {
+    let mut parity_path = path.as_os_str().to_owned();
+    parity_path.push(".par");
+    PathBuf::from(parity_path)
+}
+
+fn checksum(block: &[u8]) -> [u8; 32] {
+    Sha256::digest(block).into()
+}
+
+// Reads the data blocks of a stripe, padding what lies past the end of the file with zeros
+fn read_stripe(file: &mut File, stripe: u64) -> Result<Vec<Vec<u8>>> {
+    file.seek(SeekFrom::Start(stripe * (DATA_BLOCKS * BLOCK_SIZE) as u64))?;
+    let mut data = vec![0; DATA_BLOCKS * BLOCK_SIZE];
+    let mut filled = 0;
+    loop {
+        match file.read(&mut data[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    return Ok(data.chunks(BLOCK_SIZE).map(<[u8]>::to_vec).collect());
+}
+
+// Writes the parity file of a file, with `parity_blocks` parity blocks per stripe
+fn write_parity(path: &std::path::Path, parity_blocks: usize) -> Result<()> {
+    let codec = ReedSolomon::new(DATA_BLOCKS, parity_blocks)
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("{e:?}")))?;
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let stripes = len.div_ceil((DATA_BLOCKS * BLOCK_SIZE) as u64);
+
+    let parity_path = parity_path_of(path);
+    let temp = temp_sibling(&parity_path);
+    let result = (|| {
+        let mut out = BufWriter::new(File::create(&temp)?);
+        writeln!(
+            out,
+            "{HEADER} {len} {BLOCK_SIZE} {DATA_BLOCKS} {parity_blocks}"
+        )?;
+        for stripe in 0..stripes {
+            let mut shards = read_stripe(&mut file, stripe)?;
+            shards.extend((0..parity_blocks).map(|_| vec![0; BLOCK_SIZE]));
+            codec
+                .encode(&mut shards)
+                .map_err(|e| invalid(format!("{e:?}")))?;
+            for shard in &shards {
+                out.write_all(&checksum(shard))?;
+            }
+            for shard in &shards[DATA_BLOCKS..] {
+                out.write_all(shard)?;
+            }
+        }
+        out.into_inner()?.sync_all()?;
+        return fs::rename(&temp, &parity_path);
+    })();
+    if result.is_err() {
+        _ = fs::remove_file(&temp);
+    }
+    return Ok(result?);
+}
+
+/// Writes Reed-Solomon parity blocks for a file to `<file_path>.par`, so that `repair` can later reconstruct
+/// damaged regions, e.g. after bit rot on a flaky external disk. The file is divided into stripes of
+/// 20 blocks of 4 KiB, and `redundancy` is the size of the parity relative to the file, in percent:
+/// with `10`, each stripe gets 2 parity blocks and survives the damage of any 2 of its blocks.
+/// **Requires the `parity` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `redundancy`: **owned** `u8`, from `1` to `100` percent
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "protect_example.tar";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"precious archive ".repeat(10_000))?;
+///
+///         file_access::protect(&file_path, 10)?;
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///         file_access::delete(&"protect_example.tar.par")?;
+///     })
+/// }
+/// ```
+pub fn protect<Path: AsPath + ?Sized>(file_path: &Path, redundancy: u8) -> Result<()> {
+    if !(1..=100).contains(&redundancy) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "the redundancy must be from 1 to 100 percent",
+        ));
+    }
+    let parity_blocks = (DATA_BLOCKS * redundancy as usize).div_ceil(100);
+
+    return write_parity(&path_of(file_path), parity_blocks).context("protect", file_path);
+}
+
+/// Checks a file against the parity written by `protect`, and rewrites any damaged blocks (and truncated or
+/// extended tails) from the parity. Damaged parity blocks are regenerated too. Returns an `InvalidData` error
+/// when a stripe has more damaged blocks than parity blocks. **Requires the `parity` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`usize`> of how many blocks of the file were repaired, `0` when it was intact
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "repair_example.tar";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"precious archive ".repeat(10_000))?;
+///         file_access::protect(&file_path, 10)?;
+///
+///         // Bit rot:
+///         let rotten = file_access::read_string(&file_path)?.replacen("precious", "PRECIOUS", 1);
+///         file_access::write_string(&file_path, &rotten)?;
+///
+///         assert_eq!(file_access::repair(&file_path)?, 1);
+///         assert!(file_access::read_string(&file_path)?.starts_with("precious"));
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///         file_access::delete(&"repair_example.tar.par")?;
+///     })
+/// }
+/// ```
+pub fn repair<Path: AsPath + ?Sized>(file_path: &Path) -> Result<usize> {
+    return repair_file(&path_of(file_path)).context("repair", file_path);
+}
+
+// Repairs a file from its parity file, returning how many of its blocks were repaired
+fn repair_file(path: &std::path::Path) -> Result<usize> {
+    let mut parity = BufReader::new(File::open(parity_path_of(path))?);
+    let mut header = String::new();
+    parity.read_line(&mut header)?;
+    let numbers: Vec<usize> = header
+        .strip_prefix(HEADER)
+        .ok_or_else(|| invalid(format!("not a parity header: {header:?}")))?
+        .split_whitespace()
+        .map(|n| {
+            n.parse()
+                .map_err(|_| invalid(format!("not a parity header: {header:?}")))
+        })
+        .collect::<Result<_>>()?;
+    // Only the block layout of this version is supported
+    let [len, BLOCK_SIZE, DATA_BLOCKS, parity_blocks] = numbers[..] else {
+        return Err(invalid(format!("unsupported parity header: {header:?}")));
+    };
+    let codec =
+        ReedSolomon::new(DATA_BLOCKS, parity_blocks).map_err(|e| invalid(format!("{e:?}")))?;
+
+    let mut file = File::options().read(true).write(true).open(path)?;
+    let stripes = (len as u64).div_ceil((DATA_BLOCKS * BLOCK_SIZE) as u64);
+    let (mut repaired, mut parity_damaged) = (0, false);
+    for stripe in 0..stripes {
+        let mut checksums = vec![[0; 32]; DATA_BLOCKS + parity_blocks];
+        for checksum in &mut checksums {
+            parity.read_exact(checksum)?;
+        }
+        let mut shards = read_stripe(&mut file, stripe)?;
+        for _ in 0..parity_blocks {
+            let mut shard = vec![0; BLOCK_SIZE];
+            parity.read_exact(&mut shard)?;
+            shards.push(shard);
+        }
+
+        let mut shards: Vec<Option<Vec<u8>>> = shards
+            .into_iter()
+            .zip(&checksums)
+            .map(|(shard, expected)| (&checksum(&shard) == expected).then_some(shard))
+            .collect();
+        let damaged: Vec<usize> = (0..shards.len()).filter(|&i| shards[i].is_none()).collect();
+        if damaged.is_empty() {
+            continue;
+        }
+        if damaged.len() > parity_blocks {
+            return Err(invalid(format!(
+                "stripe {stripe} has {} damaged blocks, more than its {parity_blocks} parity blocks can repair",
+                damaged.len()
+            )));
+        }
+        codec
+            .reconstruct(&mut shards)
+            .map_err(|e| invalid(format!("{e:?}")))?;
+
+        for i in damaged {
+            if i >= DATA_BLOCKS {
+                parity_damaged = true;
+                continue;
+            }
+            let offset = (stripe as usize * DATA_BLOCKS + i) * BLOCK_SIZE;
+            if offset >= len {
+                continue; // only padding past the end of the file, e.g. if the file was extended
+            }
+            let block = shards[i].as_ref().unwrap();
+            file.seek(SeekFrom::Start(offset as u64))?;
+            file.write_all(&block[..BLOCK_SIZE.min(len - offset)])?;
+            repaired += 1;
+        }
+    }
+    file.set_len(len as u64)?;
+    file.sync_all()?;
+    if parity_damaged {
+        write_parity(path, parity_blocks)?;
+    }
+
+    return Ok(repaired);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    // Overwrites bytes of a file in place
+    fn corrupt(file_path: &str, offset: u64, bytes: &[u8]) -> Result<()> {
+        let mut file = File::options().write(true).open(file_path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        return file.write_all(bytes);
+    }
+
+    #[test]
+    fn protect_and_repair() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "parity_repair.bin";
+            let contents: Vec<u8> = (0..200_000u32).map(|i| (i * 7 % 251) as u8).collect();
+            fs::write(file_path, &contents)?;
+            protect(&file_path, 10)?;
+
+            // Action
+            corrupt(file_path, 10, b"rot")?;
+            corrupt(file_path, 50_000, b"more rot")?;
+            corrupt(file_path, 120_000, b"rot in another stripe")?;
+            let repaired = repair(&file_path)?;
+
+            // Assert
+            assert_eq!(repaired, 3);
+            assert_eq!(fs::read(file_path)?, contents);
+            assert_eq!(repair(&file_path)?, 0);
+
+            // Clean-up
+            delete(&file_path)?;
+            delete(&"parity_repair.bin.par")?;
+        })
+    }
+
+    #[test]
+    fn repair_truncated() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "parity_truncated.txt";
+            let contents = "0123456789".repeat(1_000);
+            write_string(&file_path, &contents)?;
+            protect(&file_path, 20)?;
+
+            // Action
+            File::options()
+                .write(true)
+                .open(file_path)?
+                .set_len(5_000)?;
+            repair(&file_path)?;
+
+            // Assert
+            assert_eq!(read_string(&file_path)?, contents);
+
+            // Clean-up
+            delete(&file_path)?;
+            delete(&"parity_truncated.txt.par")?;
+        })
+    }
+
+    #[test]
+    fn repair_beyond_parity() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "parity_beyond.bin";
+            fs::write(file_path, vec![1; 100_000])?;
+            protect(&file_path, 5)?;
+
+            // Action
+            corrupt(file_path, 0, &[0])?;
+            corrupt(file_path, 10_000, &[0])?;
+            let result = repair(&file_path);
+
+            // Assert
+            assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidData);
+
+            // Clean-up
+            delete(&file_path)?;
+            delete(&"parity_beyond.bin.par")?;
+        })
+    }
+}
@@ -0,0 +1,177 @@
+use crate::*;
+use std::fs::Permissions;
+
+/// Queries the permissions of a file or directory, following symlinks, such as to check `readonly()`
+/// or, on Unix, read the mode bits through `PermissionsExt`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Permissions`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///
+///         let permissions: std::fs::Permissions = file_access::permissions(&file_path)?;
+///         assert!(!permissions.readonly());
+///     })
+/// }
+/// ```
+pub fn permissions<Path: AsPath + ?Sized>(file_path: &Path) -> Result<Permissions> {
+    fs::metadata(file_path.as_path())
+        .map(|metadata| metadata.permissions())
+        .context("permissions", file_path)
+}
+
+/// Makes a file read-only, or writable again, such as to protect a generated file from edits.
+/// On Unix, making a file read-only removes the write permission from everyone, and the opposite grants it back
+/// to the owner only (like `chmod u+w`), rather than to everyone; on Windows this sets or clears the read-only attribute.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `readonly`: **owned** `bool`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "set_readonly_example.lock";
+///         file_access::write_string(&file_path, &"generated")?;
+///
+///         file_access::set_readonly(&file_path, true)?;
+///         assert!(file_access::permissions(&file_path)?.readonly());
+///
+///         // Clean-up:
+///         file_access::set_readonly(&file_path, false)?;
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn set_readonly<Path: AsPath + ?Sized>(file_path: &Path, readonly: bool) -> Result<()> {
+    let mut permissions = permissions(file_path).context("set_readonly", file_path)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = permissions.mode();
+        permissions.set_mode(match readonly {
+            true => mode & !0o222,
+            false => mode | 0o200,
+        });
+    }
+    // Windows has no write permissions for others to open up by mistake
+    #[cfg(not(unix))]
+    #[allow(clippy::permissions_set_readonly_false)]
+    permissions.set_readonly(readonly);
+
+    return fs::set_permissions(file_path.as_path(), permissions)
+        .context("set_readonly", file_path);
+}
+
+/// Gets the permission bits of a file or directory, such as `0o644`, without the file type bits. **Unix only**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`u32`>
+#[cfg(unix)]
+pub fn mode<Path: AsPath + ?Sized>(file_path: &Path) -> Result<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    return Ok(permissions(file_path).context("mode", file_path)?.mode() & 0o7777);
+}
+
+/// Sets the permission bits of a file or directory, such as `0o644` or `0o600`, like `chmod`;
+/// unlike `WriteOptions::mode`, the umask doesn't apply. **Unix only**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `mode`: **owned** `u32`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// # #[cfg(unix)]
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "set_mode_example.key";
+///         file_access::write_string(&file_path, &"s3cr3t")?;
+///
+///         file_access::set_mode(&file_path, 0o600)?;
+///         assert_eq!(file_access::mode(&file_path)?, 0o600);
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// # #[cfg(not(unix))]
+/// # fn main() {}
+/// ```
+#[cfg(unix)]
+pub fn set_mode<Path: AsPath + ?Sized>(file_path: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(file_path.as_path(), Permissions::from_mode(mode))
+        .context("set_mode", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn set_readonly() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "set_readonly.txt";
+            write_string(&file_path, &"")?;
+
+            // Action
+            super::set_readonly(&file_path, true)?;
+            let readonly = permissions(&file_path)?.readonly();
+            super::set_readonly(&file_path, false)?;
+
+            // Assert
+            assert!(readonly);
+            assert!(!permissions(&file_path)?.readonly());
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn set_mode() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "set_mode.txt";
+            write_string(&file_path, &"")?;
+
+            // Action
+            super::set_mode(&file_path, 0o640)?;
+            super::set_readonly(&file_path, true)?;
+            let readonly = mode(&file_path)?;
+            super::set_readonly(&file_path, false)?;
+
+            // Assert
+            assert_eq!(readonly, 0o440);
+            assert_eq!(mode(&file_path)?, 0o640);
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+}
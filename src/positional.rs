@@ -0,0 +1,157 @@
+use crate::*;
+use std::io::{Seek, SeekFrom};
+
+/// Reads up to `len` bytes of a file starting at byte `offset`, such as a fixed-size record, without reading the rest.
+/// Fewer bytes are returned if the file ends first, and none if `offset` is past its end.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `offset`: **owned** `u64`
+/// - `len`: **owned** `usize`
+///
+/// # Returns
+/// Result<`Vec<u8>`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "read_bytes_at_example/records.bin";
+///         file_access::write_bytes(&file_path, &b"AAAABBBBCCCC")?;
+///
+///         assert_eq!(file_access::read_bytes_at(&file_path, 4, 4)?, b"BBBB");
+///
+///         // Clean-up:
+///         file_access::delete(&"read_bytes_at_example")?;
+///     })
+/// }
+/// ```
+pub fn read_bytes_at<Path: AsPath + ?Sized>(
+    file_path: &Path,
+    offset: u64,
+    len: usize,
+) -> Result<Vec<u8>> {
+    metered(
+        "read_bytes_at",
+        |buf: &Vec<u8>| (buf.len() as u64, 0),
+        || {
+            let mut file = File::open(file_path.as_path())?;
+            file.seek(SeekFrom::Start(offset))?;
+            let left = file.metadata()?.len().saturating_sub(offset);
+            let mut buf = Vec::with_capacity(left.min(len as u64) as usize);
+            file.take(len as u64).read_to_end(&mut buf)?;
+            return Ok(buf);
+        },
+    )
+    .context("read_bytes_at", file_path)
+}
+
+/// Reads the bytes of a file in a range of offsets, such as the header or magic bytes of a large file,
+/// seeking to the start of the range and reading only up to its end. The range is cut short where the file ends,
+/// and an empty range reads nothing.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `range`: **owned** `Range<u64>`, such as `0..4`
+///
+/// # Returns
+/// Result<`Vec<u8>`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "read_range_example/image.png";
+///         file_access::write_bytes(&file_path, &b"\x89PNG\r\n\x1a\n...")?;
+///
+///         let magic = file_access::read_range(&file_path, 1..4)?;
+///         assert_eq!(magic, b"PNG");
+///
+///         // Clean-up:
+///         file_access::delete(&"read_range_example")?;
+///     })
+/// }
+/// ```
+pub fn read_range<Path: AsPath + ?Sized>(file_path: &Path, range: Range<u64>) -> Result<Vec<u8>> {
+    let len = usize::try_from(range.end.saturating_sub(range.start))
+        .map_err(|x| Error::new(ErrorKind::InvalidInput, x))
+        .context("read_range", file_path)?;
+    read_bytes_at(file_path, range.start, len).context("read_range", file_path)
+}
+
+/// Writes bytes into an **existing** file starting at byte `offset`, overwriting just that span in place,
+/// such as to patch a fixed-size record without rewriting the whole file. Writing past the end extends the file,
+/// with zeros filling any gap.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `offset`: **owned** `u64`
+/// - `bytes`: **borrowed** `AsRef<[u8]>` such as `Vec<u8>`, `[u8; N]` or `&[u8]`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "write_bytes_at_example/records.bin";
+///         file_access::write_bytes(&file_path, &b"AAAABBBBCCCC")?;
+///
+///         file_access::write_bytes_at(&file_path, 4, &b"XXXX")?;
+///         assert_eq!(file_access::read_bytes(&file_path)?, b"AAAAXXXXCCCC");
+///
+///         // Clean-up:
+///         file_access::delete(&"write_bytes_at_example")?;
+///     })
+/// }
+/// ```
+pub fn write_bytes_at<Path: AsPath + ?Sized, Bytes: AsRef<[u8]> + ?Sized>(
+    file_path: &Path,
+    offset: u64,
+    bytes: &Bytes,
+) -> Result<()> {
+    let bytes = bytes.as_ref();
+    metered(
+        "write_bytes_at",
+        |_| (0, bytes.len() as u64),
+        || {
+            let mut file = File::options().write(true).open(file_path.as_path())?;
+            file.seek(SeekFrom::Start(offset))?;
+            return file.write_all(bytes);
+        },
+    )
+    .context("write_bytes_at", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn bytes_at() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "bytes_at/records.bin";
+            write_bytes(&file, &b"AAAABBBB")?;
+
+            // Action
+            write_bytes_at(&file, 4, &b"XX")?;
+            write_bytes_at(&file, 10, &b"CC")?;
+            let missing = write_bytes_at(&"bytes_at/missing.bin", 0, &b"X");
+
+            // Assert
+            assert_eq!(read_bytes(&file)?, b"AAAAXXBB\0\0CC");
+            assert_eq!(read_bytes_at(&file, 4, 4)?, b"XXBB");
+            assert_eq!(read_bytes_at(&file, 10, 4)?, b"CC");
+            assert!(read_bytes_at(&file, 20, 4)?.is_empty());
+            assert_eq!(read_range(&file, 2..6)?, b"AAXX");
+            assert!(read_range(&file, 6..6)?.is_empty());
+            assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+
+            // Clean-up
+            delete(&"bytes_at")?;
+        })
+    }
+}
@@ -0,0 +1,83 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    io::Error,
+    ops::BitOr,
+};
+
+/// Selects which file attributes, on top of the contents and permissions, are carried over by
+/// `copy_preserving`. Combine them with `|`, e.g. `Preserve::XATTRS | Preserve::ACLS`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Preserve(u8);
+
+impl Preserve {
+    /// Only the contents and permissions.
+    pub const NONE: Self = Self(0);
+    /// Extended attributes, such as `user.*` on Linux or `com.apple.*` on macOS.
+    /// ACLs and SELinux contexts are selected separately, even where they are stored as xattrs.
+    pub const XATTRS: Self = Self(1);
+    /// POSIX ACLs on Linux, or extended ACLs on macOS.
+    pub const ACLS: Self = Self(1 << 1);
+    /// File flags set by `chflags`, such as `hidden` or `uchg`. **macOS only**, ignored elsewhere.
+    pub const FLAGS: Self = Self(1 << 2);
+    /// The SELinux security context. **Linux only**, ignored elsewhere.
+    pub const SELINUX: Self = Self(1 << 3);
+    /// Everything the platform supports.
+    pub const ALL: Self = Self(Self::XATTRS.0 | Self::ACLS.0 | Self::FLAGS.0 | Self::SELINUX.0);
+
+    /// Returns `true` if all of the attributes in `other` are selected.
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for Preserve {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// An attribute that `copy_preserving` could not carry over to the destination.
+#[derive(Debug)]
+pub struct PreserveWarning {
+    /// The attribute, e.g. `xattr user.comment` or `acls`.
+    pub item: String,
+    /// Why it could not be preserved.
+    pub error: Error,
+}
+
+impl PreserveWarning {
+    pub(crate) fn new<Item: AsRef<str>>(item: Item, error: Error) -> Self {
+        Self {
+            item: item.as_ref().to_string(),
+            error,
+        }
+    }
+}
+
+impl Display for PreserveWarning {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.item, self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn preserve_all() -> Result<()> {
+        Ok({
+            // Action
+            let all = Preserve::XATTRS | Preserve::ACLS | Preserve::FLAGS | Preserve::SELINUX;
+
+            // Assert
+            assert_eq!(all, Preserve::ALL);
+            assert!(Preserve::ALL.contains(Preserve::ACLS));
+            assert!(!Preserve::XATTRS.contains(Preserve::ACLS));
+            assert!(Preserve::XATTRS.contains(Preserve::NONE));
+        })
+    }
+}
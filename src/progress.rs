@@ -0,0 +1,313 @@
+use crate::*;
+use std::path::Path as StdPath;
+
+// How much of a file is read at a time between progress updates
+const CHUNK: usize = 64 * 1024;
+
+/// Where a long operation such as `copy_with_progress` or `sync_dir_with_progress` is at, as passed to its `Progress` hook.
+#[derive(Clone, Copy, Debug)]
+pub struct ProgressUpdate<'a> {
+    /// The bytes copied or hashed so far, over the whole operation.
+    pub bytes: u64,
+    /// The bytes the whole operation will copy or hash, when they're known up front.
+    pub total_bytes: Option<u64>,
+    /// The files done so far, including those left as they were.
+    pub files: u64,
+    /// The file being worked on, or the last one done.
+    pub path: &'a StdPath,
+}
+
+/// A hook called as a long operation makes progress: after each chunk of a file is copied or hashed,
+/// and after each file is done, such as to drive a progress bar. Any `FnMut(&ProgressUpdate)` closure is one.
+///
+/// # Examples
+/// ```
+/// use file_access::ProgressUpdate;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let mut progress = |update: &ProgressUpdate| {
+///             if let Some(total) = update.total_bytes {
+///                 eprint!("\r{}: {}/{} bytes", update.path.display(), update.bytes, total);
+///             }
+///         };
+///         file_access::copy_with_progress(&"Cargo.toml", &"progress_example/Cargo.toml", &mut progress)?;
+///
+///         // Clean-up:
+///         file_access::delete(&"progress_example")?;
+///     })
+/// }
+/// ```
+pub trait Progress {
+    /// Called with where the operation is at.
+    fn update(&mut self, update: &ProgressUpdate);
+
+    /// Checked before each chunk and each file: returning `true` stops the operation, as a `CancelToken` does.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+impl<F: FnMut(&ProgressUpdate)> Progress for F {
+    fn update(&mut self, update: &ProgressUpdate) {
+        self(update)
+    }
+}
+
+// Keeps the running totals of an operation and reports them to its hook
+pub(crate) struct Tracker<'a> {
+    progress: &'a mut dyn Progress,
+    bytes: u64,
+    total_bytes: Option<u64>,
+    files: u64,
+}
+
+impl<'a> Tracker<'a> {
+    pub(crate) fn new(progress: &'a mut dyn Progress, total_bytes: Option<u64>) -> Self {
+        Self {
+            progress,
+            bytes: 0,
+            total_bytes,
+            files: 0,
+        }
+    }
+
+    fn report(&mut self, path: &StdPath) {
+        self.progress.update(&ProgressUpdate {
+            bytes: self.bytes,
+            total_bytes: self.total_bytes,
+            files: self.files,
+            path,
+        });
+    }
+
+    // Fails with `Cancelled` once the hook says to stop
+    pub(crate) fn check(&self) -> io::Result<()> {
+        if self.progress.is_cancelled() {
+            let cancelled = Cancelled {
+                bytes: self.bytes,
+                files: self.files,
+            };
+            return Err(cancelled.into());
+        }
+        return Ok(());
+    }
+
+    // Streams a reader into `sink` in chunks, reporting after each one
+    pub(crate) fn pump(
+        &mut self,
+        path: &StdPath,
+        reader: &mut impl Read,
+        mut sink: impl FnMut(&[u8]) -> io::Result<()>,
+    ) -> io::Result<u64> {
+        let mut buffer = vec![0; CHUNK];
+        let mut pumped = 0;
+        loop {
+            self.check()?;
+            match reader.read(&mut buffer) {
+                Ok(0) => return Ok(pumped),
+                Ok(n) => {
+                    sink(&buffer[..n])?;
+                    pumped += n as u64;
+                    self.bytes += n as u64;
+                    self.report(path);
+                }
+                Err(x) if x.kind() == ErrorKind::Interrupted => continue,
+                Err(x) => return Err(x),
+            }
+        }
+    }
+
+    // Counts a file as done
+    pub(crate) fn done(&mut self, path: &StdPath) {
+        self.files += 1;
+        self.report(path);
+    }
+
+    // Copies a file like `fs::copy`, creating the destination's directory path;
+    // if cancelled midway, the partial copy is deleted
+    pub(crate) fn copy(&mut self, from: &StdPath, to: &StdPath) -> io::Result<u64> {
+        self.check()?;
+        if let Some(dir) = to.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut source = File::open(from)?;
+        let mut dest = File::create(to)?;
+        let copied = match self.pump(from, &mut source, |chunk| dest.write_all(chunk)) {
+            Ok(copied) => copied,
+            Err(x) if x.get_ref().is_some_and(|e| e.is::<Cancelled>()) => {
+                drop(dest);
+                let _ = fs::remove_file(to);
+                return Err(x);
+            }
+            Err(x) => return Err(x),
+        };
+        dest.set_permissions(source.metadata()?.permissions())?;
+
+        return Ok(copied);
+    }
+}
+
+// Lists the files below a directory, sorted by name, with where they go below another, creating the directories on the way
+pub(crate) fn plan_copy(
+    from: &StdPath,
+    to: &StdPath,
+    files: &mut Vec<(PathBuf, PathBuf)>,
+) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    let mut entries = fs::read_dir(from)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let (from, to) = (entry.path(), to.join(entry.file_name()));
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() && from.is_dir() {
+            continue; // not followed, to not loop around cycles
+        }
+        match file_type.is_dir() {
+            true => plan_copy(&from, &to, files)?,
+            false => files.push((from, to)),
+        }
+    }
+    return Ok(());
+}
+
+/// Copies a file like `copy`, calling `progress` after each chunk copied, such as to show a progress bar
+/// for a large transfer. This function will create the destination's full directory path if it doesn't exist,
+/// and will entirely replace the contents of the destination if it already exists.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `progress`: **mutably borrowed** `Progress` such as a `FnMut(&ProgressUpdate)` closure
+///
+/// # Returns
+/// Result<`u64`>, the number of bytes copied
+///
+/// # Examples
+/// ```
+/// use file_access::ProgressUpdate;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let mut updates = 0;
+///         let copied = file_access::copy_with_progress(
+///             &"Cargo.toml",
+///             &"copy_with_progress_example/Cargo.toml",
+///             &mut |_: &ProgressUpdate| updates += 1,
+///         )?;
+///         assert_eq!(copied, file_access::stat(&"Cargo.toml")?.len());
+///         assert!(updates > 0);
+///
+///         // Clean-up:
+///         file_access::delete(&"copy_with_progress_example")?;
+///     })
+/// }
+/// ```
+pub fn copy_with_progress<From: AsPath + ?Sized, To: AsPath + ?Sized, P: Progress>(
+    from: &From,
+    to: &To,
+    progress: &mut P,
+) -> Result<u64> {
+    metered(
+        "copy_with_progress",
+        |copied: &u64| (*copied, *copied),
+        || {
+            let total = fs::metadata(from.as_path())?.len();
+            let mut tracker = Tracker::new(progress, Some(total));
+            let copied = tracker.copy(from.as_path(), to.as_path())?;
+            tracker.done(from.as_path());
+            return Ok(copied);
+        },
+    )
+    .context("copy_with_progress", from)
+}
+
+/// Copies a directory recursively, calling `progress` after each chunk copied and each file done, and stopping
+/// at the first file that fails. The directories are created first, including the destination's full directory path,
+/// and the total bytes to copy are known up front. Symbolic links are copied as the files they point to,
+/// and aren't followed into directories.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `progress`: **mutably borrowed** `Progress` such as a `FnMut(&ProgressUpdate)` closure
+///
+/// # Returns
+/// Result<`u64`>, the number of bytes copied
+///
+/// # Examples
+/// ```
+/// use file_access::ProgressUpdate;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let mut files = 0;
+///         file_access::copy_dir_with_progress(&"src", &"copy_dir_with_progress_example/src", &mut |update: &ProgressUpdate| {
+///             files = update.files;
+///         })?;
+///         assert!(files > 1);
+///
+///         // Clean-up:
+///         file_access::delete(&"copy_dir_with_progress_example")?;
+///     })
+/// }
+/// ```
+pub fn copy_dir_with_progress<From: AsPath + ?Sized, To: AsPath + ?Sized, P: Progress>(
+    from: &From,
+    to: &To,
+    progress: &mut P,
+) -> Result<u64> {
+    let mut files = vec![];
+    plan_copy(from.as_path(), to.as_path(), &mut files).context("copy_dir_with_progress", from)?;
+    let mut total = 0;
+    for (file, _) in &files {
+        total += fs::metadata(file)
+            .context("copy_dir_with_progress", file)?
+            .len();
+    }
+
+    let mut tracker = Tracker::new(progress, Some(total));
+    let mut copied = 0;
+    for (file, dest) in &files {
+        copied += tracker
+            .copy(file, dest)
+            .context("copy_dir_with_progress", file)?;
+        tracker.done(file);
+    }
+    return Ok(copied);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn copy_dir_with_progress() -> Result<()> {
+        Ok({
+            // Arrange
+            write_bytes(&"copy_dir_with_progress/src/a.bin", &vec![1; CHUNK + 1])?;
+            write_string(&"copy_dir_with_progress/src/sub/b.txt", &"b")?;
+            let mut updates = vec![];
+
+            // Action
+            let copied = super::copy_dir_with_progress(
+                &"copy_dir_with_progress/src",
+                &"copy_dir_with_progress/dest",
+                &mut |update: &ProgressUpdate| {
+                    updates.push((update.bytes, update.total_bytes, update.files))
+                },
+            )?;
+
+            // Assert
+            let total = Some(CHUNK as u64 + 2);
+            assert_eq!(copied, CHUNK as u64 + 2);
+            assert_eq!(updates.first(), Some(&(CHUNK as u64, total, 0)));
+            assert_eq!(updates.last(), Some(&(CHUNK as u64 + 2, total, 2)));
+            assert_eq!(read_string(&"copy_dir_with_progress/dest/sub/b.txt")?, "b");
+
+            // Clean-up
+            delete(&"copy_dir_with_progress")?;
+        })
+    }
+}
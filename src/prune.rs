@@ -0,0 +1,91 @@
+use crate::*;
+use std::path::Path as StdPath;
+
+// Prunes the empty directories under `dir`, bottom-up, returning whether `dir` is left empty
+fn prune(dir: &StdPath, removed: &mut Vec<FilePath>) -> io::Result<bool> {
+    let mut empty = true;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        // Symlinks to directories are left alone, along with what they point to
+        if !entry.file_type()?.is_dir() || !prune(&entry.path(), removed)? {
+            empty = false;
+            continue;
+        }
+        match fs::remove_dir(entry.path()) {
+            Ok(()) => removed.push(FilePath::from(entry.path())),
+            // Something was written into it in the meantime
+            Err(x) if x.kind() == ErrorKind::DirectoryNotEmpty => empty = false,
+            Err(x) => return Err(x),
+        }
+    }
+    return Ok(empty);
+}
+
+/// Deletes every empty directory under `root`, bottom-up, so that directories holding nothing but empty directories
+/// go too, such as the ones left behind once files are moved out or deleted. The root itself is kept, even if it ends
+/// up empty, and symlinks to directories aren't followed.
+///
+/// # Parameters
+/// - `root`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`Vec<FilePath>`> of the directories deleted, each listed before its parent
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"prune_example/keep/a.txt", &"a")?;
+///         file_access::create_dir_all(&"prune_example/empty/nested")?;
+///
+///         let removed = file_access::prune_empty_dirs(&"prune_example")?;
+///         assert_eq!(removed.len(), 2);
+///         assert!(file_access::FilePath::access(&"prune_example/keep").is_dir());
+///
+///         // Clean-up:
+///         file_access::delete(&"prune_example")?;
+///     })
+/// }
+/// ```
+pub fn prune_empty_dirs<Path: AsPath + ?Sized>(root: &Path) -> Result<Vec<FilePath>> {
+    let mut removed = vec![];
+    prune(root.as_path(), &mut removed).context("prune_empty_dirs", root)?;
+    return Ok(removed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn prune_empty_dirs() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"prune/a/keep.txt", &"a")?;
+            create_dir_all(&"prune/a/empty")?;
+            create_dir_all(&"prune/b/c/d")?;
+
+            // Action
+            let removed: Vec<_> = super::prune_empty_dirs(&"prune")?
+                .iter()
+                .map(path_of)
+                .collect();
+
+            // Assert
+            assert_eq!(removed.len(), 4);
+            assert!(removed.contains(&PathBuf::from("prune/a/empty")));
+            let (d, b) = (
+                removed.iter().position(|x| x.ends_with("b/c/d")),
+                removed.iter().position(|x| x.ends_with("prune/b")),
+            );
+            assert!(d < b, "children should be removed before their parents");
+            assert!(FilePath::access(&"prune/a").is_dir());
+            assert!(!FilePath::access(&"prune").is_empty_dir());
+            assert!(super::prune_empty_dirs(&"prune/missing").is_err());
+
+            // Clean-up
+            delete(&"prune")?;
+        })
+    }
+}
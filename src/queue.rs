@@ -0,0 +1,326 @@
+use crate::*;
+use std::io::{Seek, SeekFrom};
+
+// Compacts once the consumed part of the segment is at least this large, and at least half of it
+const COMPACT_AFTER: u64 = 1024 * 1024;
+
+// Where the queue is at: which segment file holds the items, and where its unconsumed items start and end.
+// It is replaced atomically and synced to disk after each change, so that a crash or power loss mid-operation
+// leaves the previous state intact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct State {
+    segment: u64,
+    head: u64,
+    tail: u64,
+}
+
+/// A durable first-in, first-out work queue backed by a directory, safe to share across threads and processes.
+/// Items are appended to a segment file as length-prefixed records, a separate state file tracks how far
+/// they were consumed, and the consumed part of the segment is compacted away periodically.
+///
+/// # Examples
+/// ```
+/// use file_access::queue::FileQueue;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let queue = FileQueue::open(&"queue_example")?;
+///         queue.push(&"first job")?;
+///         queue.push(&vec![1, 2, 3])?;
+///
+///         assert_eq!(queue.len()?, 2);
+///         assert_eq!(queue.pop()?, Some(b"first job".to_vec()));
+///         assert_eq!(queue.peek()?, Some(vec![1, 2, 3]));
+///
+///         // Clean-up:
+///         file_access::delete(&"queue_example")?;
+///     })
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FileQueue {
+    dir: PathBuf,
+}
+
+fn invalid<Message: AsRef<str>>(message: Message) -> Error {
+    Error::new(ErrorKind::InvalidData, message.as_ref())
+}
+
+impl FileQueue {
+    /// Opens the queue stored in a directory, creating it (and its full directory path) if it doesn't exist.
+    ///
+    /// # Parameters
+    /// - `dir_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+    ///
+    /// # Returns
+    /// Result<`FileQueue`>
+    pub fn open<Path: AsPath + ?Sized>(dir_path: &Path) -> Result<Self> {
+        let dir = path_of(dir_path);
+        fs::create_dir_all(&dir).context("FileQueue::open", dir_path)?;
+
+        return Ok(Self { dir });
+    }
+
+    // Runs `f` while holding the lock
+    fn locked<T>(&self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        let _lock = internal::lock::exclusive(&self.dir.join("lock"))?;
+        return f();
+    }
+
+    fn segment_path(&self, segment: u64) -> PathBuf {
+        self.dir.join(format!("segment.{segment}"))
+    }
+
+    fn read_state(&self) -> Result<State> {
+        let text = match fs::read_to_string(self.dir.join("state")) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Ok(State {
+                    segment: 0,
+                    head: 0,
+                    tail: 0,
+                })
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let numbers: Vec<u64> = text
+            .split_whitespace()
+            .map(|n| {
+                n.parse()
+                    .map_err(|_| invalid(format!("not a queue state: {text:?}")))
+            })
+            .collect::<Result<_>>()?;
+        let [segment, head, tail] = numbers[..] else {
+            return Err(invalid(format!("not a queue state: {text:?}")));
+        };
+
+        return Ok(State {
+            segment,
+            head,
+            tail,
+        });
+    }
+
+    fn write_state(&self, state: State) -> Result<()> {
+        let text = format!("{} {} {}\n", state.segment, state.head, state.tail);
+        Ok(write_via_rename(
+            &self.dir.join("state"),
+            text.as_bytes(),
+            true,
+        )?)
+    }
+
+    // Reads the record starting at `offset`, returning it along with where the next record starts
+    fn read_record(&self, state: State, offset: u64) -> Result<(Vec<u8>, u64)> {
+        let mut segment = File::open(self.segment_path(state.segment))?;
+        segment.seek(SeekFrom::Start(offset))?;
+        let mut len = [0; 4];
+        segment.read_exact(&mut len)?;
+        let len = u32::from_le_bytes(len) as u64;
+        if offset + 4 + len > state.tail {
+            return Err(invalid("a queue record runs past the end of the queue"));
+        }
+        let mut item = vec![0; len as usize];
+        segment.read_exact(&mut item)?;
+
+        return Ok((item, offset + 4 + len));
+    }
+
+    // Moves the unconsumed items to a new segment, which becomes current when the state is replaced
+    fn compact(&self, state: State) -> Result<State> {
+        let mut items = vec![0; (state.tail - state.head) as usize];
+        let mut segment = File::open(self.segment_path(state.segment))?;
+        segment.seek(SeekFrom::Start(state.head))?;
+        segment.read_exact(&mut items)?;
+
+        let compacted = State {
+            segment: state.segment + 1,
+            head: 0,
+            tail: items.len() as u64,
+        };
+        let mut next = File::create(self.segment_path(compacted.segment))?;
+        next.write_all(&items)?;
+        next.sync_all()?;
+        self.write_state(compacted)?;
+        _ = fs::remove_file(self.segment_path(state.segment));
+
+        return Ok(compacted);
+    }
+
+    /// Adds an item to the back of the queue.
+    ///
+    /// # Parameters
+    /// - `item`: **borrowed** `AsRef<[u8]>` such as `&str`, `String` or `Vec<u8>`
+    ///
+    /// # Returns
+    /// Result<`()`>
+    pub fn push<Item: AsRef<[u8]>>(&self, item: &Item) -> Result<()> {
+        let item = item.as_ref();
+        let len = u32::try_from(item.len())
+            .map_err(|_| Error::new(ErrorKind::InvalidInput, "a queue item must be under 4 GiB"))
+            .context("FileQueue::push", &self.dir)?;
+
+        self.locked(|| {
+            let mut state = self.read_state()?;
+            let mut segment = File::options()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(self.segment_path(state.segment))?;
+            // Writes at the recorded tail, over whatever a crashed push may have left there
+            segment.seek(SeekFrom::Start(state.tail))?;
+            segment.write_all(&len.to_le_bytes())?;
+            segment.write_all(item)?;
+            segment.sync_data()?;
+            state.tail += 4 + len as u64;
+
+            return self.write_state(state);
+        })
+        .context("FileQueue::push", &self.dir)
+    }
+
+    /// Removes and returns the item at the front of the queue, or `None` if it's empty.
+    ///
+    /// # Returns
+    /// Result<`Option<Vec<u8>>`>
+    pub fn pop(&self) -> Result<Option<Vec<u8>>> {
+        self.locked(|| {
+            let mut state = self.read_state()?;
+            if state.head == state.tail {
+                return Ok(None);
+            }
+            let (item, next) = self.read_record(state, state.head)?;
+            state.head = next;
+            if state.head >= COMPACT_AFTER && state.head >= state.tail / 2 {
+                self.compact(state)?;
+            } else {
+                self.write_state(state)?;
+            }
+
+            return Ok(Some(item));
+        })
+        .context("FileQueue::pop", &self.dir)
+    }
+
+    /// Returns the item at the front of the queue without removing it, or `None` if it's empty.
+    ///
+    /// # Returns
+    /// Result<`Option<Vec<u8>>`>
+    pub fn peek(&self) -> Result<Option<Vec<u8>>> {
+        self.locked(|| {
+            let state = self.read_state()?;
+            if state.head == state.tail {
+                return Ok(None);
+            }
+            return Ok(Some(self.read_record(state, state.head)?.0));
+        })
+        .context("FileQueue::peek", &self.dir)
+    }
+
+    /// Counts the items in the queue, by walking the record headers.
+    ///
+    /// # Returns
+    /// Result<`usize`>
+    pub fn len(&self) -> Result<usize> {
+        self.locked(|| {
+            let state = self.read_state()?;
+            if state.head == state.tail {
+                return Ok(0);
+            }
+            let mut segment = File::open(self.segment_path(state.segment))?;
+            let (mut offset, mut count) = (state.head, 0);
+            while offset < state.tail {
+                segment.seek(SeekFrom::Start(offset))?;
+                let mut len = [0; 4];
+                segment.read_exact(&mut len)?;
+                offset += 4 + u32::from_le_bytes(len) as u64;
+                count += 1;
+            }
+            return Ok(count);
+        })
+        .context("FileQueue::len", &self.dir)
+    }
+
+    /// Checks whether the queue has no items.
+    ///
+    /// # Returns
+    /// Result<`bool`>
+    pub fn is_empty(&self) -> Result<bool> {
+        self.locked(|| {
+            let state = self.read_state()?;
+            return Ok(state.head == state.tail);
+        })
+        .context("FileQueue::is_empty", &self.dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn push_pop_peek() -> Result<()> {
+        Ok({
+            // Arrange
+            let queue = FileQueue::open(&"queue_basics")?;
+
+            // Action
+            queue.push(&"a")?;
+            queue.push(&String::from("bb"))?;
+            queue.push(&Vec::<u8>::new())?;
+
+            // Assert
+            assert_eq!(queue.len()?, 3);
+            assert_eq!(queue.peek()?, Some(b"a".to_vec()));
+            assert_eq!(queue.pop()?, Some(b"a".to_vec()));
+            let reopened = FileQueue::open(&"queue_basics")?;
+            assert_eq!(reopened.pop()?, Some(b"bb".to_vec()));
+            assert_eq!(reopened.pop()?, Some(vec![]));
+            assert_eq!(reopened.pop()?, None);
+            assert!(reopened.is_empty()?);
+            write_string(&"queue_basics/state", &"garbage")?;
+            let error = reopened.pop().unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+            assert_eq!(error.operation, Some("FileQueue::pop"));
+            assert_eq!(error.path, Some(PathBuf::from("queue_basics")));
+
+            // Clean-up
+            delete(&"queue_basics")?;
+        })
+    }
+
+    #[test]
+    fn compaction() -> Result<()> {
+        Ok({
+            // Arrange
+            let queue = FileQueue::open(&"queue_compaction")?;
+            let item = vec![7; 64 * 1024];
+            for _ in 0..20 {
+                queue.push(&item)?;
+            }
+
+            // Action
+            for _ in 0..17 {
+                queue.pop()?;
+            }
+
+            // Assert
+            assert_eq!(
+                queue.read_state()?.segment,
+                1,
+                "the queue should have been compacted"
+            );
+            assert!(!queue.segment_path(0).exists());
+            assert_eq!(queue.len()?, 3);
+            queue.push(&"last")?;
+            assert_eq!(queue.pop()?, Some(item.clone()));
+            assert_eq!(queue.pop()?, Some(item.clone()));
+            assert_eq!(queue.pop()?, Some(item));
+            assert_eq!(queue.pop()?, Some(b"last".to_vec()));
+
+            // Clean-up
+            delete(&"queue_compaction")?;
+        })
+    }
+}
@@ -0,0 +1,180 @@
+use crate::*;
+
+/// Replaces every occurrence of a string in a file, like `sed 's/from/to/g'`, and returns how many were replaced.
+/// The file is rewritten atomically, like `write_string_atomic`, keeping its permissions, and only if anything was replaced.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `from`: **borrowed** `AsRef<str>` such as `String` or `&str`, which can't be empty
+/// - `to`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// Result<`usize`> of how many occurrences were replaced
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "replace_string_example.toml";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"host = \"staging\"\nbackup = \"staging\"")?;
+///
+///         let replaced = file_access::replace_string(&file_path, &"staging", &"production")?;
+///         assert_eq!(replaced, 2);
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn replace_string<Path: AsPath + ?Sized, From: AsRef<str> + ?Sized, To: AsRef<str> + ?Sized>(
+    file_path: &Path,
+    from: &From,
+    to: &To,
+) -> Result<usize> {
+    let from = from.as_ref();
+    if from.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "can't replace an empty string",
+        ))
+        .context("replace_string", file_path);
+    }
+    let text = read_string(file_path)?;
+    let count = text.matches(from).count();
+    if count > 0 {
+        write_string_atomic(file_path, &text.replace(from, to.as_ref()), false)?;
+    }
+
+    return Ok(count);
+}
+
+/// Replaces every match of a regular expression in a file and returns how many were replaced.
+/// The replacement may refer to capture groups, as `$1` or `${name}`. The file is rewritten atomically,
+/// like `write_string_atomic`, keeping its permissions, and only if anything was replaced. **Requires the `regex` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `pattern`: **borrowed** `AsRef<str>` such as `String` or `&str`, in the `regex` crate's syntax
+/// - `replacement`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// Result<`usize`> of how many matches were replaced, failing with `ErrorKind::InvalidInput` if the pattern is invalid
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "replace_regex_example.txt";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"version = 1.2.3")?;
+///
+///         let replaced = file_access::replace_regex(&file_path, &r"(\d+)\.(\d+)\.\d+", &"$1.$2.4")?;
+///         assert_eq!(replaced, 1);
+///         assert_eq!(file_access::read_string(&file_path)?, "version = 1.2.4");
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+#[cfg(feature = "regex")]
+pub fn replace_regex<
+    Path: AsPath + ?Sized,
+    Pattern: AsRef<str> + ?Sized,
+    Replacement: AsRef<str> + ?Sized,
+>(
+    file_path: &Path,
+    pattern: &Pattern,
+    replacement: &Replacement,
+) -> Result<usize> {
+    let regex = regex::Regex::new(pattern.as_ref())
+        .map_err(|x| Error::new(ErrorKind::InvalidInput, x))
+        .context("replace_regex", file_path)?;
+    let text = read_string(file_path)?;
+    let count = regex.find_iter(&text).count();
+    if count > 0 {
+        let replaced = regex.replace_all(&text, replacement.as_ref());
+        write_string_atomic(file_path, &replaced, false)?;
+    }
+
+    return Ok(count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn replace_string() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "replace_string/file_access.txt";
+            write_string(&file, &"one fish, two fish")?;
+
+            // Action
+            let replaced = super::replace_string(&file, &"fish", &"cats")?;
+            let none = super::replace_string(&file, &"fish", &"cats")?;
+
+            // Assert
+            assert_eq!((replaced, none), (2, 0));
+            assert_eq!(read_string(&file)?, "one cats, two cats");
+            assert_eq!(
+                super::replace_string(&file, &"", &"x").unwrap_err().kind(),
+                ErrorKind::InvalidInput
+            );
+
+            // Clean-up
+            delete(&"replace_string")?;
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn replace_regex() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "replace_regex/file_access.txt";
+            write_string(&file, &"2024-01-31\n2025-12-01")?;
+
+            // Action
+            let replaced = super::replace_regex(&file, &r"(\d{4})-(\d{2})-(\d{2})", &"$3/$2/$1")?;
+
+            // Assert
+            assert_eq!(replaced, 2);
+            assert_eq!(read_string(&file)?, "31/01/2024\n01/12/2025");
+            assert_eq!(
+                super::replace_regex(&file, &"(", &"").unwrap_err().kind(),
+                ErrorKind::InvalidInput
+            );
+
+            // Clean-up
+            delete(&"replace_regex")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn replace_keeps_permissions() -> Result<()> {
+        use std::{fs, os::unix::fs::PermissionsExt};
+
+        Ok({
+            // Arrange
+            let file = "replace_mode/script.sh";
+            write_string(&file, &"echo staging")?;
+            fs::set_permissions(file, fs::Permissions::from_mode(0o700))?;
+
+            // Action
+            super::replace_string(&file, &"staging", &"production")?;
+            #[cfg(feature = "regex")]
+            super::replace_regex(&file, &"prod(uction)", &"prod")?;
+
+            // Assert
+            assert_eq!(fs::metadata(file)?.permissions().mode() & 0o7777, 0o700);
+
+            // Clean-up
+            delete(&"replace_mode")?;
+        })
+    }
+}
@@ -0,0 +1,210 @@
+use crate::*;
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// How `retry` retries: how many times, and how long it backs off in between.
+/// Each delay doubles the previous one, up to `max_delay`, and is randomized by up to half
+/// so that processes contending for the same file don't retry in lockstep.
+///
+/// # Examples
+/// ```
+/// use file_access::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy {
+///     max_attempts: 10,
+///     ..RetryPolicy::default()
+/// };
+/// assert_eq!(policy.initial_delay, Duration::from_millis(10));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// How many times to try at most, including the first one.
+    pub max_attempts: u32,
+    /// The delay before the first retry.
+    pub initial_delay: Duration,
+    /// The longest delay between two tries.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Tries 5 times, backing off from 10 milliseconds up to 1 second.
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Tells whether an error is transient, i.e. whether the operation may succeed if tried again:
+/// interruptions, timeouts, busy or locked files (including Windows sharing violations, as caused by antivirus
+/// scanners and indexers) and resources that are temporarily unavailable. Anything else, such as a missing file,
+/// a permission error or invalid data, is fatal. An operation stopped by a `CancelToken` isn't retried either,
+/// though it's `Interrupted` too.
+///
+/// # Parameters
+/// - `error`: **borrowed** `file_access::Error`
+///
+/// # Returns
+/// `bool`
+///
+/// # Examples
+/// ```
+/// use file_access::Error;
+/// use std::io::ErrorKind;
+///
+/// assert!(file_access::is_retryable(&Error::from(ErrorKind::Interrupted)));
+/// assert!(!file_access::is_retryable(&Error::from(ErrorKind::NotFound)));
+/// ```
+pub fn is_retryable(error: &Error) -> bool {
+    if error.get_ref().is_some_and(|e| e.is::<Cancelled>()) {
+        return false;
+    }
+    match error.kind() {
+        ErrorKind::Interrupted
+        | ErrorKind::WouldBlock
+        | ErrorKind::TimedOut
+        | ErrorKind::ResourceBusy => return true,
+        _ => {}
+    }
+
+    #[cfg(unix)]
+    return matches!(
+        error.raw_os_error(),
+        Some(libc::EAGAIN | libc::EBUSY | libc::ETXTBSY | libc::EINTR)
+    );
+    #[cfg(windows)]
+    return matches!(
+        error.raw_os_error().map(|code| code as u32),
+        Some(
+            windows_sys::Win32::Foundation::ERROR_SHARING_VIOLATION
+                | windows_sys::Win32::Foundation::ERROR_LOCK_VIOLATION
+        )
+    );
+    #[cfg(not(any(unix, windows)))]
+    return false;
+}
+
+// A random fraction in [0, 1), good enough to spread retries apart
+fn jitter() -> f64 {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos() as u64;
+    let mut x = nanos
+        ^ (COUNTER.fetch_add(1, Ordering::Relaxed) as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    // xorshift64*
+    x ^= x >> 12;
+    x ^= x << 25;
+    x ^= x >> 27;
+    return (x.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 11) as f64 / (1u64 << 53) as f64;
+}
+
+/// Runs an operation, which can be any sequence of calls, and runs it again while it fails with an error
+/// that `is_retryable`, backing off in between as set by the `RetryPolicy`. Fatal errors,
+/// and the last error once out of attempts, are returned as they are.
+///
+/// # Parameters
+/// - `policy`: **borrowed** `RetryPolicy`
+/// - `operation`: **owned** `FnMut() -> file_access::Result<T>`
+///
+/// # Returns
+/// Result<`T`>
+///
+/// # Examples
+/// ```
+/// use file_access::RetryPolicy;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "retry_example.txt";
+///         let file_path: String = String::from(file_path);
+///
+///         file_access::retry(&RetryPolicy::default(), || {
+///             file_access::write_string(&file_path, &"Hello")?;
+///             file_access::append_string(&file_path, &", World!")
+///         })?;
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn retry<T>(policy: &RetryPolicy, mut operation: impl FnMut() -> Result<T>) -> Result<T> {
+    let mut delay = policy.initial_delay;
+    let mut attempt = 1;
+    loop {
+        match operation() {
+            Err(e) if attempt < policy.max_attempts && is_retryable(&e) => {
+                thread::sleep(delay.mul_f64(0.5 + jitter() / 2.0));
+                delay = (delay * 2).min(policy.max_delay);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn retry_transient() -> Result<()> {
+        Ok({
+            // Arrange
+            let policy = RetryPolicy {
+                initial_delay: Duration::from_millis(1),
+                ..RetryPolicy::default()
+            };
+            let mut attempts = 0;
+
+            // Action
+            let result = retry(&policy, || {
+                attempts += 1;
+                match attempts {
+                    1 | 2 => Err(Error::from(ErrorKind::ResourceBusy)),
+                    _ => Ok(attempts),
+                }
+            });
+
+            // Assert
+            assert_eq!(result?, 3);
+        })
+    }
+
+    #[test]
+    fn retry_fatal_and_exhausted() -> Result<()> {
+        Ok({
+            // Arrange
+            let policy = RetryPolicy {
+                max_attempts: 3,
+                initial_delay: Duration::from_millis(1),
+                ..RetryPolicy::default()
+            };
+            let (mut fatal, mut transient) = (0, 0);
+
+            // Action
+            let fatal_result: crate::Result<()> = retry(&policy, || {
+                fatal += 1;
+                read_string(&"retry_missing.txt").map(|_| ())
+            });
+            let transient_result: crate::Result<()> = retry(&policy, || {
+                transient += 1;
+                Err(Error::from(ErrorKind::Interrupted))
+            });
+
+            // Assert
+            assert_eq!(fatal_result.unwrap_err().kind(), ErrorKind::NotFound);
+            assert_eq!(fatal, 1);
+            assert_eq!(transient_result.unwrap_err().kind(), ErrorKind::Interrupted);
+            assert_eq!(transient, 3);
+        })
+    }
+}
@@ -0,0 +1,302 @@
+use crate::*;
+use std::{
+    path::Path as StdPath,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// When `rotate` archives a file, such as a log written with `append_string`, and how many archives it keeps.
+/// A file is rotated once it reaches `max_size` bytes, or once it's older than `max_age`, going by when it was created;
+/// on filesystems that don't record creation, `rotate` records when it first saw the file in a hidden `.file.created`
+/// next to it. With neither set, it's always rotated.
+///
+/// # Examples
+/// ```
+/// use file_access::RotationPolicy;
+///
+/// let policy = RotationPolicy {
+///     max_size: Some(10 * 1024 * 1024),
+///     max_files: 5,
+///     ..RotationPolicy::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RotationPolicy {
+    /// The size, in bytes, the file is rotated at.
+    pub max_size: Option<u64>,
+    /// The age the file is rotated at.
+    pub max_age: Option<Duration>,
+    /// How many archives to keep, as `file.1` (the latest) up to `file.n`; older ones are deleted.
+    /// With `0`, the file is deleted rather than archived.
+    pub max_files: usize,
+    /// Whether to gzip the archives, as `file.1.gz` and so on. **Requires the `gzip` feature**;
+    /// without it, `rotate` fails with `ErrorKind::Unsupported`.
+    pub compress: bool,
+}
+
+impl RotationPolicy {
+    // Tells whether a file with this metadata is due to be rotated
+    fn triggers(&self, path: &StdPath, metadata: &Metadata) -> io::Result<bool> {
+        if self.max_size.is_none() && self.max_age.is_none() {
+            return Ok(true);
+        }
+        if self
+            .max_size
+            .is_some_and(|max_size| metadata.len() >= max_size)
+        {
+            return Ok(true);
+        }
+        if let Some(max_age) = self.max_age {
+            let age = started(path, metadata.created().ok())?
+                .elapsed()
+                .unwrap_or_default();
+            return Ok(age >= max_age);
+        }
+        return Ok(false);
+    }
+}
+
+// Names the hidden file recording when `rotate` first saw a file, `.file.log.created`
+fn created_path(path: &StdPath) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    return path.with_file_name(format!(".{name}.created"));
+}
+
+// Tells when a file was started: when it was created, or else when `rotate` first saw it, which is recorded
+// the first time; its modification time won't do, as every write to a log moves it along
+fn started(path: &StdPath, created: Option<SystemTime>) -> io::Result<SystemTime> {
+    if let Some(created) = created {
+        return Ok(created);
+    }
+    let created_path = created_path(path);
+    // A record that can't be parsed is written again below
+    match fs::read_to_string(&created_path).map(|text| text.trim().parse::<u64>()) {
+        Ok(Ok(secs)) => return Ok(UNIX_EPOCH + Duration::from_secs(secs)),
+        Ok(Err(_)) => {}
+        Err(x) if x.kind() == ErrorKind::NotFound => {}
+        Err(x) => return Err(x),
+    }
+    let now = SystemTime::now();
+    let secs = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    fs::write(&created_path, secs.to_string())?;
+
+    return Ok(now);
+}
+
+// Names the `n`th archive of a file, `file.log.n`, followed by `suffix`
+fn archive_path(path: &StdPath, n: usize, suffix: &str) -> PathBuf {
+    let mut archive = path.as_os_str().to_os_string();
+    archive.push(format!(".{n}{suffix}"));
+
+    return PathBuf::from(archive);
+}
+
+/// Archives a file, such as a log, if it's due under a `RotationPolicy`: the archives are shifted along,
+/// `file.1` becoming `file.2` and so on, dropping those past `max_files`, then the file is renamed to `file.1`
+/// (and gzipped to `file.1.gz` with `compress`). The next write, such as `append_string`, starts a new file.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `policy`: `RotationPolicy`, such as `RotationPolicy { max_size: Some(1024), max_files: 3, ..Default::default() }`
+///
+/// # Returns
+/// Result<`bool`>, whether the file was rotated; a file that doesn't exist isn't
+///
+/// # Examples
+/// ```
+/// use file_access::RotationPolicy;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "rotate_example/app.log";
+///         let policy = RotationPolicy {
+///             max_size: Some(16),
+///             max_files: 3,
+///             ..RotationPolicy::default()
+///         };
+///
+///         file_access::append_string(&file_path, &"started\n")?;
+///         assert!(!file_access::rotate(&file_path, policy)?);
+///         file_access::append_string(&file_path, &"stopped\n")?;
+///         assert!(file_access::rotate(&file_path, policy)?);
+///         assert_eq!(file_access::read_string(&"rotate_example/app.log.1")?, "started\nstopped\n");
+///
+///         // Clean-up:
+///         file_access::delete(&"rotate_example")?;
+///     })
+/// }
+/// ```
+pub fn rotate<Path: AsPath + ?Sized>(file_path: &Path, policy: RotationPolicy) -> Result<bool> {
+    let rotate = || -> io::Result<bool> {
+        let path = file_path.as_path();
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(x) if x.kind() == ErrorKind::NotFound => return Ok(false),
+            Err(x) => return Err(x),
+        };
+        if policy.compress && !cfg!(feature = "gzip") {
+            return Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "compressing archives requires the `gzip` feature",
+            ));
+        }
+        if !policy.triggers(path, &metadata)? {
+            return Ok(false);
+        }
+        // The next file starts a new record of when it was first seen
+        match fs::remove_file(created_path(path)) {
+            Err(x) if x.kind() != ErrorKind::NotFound => return Err(x),
+            _ => {}
+        }
+        if policy.max_files == 0 {
+            fs::remove_file(path)?;
+            return Ok(true);
+        }
+        // Archives may be compressed or not, if the policy changed, so both are shifted along
+        for suffix in ["", ".gz"] {
+            let oldest = archive_path(path, policy.max_files, suffix);
+            if oldest.exists() {
+                fs::remove_file(oldest)?;
+            }
+            for n in (1..policy.max_files).rev() {
+                let archive = archive_path(path, n, suffix);
+                if archive.exists() {
+                    fs::rename(&archive, archive_path(path, n + 1, suffix))?;
+                }
+            }
+        }
+        let archive = archive_path(path, 1, "");
+        fs::rename(path, &archive)?;
+        #[cfg(feature = "gzip")]
+        if policy.compress {
+            gzip::gzip_file(&archive, &archive_path(path, 1, ".gz"))?;
+            fs::remove_file(&archive)?;
+        }
+        return Ok(true);
+    };
+    rotate().context("rotate", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn rotate() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "rotate/app.log";
+            let policy = RotationPolicy {
+                max_size: Some(4),
+                max_files: 2,
+                ..RotationPolicy::default()
+            };
+
+            // Action
+            let mut rotated = vec![];
+            for line in ["a\n", "b\n", "c\n", "d\n", "e\n", "f\n", "g\n"] {
+                append_string(&file, &line)?;
+                rotated.push(super::rotate(&file, policy)?);
+            }
+
+            // Assert
+            assert_eq!(rotated, [false, true, false, true, false, true, false]);
+            assert_eq!(read_string(&file)?, "g\n");
+            assert_eq!(read_string(&"rotate/app.log.1")?, "e\nf\n");
+            assert_eq!(read_string(&"rotate/app.log.2")?, "c\nd\n");
+            assert!(!PathBuf::from("rotate/app.log.3").exists());
+            assert!(!super::rotate(&"rotate/missing.log", policy)?);
+
+            // Clean-up
+            delete(&"rotate")?;
+        })
+    }
+
+    #[test]
+    fn rotate_by_first_seen() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = path_of(&"rotate_by_first_seen/app.log");
+            append_string(&file, &"a\n")?;
+            let created = created_path(&file);
+
+            // Action
+            let first = started(&file, None)?;
+            append_string(&file, &"b\n")?;
+            let again = started(&file, None)?;
+            let policy = RotationPolicy {
+                max_age: Some(Duration::ZERO),
+                max_files: 1,
+                ..RotationPolicy::default()
+            };
+            let rotated = super::rotate(&file, policy)?;
+
+            // Assert
+            let secs = |time: SystemTime| time.duration_since(UNIX_EPOCH).unwrap().as_secs();
+            assert_eq!(secs(again), secs(first));
+            assert!(rotated);
+            assert!(
+                !created.exists(),
+                "a rotated file's record should be removed"
+            );
+
+            // Clean-up
+            delete(&"rotate_by_first_seen")?;
+        })
+    }
+
+    #[test]
+    #[cfg(not(feature = "gzip"))]
+    fn rotate_compressed_unsupported() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "rotate_compressed_unsupported/app.log";
+            append_string(&file, &"a\n")?;
+            let policy = RotationPolicy {
+                compress: true,
+                max_files: 1,
+                ..RotationPolicy::default()
+            };
+
+            // Action
+            let result = super::rotate(&file, policy);
+
+            // Assert
+            assert_eq!(result.unwrap_err().kind(), ErrorKind::Unsupported);
+            assert!(PathBuf::from(file).exists());
+
+            // Clean-up
+            delete(&"rotate_compressed_unsupported")?;
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn rotate_compressed() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "rotate_compressed/app.log";
+            let policy = RotationPolicy {
+                max_age: Some(Duration::ZERO),
+                max_files: 2,
+                compress: true,
+                ..RotationPolicy::default()
+            };
+
+            // Action
+            for line in ["a\n", "b\n", "c\n"] {
+                append_string(&file, &line)?;
+                super::rotate(&file, policy)?;
+            }
+
+            // Assert
+            assert!(!PathBuf::from(file).exists());
+            assert_eq!(read_string_gz(&"rotate_compressed/app.log.1.gz")?, "c\n");
+            assert_eq!(read_string_gz(&"rotate_compressed/app.log.2.gz")?, "b\n");
+            assert!(!PathBuf::from("rotate_compressed/app.log.3.gz").exists());
+
+            // Clean-up
+            delete(&"rotate_compressed")?;
+        })
+    }
+}
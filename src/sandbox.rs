@@ -0,0 +1,463 @@
+use crate::*;
+use std::path::{Component, Path as StdPath};
+#[cfg(unix)]
+use std::{
+    ffi::{CString, OsStr},
+    fs::{File, Metadata},
+    io::{Read, Write},
+    os::{
+        fd::{AsFd, AsRawFd, OwnedFd},
+        unix::ffi::OsStrExt,
+    },
+    sync::Arc,
+};
+
+/// A base directory that untrusted paths, such as ones from an HTTP request, are kept inside of.
+/// Paths are taken relative to the root; absolute paths, `..` segments that climb above the root, and symlinks
+/// that point outside of it are refused with a `PermissionDenied` error.
+///
+/// As a `FileSystem`, the sandbox resolves the path again on every operation. On Unix, it does so beneath a handle
+/// to the root held since `new`, so a symlink swapped in after `open`, or even halfway through an operation, can't
+/// lead out of it: Linux uses `openat2(2)` with `RESOLVE_BENEATH`, which still follows symlinks that stay inside
+/// the root, and other Unixes walk the path one component at a time, refusing every symlink. Elsewhere, the path is
+/// checked and then used, which leaves a window for such a swap between the two.
+/// Lists return paths relative to the root, ready to be opened again.
+///
+/// # Examples
+/// ```
+/// use file_access::Sandbox;
+/// use std::io::ErrorKind;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"sandbox_example/public/index.html", &"<h1>Hi</h1>")?;
+///         let sandbox = Sandbox::new(&"sandbox_example/public")?;
+///
+///         assert_eq!(sandbox.open(&"index.html")?.read_string()?, "<h1>Hi</h1>");
+///         assert_eq!(sandbox.open(&"../../Cargo.toml").unwrap_err().kind(), ErrorKind::PermissionDenied);
+///         assert_eq!(sandbox.open(&"/etc/passwd").unwrap_err().kind(), ErrorKind::PermissionDenied);
+///
+///         // Clean-up:
+///         file_access::delete(&"sandbox_example")?;
+///     })
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Sandbox {
+    root: PathBuf,
+    #[cfg(unix)]
+    dir: Arc<OwnedFd>,
+}
+
+fn escapes() -> io::Error {
+    io::Error::new(ErrorKind::PermissionDenied, "the path escapes the sandbox")
+}
+
+// Collapses `.` and `..` in an untrusted path, refusing absolute paths and `..` segments that climb above the root
+fn relative(path: &StdPath) -> io::Result<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => relative.push(name),
+            Component::CurDir => {}
+            Component::ParentDir if relative.pop() => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(escapes())
+            }
+        }
+    }
+    return Ok(relative);
+}
+
+impl Sandbox {
+    /// Creates a sandbox rooted at an **existing** directory, resolved to its canonical path.
+    pub fn new<Path: AsPath + ?Sized>(root: &Path) -> Result<Self> {
+        let root = fs::canonicalize(root.as_path()).context("sandbox", root)?;
+        if !root.is_dir() {
+            return Err(ErrorKind::NotADirectory).context("sandbox", &root);
+        }
+        #[cfg(unix)]
+        let dir = Arc::new(File::open(&root).context("sandbox", &root)?.into());
+        return Ok(Self {
+            root,
+            #[cfg(unix)]
+            dir,
+        });
+    }
+
+    /// The canonical path of the root directory.
+    pub fn root(&self) -> &StdPath {
+        &self.root
+    }
+
+    // Joins a path under the root, checking that it, and whatever part of it exists on disk, stays inside
+    fn full_path(&self, path: &StdPath) -> io::Result<PathBuf> {
+        let full = self.root.join(relative(path)?);
+        // Symlinks are resolved on the deepest part of the path that exists
+        let existing = full.ancestors().find(|ancestor| ancestor.exists());
+        if let Some(existing) = existing {
+            if !fs::canonicalize(existing)?.starts_with(&self.root) {
+                return Err(escapes());
+            }
+        }
+        if full.is_symlink() && !fs::canonicalize(&full).is_ok_and(|x| x.starts_with(&self.root)) {
+            return Err(escapes()); // a dangling link could be made to point anywhere
+        }
+        return Ok(full);
+    }
+
+    /// Resolves an untrusted path under the root into a `FilePath`, refusing it if it escapes.
+    /// The check is made now: operations on the returned `FilePath` go straight to the disk,
+    /// see `open` to check on every operation.
+    ///
+    /// # Parameters
+    /// - `untrusted`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`, relative to the root
+    ///
+    /// # Returns
+    /// Result<`FilePath`>, failing with `PermissionDenied` if the path escapes the root
+    pub fn path<Path: AsPath + ?Sized>(&self, untrusted: &Path) -> Result<FilePath> {
+        self.full_path(untrusted.as_path())
+            .map(FilePath::from)
+            .context("sandbox", untrusted)
+    }
+
+    /// Opens an untrusted path under the root as a `RoutedPath` whose every operation is resolved again by the sandbox.
+    ///
+    /// # Parameters
+    /// - `untrusted`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`, relative to the root
+    ///
+    /// # Returns
+    /// Result<`RoutedPath`>, failing with `PermissionDenied` if the path escapes the root
+    pub fn open<Path: AsPath + ?Sized>(&self, untrusted: &Path) -> Result<RoutedPath<'_>> {
+        self.full_path(untrusted.as_path())
+            .context("sandbox", untrusted)?;
+        return Ok(RoutedPath::new(self, untrusted.as_path().to_path_buf()));
+    }
+
+    // Runs an operation on the relative form of a path
+    fn run<T>(
+        &self,
+        operation: &'static str,
+        path: &StdPath,
+        f: impl FnOnce(&StdPath) -> io::Result<T>,
+    ) -> Result<T> {
+        relative(path)
+            .and_then(|relative| f(&relative))
+            .context(operation, path)
+    }
+}
+
+// Every operation opens the path beneath the root handle, and works on what it opened rather than on the path
+#[cfg(unix)]
+impl Sandbox {
+    // Opens a relative path beneath the root; leaving it through a symlink fails with `PermissionDenied`
+    fn open_at(&self, relative: &StdPath, flags: libc::c_int) -> io::Result<OwnedFd> {
+        internal::sys::open_beneath(self.dir.as_fd(), relative, flags).map_err(|x| {
+            match x.raw_os_error() {
+                Some(libc::EXDEV | libc::ELOOP) => escapes(),
+                _ => x,
+            }
+        })
+    }
+
+    // Opens a directory beneath the root, creating whatever part of it is missing
+    fn create_dirs(&self, relative: &StdPath) -> io::Result<OwnedFd> {
+        let directory = libc::O_RDONLY | libc::O_DIRECTORY;
+        let mut dir = self.open_at(StdPath::new(""), directory)?;
+        let mut prefix = PathBuf::new();
+        for name in relative.iter() {
+            prefix.push(name);
+            let name = CString::new(name.as_bytes())?;
+            if unsafe { libc::mkdirat(dir.as_raw_fd(), name.as_ptr(), 0o777) } != 0 {
+                let error = io::Error::last_os_error();
+                if error.kind() != ErrorKind::AlreadyExists {
+                    return Err(error);
+                }
+            }
+            dir = self.open_at(&prefix, directory)?;
+        }
+        return Ok(dir);
+    }
+
+    // Opens the directory holding a path beneath the root, along with the name of the path in it;
+    // the root itself has no name, and is refused
+    fn open_parent(&self, relative: &StdPath, create: bool) -> io::Result<(OwnedFd, CString)> {
+        let name = CString::new(relative.file_name().ok_or_else(escapes)?.as_bytes())?;
+        let parent = relative.parent().unwrap_or(StdPath::new(""));
+        let dir = match create {
+            true => self.create_dirs(parent)?,
+            false => self.open_at(parent, libc::O_RDONLY | libc::O_DIRECTORY)?,
+        };
+        return Ok((dir, name));
+    }
+
+    fn metadata(&self, relative: &StdPath) -> io::Result<Metadata> {
+        #[cfg(target_os = "linux")]
+        let lookup = libc::O_PATH;
+        #[cfg(not(target_os = "linux"))]
+        let lookup = libc::O_RDONLY | libc::O_NONBLOCK;
+        File::from(self.open_at(relative, lookup)?).metadata()
+    }
+
+    fn read(&self, relative: &StdPath) -> io::Result<Vec<u8>> {
+        let mut bytes = vec![];
+        File::from(self.open_at(relative, libc::O_RDONLY)?).read_to_end(&mut bytes)?;
+        return Ok(bytes);
+    }
+
+    // Opens a file for writing beneath the root, creating it and its full directory path
+    fn create(&self, relative: &StdPath, append: bool) -> io::Result<File> {
+        self.create_dirs(relative.parent().unwrap_or(StdPath::new("")))?;
+        let flags = libc::O_WRONLY | libc::O_CREAT;
+        let flags = flags
+            | if append {
+                libc::O_APPEND
+            } else {
+                libc::O_TRUNC
+            };
+        return Ok(File::from(self.open_at(relative, flags)?));
+    }
+
+    fn write(&self, relative: &StdPath, bytes: &[u8], append: bool) -> io::Result<()> {
+        self.create(relative, append)?.write_all(bytes)
+    }
+
+    fn make_dirs(&self, relative: &StdPath) -> io::Result<()> {
+        self.create_dirs(relative).map(|_| ())
+    }
+
+    fn remove(&self, relative: &StdPath) -> io::Result<()> {
+        let (dir, name) = self.open_parent(relative, false)?;
+        return Dir::from_fd(dir).remove_entry(&name);
+    }
+
+    fn copy_file(&self, from: &StdPath, to: &StdPath) -> io::Result<()> {
+        let mut source = File::from(self.open_at(from, libc::O_RDONLY)?);
+        io::copy(&mut source, &mut self.create(to, false)?)?;
+        return Ok(());
+    }
+
+    fn move_to(&self, from: &StdPath, to: &StdPath) -> io::Result<()> {
+        let (from_dir, from_name) = self.open_parent(from, false)?;
+        let (to_dir, to_name) = self.open_parent(to, true)?;
+        let ret = unsafe {
+            libc::renameat(
+                from_dir.as_raw_fd(),
+                from_name.as_ptr(),
+                to_dir.as_raw_fd(),
+                to_name.as_ptr(),
+            )
+        };
+        return match ret {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        };
+    }
+
+    fn entries(&self, relative: &StdPath) -> io::Result<Vec<PathBuf>> {
+        let dir = self.open_at(relative, libc::O_RDONLY | libc::O_DIRECTORY)?;
+        let mut entries: Vec<_> = Dir::from_fd(dir)
+            .entries()?
+            .iter()
+            .map(|name| relative.join(OsStr::from_bytes(name.to_bytes())))
+            .collect();
+        entries.sort();
+        return Ok(entries);
+    }
+}
+
+// Without a way to resolve beneath a directory handle, the path is checked and then used
+#[cfg(not(unix))]
+impl Sandbox {
+    fn metadata(&self, relative: &StdPath) -> io::Result<fs::Metadata> {
+        fs::metadata(self.full_path(relative)?)
+    }
+
+    fn read(&self, relative: &StdPath) -> io::Result<Vec<u8>> {
+        Ok(read_bytes(&self.full_path(relative)?)?)
+    }
+
+    fn write(&self, relative: &StdPath, bytes: &[u8], append: bool) -> io::Result<()> {
+        let full = self.full_path(relative)?;
+        return Ok(match append {
+            true => append_bytes(&full, bytes)?,
+            false => write_bytes(&full, bytes)?,
+        });
+    }
+
+    fn make_dirs(&self, relative: &StdPath) -> io::Result<()> {
+        fs::create_dir_all(self.full_path(relative)?)
+    }
+
+    fn remove(&self, relative: &StdPath) -> io::Result<()> {
+        if relative.as_os_str().is_empty() {
+            return Err(escapes());
+        }
+        return Ok(delete(&self.full_path(relative)?)?);
+    }
+
+    fn copy_file(&self, from: &StdPath, to: &StdPath) -> io::Result<()> {
+        Ok(copy(&self.full_path(from)?, &self.full_path(to)?)?)
+    }
+
+    fn move_to(&self, from: &StdPath, to: &StdPath) -> io::Result<()> {
+        if from.as_os_str().is_empty() {
+            return Err(escapes());
+        }
+        return Ok(rename(&self.full_path(from)?, &self.full_path(to)?)?);
+    }
+
+    fn entries(&self, relative: &StdPath) -> io::Result<Vec<PathBuf>> {
+        let mut entries = vec![];
+        for entry in fs::read_dir(self.full_path(relative)?)? {
+            entries.push(relative.join(entry?.file_name()));
+        }
+        entries.sort();
+        return Ok(entries);
+    }
+}
+
+impl FileSystem for Sandbox {
+    fn read_bytes(&self, path: &StdPath) -> Result<Vec<u8>> {
+        self.run("read_bytes", path, |relative| self.read(relative))
+    }
+
+    fn write_bytes(&self, path: &StdPath, bytes: &[u8]) -> Result<()> {
+        self.run("write_bytes", path, |relative| {
+            self.write(relative, bytes, false)
+        })
+    }
+
+    fn append_bytes(&self, path: &StdPath, bytes: &[u8]) -> Result<()> {
+        self.run("append_bytes", path, |relative| {
+            self.write(relative, bytes, true)
+        })
+    }
+
+    fn exists(&self, path: &StdPath) -> bool {
+        relative(path).is_ok_and(|relative| self.metadata(&relative).is_ok())
+    }
+
+    fn is_dir(&self, path: &StdPath) -> bool {
+        relative(path).is_ok_and(|relative| self.metadata(&relative).is_ok_and(|x| x.is_dir()))
+    }
+
+    fn create_dir_all(&self, path: &StdPath) -> Result<()> {
+        self.run("create_dir_all", path, |relative| self.make_dirs(relative))
+    }
+
+    fn delete(&self, path: &StdPath) -> Result<()> {
+        self.run("delete", path, |relative| self.remove(relative))
+    }
+
+    fn copy(&self, from: &StdPath, to: &StdPath) -> Result<()> {
+        let to_relative = relative(to).context("copy", to)?;
+        self.run("copy", from, |from| self.copy_file(from, &to_relative))
+    }
+
+    fn rename(&self, from: &StdPath, to: &StdPath) -> Result<()> {
+        let to_relative = relative(to).context("rename", to)?;
+        self.run("rename", from, |from| self.move_to(from, &to_relative))
+    }
+
+    fn list(&self, path: &StdPath) -> Result<Vec<PathBuf>> {
+        self.run("list", path, |relative| self.entries(relative))
+    }
+
+    fn size(&self, path: &StdPath) -> Result<u64> {
+        self.run("size", path, |relative| Ok(self.metadata(relative)?.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn sandbox() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"sandbox/root/a.txt", &"a")?;
+            write_string(&"sandbox/secret.txt", &"secret")?;
+            let sandbox = Sandbox::new(&"sandbox/root")?;
+
+            // Action
+            let inside = sandbox.open(&"sub/../a.txt")?.read_string()?;
+            sandbox.open(&"./new/b.txt")?.write_string(&"b")?;
+            let listed = sandbox.open(&"new")?.list()?;
+            let climbed = sandbox.path(&"../secret.txt");
+            let absolute = sandbox.path(&"/sandbox/secret.txt");
+            let root_deleted = sandbox.open(&".")?.delete();
+
+            // Assert
+            assert_eq!(inside, "a");
+            assert_eq!(listed.len(), 1);
+            assert_eq!(listed[0].path(), StdPath::new("new/b.txt"));
+            assert_eq!(listed[0].read_string()?, "b");
+            assert_eq!(climbed.err().unwrap().kind(), ErrorKind::PermissionDenied);
+            assert_eq!(absolute.err().unwrap().kind(), ErrorKind::PermissionDenied);
+            assert_eq!(
+                root_deleted.unwrap_err().kind(),
+                ErrorKind::PermissionDenied
+            );
+            assert!(sandbox.path(&"missing/file.txt").is_ok());
+
+            // Clean-up
+            delete(&"sandbox")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn sandbox_symlink_swapped_after_open() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"sandbox_swap/root/data/file.txt", &"inside")?;
+            write_string(&"sandbox_swap/outside/file.txt", &"secret")?;
+            let sandbox = Sandbox::new(&"sandbox_swap/root")?;
+            let routed = sandbox.open(&"data/file.txt")?;
+
+            // Action
+            fs::rename("sandbox_swap/root/data", "sandbox_swap/root/moved")?;
+            std::os::unix::fs::symlink("../outside", "sandbox_swap/root/data")?;
+            let read = routed.read_string();
+            let written = routed.write_string(&"overwritten");
+            let deleted = routed.delete();
+
+            // Assert
+            assert_eq!(read.unwrap_err().kind(), ErrorKind::PermissionDenied);
+            assert_eq!(written.unwrap_err().kind(), ErrorKind::PermissionDenied);
+            assert_eq!(deleted.unwrap_err().kind(), ErrorKind::PermissionDenied);
+            assert!(!routed.exists());
+            assert_eq!(read_string(&"sandbox_swap/outside/file.txt")?, "secret");
+
+            // Clean-up
+            delete(&"sandbox_swap")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn sandbox_symlinks() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"sandbox_symlinks/root/a.txt", &"a")?;
+            write_string(&"sandbox_symlinks/secret.txt", &"secret")?;
+            std::os::unix::fs::symlink("..", "sandbox_symlinks/root/up")?;
+            std::os::unix::fs::symlink("a.txt", "sandbox_symlinks/root/inner")?;
+            let sandbox = Sandbox::new(&"sandbox_symlinks/root")?;
+
+            // Action
+            let inner = sandbox.open(&"inner")?.read_string()?;
+            let escaped = sandbox.open(&"up/secret.txt");
+
+            // Assert
+            assert_eq!(inner, "a");
+            assert_eq!(escaped.unwrap_err().kind(), ErrorKind::PermissionDenied);
+
+            // Clean-up
+            delete(&"sandbox_symlinks")?;
+        })
+    }
+}
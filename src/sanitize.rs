@@ -0,0 +1,122 @@
+use crate::*;
+
+// The device names Windows reserves in every directory, with or without an extension
+const RESERVED: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+// The characters that separate paths, or that Windows doesn't allow in names
+const FORBIDDEN: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+fn is_reserved(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name).trim_end();
+    RESERVED
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+}
+
+// Cleans up a name, or fails if nothing safe is left of it
+pub(crate) fn clean(name: &str) -> io::Result<String> {
+    let cleaned: String = name
+        .chars()
+        .filter(|c| !c.is_control() && !FORBIDDEN.contains(c))
+        .collect();
+    // Windows drops trailing dots and spaces, which would make `a.txt.` and `a.txt` the same file
+    let cleaned = cleaned.trim_end_matches(['.', ' ']);
+    if cleaned.is_empty() {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "nothing is left of the name",
+        ));
+    }
+    if is_reserved(cleaned) {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "the name is reserved by Windows",
+        ));
+    }
+    return Ok(cleaned.to_string());
+}
+
+/// Turns an untrusted name, such as the name of an uploaded file, into one that's safe to use as a single file name
+/// on any platform: path separators, control characters and the characters Windows forbids (`:*?"<>|`) are removed,
+/// and so are trailing dots and spaces, which also strips `..`. Names that would be left empty, or that Windows
+/// reserves for devices (`CON`, `NUL`, `COM1`, ... with or without an extension), are refused rather than altered.
+///
+/// # Parameters
+/// - `name`: **borrowed** `AsRef<str>` such as `String` or `&str`
+///
+/// # Returns
+/// Result<`String`>, failing with `ErrorKind::InvalidInput` if no safe name is left
+///
+/// # Examples
+/// ```
+/// use std::io::ErrorKind;
+///
+/// assert_eq!(file_access::sanitize_file_name(&"../../etc/passwd").unwrap(), "....etcpasswd");
+/// assert_eq!(file_access::sanitize_file_name(&"report:v2?.pdf").unwrap(), "reportv2.pdf");
+/// assert_eq!(file_access::sanitize_file_name(&"..").unwrap_err().kind(), ErrorKind::InvalidInput);
+/// assert_eq!(file_access::sanitize_file_name(&"nul.txt").unwrap_err().kind(), ErrorKind::InvalidInput);
+/// ```
+pub fn sanitize_file_name<Text: AsRef<str> + ?Sized>(name: &Text) -> Result<String> {
+    clean(name.as_ref()).context("sanitize_file_name", name.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn sanitize_file_name() -> Result<()> {
+        Ok({
+            // Arrange
+            let names = [
+                "a.txt",
+                ".bashrc",
+                "a/b\\c",
+                "tab\there",
+                "name. . ",
+                "Com1 .log",
+                "com10",
+                "",
+            ];
+
+            // Action
+            let sanitized: Vec<_> = names
+                .iter()
+                .map(|name| super::sanitize_file_name(name).ok())
+                .collect();
+            let base = FilePath::access(&"base");
+            let joined = ["a/./b.txt", "a/../b.txt", "a\\b.txt", "aux", "", "C:evil"]
+                .map(|segment| base.safe_join(segment).map(|path| path_of(&path)).ok());
+
+            // Assert
+            assert_eq!(
+                sanitized,
+                [
+                    Some("a.txt".to_string()),
+                    Some(".bashrc".to_string()),
+                    Some("abc".to_string()),
+                    Some("tabhere".to_string()),
+                    Some("name".to_string()),
+                    None,
+                    Some("com10".to_string()),
+                    None,
+                ]
+            );
+            assert_eq!(
+                joined,
+                [
+                    Some(PathBuf::from("base/a/b.txt")),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None
+                ]
+            );
+        })
+    }
+}
@@ -0,0 +1,436 @@
+use crate::{
+    internal::sha256::{digest_file, hex},
+    *,
+};
+use ed25519_dalek::{Signature, Signer};
+pub use ed25519_dalek::{SigningKey, VerifyingKey}; // re-export the key types
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeSet, path::Path as StdPath};
+
+// Gets the path of the detached signature of a file
+fn sig_path_of<Path: AsPath + ?Sized>(file_path: &Path) -> PathBuf {
+    let mut sig_path = file_path.as_path().as_os_str().to_owned();
+    sig_path.push(".sig");
+    PathBuf::from(sig_path)
+}
+
+fn invalid<Message: AsRef<str>>(message: Message) -> Error {
+    Error::new(ErrorKind::InvalidData, message.as_ref())
+}
+
+// Joins the components of a relative path with `/`, failing on one that isn't valid UTF-8,
+// which a manifest line couldn't name so that `verify_manifest` finds it again
+fn relative_name(relative: &StdPath) -> Result<String> {
+    let names = relative
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| {
+            invalid(format!(
+                "can't list a path that isn't valid UTF-8: {relative:?}"
+            ))
+        })?;
+    return Ok(names.join("/"));
+}
+
+// Gets the names `walk` lists the manifest and its signature under, if they exist inside `root`,
+// however the manifest path was spelled, such as through `./` or `..`
+fn manifest_names<Manifest: AsPath + ?Sized>(
+    root: &StdPath,
+    manifest_path: &Manifest,
+) -> Result<Vec<String>> {
+    let root = fs::canonicalize(root)?;
+    let mut names = vec![];
+    for path in [path_of(manifest_path), sig_path_of(manifest_path)] {
+        let Ok(path) = fs::canonicalize(path) else {
+            continue; // not written yet
+        };
+        if let Ok(relative) = path.strip_prefix(&root) {
+            names.push(relative_name(relative)?);
+        }
+    }
+    return Ok(names);
+}
+
+// Collects every regular file below `dir` as its `/`-separated path relative to `root`
+fn walk(root: &StdPath, dir: &StdPath, files: &mut Vec<String>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?; // doesn't follow symlinks
+        if metadata.is_dir() {
+            walk(root, &entry.path(), files)?;
+        } else if metadata.is_file() {
+            let path = entry.path();
+            files.push(relative_name(path.strip_prefix(root).unwrap_or(&path))?);
+        }
+    }
+    return Ok(());
+}
+
+/// Signs a file with an Ed25519 key, writing the detached 64-byte signature next to it as `<file_path>.sig`.
+/// What gets signed is the SHA-256 digest of the contents, so files of any size are signed without being loaded into memory.
+/// **Requires the `sign` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `key`: **borrowed** `SigningKey`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// use file_access::SigningKey;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "sign_example.txt";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"release artifact")?;
+///
+///         let key = SigningKey::from_bytes(&[7; 32]);
+///         file_access::sign(&file_path, &key)?;
+///         assert!(file_access::verify(&file_path, &key.verifying_key())?);
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///         file_access::delete(&"sign_example.txt.sig")?;
+///     })
+/// }
+/// ```
+pub fn sign<Path: AsPath + ?Sized>(file_path: &Path, key: &SigningKey) -> Result<()> {
+    let digest = digest_file(&path_of(file_path)).context("sign", file_path)?;
+    let signature = key.sign(&digest);
+
+    return fs::write(sig_path_of(file_path), signature.to_bytes()).context("sign", file_path);
+}
+
+/// Verifies a file against the detached signature written by `sign`. Returns `false` if the file or its signature
+/// were tampered with or signed by another key, and an error if the signature file is missing or malformed.
+/// **Requires the `sign` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `key`: **borrowed** `VerifyingKey`
+///
+/// # Returns
+/// Result<`bool`>
+///
+/// # Examples
+/// ```
+/// use file_access::SigningKey;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "verify_example.txt";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"release artifact")?;
+///
+///         let key = SigningKey::from_bytes(&[7; 32]);
+///         file_access::sign(&file_path, &key)?;
+///         file_access::append_string(&file_path, &", tampered")?;
+///         assert!(!file_access::verify(&file_path, &key.verifying_key())?);
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///         file_access::delete(&"verify_example.txt.sig")?;
+///     })
+/// }
+/// ```
+pub fn verify<Path: AsPath + ?Sized>(file_path: &Path, key: &VerifyingKey) -> Result<bool> {
+    let digest = digest_file(&path_of(file_path)).context("verify", file_path)?;
+    return verify_digest(&digest, file_path, key);
+}
+
+// Checks the detached signature of a file against the digest of its contents
+fn verify_digest<Path: AsPath + ?Sized>(
+    digest: &[u8; 32],
+    file_path: &Path,
+    key: &VerifyingKey,
+) -> Result<bool> {
+    let sig_path = sig_path_of(file_path);
+    let signature: [u8; 64] = fs::read(&sig_path)
+        .context("verify", &sig_path)?
+        .try_into()
+        .map_err(|_| invalid("a signature must be 64 bytes"))
+        .context("verify", &sig_path)?;
+
+    return Ok(key
+        .verify_strict(digest, &Signature::from_bytes(&signature))
+        .is_ok());
+}
+
+/// Writes a manifest listing the SHA-256 digest of every file below a directory, in the `sha256sum` format
+/// (`<digest>  <relative path>`, sorted by path), and signs it with `sign`. The manifest and its signature
+/// are left out of the listing when they are written inside the directory, however their path is spelled.
+/// **Requires the `sign` feature**.
+///
+/// Fails with `ErrorKind::InvalidData` if a path below the directory isn't valid UTF-8,
+/// and with `ErrorKind::InvalidInput` if one contains a newline, as a manifest line can't name either.
+///
+/// # Parameters
+/// - `dir_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `manifest_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `key`: **borrowed** `SigningKey`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// use file_access::SigningKey;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let dir_path: &str = "sign_manifest_example";
+///         let dir_path: String = String::from(dir_path);
+///         file_access::write_string(&"sign_manifest_example/app.bin", &"release artifact")?;
+///
+///         let key = SigningKey::from_bytes(&[7; 32]);
+///         file_access::sign_manifest(&dir_path, &"sign_manifest_example/SHA256SUMS", &key)?;
+///         println!("{}", file_access::read_string(&"sign_manifest_example/SHA256SUMS")?);
+///
+///         // Clean-up:
+///         file_access::delete(&dir_path)?;
+///     })
+/// }
+/// ```
+pub fn sign_manifest<Dir: AsPath + ?Sized, Manifest: AsPath + ?Sized>(
+    dir_path: &Dir,
+    manifest_path: &Manifest,
+    key: &SigningKey,
+) -> Result<()> {
+    let root = path_of(dir_path);
+    let mut files = vec![];
+    walk(&root, &root, &mut files).context("sign_manifest", dir_path)?;
+    files.sort();
+
+    let excluded = manifest_names(&root, manifest_path).context("sign_manifest", dir_path)?;
+    let mut lines = vec![];
+    for file in files {
+        if file.contains('\n') {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("can't list a path containing a newline: {file:?}"),
+            ));
+        }
+        if excluded.contains(&file) {
+            continue;
+        }
+        let path = root.join(&file);
+        let digest = digest_file(&path).context("sign_manifest", &*path.to_string_lossy())?;
+        lines.push(format!("{}  {file}", hex(&digest)));
+    }
+    write_lines(manifest_path, &lines)?;
+
+    return sign(manifest_path, key);
+}
+
+/// Verifies a manifest written by `sign_manifest`: its signature, then the digest of every file it lists
+/// relative to the directory. The manifest is read once, and the bytes whose signature was checked are the ones
+/// parsed. Returns `false` if the manifest is forged, if any listed file is missing or different, or if the directory
+/// holds a file the manifest doesn't list (other than the manifest and its signature). **Requires the `sign` feature**.
+///
+/// # Parameters
+/// - `dir_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `manifest_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `key`: **borrowed** `VerifyingKey`
+///
+/// # Returns
+/// Result<`bool`>
+///
+/// # Examples
+/// ```
+/// use file_access::SigningKey;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let dir_path: &str = "verify_manifest_example";
+///         let dir_path: String = String::from(dir_path);
+///         file_access::write_string(&"verify_manifest_example/app.bin", &"release artifact")?;
+///
+///         let key = SigningKey::from_bytes(&[7; 32]);
+///         file_access::sign_manifest(&dir_path, &"verify_manifest_example.sums", &key)?;
+///         assert!(file_access::verify_manifest(&dir_path, &"verify_manifest_example.sums", &key.verifying_key())?);
+///
+///         // Clean-up:
+///         file_access::delete(&dir_path)?;
+///         file_access::delete(&"verify_manifest_example.sums")?;
+///         file_access::delete(&"verify_manifest_example.sums.sig")?;
+///     })
+/// }
+/// ```
+pub fn verify_manifest<Dir: AsPath + ?Sized, Manifest: AsPath + ?Sized>(
+    dir_path: &Dir,
+    manifest_path: &Manifest,
+    key: &VerifyingKey,
+) -> Result<bool> {
+    let manifest = fs::read(manifest_path.as_path()).context("verify_manifest", manifest_path)?;
+    if !verify_digest(&Sha256::digest(&manifest).into(), manifest_path, key)? {
+        return Ok(false);
+    }
+    let manifest = String::from_utf8(manifest)
+        .map_err(|_| invalid("the manifest isn't valid UTF-8"))
+        .context("verify_manifest", manifest_path)?;
+
+    let root = path_of(dir_path);
+    let mut listed = BTreeSet::new();
+    for line in manifest.lines() {
+        let (digest, file) = line
+            .split_once("  ")
+            .ok_or_else(|| invalid(format!("not a manifest line: {line:?}")))?;
+        match digest_file(&root.join(file)) {
+            Ok(actual) if hex(&actual) == digest => {}
+            Ok(_) => return Ok(false),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(false),
+            Err(e) => {
+                return Err(e).context("verify_manifest", &*root.join(file).to_string_lossy())
+            }
+        }
+        listed.insert(file);
+    }
+
+    // A file added since the manifest was signed fails it too
+    let mut files = vec![];
+    walk(&root, &root, &mut files).context("verify_manifest", dir_path)?;
+    let excluded = manifest_names(&root, manifest_path).context("verify_manifest", dir_path)?;
+    let unlisted = files
+        .iter()
+        .any(|file| !listed.contains(file.as_str()) && !excluded.contains(file));
+    return Ok(!unlisted);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn sign_and_verify() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "sign_and_verify.txt";
+            let key = SigningKey::from_bytes(&[1; 32]);
+            let other_key = SigningKey::from_bytes(&[2; 32]);
+            write_string(&file_path, &"Hello, World!")?;
+
+            // Action
+            sign(&file_path, &key)?;
+
+            // Assert
+            assert_eq!(fs::read("sign_and_verify.txt.sig")?.len(), 64);
+            assert!(verify(&file_path, &key.verifying_key())?);
+            assert!(!verify(&file_path, &other_key.verifying_key())?);
+            write_string(&file_path, &"Hello, World?")?;
+            assert!(!verify(&file_path, &key.verifying_key())?);
+
+            // Clean-up
+            delete(&file_path)?;
+            delete(&"sign_and_verify.txt.sig")?;
+        })
+    }
+
+    #[test]
+    fn sign_and_verify_manifest() -> Result<()> {
+        Ok({
+            // Arrange
+            let dir_path = "sign_manifest";
+            let manifest_path = "sign_manifest/SHA256SUMS";
+            let key = SigningKey::from_bytes(&[1; 32]);
+            write_string(&"sign_manifest/a.txt", &"a")?;
+            write_string(&"sign_manifest/sub/b.txt", &"b")?;
+
+            // Action
+            sign_manifest(&dir_path, &manifest_path, &key)?;
+
+            // Assert
+            assert_eq!(
+                read_lines(&manifest_path)?,
+                vec![
+                    "ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb  a.txt",
+                    "3e23e8160039594a33894f6564e1b1348bbd7a0088d42c4acb73eeaed59c009d  sub/b.txt",
+                ]
+            );
+            assert!(verify_manifest(
+                &dir_path,
+                &manifest_path,
+                &key.verifying_key()
+            )?);
+            write_string(&"sign_manifest/extra.txt", &"not signed")?;
+            assert!(!verify_manifest(
+                &dir_path,
+                &manifest_path,
+                &key.verifying_key()
+            )?);
+            delete(&"sign_manifest/extra.txt")?;
+            write_string(&"sign_manifest/sub/b.txt", &"B")?;
+            assert!(!verify_manifest(
+                &dir_path,
+                &manifest_path,
+                &key.verifying_key()
+            )?);
+
+            // Clean-up
+            delete(&dir_path)?;
+        })
+    }
+
+    #[test]
+    fn sign_manifest_spelled_differently() -> Result<()> {
+        Ok({
+            // Arrange
+            let key = SigningKey::from_bytes(&[1; 32]);
+            write_string(&"sign_manifest_spelling/a.txt", &"a")?;
+
+            // Action
+            sign_manifest(
+                &"./sign_manifest_spelling",
+                &"sign_manifest_spelling/SUMS",
+                &key,
+            )?;
+            sign_manifest(
+                &"./sign_manifest_spelling",
+                &"sign_manifest_spelling/SUMS",
+                &key,
+            )?;
+
+            // Assert
+            assert_eq!(read_lines(&"sign_manifest_spelling/SUMS")?.len(), 1);
+            assert!(verify_manifest(
+                &"sign_manifest_spelling",
+                &"./sign_manifest_spelling/../sign_manifest_spelling/SUMS",
+                &key.verifying_key()
+            )?);
+
+            // Clean-up
+            delete(&"sign_manifest_spelling")?;
+        })
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn sign_manifest_non_utf8() -> Result<()> {
+        use std::os::unix::ffi::OsStrExt;
+
+        Ok({
+            // Arrange
+            let key = SigningKey::from_bytes(&[1; 32]);
+            let name = std::ffi::OsStr::from_bytes(b"caf\xe9.txt");
+            write_string(&PathBuf::from("sign_manifest_non_utf8").join(name), &"a")?;
+
+            // Action
+            let error = sign_manifest(
+                &"sign_manifest_non_utf8",
+                &"sign_manifest_non_utf8.sums",
+                &key,
+            );
+
+            // Assert
+            assert_eq!(error.unwrap_err().kind(), ErrorKind::InvalidData);
+            assert!(!path_of(&"sign_manifest_non_utf8.sums").exists());
+
+            // Clean-up
+            delete(&"sign_manifest_non_utf8")?;
+        })
+    }
+}
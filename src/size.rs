@@ -0,0 +1,203 @@
+use crate::*;
+
+/// Which units `format_size` uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SizeUnits {
+    /// Powers of 1024: `KiB`, `MiB`, `GiB`, ..., as used for memory and by most file managers on Linux and Windows.
+    Binary,
+    /// Powers of 1000: `kB`, `MB`, `GB`, ..., as used for disk capacities and by macOS.
+    Decimal,
+}
+
+/// Formats a size in bytes for people, such as `512 B`, `1.4 GiB` or `1.5 GB`: exact bytes below one kilo-unit,
+/// and one decimal place above it.
+///
+/// # Parameters
+/// - `bytes`: **owned** `u64`
+/// - `units`: **owned** `SizeUnits`
+///
+/// # Returns
+/// `String`
+///
+/// # Examples
+/// ```
+/// use file_access::SizeUnits;
+///
+/// assert_eq!(file_access::format_size(1_500_000, SizeUnits::Binary), "1.4 MiB");
+/// assert_eq!(file_access::format_size(1_500_000, SizeUnits::Decimal), "1.5 MB");
+/// ```
+pub fn format_size(bytes: u64, units: SizeUnits) -> String {
+    let (base, names) = match units {
+        SizeUnits::Binary => (1024.0, ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"]),
+        SizeUnits::Decimal => (1000.0, ["B", "kB", "MB", "GB", "TB", "PB", "EB"]),
+    };
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    // Moves up a unit while the size would be shown as `base` or more, e.g. "1024.0 KiB"
+    while (size * 10.0).round() / 10.0 >= base && unit < names.len() - 1 {
+        size /= base;
+        unit += 1;
+    }
+
+    return match unit {
+        0 => format!("{bytes} B"),
+        _ => format!("{size:.1} {}", names[unit]),
+    };
+}
+
+/// Gets the size of a file in bytes, following symlinks.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`u64`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "size_example.txt";
+///         file_access::write_string(&file_path, &"Hello, World!")?;
+///
+///         assert_eq!(file_access::size(&file_path)?, 13);
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn size<Path: AsPath + ?Sized>(file_path: &Path) -> Result<u64> {
+    let metadata = fs::metadata(file_path.as_path()).context("size", file_path)?;
+
+    return Ok(metadata.len());
+}
+
+/// Checks whether a file has no contents, or a directory has no entries.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`bool`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "is_empty_example/file.txt";
+///         file_access::write_string(&file_path, &"")?;
+///
+///         assert!(file_access::is_empty(&file_path)?);
+///         assert!(!file_access::is_empty(&"is_empty_example")?);
+///
+///         // Clean-up:
+///         file_access::delete(&"is_empty_example")?;
+///     })
+/// }
+/// ```
+pub fn is_empty<Path: AsPath + ?Sized>(file_path: &Path) -> Result<bool> {
+    let check = || -> io::Result<bool> {
+        let metadata = fs::metadata(file_path.as_path())?;
+        if metadata.is_dir() {
+            return Ok(fs::read_dir(file_path.as_path())?.next().is_none());
+        }
+        return Ok(metadata.len() == 0);
+    };
+    check().context("is_empty", file_path)
+}
+
+/// Gets the size of a file, formatted with binary units such as `1.4 GiB`; see `format_size`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`String`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "Cargo.toml";
+///         let file_path: String = String::from(file_path);
+///
+///         let size: String = file_access::human_size(&file_path)?;
+///         println!("{size}");
+///     })
+/// }
+/// ```
+pub fn human_size<Path: AsPath + ?Sized>(file_path: &Path) -> Result<String> {
+    let metadata = fs::metadata(file_path.as_path()).context("human_size", file_path)?;
+
+    return Ok(format_size(metadata.len(), SizeUnits::Binary));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn format_size() -> Result<()> {
+        Ok({
+            // Assert
+            for (bytes, units, expected) in [
+                (0, SizeUnits::Binary, "0 B"),
+                (1_023, SizeUnits::Binary, "1023 B"),
+                (1_024, SizeUnits::Binary, "1.0 KiB"),
+                (1_048_575, SizeUnits::Binary, "1.0 MiB"),
+                (1_503_238_554, SizeUnits::Binary, "1.4 GiB"),
+                (999, SizeUnits::Decimal, "999 B"),
+                (1_500, SizeUnits::Decimal, "1.5 kB"),
+                (u64::MAX, SizeUnits::Decimal, "18.4 EB"),
+            ] {
+                assert_eq!(super::format_size(bytes, units), expected);
+            }
+        })
+    }
+
+    #[test]
+    fn size_and_is_empty() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"size_and_is_empty/a.txt", &"x".repeat(2_048))?;
+            write_string(&"size_and_is_empty/b.txt", &"")?;
+            fs::create_dir("size_and_is_empty/c")?;
+
+            // Action
+            let size = super::size(&"size_and_is_empty/a.txt")?;
+            let missing = super::size(&"size_and_is_empty/missing.txt");
+
+            // Assert
+            assert_eq!(size, 2_048);
+            assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+            assert!(!is_empty(&"size_and_is_empty/a.txt")?);
+            assert!(is_empty(&"size_and_is_empty/b.txt")?);
+            assert!(is_empty(&"size_and_is_empty/c")?);
+            assert!(!is_empty(&"size_and_is_empty")?);
+
+            // Clean-up
+            delete(&"size_and_is_empty")?;
+        })
+    }
+
+    #[test]
+    fn human_size() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "human_size.txt";
+            write_string(&file_path, &"x".repeat(2_048))?;
+
+            // Action
+            let size = super::human_size(&file_path)?;
+
+            // Assert
+            assert_eq!(size, "2.0 KiB");
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+}
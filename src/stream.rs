@@ -0,0 +1,218 @@
+use crate::*;
+
+/// How `open_writer` treats the existing contents of a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Writes after the existing contents.
+    Append,
+    /// Replaces the existing contents.
+    Truncate,
+}
+
+/// Opens a file for reading through a buffer, such as to hand it to a parser or `serde` deserializer
+/// that reads from any `BufRead`/`Read`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`BufReader<File>`>
+///
+/// # Examples
+/// ```
+/// use std::io::BufRead;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let reader = file_access::open_reader(&"Cargo.toml")?;
+///         let first = reader.lines().next().unwrap()?;
+///         assert_eq!(first, "[package]");
+///     })
+/// }
+/// ```
+pub fn open_reader<Path: AsPath + ?Sized>(file_path: &Path) -> Result<BufReader<File>> {
+    get_file(file_path)
+        .map(BufReader::new)
+        .context("open_reader", file_path)
+}
+
+/// Opens a file for writing through a buffer, such as to hand it to a serializer or encoder that writes to any `Write`,
+/// appending to or replacing its contents as set by `WriteMode`. This function will create the file
+/// **and its full directory path** if they don't exist.
+///
+/// Buffered bytes are written when the writer is dropped, but errors can then only be ignored:
+/// call `flush` when done to get them.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `mode`: `WriteMode`, such as `WriteMode::Append`
+///
+/// # Returns
+/// Result<`BufWriter<File>`>
+///
+/// # Examples
+/// ```
+/// use file_access::WriteMode;
+/// use std::io::Write;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "open_writer_example/report.csv";
+///
+///         let mut writer = file_access::open_writer(&file_path, WriteMode::Truncate)?;
+///         writeln!(writer, "name,size")?;
+///         writeln!(writer, "Cargo.toml,{}", file_access::stat(&"Cargo.toml")?.len())?;
+///         writer.flush()?;
+///
+///         // Clean-up:
+///         file_access::delete(&"open_writer_example")?;
+///     })
+/// }
+/// ```
+pub fn open_writer<Path: AsPath + ?Sized>(
+    file_path: &Path,
+    mode: WriteMode,
+) -> Result<BufWriter<File>> {
+    let open = || -> io::Result<File> {
+        if let Some(dir) = path_of(file_path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut open = File::options();
+        match mode {
+            WriteMode::Append => open.append(true).create(true),
+            WriteMode::Truncate => open.write(true).create(true).truncate(true),
+        };
+        return open.open(file_path.as_path());
+    };
+    open().map(BufWriter::new).context("open_writer", file_path)
+}
+
+/// Writes everything a reader yields to a file, streaming it through a buffer, such as to save the body of a download
+/// without holding it in memory. This function will create the file **and its full directory path** if they don't exist,
+/// and will entirely replace the contents; if the reader fails midway, the file is left with what was written so far.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `reader`: **mutably borrowed** `Read` such as `File`, `TcpStream` or `&[u8]`
+///
+/// # Returns
+/// Result<`u64`>, the number of bytes written
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "write_from_example/download.bin";
+///         let mut body: &[u8] = b"Hello, World!";
+///
+///         let written = file_access::write_from(&file_path, &mut body)?;
+///         assert_eq!(written, 13);
+///
+///         // Clean-up:
+///         file_access::delete(&"write_from_example")?;
+///     })
+/// }
+/// ```
+pub fn write_from<Path: AsPath + ?Sized, Reader: Read + ?Sized>(
+    file_path: &Path,
+    reader: &mut Reader,
+) -> Result<u64> {
+    metered(
+        "write_from",
+        |written: &u64| (0, *written),
+        || {
+            if let Some(dir) = path_of(file_path).parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let mut writer = BufWriter::new(File::create(file_path.as_path())?);
+            let written = io::copy(reader, &mut writer)?;
+            writer.flush()?;
+            return Ok(written);
+        },
+    )
+    .context("write_from", file_path)
+}
+
+/// Streams the contents of a file into a writer through a buffer, such as into a socket or an HTTP response body,
+/// without reading the whole file into memory.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `writer`: **mutably borrowed** `Write` such as `File`, `TcpStream` or `Vec<u8>`
+///
+/// # Returns
+/// Result<`u64`>, the number of bytes read
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let mut body: Vec<u8> = vec![];
+///         let sent = file_access::read_into(&"Cargo.toml", &mut body)?;
+///         assert_eq!(sent, body.len() as u64);
+///     })
+/// }
+/// ```
+pub fn read_into<Path: AsPath + ?Sized, Writer: Write + ?Sized>(
+    file_path: &Path,
+    writer: &mut Writer,
+) -> Result<u64> {
+    metered(
+        "read_into",
+        |read: &u64| (*read, 0),
+        || io::copy(&mut get_file(file_path)?, writer),
+    )
+    .context("read_into", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn open_writer() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "open_writer/log.txt";
+
+            // Action
+            let mut writer = super::open_writer(&file, WriteMode::Truncate)?;
+            writer.write_all(b"a\n")?;
+            writer.flush()?;
+            let mut writer = super::open_writer(&file, WriteMode::Append)?;
+            writer.write_all(b"b\n")?;
+            writer.flush()?;
+
+            // Assert
+            let lines = open_reader(&file)?.lines().collect::<Result<Vec<_>>>()?;
+            assert_eq!(lines, ["a", "b"]);
+            super::open_writer(&file, WriteMode::Truncate)?.flush()?;
+            assert_eq!(read_string(&file)?, "");
+
+            // Clean-up
+            delete(&"open_writer")?;
+        })
+    }
+
+    #[test]
+    fn write_from() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "write_from/copy.txt";
+            let mut body: &[u8] = b"Hello, World!";
+            let mut sink = vec![];
+
+            // Action
+            let written = super::write_from(&file, &mut body)?;
+            let read = read_into(&file, &mut sink)?;
+
+            // Assert
+            assert_eq!((written, read), (13, 13));
+            assert_eq!(sink, b"Hello, World!");
+
+            // Clean-up
+            delete(&"write_from")?;
+        })
+    }
+}
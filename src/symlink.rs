@@ -0,0 +1,165 @@
+use crate::*;
+use std::path::Path as StdPath;
+
+// Creates a symlink at `link` pointing to `target`, as given (a relative target is relative to the link's directory)
+fn create(target: &StdPath, link: &StdPath) -> io::Result<()> {
+    if let Some(dir) = link.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+
+    #[cfg(unix)]
+    return std::os::unix::fs::symlink(target, link);
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::{symlink_dir, symlink_file};
+
+        // Windows tells file and directory symlinks apart, by what the target is when it's created
+        let resolved = link.parent().unwrap_or(".".as_ref()).join(target);
+        return match resolved.is_dir() {
+            true => symlink_dir(target, link),
+            false => symlink_file(target, link),
+        };
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = (target, link);
+        return Err(ErrorKind::Unsupported.into());
+    }
+}
+
+/// Creates a symbolic link at `link_path` pointing to `target`, such as a `current` link to the latest release.
+/// A relative `target` is resolved from the link's directory, not the working directory, and doesn't need to exist.
+/// This function will create the link's full directory path if it doesn't exist, and fails with
+/// `ErrorKind::AlreadyExists` if something is already at `link_path`. On Windows, creating symlinks takes
+/// Developer Mode or administrator rights.
+///
+/// # Parameters
+/// - `target`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `link_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```no_run
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"symlink_example/releases/v2/app.conf", &"port = 8080")?;
+///
+///         file_access::symlink(&"releases/v2", &"symlink_example/current")?;
+///         let text = file_access::read_string(&"symlink_example/current/app.conf")?;
+///         assert_eq!(text, "port = 8080");
+///
+///         // Clean-up:
+///         file_access::delete(&"symlink_example")?;
+///     })
+/// }
+/// ```
+pub fn symlink<Target: AsPath + ?Sized, Link: AsPath + ?Sized>(
+    target: &Target,
+    link_path: &Link,
+) -> Result<()> {
+    create(target.as_path(), link_path.as_path()).context("symlink", link_path)
+}
+
+/// Reads where a symbolic link points to, as it was created (a relative target stays relative), without following it.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`PathBuf`>, failing with `ErrorKind::InvalidInput` if the path isn't a symlink
+pub fn read_link<Path: AsPath + ?Sized>(file_path: &Path) -> Result<PathBuf> {
+    fs::read_link(file_path.as_path()).context("read_link", file_path)
+}
+
+/// Resolves a path to the absolute path of what it refers to, following every symlink along the way and removing
+/// `.` and `..` components; the path must exist. On Windows the result has the `\\?\` verbatim prefix.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`PathBuf`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let resolved = file_access::resolve(&"src/../Cargo.toml")?;
+///         assert!(resolved.is_absolute() && resolved.ends_with("Cargo.toml"));
+///     })
+/// }
+/// ```
+pub fn resolve<Path: AsPath + ?Sized>(file_path: &Path) -> Result<PathBuf> {
+    fs::canonicalize(file_path.as_path()).context("resolve", file_path)
+}
+
+/// Copies a file like `copy`, except that a symlink is copied as a symlink with the same target,
+/// rather than as a regular file holding the contents it points to. A symlink at the destination is replaced,
+/// and a dangling symlink is copied as it is.
+///
+/// # Parameters
+/// - `from`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `to`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+pub fn copy_symlink<From: AsPath + ?Sized, To: AsPath + ?Sized>(
+    from: &From,
+    to: &To,
+) -> Result<()> {
+    let metadata = fs::symlink_metadata(from.as_path()).context("copy_symlink", from)?;
+    if !metadata.file_type().is_symlink() {
+        return copy(from, to);
+    }
+    let copy = || -> io::Result<()> {
+        let target = fs::read_link(from.as_path())?;
+        let to = to.as_path();
+        if fs::symlink_metadata(to).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+            fs::remove_file(to)?;
+        }
+        return create(&target, to);
+    };
+    copy().context("copy_symlink", to)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use super::*;
+    #[cfg(unix)]
+    use std::io::Result;
+
+    #[test]
+    #[cfg(unix)]
+    fn symlink() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"symlink_test/v1/app.conf", &"v1")?;
+
+            // Action
+            super::symlink(&"v1", &"symlink_test/current")?;
+            copy_symlink(&"symlink_test/current", &"symlink_test/copy")?;
+            super::symlink(&"missing", &"symlink_test/dangling")?;
+            copy_symlink(&"symlink_test/dangling", &"symlink_test/dangling_copy")?;
+
+            // Assert
+            assert_eq!(read_string(&"symlink_test/current/app.conf")?, "v1");
+            assert_eq!(read_link(&"symlink_test/copy")?, PathBuf::from("v1"));
+            assert_eq!(
+                read_link(&"symlink_test/dangling_copy")?,
+                PathBuf::from("missing")
+            );
+            assert_eq!(
+                resolve(&"symlink_test/copy/app.conf")?,
+                resolve(&"symlink_test/v1/app.conf")?
+            );
+            let exists = super::symlink(&"v1", &"symlink_test/current").unwrap_err();
+            assert_eq!(exists.kind(), ErrorKind::AlreadyExists);
+
+            // Clean-up
+            delete(&"symlink_test")?;
+        })
+    }
+}
@@ -0,0 +1,610 @@
+use crate::{internal::glob::glob_match, progress::Tracker, *};
+use std::{collections::BTreeSet, ffi::OsString, path::Path as StdPath};
+
+/// How `sync_dir` mirrors a directory: which files take part, chosen with `/`-separated glob patterns
+/// matched against paths relative to the source (such as `"*.rs"` or `"target/**"`), whether files that
+/// aren't in the source are deleted from the destination, how changed files are told apart, and whether
+/// anything is actually done. Settings are chained onto it builder-style; by default every file is copied
+/// when its size or modification time differs, and nothing is deleted.
+///
+/// # Examples
+/// ```
+/// use file_access::SyncOptions;
+///
+/// let options = SyncOptions::new().include(&"**/*.rs").exclude(&"target").delete_extraneous(true);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SyncOptions {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    delete_extraneous: bool,
+    checksum: bool,
+    dry_run: bool,
+}
+
+impl SyncOptions {
+    /// Creates the default options, which copy every new or changed file and delete nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only syncs the files matching this pattern, or any other included one. Directories are still walked.
+    pub fn include<Pattern: AsRef<str> + ?Sized>(mut self, pattern: &Pattern) -> Self {
+        self.include.push(pattern.as_ref().to_string());
+        self
+    }
+
+    /// Leaves out the files and directories matching this pattern, on both sides: they are neither copied
+    /// nor deleted, and excluded directories aren't walked.
+    pub fn exclude<Pattern: AsRef<str> + ?Sized>(mut self, pattern: &Pattern) -> Self {
+        self.exclude.push(pattern.as_ref().to_string());
+        self
+    }
+
+    /// Whether to delete the files and directories of the destination that aren't in the source.
+    pub fn delete_extraneous(mut self, delete_extraneous: bool) -> Self {
+        self.delete_extraneous = delete_extraneous;
+        self
+    }
+
+    /// Whether to compare the contents of files that exist on both sides, instead of their size and modification time.
+    pub fn checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Whether to only report what would be done, without changing the destination.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    fn excluded(&self, relative: &StdPath) -> bool {
+        let relative = slashed(relative);
+        self.exclude
+            .iter()
+            .any(|pattern| glob_match(pattern, &relative))
+    }
+
+    fn included(&self, relative: &StdPath) -> bool {
+        let relative = slashed(relative);
+        self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| glob_match(pattern, &relative))
+    }
+}
+
+/// What `sync_dir` did, or would do with `dry_run`, as paths relative to the directories.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// The files copied because the destination didn't have them.
+    pub added: Vec<PathBuf>,
+    /// The files copied over a changed destination file.
+    pub updated: Vec<PathBuf>,
+    /// The files and directories deleted from the destination.
+    pub deleted: Vec<PathBuf>,
+    /// The files left as they were.
+    pub unchanged: Vec<PathBuf>,
+}
+
+impl SyncReport {
+    /// Returns `true` if the destination wasn't changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.deleted.is_empty()
+    }
+}
+
+// Joins the components of a relative path with `/` for glob patterns to match against; only the matching
+// sees names that aren't valid UTF-8 with `U+FFFD` in them
+fn slashed(relative: &StdPath) -> String {
+    let components: Vec<_> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect();
+    return components.join("/");
+}
+
+// Reads a directory's entries, sorted by name, or none if it doesn't exist
+fn entries(dir: &StdPath) -> io::Result<Vec<(OsString, fs::FileType)>> {
+    let read_dir = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(x) if x.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(x) => return Err(x),
+    };
+    let mut entries = vec![];
+    for entry in read_dir {
+        let entry = entry?;
+        entries.push((entry.file_name(), entry.file_type()?));
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    return Ok(entries);
+}
+
+// Removes a file, symlink or directory without following symlinks
+fn remove(path: &StdPath) -> io::Result<()> {
+    match fs::symlink_metadata(path)?.is_dir() {
+        true => fs::remove_dir_all(path),
+        false => fs::remove_file(path),
+    }
+}
+
+// A file to copy unless the destination already matches it, left for after the walk so that
+// the copies can run in parallel
+struct Transfer {
+    source: PathBuf,
+    dest: PathBuf,
+    relative: PathBuf,
+    exists: bool,
+}
+
+enum Outcome {
+    Added,
+    Updated,
+    Unchanged,
+}
+
+impl Transfer {
+    // Copies the file unless the destination already matches it, counting it as done on the tracker if any
+    fn run(&self, options: &SyncOptions, mut tracker: Option<&mut Tracker>) -> io::Result<Outcome> {
+        if let Some(tracker) = &tracker {
+            tracker.check()?;
+        }
+        let outcome = self.transfer(options, tracker.as_deref_mut())?;
+        if let Some(tracker) = tracker {
+            tracker.done(&self.source);
+        }
+        return Ok(outcome);
+    }
+
+    fn transfer(
+        &self,
+        options: &SyncOptions,
+        tracker: Option<&mut Tracker>,
+    ) -> io::Result<Outcome> {
+        if self.exists && matches(options, &self.source, &self.dest)? {
+            return Ok(Outcome::Unchanged);
+        }
+        if !options.dry_run {
+            // Copies next to the destination and renames over it, which replaces a symlink there instead of
+            // writing through it to wherever it points
+            let temp = temp_sibling(&self.dest);
+            let copied = self
+                .copy(&temp, tracker)
+                .and_then(|_| fs::rename(&temp, &self.dest));
+            if copied.is_err() {
+                _ = fs::remove_file(&temp);
+            }
+            copied?;
+        }
+        return Ok(match self.exists {
+            true => Outcome::Updated,
+            false => Outcome::Added,
+        });
+    }
+
+    fn copy(&self, to: &StdPath, tracker: Option<&mut Tracker>) -> io::Result<()> {
+        match tracker {
+            Some(tracker) => tracker.copy(&self.source, to)?,
+            None => fs::copy(&self.source, to)?,
+        };
+        // Keeps the source's modification time, which tells the next sync the file is unchanged
+        let modified = fs::metadata(&self.source)?.modified()?;
+        set_modified(to, modified)?;
+
+        return Ok(());
+    }
+}
+
+// Tells whether a destination file matches its source; a symlink never does, so that it's replaced by a copy
+fn matches(options: &SyncOptions, source: &StdPath, dest: &StdPath) -> io::Result<bool> {
+    if fs::symlink_metadata(dest)?.is_symlink() {
+        return Ok(false);
+    }
+    if options.checksum {
+        return Ok(same_content(source, dest)?);
+    }
+    let (source, dest) = (fs::metadata(source)?, fs::metadata(dest)?);
+    return Ok(source.len() == dest.len() && source.modified()? == dest.modified()?);
+}
+
+// Runs the transfers in order, reporting to the hook if any, or spread over rayon's thread pool,
+// stopping at the first error
+fn run_all(
+    transfers: &[Transfer],
+    options: &SyncOptions,
+    parallel: bool,
+    progress: Option<&mut dyn Progress>,
+) -> io::Result<Vec<Outcome>> {
+    if let Some(progress) = progress {
+        let mut tracker = Tracker::new(progress, None);
+        return transfers
+            .iter()
+            .map(|t| t.run(options, Some(&mut tracker)))
+            .collect();
+    }
+    #[cfg(feature = "rayon")]
+    if parallel {
+        use rayon::prelude::*;
+
+        return transfers.par_iter().map(|t| t.run(options, None)).collect();
+    }
+    let _ = parallel;
+    return transfers.iter().map(|t| t.run(options, None)).collect();
+}
+
+struct Syncer<'a> {
+    source: &'a StdPath,
+    dest: &'a StdPath,
+    options: &'a SyncOptions,
+    transfers: Vec<Transfer>,
+    report: SyncReport,
+}
+
+impl Syncer<'_> {
+    // Mirrors the source directory at `relative` onto the destination
+    fn sync(&mut self, relative: &StdPath) -> io::Result<()> {
+        let (source_dir, dest_dir) = (self.source.join(relative), self.dest.join(relative));
+        let mut seen = BTreeSet::new();
+        for (name, file_type) in entries(&source_dir)? {
+            let relative = relative.join(&name);
+            let (source, dest) = (source_dir.join(&name), dest_dir.join(&name));
+            if self.options.excluded(&relative) {
+                continue;
+            }
+            seen.insert(name);
+            if file_type.is_symlink() && source.is_dir() {
+                continue; // not followed, to not loop around cycles
+            }
+            if file_type.is_dir() {
+                if dest.is_file() || dest.is_symlink() {
+                    self.delete(&dest, &relative)?;
+                }
+                if !self.options.dry_run {
+                    fs::create_dir_all(&dest)?;
+                }
+                self.sync(&relative)?;
+            } else if self.options.included(&relative) {
+                self.copy(&source, &dest, relative)?;
+            }
+        }
+        if self.options.delete_extraneous {
+            self.prune(relative, &seen)?;
+        }
+        return Ok(());
+    }
+
+    // Queues a file to be copied, once whatever directory is in its way is deleted
+    fn copy(&mut self, source: &StdPath, dest: &StdPath, relative: PathBuf) -> io::Result<()> {
+        let exists = match fs::symlink_metadata(dest) {
+            Ok(metadata) if metadata.is_dir() => {
+                self.delete(dest, &relative)?;
+                false
+            }
+            Ok(_) => true,
+            Err(x) if x.kind() == ErrorKind::NotFound => false,
+            Err(x) => return Err(x),
+        };
+        self.transfers.push(Transfer {
+            source: source.to_path_buf(),
+            dest: dest.to_path_buf(),
+            relative,
+            exists,
+        });
+        return Ok(());
+    }
+
+    // Deletes what the destination directory at `relative` has that the source didn't
+    fn prune(&mut self, relative: &StdPath, seen: &BTreeSet<OsString>) -> io::Result<()> {
+        let dest_dir = self.dest.join(relative);
+        for (name, file_type) in entries(&dest_dir)? {
+            let relative = relative.join(&name);
+            let dest = dest_dir.join(&name);
+            if seen.contains(&name) || self.options.excluded(&relative) {
+                continue;
+            }
+            if file_type.is_dir() {
+                // Only the included files are deleted, along with the directories they leave empty
+                self.prune(&relative, &BTreeSet::new())?;
+                if self.options.include.is_empty() || entries(&dest)?.is_empty() {
+                    self.delete(&dest, &relative)?;
+                }
+            } else if self.options.included(&relative) {
+                self.delete(&dest, &relative)?;
+            }
+        }
+        return Ok(());
+    }
+
+    fn delete(&mut self, dest: &StdPath, relative: &StdPath) -> io::Result<()> {
+        if !self.options.dry_run {
+            remove(dest)?;
+        }
+        self.report.deleted.push(relative.to_path_buf());
+
+        return Ok(());
+    }
+}
+
+/// Makes a destination directory mirror a source directory, recursively: files that are new or changed in
+/// the source are copied over, keeping their modification time, and with `SyncOptions::delete_extraneous`,
+/// files and directories that aren't in the source are deleted from the destination. Symlinks are copied as
+/// the files they point to, and aren't followed into directories; a symlink in the destination is replaced by the copy
+/// rather than written through. This function will create the destination's full directory path if it doesn't exist.
+///
+/// # Parameters
+/// - `source`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `dest`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `options`: **borrowed** `SyncOptions`, such as `&SyncOptions::new().delete_extraneous(true)`
+///
+/// # Returns
+/// Result<`SyncReport`> of the files added, updated, deleted and left unchanged
+///
+/// # Examples
+/// ```
+/// use file_access::SyncOptions;
+/// use std::path::PathBuf;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let options = SyncOptions::new().include(&"**/*.rs").delete_extraneous(true);
+///
+///         let report = file_access::sync_dir(&"src", &"sync_dir_example/src", &options)?;
+///         assert!(report.added.contains(&PathBuf::from("lib.rs")));
+///
+///         let report = file_access::sync_dir(&"src", &"sync_dir_example/src", &options)?;
+///         assert!(report.is_empty());
+///
+///         // Clean-up:
+///         file_access::delete(&"sync_dir_example")?;
+///     })
+/// }
+/// ```
+pub fn sync_dir<Source: AsPath + ?Sized, Dest: AsPath + ?Sized>(
+    source: &Source,
+    dest: &Dest,
+    options: &SyncOptions,
+) -> Result<SyncReport> {
+    mirror("sync_dir", source, dest, options, false, None)
+}
+
+/// Makes a destination directory mirror a source directory like `sync_dir`, calling `progress` after each chunk
+/// copied and each file done, whether it was copied or left as it was. The total bytes aren't known up front,
+/// since unchanged files aren't read.
+///
+/// # Parameters
+/// - `source`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `dest`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `options`: **borrowed** `SyncOptions`, such as `&SyncOptions::new().delete_extraneous(true)`
+/// - `progress`: **mutably borrowed** `Progress` such as a `FnMut(&ProgressUpdate)` closure
+///
+/// # Returns
+/// Result<`SyncReport`> of the files added, updated, deleted and left unchanged
+///
+/// # Examples
+/// ```
+/// use file_access::{ProgressUpdate, SyncOptions};
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let mut files = 0;
+///         let report = file_access::sync_dir_with_progress(
+///             &"src",
+///             &"sync_dir_with_progress_example/src",
+///             &SyncOptions::new(),
+///             &mut |update: &ProgressUpdate| files = update.files,
+///         )?;
+///         assert_eq!(files as usize, report.added.len());
+///
+///         // Clean-up:
+///         file_access::delete(&"sync_dir_with_progress_example")?;
+///     })
+/// }
+/// ```
+pub fn sync_dir_with_progress<Source: AsPath + ?Sized, Dest: AsPath + ?Sized, P: Progress>(
+    source: &Source,
+    dest: &Dest,
+    options: &SyncOptions,
+    progress: &mut P,
+) -> Result<SyncReport> {
+    mirror(
+        "sync_dir_with_progress",
+        source,
+        dest,
+        options,
+        false,
+        Some(progress),
+    )
+}
+
+// Syncs a directory, copying the files that need it in parallel, or reporting progress, if asked to
+pub(crate) fn mirror<Source: AsPath + ?Sized, Dest: AsPath + ?Sized>(
+    operation: &'static str,
+    source: &Source,
+    dest: &Dest,
+    options: &SyncOptions,
+    parallel: bool,
+    progress: Option<&mut dyn Progress>,
+) -> Result<SyncReport> {
+    // bytes aren't counted, as comparing files reads an unknown part of them
+    metered_with_context(
+        operation,
+        |_| (0, 0),
+        || {
+            if !source.as_path().is_dir() {
+                let kind = match source.as_path().exists() {
+                    true => ErrorKind::NotADirectory,
+                    false => ErrorKind::NotFound,
+                };
+                return Err(kind).context(operation, source);
+            }
+            if !options.dry_run {
+                fs::create_dir_all(dest.as_path()).context(operation, dest)?;
+            }
+            let mut syncer = Syncer {
+                source: source.as_path(),
+                dest: dest.as_path(),
+                options,
+                transfers: vec![],
+                report: SyncReport::default(),
+            };
+            syncer.sync(StdPath::new("")).context(operation, source)?;
+            let outcomes = run_all(&syncer.transfers, options, parallel, progress)
+                .context(operation, source)?;
+
+            let mut report = syncer.report;
+            for (transfer, outcome) in syncer.transfers.into_iter().zip(outcomes) {
+                match outcome {
+                    Outcome::Added => report.added.push(transfer.relative),
+                    Outcome::Updated => report.updated.push(transfer.relative),
+                    Outcome::Unchanged => report.unchanged.push(transfer.relative),
+                }
+            }
+            return Ok(report);
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn sync_dir() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"sync_dir/src/a.txt", &"a")?;
+            write_string(&"sync_dir/src/b/c.txt", &"c")?;
+            write_string(&"sync_dir/src/b/d.log", &"d")?;
+            write_string(&"sync_dir/dest/a.txt", &"old a")?;
+            write_string(&"sync_dir/dest/extra.txt", &"")?;
+            write_string(&"sync_dir/dest/gone/e.txt", &"")?;
+            write_string(&"sync_dir/dest/keep.log", &"")?;
+            let options = SyncOptions::new()
+                .exclude(&"**/*.log")
+                .delete_extraneous(true);
+
+            // Action
+            let dry_run = super::sync_dir(
+                &"sync_dir/src",
+                &"sync_dir/dest",
+                &options.clone().dry_run(true),
+            )?;
+            let first = super::sync_dir(&"sync_dir/src", &"sync_dir/dest", &options)?;
+            let second = super::sync_dir(&"sync_dir/src", &"sync_dir/dest", &options)?;
+
+            // Assert
+            assert_eq!(dry_run, first);
+            assert_eq!(first.added, ["b/c.txt"].map(PathBuf::from));
+            assert_eq!(first.updated, ["a.txt"].map(PathBuf::from));
+            assert_eq!(
+                first.deleted,
+                ["extra.txt", "gone/e.txt", "gone"].map(PathBuf::from)
+            );
+            assert!(second.is_empty());
+            assert_eq!(second.unchanged, ["a.txt", "b/c.txt"].map(PathBuf::from));
+            assert_eq!(read_string(&"sync_dir/dest/a.txt")?, "a");
+            assert!(!FilePath::access(&"sync_dir/dest/b/d.log")
+                .as_path()
+                .exists());
+            assert!(FilePath::access(&"sync_dir/dest/keep.log")
+                .as_path()
+                .exists());
+
+            // Clean-up
+            delete(&"sync_dir")?;
+        })
+    }
+
+    #[test]
+    fn sync_dir_include() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"sync_dir_include/src/a.rs", &"fn main() {}")?;
+            write_string(&"sync_dir_include/src/b.md", &"# b")?;
+            write_string(&"sync_dir_include/dest/old/c.rs", &"")?;
+            write_string(&"sync_dir_include/dest/old/d.md", &"")?;
+            let options = SyncOptions::new()
+                .include(&"**/*.rs")
+                .delete_extraneous(true)
+                .checksum(true);
+
+            // Action
+            let report =
+                super::sync_dir(&"sync_dir_include/src", &"sync_dir_include/dest", &options)?;
+
+            // Assert
+            assert_eq!(report.added, ["a.rs"].map(PathBuf::from));
+            assert_eq!(report.deleted, ["old/c.rs"].map(PathBuf::from));
+            assert!(FilePath::access(&"sync_dir_include/dest/old/d.md")
+                .as_path()
+                .exists());
+            let missing = super::sync_dir(&"sync_dir_include/missing", &"x", &options);
+            assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+
+            // Clean-up
+            delete(&"sync_dir_include")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn sync_dir_non_utf8() -> Result<()> {
+        Ok({
+            // Arrange
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+            let name = OsStr::from_bytes(b"caf\xe9.txt");
+            write_string(&PathBuf::from("sync_dir_non_utf8/src").join(name), &"a")?;
+
+            // Action
+            let report = super::sync_dir(
+                &"sync_dir_non_utf8/src",
+                &"sync_dir_non_utf8/dest",
+                &SyncOptions::new(),
+            )?;
+
+            // Assert
+            assert_eq!(report.added, [PathBuf::from(name)]);
+            let copied = PathBuf::from("sync_dir_non_utf8/dest").join(name);
+            assert_eq!(read_string(&copied)?, "a");
+
+            // Clean-up
+            delete(&"sync_dir_non_utf8")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn sync_dir_dest_symlink() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"sync_dir_dest_symlink/src/a.txt", &"mirrored")?;
+            write_string(&"sync_dir_dest_symlink/outside.txt", &"untouched")?;
+            create_dir(&"sync_dir_dest_symlink/dest")?;
+            std::os::unix::fs::symlink("../outside.txt", "sync_dir_dest_symlink/dest/a.txt")?;
+
+            // Action
+            let report = super::sync_dir(
+                &"sync_dir_dest_symlink/src",
+                &"sync_dir_dest_symlink/dest",
+                &SyncOptions::new(),
+            )?;
+
+            // Assert
+            assert_eq!(report.updated, ["a.txt"].map(PathBuf::from));
+            assert_eq!(
+                read_string(&"sync_dir_dest_symlink/outside.txt")?,
+                "untouched"
+            );
+            let copied = StdPath::new("sync_dir_dest_symlink/dest/a.txt");
+            assert!(!copied.is_symlink(), "the symlink should be replaced");
+            assert_eq!(read_string(&copied)?, "mirrored");
+
+            // Clean-up
+            delete(&"sync_dir_dest_symlink")?;
+        })
+    }
+}
@@ -0,0 +1,399 @@
+use crate::*;
+use std::{
+    ops::Deref,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Where `temp_file_with` and `temp_dir_with` create their file or directory, and what it's named:
+/// `{prefix}{unique}{suffix}`. Settings are chained onto it builder-style; by default it's created
+/// in the system's temporary directory, such as `/tmp`, with the prefix `tmp` and no suffix.
+///
+/// # Examples
+/// ```
+/// use file_access::TempOptions;
+///
+/// let options = TempOptions::new().prefix("report-").suffix(".csv").dir("target");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TempOptions {
+    prefix: Option<String>,
+    suffix: String,
+    dir: Option<PathBuf>,
+}
+
+impl TempOptions {
+    /// Creates the default options, for a file or directory in the system's temporary directory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// What the name starts with, such as `"report-"`.
+    pub fn prefix<Text: AsRef<str> + ?Sized>(mut self, prefix: &Text) -> Self {
+        self.prefix = Some(prefix.as_ref().to_string());
+        self
+    }
+
+    /// What the name ends with, such as an extension like `".csv"`.
+    pub fn suffix<Text: AsRef<str> + ?Sized>(mut self, suffix: &Text) -> Self {
+        self.suffix = suffix.as_ref().to_string();
+        self
+    }
+
+    /// The directory to create it in, instead of the system's temporary directory.
+    /// It's created, along with its full path, if it doesn't exist.
+    pub fn dir<Path: AsPath + ?Sized>(mut self, dir: &Path) -> Self {
+        self.dir = Some(path_of(dir));
+        self
+    }
+
+    // The directory to create it in
+    fn parent(&self) -> PathBuf {
+        self.dir.clone().unwrap_or_else(std::env::temp_dir)
+    }
+
+    // Creates a uniquely named entry with `create`, retrying with another name if one is taken
+    fn create(&self, create: impl Fn(&std::path::Path) -> io::Result<()>) -> io::Result<FilePath> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let dir = self.parent();
+        fs::create_dir_all(&dir)?;
+        loop {
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .subsec_nanos();
+            let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let name = format!(
+                "{}{}.{count}.{nanos:08x}{}",
+                self.prefix.as_deref().unwrap_or("tmp"),
+                process::id(),
+                self.suffix
+            );
+            let path = dir.join(name);
+            match create(&path) {
+                Err(x) if x.kind() == ErrorKind::AlreadyExists => continue,
+                result => return result.map(|_| FilePath::from(path)),
+            }
+        }
+    }
+}
+
+/// A guard which deletes a file, or a directory along with everything in it, when dropped, unless `keep` is called:
+/// the one behind `delete_on_drop`, `temp_file` and `temp_dir`, such as to not leave test artifacts behind even when
+/// an assertion fails. It dereferences to the `FilePath`, so every `FilePath` method can be used on it,
+/// and can be passed to every function taking a path.
+///
+/// # Examples
+/// ```
+/// use file_access::FilePath;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file = FilePath::access(&"delete_on_drop_example.txt").delete_on_drop();
+///         file.write_string(&"Hello, World!")?;
+///         assert!(file.exists());
+///     }) // deleted here
+/// }
+/// ```
+pub struct DeleteOnDrop {
+    file: FilePath,
+    kept: bool,
+    remove: fn(&std::path::Path) -> io::Result<()>,
+}
+
+/// A temporary file, as created by `temp_file`: a `DeleteOnDrop` guard which deletes the file when dropped.
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let temp = file_access::temp_file()?;
+///         temp.write_string(&"scratch")?;
+///         assert_eq!(file_access::read_string(&temp)?, "scratch");
+///     }) // deleted here
+/// }
+/// ```
+pub type TempFile = DeleteOnDrop;
+
+/// A temporary directory, as created by `temp_dir`: a `DeleteOnDrop` guard which deletes the directory,
+/// along with everything in it, when dropped.
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let temp = file_access::temp_dir()?;
+///         temp.join("nested/file.txt").write_string(&"scratch")?;
+///     }) // deleted here, with its contents
+/// }
+/// ```
+pub type TempDir = DeleteOnDrop;
+
+impl DeleteOnDrop {
+    fn new(file: FilePath, remove: fn(&std::path::Path) -> io::Result<()>) -> Self {
+        Self {
+            file,
+            kept: false,
+            remove,
+        }
+    }
+
+    /// The path of the file or directory.
+    pub fn path(&self) -> &FilePath {
+        &self.file
+    }
+
+    /// Keeps the file or directory rather than deleting it, returning its path.
+    pub fn keep(mut self) -> FilePath {
+        self.kept = true;
+        FilePath::from(path_of(&self.file))
+    }
+}
+
+impl Deref for DeleteOnDrop {
+    type Target = FilePath;
+
+    fn deref(&self) -> &FilePath {
+        &self.file
+    }
+}
+
+impl AsPath for DeleteOnDrop {
+    fn as_path(&self) -> &std::path::Path {
+        self.file.as_path()
+    }
+}
+
+impl Drop for DeleteOnDrop {
+    fn drop(&mut self) {
+        if !self.kept {
+            _ = (self.remove)(self.file.as_path());
+        }
+    }
+}
+
+/// Returns a guard which deletes a file, or a directory along with everything in it, when it's dropped,
+/// unless `keep` is called. Nothing is created; whatever is at the path when the guard is dropped is deleted,
+/// and it's no error if there's nothing.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// `DeleteOnDrop`
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let _guard = file_access::delete_on_drop(&"delete_on_drop_dir_example");
+///         file_access::write_string(&"delete_on_drop_dir_example/a.txt", &"a")?;
+///     }) // ./delete_on_drop_dir_example/ is deleted here
+/// }
+/// ```
+pub fn delete_on_drop<Path: AsPath + ?Sized>(file_path: &Path) -> DeleteOnDrop {
+    DeleteOnDrop::new(FilePath::access(file_path), |path| Ok(delete(path)?))
+}
+
+/// Creates an empty, uniquely named file in the system's temporary directory, which is deleted when
+/// the returned guard is dropped. See `temp_file_with` to choose its name and directory.
+///
+/// # Returns
+/// Result<`TempFile`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let temp = file_access::temp_file()?;
+///         temp.write_lines(&vec!["a", "b"])?;
+///         assert_eq!(temp.read_lines()?, ["a", "b"]);
+///     })
+/// }
+/// ```
+pub fn temp_file() -> Result<TempFile> {
+    temp_file_with(TempOptions::new())
+}
+
+/// Creates an empty, uniquely named file as set by `TempOptions`, which is deleted when the returned guard is dropped.
+/// On Unix, only its owner can read or write it (mode `0o600`).
+///
+/// # Parameters
+/// - `options`: `TempOptions`, such as `TempOptions::new().suffix(".csv")`
+///
+/// # Returns
+/// Result<`TempFile`>
+///
+/// # Examples
+/// ```
+/// use file_access::TempOptions;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let temp = file_access::temp_file_with(TempOptions::new().prefix("report-").suffix(".csv"))?;
+///         assert!(temp.as_ref().ends_with(".csv"));
+///     })
+/// }
+/// ```
+pub fn temp_file_with(options: TempOptions) -> Result<TempFile> {
+    let create = |path: &std::path::Path| {
+        let mut open = File::options();
+        open.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            open.mode(0o600);
+        }
+        open.open(path)?;
+        return Ok(());
+    };
+    let file = options
+        .create(create)
+        .context("temp_file", &options.parent())?;
+
+    return Ok(TempFile::new(file, |path| fs::remove_file(path)));
+}
+
+/// Creates an empty, uniquely named directory in the system's temporary directory, which is deleted along with
+/// everything in it when the returned guard is dropped. See `temp_dir_with` to choose its name and parent directory.
+///
+/// # Returns
+/// Result<`TempDir`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let temp = file_access::temp_dir()?;
+///         temp.join("a.txt").write_string(&"a")?;
+///         assert_eq!(file_access::list_dir(&temp, Default::default())?.len(), 1);
+///     })
+/// }
+/// ```
+pub fn temp_dir() -> Result<TempDir> {
+    temp_dir_with(TempOptions::new())
+}
+
+/// Creates an empty, uniquely named directory as set by `TempOptions`, which is deleted along with everything in it
+/// when the returned guard is dropped. On Unix, only its owner can enter or list it (mode `0o700`).
+///
+/// # Parameters
+/// - `options`: `TempOptions`, such as `TempOptions::new().dir("target")`
+///
+/// # Returns
+/// Result<`TempDir`>
+///
+/// # Examples
+/// ```
+/// use file_access::TempOptions;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let temp = file_access::temp_dir_with(TempOptions::new().prefix("build-").dir("target"))?;
+///         assert!(temp.is_dir());
+///     })
+/// }
+/// ```
+pub fn temp_dir_with(options: TempOptions) -> Result<TempDir> {
+    let create = |path: &std::path::Path| {
+        #[cfg(unix)]
+        use std::os::unix::fs::DirBuilderExt;
+
+        #[cfg(unix)]
+        return fs::DirBuilder::new().mode(0o700).create(path);
+
+        #[cfg(not(unix))]
+        return fs::DirBuilder::new().create(path);
+    };
+    let dir = options
+        .create(create)
+        .context("temp_dir", &options.parent())?;
+
+    return Ok(TempDir::new(dir, |path| fs::remove_dir_all(path)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn temp_file() -> Result<()> {
+        Ok({
+            // Arrange
+            let options = TempOptions::new()
+                .prefix("pre-")
+                .suffix(".txt")
+                .dir("temp_file");
+
+            // Action
+            let temp = temp_file_with(options.clone())?;
+            let other = temp_file_with(options)?;
+            temp.write_string(&"scratch")?;
+            let path = path_of(temp.path());
+            let kept = other.keep();
+            drop(temp);
+
+            // Assert
+            let name = kept.as_path().file_name().unwrap().to_string_lossy();
+            assert!(name.starts_with("pre-") && name.ends_with(".txt"));
+            assert!(!path.exists());
+            assert!(kept.exists());
+            assert!(super::temp_file()?
+                .as_path()
+                .starts_with(std::env::temp_dir()));
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mode = fs::metadata(kept.as_path())?.permissions().mode();
+                assert_eq!(mode & 0o777, 0o600);
+            }
+
+            // Clean-up
+            delete(&"temp_file")?;
+        })
+    }
+
+    #[test]
+    fn delete_on_drop() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"delete_on_drop/a/b.txt", &"b")?;
+            write_string(&"delete_on_drop/c.txt", &"c")?;
+
+            // Action
+            drop(super::delete_on_drop(&"delete_on_drop/a"));
+            drop(super::delete_on_drop(&"delete_on_drop/missing"));
+            let kept = super::delete_on_drop(&"delete_on_drop/c.txt").keep();
+
+            // Assert
+            assert!(!path_of(&"delete_on_drop/a").exists());
+            assert!(kept.exists());
+
+            // Clean-up
+            delete(&"delete_on_drop")?;
+        })
+    }
+
+    #[test]
+    fn temp_dir() -> Result<()> {
+        Ok({
+            // Arrange
+            let temp = super::temp_dir()?;
+
+            // Action
+            #[cfg(unix)]
+            let mode = {
+                use std::os::unix::fs::PermissionsExt;
+                fs::metadata(temp.as_path())?.permissions().mode()
+            };
+            temp.join("a/b.txt").write_string(&"b")?;
+            let path = path_of(temp.path());
+            drop(temp);
+
+            // Assert
+            #[cfg(unix)]
+            assert_eq!(mode & 0o777, 0o700);
+            assert!(!path.exists());
+        })
+    }
+}
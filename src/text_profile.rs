@@ -0,0 +1,291 @@
+use crate::*;
+
+/// The encoding of a text file, as detected by `text_profile`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+    /// Neither marked by a byte order mark nor valid UTF-8, e.g. Latin-1 or binary.
+    Unknown,
+}
+
+/// A line ending style.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`, as on Unix.
+    Lf,
+    /// `\r\n`, as on Windows.
+    CrLf,
+    /// `\r`, as on classic Mac OS.
+    Cr,
+    /// The text has a single line.
+    None,
+}
+
+/// An indentation style.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Indentation {
+    Tabs,
+    /// Spaces, by the most common step between indentation levels.
+    Spaces(usize),
+    /// No line is indented.
+    None,
+}
+
+/// What `text_profile` found out about a text file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextProfile {
+    pub encoding: TextEncoding,
+    /// Whether the encoding is marked by a byte order mark.
+    pub bom: bool,
+    /// The most common line ending.
+    pub line_ending: LineEnding,
+    /// Whether more than one kind of line ending is used.
+    pub mixed_line_endings: bool,
+    /// How many lines end with spaces or tabs.
+    pub trailing_whitespace: usize,
+    /// The most common indentation style.
+    pub indentation: Indentation,
+    /// Whether some lines are indented with tabs and others with spaces.
+    pub mixed_indentation: bool,
+}
+
+// Detects the encoding from the byte order mark or else UTF-8 validity, and decodes the text
+fn decode(bytes: &[u8]) -> (TextEncoding, bool, String) {
+    fn units<const N: usize>(
+        bytes: &[u8],
+        from: fn([u8; N]) -> u32,
+    ) -> impl Iterator<Item = u32> + '_ {
+        bytes
+            .chunks_exact(N)
+            .map(move |chunk| from(chunk.try_into().unwrap()))
+    }
+    fn utf16(units: impl Iterator<Item = u32>) -> String {
+        char::decode_utf16(units.map(|unit| unit as u16))
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()
+    }
+    fn utf32(units: impl Iterator<Item = u32>) -> String {
+        units
+            .map(|unit| char::from_u32(unit).unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()
+    }
+
+    if let Some(rest) = bytes.strip_prefix(b"\xff\xfe\x00\x00") {
+        return (
+            TextEncoding::Utf32Le,
+            true,
+            utf32(units(rest, u32::from_le_bytes)),
+        );
+    }
+    if let Some(rest) = bytes.strip_prefix(b"\x00\x00\xfe\xff") {
+        return (
+            TextEncoding::Utf32Be,
+            true,
+            utf32(units(rest, u32::from_be_bytes)),
+        );
+    }
+    if let Some(rest) = bytes.strip_prefix(b"\xff\xfe") {
+        let from = |b: [u8; 2]| u16::from_le_bytes(b) as u32;
+        return (TextEncoding::Utf16Le, true, utf16(units(rest, from)));
+    }
+    if let Some(rest) = bytes.strip_prefix(b"\xfe\xff") {
+        let from = |b: [u8; 2]| u16::from_be_bytes(b) as u32;
+        return (TextEncoding::Utf16Be, true, utf16(units(rest, from)));
+    }
+    let (bom, rest) = match bytes.strip_prefix(b"\xef\xbb\xbf") {
+        Some(rest) => (true, rest),
+        None => (false, bytes),
+    };
+    return match std::str::from_utf8(rest) {
+        Ok(text) => (TextEncoding::Utf8, bom, text.to_string()),
+        Err(_) => (
+            TextEncoding::Unknown,
+            false,
+            String::from_utf8_lossy(rest).to_string(),
+        ),
+    };
+}
+
+// Picks the most common of the counted kinds, or `none` if nothing was counted
+fn dominant<Kind: Copy>(counts: &[(Kind, usize)], none: Kind) -> Kind {
+    counts
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .max_by_key(|(_, count)| *count)
+        .map_or(none, |(kind, _)| *kind)
+}
+
+/// Profiles a text file in one pass: its encoding and byte order mark, its dominant and any mixed line endings,
+/// how many lines have trailing whitespace, and its indentation style (tabs, or spaces and their width),
+/// as a cheap pre-pass for formatters and linters.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`TextProfile`>
+///
+/// # Examples
+/// ```
+/// use file_access::{Indentation, LineEnding};
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "text_profile_example.py";
+///         let file_path: String = String::from(file_path);
+///         file_access::write_string(&file_path, &"def f():\r\n    return 1\r\n")?;
+///
+///         let profile = file_access::text_profile(&file_path)?;
+///         assert_eq!(profile.line_ending, LineEnding::CrLf);
+///         assert_eq!(profile.indentation, Indentation::Spaces(4));
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn text_profile<Path: AsPath + ?Sized>(file_path: &Path) -> Result<TextProfile> {
+    let mut bytes = vec![];
+    get_file(file_path)
+        .and_then(|mut file| file.read_to_end(&mut bytes))
+        .context("text_profile", file_path)?;
+    let (encoding, bom, text) = decode(&bytes);
+
+    let (mut lf, mut crlf, mut cr) = (0, 0, 0);
+    let mut lines = vec![];
+    let mut rest = text.as_str();
+    while let Some(i) = rest.find(['\r', '\n']) {
+        lines.push(&rest[..i]);
+        rest = match rest[i..].strip_prefix("\r\n") {
+            Some(next) => {
+                crlf += 1;
+                next
+            }
+            None if rest[i..].starts_with('\r') => {
+                cr += 1;
+                &rest[i + 1..]
+            }
+            None => {
+                lf += 1;
+                &rest[i + 1..]
+            }
+        };
+    }
+    lines.push(rest);
+
+    let mut trailing_whitespace = 0;
+    let (mut tabs, mut spaces) = (0, 0);
+    let mut steps = std::collections::BTreeMap::new();
+    let mut previous = 0;
+    for line in lines {
+        if line.ends_with([' ', '\t']) {
+            trailing_whitespace += 1;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with('\t') {
+            tabs += 1;
+        } else {
+            let indent = line.len() - line.trim_start_matches(' ').len();
+            if indent > 0 {
+                spaces += 1;
+            }
+            if indent > previous {
+                *steps.entry(indent - previous).or_insert(0) += 1;
+            }
+            previous = indent;
+        }
+    }
+    let width = dominant(&steps.into_iter().collect::<Vec<_>>(), 0);
+
+    return Ok(TextProfile {
+        encoding,
+        bom,
+        line_ending: dominant(
+            &[
+                (LineEnding::Lf, lf),
+                (LineEnding::CrLf, crlf),
+                (LineEnding::Cr, cr),
+            ],
+            LineEnding::None,
+        ),
+        mixed_line_endings: [lf, crlf, cr].iter().filter(|&&count| count > 0).count() > 1,
+        trailing_whitespace,
+        indentation: dominant(
+            &[
+                (Indentation::Tabs, tabs),
+                (Indentation::Spaces(width), spaces),
+            ],
+            Indentation::None,
+        ),
+        mixed_indentation: tabs > 0 && spaces > 0,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn text_profile() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "text_profile.rs";
+            write_string(
+                &file_path,
+                &"fn main() {\n  if true {\n    run(); \r\n  }\n}\n",
+            )?;
+
+            // Action
+            let profile = super::text_profile(&file_path)?;
+
+            // Assert
+            assert_eq!(
+                profile,
+                TextProfile {
+                    encoding: TextEncoding::Utf8,
+                    bom: false,
+                    line_ending: LineEnding::Lf,
+                    mixed_line_endings: true,
+                    trailing_whitespace: 1,
+                    indentation: Indentation::Spaces(2),
+                    mixed_indentation: false,
+                }
+            );
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+
+    #[test]
+    fn text_profile_utf16() -> Result<()> {
+        Ok({
+            // Arrange
+            let file_path = "text_profile_utf16.txt";
+            let mut bytes = vec![0xff, 0xfe];
+            for unit in "a\r\n\tb\r\n".encode_utf16() {
+                bytes.extend(unit.to_le_bytes());
+            }
+            fs::write(file_path, bytes)?;
+
+            // Action
+            let profile = super::text_profile(&file_path)?;
+
+            // Assert
+            assert_eq!(profile.encoding, TextEncoding::Utf16Le);
+            assert!(profile.bom);
+            assert_eq!(profile.line_ending, LineEnding::CrLf);
+            assert_eq!(profile.indentation, Indentation::Tabs);
+
+            // Clean-up
+            delete(&file_path)?;
+        })
+    }
+}
@@ -0,0 +1,289 @@
+use crate::*;
+use std::{fs::FileTimes, time::SystemTime};
+
+// Opens a file or directory to set its times, which Windows only allows through a writable handle
+fn open_for_times(path: &std::path::Path) -> io::Result<File> {
+    #[cfg(unix)]
+    return File::open(path);
+    #[cfg(not(unix))]
+    return File::options().write(true).open(path);
+}
+
+/// Gets when a file was last modified, following symlinks.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`SystemTime`>
+///
+/// # Examples
+/// ```
+/// use std::time::SystemTime;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let modified: SystemTime = file_access::modified(&"Cargo.toml")?;
+///         println!("{:?}", modified.elapsed());
+///     })
+/// }
+/// ```
+pub fn modified<Path: AsPath + ?Sized>(file_path: &Path) -> Result<SystemTime> {
+    fs::metadata(file_path.as_path())
+        .and_then(|metadata| metadata.modified())
+        .context("modified", file_path)
+}
+
+/// Gets when a file was created, following symlinks. Not every platform and filesystem records it,
+/// in which case `ErrorKind::Unsupported` is returned.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`SystemTime`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         if let Ok(created) = file_access::created(&"Cargo.toml") {
+///             println!("{:?}", created.elapsed());
+///         }
+///     })
+/// }
+/// ```
+pub fn created<Path: AsPath + ?Sized>(file_path: &Path) -> Result<SystemTime> {
+    fs::metadata(file_path.as_path())
+        .and_then(|metadata| metadata.created())
+        .context("created", file_path)
+}
+
+/// Gets when a file was last accessed, following symlinks. Many systems only update it coarsely,
+/// such as Linux's default `relatime`, or not at all.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`SystemTime`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         println!("{:?}", file_access::accessed(&"Cargo.toml")?);
+///     })
+/// }
+/// ```
+pub fn accessed<Path: AsPath + ?Sized>(file_path: &Path) -> Result<SystemTime> {
+    fs::metadata(file_path.as_path())
+        .and_then(|metadata| metadata.accessed())
+        .context("accessed", file_path)
+}
+
+/// Sets when a file or directory was last modified, such as to stamp a build output. The access time is left as it is.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `modified`: **owned** `SystemTime`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, SystemTime};
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "set_modified_example.txt";
+///         let an_hour_ago = SystemTime::now() - Duration::from_secs(60 * 60);
+///         file_access::write_string(&file_path, &"Hello, World!")?;
+///
+///         file_access::set_modified(&file_path, an_hour_ago)?;
+///         assert_eq!(file_access::modified(&file_path)?, an_hour_ago);
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn set_modified<Path: AsPath + ?Sized>(file_path: &Path, modified: SystemTime) -> Result<()> {
+    open_for_times(file_path.as_path())
+        .and_then(|file| file.set_modified(modified))
+        .context("set_modified", file_path)
+}
+
+/// Sets when a file or directory was last accessed and modified, like `touch -d`.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `accessed`: **owned** `SystemTime`
+/// - `modified`: **owned** `SystemTime`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, SystemTime};
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "set_times_example.txt";
+///         let epoch = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+///         file_access::write_string(&file_path, &"Hello, World!")?;
+///
+///         file_access::set_times(&file_path, epoch, epoch)?;
+///         assert_eq!(file_access::modified(&file_path)?, epoch);
+///
+///         // Clean-up:
+///         file_access::delete(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn set_times<Path: AsPath + ?Sized>(
+    file_path: &Path,
+    accessed: SystemTime,
+    modified: SystemTime,
+) -> Result<()> {
+    let times = FileTimes::new()
+        .set_accessed(accessed)
+        .set_modified(modified);
+    open_for_times(file_path.as_path())
+        .and_then(|file| file.set_times(times))
+        .context("set_times", file_path)
+}
+
+// Gets when a file was last modified, or `None` if it doesn't exist
+fn modified_if_exists(path: &std::path::Path) -> io::Result<Option<SystemTime>> {
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(Some(metadata.modified()?)),
+        Err(x) if x.kind() == ErrorKind::NotFound => Ok(None),
+        Err(x) => Err(x),
+    }
+}
+
+/// Checks whether a file was modified more recently than another, such as to rebuild an output only when
+/// its source changed. A file that doesn't exist counts as older than any that does, so a missing output
+/// is always out of date, and a missing source is never newer.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `other`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`bool`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let (source, output) = ("is_newer_than_example/main.c", "is_newer_than_example/main.o");
+///         file_access::write_string(&source, &"int main() {}")?;
+///
+///         if file_access::is_newer_than(&source, &output)? {
+///             file_access::copy(&source, &output)?; // rebuild
+///         }
+///         assert!(!file_access::is_newer_than(&source, &output)?);
+///
+///         // Clean-up:
+///         file_access::delete(&"is_newer_than_example")?;
+///     })
+/// }
+/// ```
+pub fn is_newer_than<Path: AsPath + ?Sized, Other: AsPath + ?Sized>(
+    file_path: &Path,
+    other: &Other,
+) -> Result<bool> {
+    let modified = modified_if_exists(file_path.as_path()).context("is_newer_than", file_path)?;
+    let other_modified = modified_if_exists(other.as_path()).context("is_newer_than", other)?;
+
+    return Ok(modified > other_modified);
+}
+
+/// Checks whether a file was modified less recently than another. A file that doesn't exist counts as older
+/// than any that does; this is `is_newer_than` the other way around.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `other`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`bool`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         assert!(file_access::is_older_than(&"is_older_than_example/missing.txt", &"Cargo.toml")?);
+///     })
+/// }
+/// ```
+pub fn is_older_than<Path: AsPath + ?Sized, Other: AsPath + ?Sized>(
+    file_path: &Path,
+    other: &Other,
+) -> Result<bool> {
+    is_newer_than(other, file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Result, time::Duration};
+
+    #[test]
+    fn times() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "times/file.txt";
+            let (accessed, modified) = (
+                SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000),
+                SystemTime::UNIX_EPOCH + Duration::from_secs(1_200_000_000),
+            );
+            write_string(&file, &"Hello, World!")?;
+
+            // Action
+            super::set_times(&file, accessed, modified)?;
+            super::set_modified(&"times", modified)?;
+
+            // Assert
+            assert_eq!(super::accessed(&file)?, accessed);
+            assert_eq!(super::modified(&file)?, modified);
+            assert_eq!(super::modified(&"times")?, modified);
+            let missing = super::modified(&"times/missing.txt");
+            assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+            if let Ok(created) = super::created(&file) {
+                assert!(created > modified);
+            }
+
+            // Clean-up
+            delete(&"times")?;
+        })
+    }
+
+    #[test]
+    fn newer_and_older() -> Result<()> {
+        Ok({
+            // Arrange
+            let (old, new, missing) = ("newer/old.txt", "newer/new.txt", "newer/missing.txt");
+            write_string(&old, &"old")?;
+            write_string(&new, &"new")?;
+            super::set_modified(&old, SystemTime::UNIX_EPOCH + Duration::from_secs(1))?;
+
+            // Assert
+            assert!(is_newer_than(&new, &old)?);
+            assert!(!is_newer_than(&old, &new)?);
+            assert!(!is_newer_than(&old, &old)?);
+            assert!(is_older_than(&old, &new)?);
+            assert!(is_newer_than(&old, &missing)?);
+            assert!(!is_newer_than(&missing, &old)?);
+            assert!(is_older_than(&missing, &old)?);
+            assert!(!is_newer_than(&missing, &missing)?);
+
+            // Clean-up
+            delete(&"newer")?;
+        })
+    }
+}
@@ -0,0 +1,297 @@
+use crate::*;
+use std::path::Path as StdPath;
+
+// A change staged in a `Transaction`
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Step {
+    Write(PathBuf, Vec<u8>),
+    Copy(PathBuf, PathBuf),
+    Rename(PathBuf, PathBuf),
+    Delete(PathBuf),
+}
+
+// How to take back a change that was applied, in reverse order
+#[derive(Debug)]
+enum Undo {
+    // A directory that was created, removed only if still empty
+    RemoveDir(PathBuf),
+    // A file that was written
+    Remove(PathBuf),
+    // What was at a path before being replaced or deleted, moved aside to a backup
+    Restore { backup: PathBuf, path: PathBuf },
+    // A file or directory that was renamed
+    MoveBack { from: PathBuf, to: PathBuf },
+}
+
+/// A set of writes, copies, renames and deletes applied all-or-nothing, such as to update several config files together.
+/// Changes are staged builder-style and only applied by `commit`, in order; if one of them fails, those already applied
+/// are rolled back, restoring what was overwritten or deleted. Files about to be replaced or deleted are moved aside to
+/// hidden backups next to them (renamed, not copied), which are removed once every change succeeded.
+///
+/// Rolling back is best-effort: a crash mid-commit leaves the backups in place, to be restored by hand.
+///
+/// # Examples
+/// ```
+/// use file_access::Transaction;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         file_access::write_string(&"transaction_example/app.conf", &"port = 80")?;
+///
+///         let result = Transaction::new()
+///             .write_string(&"transaction_example/app.conf", &"port = 8080")
+///             .copy(&"transaction_example/missing.conf", &"transaction_example/db.conf")
+///             .commit();
+///         assert!(result.is_err());
+///         assert_eq!(file_access::read_string(&"transaction_example/app.conf")?, "port = 80");
+///
+///         // Clean-up:
+///         file_access::delete(&"transaction_example")?;
+///     })
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Transaction {
+    steps: Vec<Step>,
+}
+
+impl Transaction {
+    /// Creates an empty transaction.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stages writing a string to a file, creating it and its full directory path if they don't exist.
+    pub fn write_string<Path: AsPath + ?Sized, Text: AsRef<str> + ?Sized>(
+        self,
+        file_path: &Path,
+        text: &Text,
+    ) -> Self {
+        self.write_bytes(file_path, &text.as_ref().as_bytes())
+    }
+
+    /// Stages writing bytes to a file, creating it and its full directory path if they don't exist.
+    pub fn write_bytes<Path: AsPath + ?Sized, Bytes: AsRef<[u8]> + ?Sized>(
+        mut self,
+        file_path: &Path,
+        bytes: &Bytes,
+    ) -> Self {
+        let step = Step::Write(path_of(file_path), bytes.as_ref().to_vec());
+        self.steps.push(step);
+        self
+    }
+
+    /// Stages copying a file to a destination, creating the destination's full directory path if it doesn't exist.
+    pub fn copy<From: AsPath + ?Sized, To: AsPath + ?Sized>(
+        mut self,
+        from: &From,
+        to: &To,
+    ) -> Self {
+        self.steps.push(Step::Copy(path_of(from), path_of(to)));
+        self
+    }
+
+    /// Stages renaming a file or directory, creating the destination's full directory path if it doesn't exist.
+    pub fn rename<From: AsPath + ?Sized, To: AsPath + ?Sized>(
+        mut self,
+        from: &From,
+        to: &To,
+    ) -> Self {
+        self.steps.push(Step::Rename(path_of(from), path_of(to)));
+        self
+    }
+
+    /// Stages deleting a file, or a directory recursively.
+    pub fn delete<Path: AsPath + ?Sized>(mut self, file_path: &Path) -> Self {
+        self.steps.push(Step::Delete(path_of(file_path)));
+        self
+    }
+
+    /// Applies the staged changes in order. If one fails, the ones already applied are rolled back
+    /// and its error is returned.
+    ///
+    /// # Returns
+    /// Result<`()`>
+    pub fn commit(self) -> Result<()> {
+        let mut undos = vec![];
+        for step in &self.steps {
+            if let Err(x) = apply(step, &mut undos) {
+                rollback(undos);
+                let path = match step {
+                    Step::Write(path, _) | Step::Delete(path) => path,
+                    Step::Copy(from, _) | Step::Rename(from, _) => from,
+                };
+                return Err(x).context("Transaction::commit", path);
+            }
+        }
+        // Every change succeeded, so the backups are no longer needed
+        for undo in undos {
+            if let Undo::Restore { backup, .. } = undo {
+                _ = remove(&backup);
+            }
+        }
+        return Ok(());
+    }
+}
+
+// Removes a file, symlink or directory without following symlinks
+fn remove(path: &StdPath) -> io::Result<()> {
+    match fs::symlink_metadata(path)?.is_dir() {
+        true => fs::remove_dir_all(path),
+        false => fs::remove_file(path),
+    }
+}
+
+// Creates the missing directories above `path`, recording each to be removed
+fn create_parents(path: &StdPath, undos: &mut Vec<Undo>) -> io::Result<()> {
+    let missing: Vec<_> = path
+        .ancestors()
+        .skip(1)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .take_while(|dir| !dir.exists())
+        .collect();
+    for dir in missing.into_iter().rev() {
+        fs::create_dir(dir)?;
+        undos.push(Undo::RemoveDir(dir.to_path_buf()));
+    }
+    return Ok(());
+}
+
+// Moves whatever is at `path` aside to a backup, if anything
+fn back_up(path: &StdPath, undos: &mut Vec<Undo>) -> io::Result<()> {
+    if fs::symlink_metadata(path).is_ok() {
+        let backup = temp_sibling(path);
+        fs::rename(path, &backup)?;
+        undos.push(Undo::Restore {
+            backup,
+            path: path.to_path_buf(),
+        });
+    }
+    return Ok(());
+}
+
+fn apply(step: &Step, undos: &mut Vec<Undo>) -> io::Result<()> {
+    match step {
+        Step::Write(path, bytes) => {
+            create_parents(path, undos)?;
+            back_up(path, undos)?;
+            undos.push(Undo::Remove(path.clone()));
+            return write_via_rename(path, bytes, true);
+        }
+        Step::Copy(from, to) => {
+            fs::metadata(from)?; // fails before touching the destination if the source is missing
+            create_parents(to, undos)?;
+            back_up(to, undos)?;
+            undos.push(Undo::Remove(to.clone()));
+            return fs::copy(from, to).map(|_| ());
+        }
+        Step::Rename(from, to) => {
+            fs::symlink_metadata(from)?;
+            create_parents(to, undos)?;
+            back_up(to, undos)?;
+            fs::rename(from, to)?;
+            undos.push(Undo::MoveBack {
+                from: to.clone(),
+                to: from.clone(),
+            });
+            return Ok(());
+        }
+        Step::Delete(path) => {
+            fs::symlink_metadata(path)?;
+            return back_up(path, undos);
+        }
+    }
+}
+
+// Takes back the changes applied so far, latest first, carrying on past failures
+fn rollback(undos: Vec<Undo>) {
+    for undo in undos.into_iter().rev() {
+        _ = match undo {
+            Undo::RemoveDir(dir) => fs::remove_dir(dir),
+            Undo::Remove(path) => remove(&path),
+            Undo::Restore { backup, path } => fs::rename(backup, path),
+            Undo::MoveBack { from, to } => fs::rename(from, to),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn commit() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"transaction_commit/a.txt", &"a")?;
+            write_string(&"transaction_commit/b.txt", &"b")?;
+            write_string(&"transaction_commit/c.txt", &"c")?;
+
+            // Action
+            Transaction::new()
+                .write_string(&"transaction_commit/a.txt", &"new a")
+                .copy(&"transaction_commit/a.txt", &"transaction_commit/d/a.txt")
+                .rename(&"transaction_commit/b.txt", &"transaction_commit/c.txt")
+                .delete(&"transaction_commit/d")
+                .commit()?;
+
+            // Assert
+            assert_eq!(read_string(&"transaction_commit/a.txt")?, "new a");
+            assert_eq!(read_string(&"transaction_commit/c.txt")?, "b");
+            let left = list_dir(
+                &"transaction_commit",
+                ListOptions {
+                    hidden: true,
+                    ..ListOptions::default()
+                },
+            )?;
+            assert_eq!(left.len(), 2, "the backups should have been removed");
+
+            // Clean-up
+            delete(&"transaction_commit")?;
+        })
+    }
+
+    #[test]
+    fn rollback() -> Result<()> {
+        Ok({
+            // Arrange
+            write_string(&"transaction_rollback/a.txt", &"a")?;
+            write_string(&"transaction_rollback/b.txt", &"b")?;
+            write_string(&"transaction_rollback/c.txt", &"c")?;
+
+            // Action
+            let result = Transaction::new()
+                .write_string(&"transaction_rollback/a.txt", &"new a")
+                .write_string(&"transaction_rollback/new/dirs/d.txt", &"d")
+                .rename(&"transaction_rollback/b.txt", &"transaction_rollback/c.txt")
+                .delete(&"transaction_rollback/a.txt")
+                .delete(&"transaction_rollback/missing.txt")
+                .commit();
+
+            // Assert
+            let error = result.unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::NotFound);
+            assert_eq!(
+                error.path,
+                Some(PathBuf::from("transaction_rollback/missing.txt"))
+            );
+            assert_eq!(read_string(&"transaction_rollback/a.txt")?, "a");
+            assert_eq!(read_string(&"transaction_rollback/b.txt")?, "b");
+            assert_eq!(read_string(&"transaction_rollback/c.txt")?, "c");
+            assert!(!PathBuf::from("transaction_rollback/new").exists());
+            let left = list_dir(
+                &"transaction_rollback",
+                ListOptions {
+                    hidden: true,
+                    ..ListOptions::default()
+                },
+            )?;
+            assert_eq!(left.len(), 3, "the backups should have been restored");
+
+            // Clean-up
+            delete(&"transaction_rollback")?;
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use crate::*;
+
+/// Moves a file or directory to the platform's trash (the Recycle Bin on Windows, the Trash on macOS and
+/// freedesktop.org desktops), where it can be restored from, instead of deleting it permanently like `delete`,
+/// such as for tools run by end users. **Requires the `trash` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```no_run
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "trash_example/notes.txt";
+///         file_access::write_string(&file_path, &"Hello, World!")?;
+///
+///         file_access::trash(&file_path)?;
+///     })
+/// }
+/// ```
+pub fn trash<Path: AsPath + ?Sized>(file_path: &Path) -> Result<()> {
+    fs::symlink_metadata(file_path.as_path())
+        .and_then(|_| ::trash::delete(file_path.as_path()).map_err(io::Error::other))
+        .context("trash", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(target_os = "linux")]
+    use super::*;
+    #[cfg(target_os = "linux")]
+    use std::io::Result;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn trash() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "trash_test/file.txt";
+            write_string(&file, &"Hello, World!")?;
+            let original = fs::canonicalize(file)?;
+
+            // Action
+            super::trash(&file)?;
+            let missing = super::trash(&"trash_test/missing.txt");
+
+            // Assert
+            assert!(!original.exists());
+            assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+            let trashed: Vec<_> = ::trash::os_limited::list()
+                .map_err(io::Error::other)?
+                .into_iter()
+                .filter(|item| item.original_path() == original)
+                .collect();
+            assert_eq!(trashed.len(), 1);
+
+            // Clean-up
+            ::trash::os_limited::purge_all(trashed).map_err(io::Error::other)?;
+            delete(&"trash_test")?;
+        })
+    }
+}
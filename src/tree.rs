@@ -0,0 +1,240 @@
+use crate::{
+    internal::{
+        parallel,
+        sha256::{digest_file, hex},
+    },
+    *,
+};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, ffi::OsString, path::Path as StdPath};
+
+// The first byte hashed for each kind of node, so that e.g. a file can't collide with a directory
+const FILE: u8 = b'f';
+const DIR: u8 = b'd';
+const LINK: u8 = b'l';
+
+enum Node {
+    // Indexes the list of files whose contents are hashed in parallel
+    File {
+        mode: u32,
+        content: usize,
+    },
+    Dir {
+        mode: u32,
+        children: BTreeMap<OsString, Node>,
+    },
+    Link {
+        target: PathBuf,
+    },
+}
+
+#[cfg(unix)]
+fn mode_of(metadata: &Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+
+    metadata.permissions().mode() & 0o7777
+}
+
+// Approximates a Unix mode from the read-only attribute
+#[cfg(not(unix))]
+fn mode_of(metadata: &Metadata) -> u32 {
+    let mode = if metadata.is_dir() { 0o755 } else { 0o644 };
+    if metadata.permissions().readonly() {
+        mode & 0o555
+    } else {
+        mode
+    }
+}
+
+// Builds the node for `path` without following symlinks, collecting the regular files and their sizes into `files`
+fn walk(
+    path: &StdPath,
+    metadata: Metadata,
+    files: &mut Vec<(PathBuf, u64)>,
+) -> Result<Option<Node>> {
+    let mode = mode_of(&metadata);
+    let file_type = metadata.file_type();
+    if file_type.is_dir() {
+        let mut children = BTreeMap::new();
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            if let Some(child) = walk(&entry.path(), entry.metadata()?, files)? {
+                children.insert(name, child);
+            }
+        }
+        return Ok(Some(Node::Dir { mode, children }));
+    }
+    if file_type.is_symlink() {
+        let target = fs::read_link(path)?;
+        return Ok(Some(Node::Link { target }));
+    }
+    if file_type.is_file() {
+        files.push((path.to_path_buf(), metadata.len()));
+        return Ok(Some(Node::File {
+            mode,
+            content: files.len() - 1,
+        }));
+    }
+    return Ok(None); // sockets, fifos and devices have no meaningful contents
+}
+
+// Hashes a node from its kind, mode and either its contents, its target or its (sorted) children
+fn hash_node(node: &Node, contents: &[[u8; 32]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    match node {
+        Node::File { mode, content } => {
+            hasher.update([FILE]);
+            hasher.update(mode.to_be_bytes());
+            hasher.update(contents[*content]);
+        }
+        Node::Dir { mode, children } => {
+            hasher.update([DIR]);
+            hasher.update(mode.to_be_bytes());
+            for (name, child) in children {
+                // The raw bytes of the name, which are its UTF-8 when it's valid Unicode
+                let name = name.as_encoded_bytes();
+                hasher.update((name.len() as u64).to_be_bytes());
+                hasher.update(name);
+                hasher.update(hash_node(child, contents));
+            }
+        }
+        Node::Link { target } => {
+            hasher.update([LINK]);
+            hasher.update(target.as_os_str().as_encoded_bytes());
+        }
+    }
+    return hasher.finalize().into();
+}
+
+/// Computes a SHA-256 Merkle hash over a file or directory tree: its relative paths, permission modes and contents,
+/// so that two machines can verify they hold identical trees by comparing a single value.
+/// The name and location of the root itself are not part of the hash, and file contents are hashed in parallel.
+/// Symbolic links below the root are hashed by their target rather than followed, and special files are ignored.
+/// Outside of Unix, modes are approximated from the read-only attribute. **Requires the `hash` feature**, which is on by default.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`String`> of 64 lowercase hex digits
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "src";
+///         let file_path: String = String::from(file_path);
+///
+///         let hash: String = file_access::tree_hash(&file_path)?;
+///         println!("{hash}");
+///     })
+/// }
+/// ```
+pub fn tree_hash<Path: AsPath + ?Sized>(file_path: &Path) -> Result<String> {
+    let hash = || -> Result<(String, u64)> {
+        let path = path_of(file_path);
+        let mut files = vec![];
+        let metadata = fs::metadata(&path).context("tree_hash", file_path)?;
+        let root = walk(&path, metadata, &mut files)
+            .context("tree_hash", file_path)?
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "not a file or directory"))
+            .context("tree_hash", file_path)?;
+
+        let contents = parallel::map(&files, |(file, _)| {
+            digest_file(file).context("tree_hash", file)
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
+        let read = files.iter().map(|(_, len)| len).sum();
+
+        return Ok((hex(&hash_node(&root, &contents)), read));
+    };
+    return metered_with_context("tree_hash", |(_, read)| (*read, 0), hash).map(|(hash, _)| hash);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn tree_hash() -> Result<()> {
+        Ok({
+            // Arrange
+            for root in ["tree_hash/a", "tree_hash/b"] {
+                write_string(&format!("{root}/x.txt"), &"hello")?;
+                write_string(&format!("{root}/sub/y.txt"), &"world")?;
+            }
+
+            // Action
+            let a = super::tree_hash(&"tree_hash/a")?;
+            let b = super::tree_hash(&"tree_hash/b")?;
+            write_string(&"tree_hash/b/sub/y.txt", &"World")?;
+            let changed = super::tree_hash(&"tree_hash/b")?;
+            write_string(&"tree_hash/b/sub/y.txt", &"world")?;
+            rename(&"tree_hash/b/x.txt", &"tree_hash/b/z.txt")?;
+            let renamed = super::tree_hash(&"tree_hash/b")?;
+
+            // Assert
+            assert_eq!(a.len(), 64);
+            assert_eq!(
+                a, b,
+                "identical trees should hash the same wherever they are"
+            );
+            assert_ne!(a, changed);
+            assert_ne!(a, renamed);
+
+            // Clean-up
+            delete(&"tree_hash")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn tree_hash_covers_modes() -> Result<()> {
+        Ok({
+            use std::os::unix::fs::PermissionsExt;
+
+            // Arrange
+            write_string(&"tree_hash_modes/run.sh", &"#!/bin/sh")?;
+            let before = super::tree_hash(&"tree_hash_modes")?;
+
+            // Action
+            fs::set_permissions("tree_hash_modes/run.sh", fs::Permissions::from_mode(0o755))?;
+            let after = super::tree_hash(&"tree_hash_modes")?;
+
+            // Assert
+            assert_ne!(before, after);
+
+            // Clean-up
+            delete(&"tree_hash_modes")?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn tree_hash_non_utf8_names() -> Result<()> {
+        Ok({
+            // Arrange
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+            let a = PathBuf::from("tree_hash_non_utf8/a").join(OsStr::from_bytes(b"\xe9.txt"));
+            let b = PathBuf::from("tree_hash_non_utf8/b").join(OsStr::from_bytes(b"\xe8.txt"));
+            write_string(&a, &"hello")?;
+            write_string(&b, &"hello")?;
+
+            // Action
+            let a = super::tree_hash(&"tree_hash_non_utf8/a")?;
+            let b = super::tree_hash(&"tree_hash_non_utf8/b")?;
+
+            // Assert
+            assert_ne!(
+                a, b,
+                "names that only differ in invalid UTF-8 should hash apart"
+            );
+
+            // Clean-up
+            delete(&"tree_hash_non_utf8")?;
+        })
+    }
+}
@@ -0,0 +1,99 @@
+use crate::*;
+use std::path::Path as StdPath;
+
+// Sets the size of an existing file through a handle open for writing
+fn resize(path: &StdPath, len: u64) -> io::Result<()> {
+    File::options().write(true).open(path)?.set_len(len)
+}
+
+/// Empties an **existing** file in place, such as a log file that another process keeps open for appending,
+/// without replacing it: its permissions, owner and hard links are kept.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "truncate_example/app.log";
+///         file_access::write_string(&file_path, &"lots of lines")?;
+///
+///         file_access::truncate(&file_path)?;
+///         assert_eq!(file_access::read_string(&file_path)?, "");
+///
+///         // Clean-up:
+///         file_access::delete(&"truncate_example")?;
+///     })
+/// }
+/// ```
+pub fn truncate<Path: AsPath + ?Sized>(file_path: &Path) -> Result<()> {
+    resize(file_path.as_path(), 0).context("truncate", file_path)
+}
+
+/// Resizes an **existing** file to `len` bytes in place: a longer file is cut short,
+/// and a shorter one is extended with zeros (without taking disk space for them where holes are supported;
+/// see `allocate` to reserve it).
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `len`: **owned** `u64`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "set_len_example/data.bin";
+///         file_access::write_string(&file_path, &"Hello, World!")?;
+///
+///         file_access::set_len(&file_path, 5)?;
+///         assert_eq!(file_access::read_string(&file_path)?, "Hello");
+///
+///         // Clean-up:
+///         file_access::delete(&"set_len_example")?;
+///     })
+/// }
+/// ```
+pub fn set_len<Path: AsPath + ?Sized>(file_path: &Path, len: u64) -> Result<()> {
+    resize(file_path.as_path(), len).context("set_len", file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn set_len() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "set_len/data.bin";
+            write_string(&file, &"Hello, World!")?;
+
+            // Action
+            super::set_len(&file, 5)?;
+            let cut = read_string(&file)?;
+            super::set_len(&file, 7)?;
+            let extended = read_bytes(&file)?;
+            truncate(&file)?;
+            let missing = truncate(&"set_len/missing.bin");
+
+            // Assert
+            assert_eq!(cut, "Hello");
+            assert_eq!(extended, b"Hello\0\0");
+            assert_eq!(read_bytes(&file)?, b"");
+            let missing = missing.unwrap_err();
+            assert_eq!(missing.kind(), ErrorKind::NotFound);
+            assert_eq!(missing.path, Some(PathBuf::from("set_len/missing.bin")));
+
+            // Clean-up
+            delete(&"set_len")?;
+        })
+    }
+}
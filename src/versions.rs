@@ -0,0 +1,103 @@
+use crate::*;
+use std::path::Path as StdPath;
+
+// Names the `n`th previous version of a file, `file.txt.n`
+fn version_path(path: &StdPath, n: usize) -> PathBuf {
+    let mut versioned = path.as_os_str().to_os_string();
+    versioned.push(format!(".{n}"));
+
+    return PathBuf::from(versioned);
+}
+
+// Keeps a copy of a file as its latest previous version, shifting the older ones up and dropping the `versions`th.
+// The file itself is copied rather than moved, so it keeps its permissions and hard links.
+pub(crate) fn rotate_versions(path: &StdPath, versions: usize) -> io::Result<()> {
+    if versions == 0 || !path.is_file() {
+        return Ok(());
+    }
+    for n in (1..versions).rev() {
+        let version = version_path(path, n);
+        if version.exists() {
+            fs::rename(&version, version_path(path, n + 1))?;
+        }
+    }
+    fs::copy(path, version_path(path, 1))?;
+
+    return Ok(());
+}
+
+/// Replaces a file with one of its previous versions, as kept by writing it with `WriteOptions::versioned`:
+/// `1` is the latest, stored next to it as `file.1`, `2` the one before it, and so on. The versions are left as they are.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `n`: **owned** `usize`, from `1`
+///
+/// # Returns
+/// Result<`()`>, failing with `ErrorKind::NotFound` if there's no such version
+///
+/// # Examples
+/// ```
+/// use file_access::WriteOptions;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "restore_version_example/app.conf";
+///         let options = WriteOptions::new().versioned(3);
+///
+///         file_access::write_string_with(&file_path, &"port = 80", options)?;
+///         file_access::write_string_with(&file_path, &"port = 8080", options)?;
+///         file_access::restore_version(&file_path, 1)?;
+///         assert_eq!(file_access::read_string(&file_path)?, "port = 80");
+///
+///         // Clean-up:
+///         file_access::delete(&"restore_version_example")?;
+///     })
+/// }
+/// ```
+pub fn restore_version<Path: AsPath + ?Sized>(file_path: &Path, n: usize) -> Result<()> {
+    if n == 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "versions are numbered from 1",
+        ))
+        .context("restore_version", file_path);
+    }
+    let version = version_path(file_path.as_path(), n);
+    fs::copy(&version, file_path.as_path())
+        .map(|_| ())
+        .context("restore_version", &version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    #[test]
+    fn versioned() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "versioned/app.conf";
+            let options = WriteOptions::new().versioned(2);
+
+            // Action
+            for port in [80, 81, 82, 83] {
+                write_string_with(&file, &format!("port = {port}"), options)?;
+            }
+            let missing = super::restore_version(&file, 3);
+
+            // Assert
+            assert_eq!(read_string(&"versioned/app.conf")?, "port = 83");
+            assert_eq!(read_string(&"versioned/app.conf.1")?, "port = 82");
+            assert_eq!(read_string(&"versioned/app.conf.2")?, "port = 81");
+            assert!(!PathBuf::from("versioned/app.conf.3").exists());
+            assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+            super::restore_version(&file, 2)?;
+            assert_eq!(read_string(&file)?, "port = 81");
+
+            // Clean-up
+            delete(&"versioned")?;
+        })
+    }
+}
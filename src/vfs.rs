@@ -0,0 +1,561 @@
+use crate::*;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Component, Path as StdPath},
+    sync::Mutex,
+};
+
+/// The file operations of a backend that `FilePath` operations can be routed through with `FilePath::on`:
+/// `OsFileSystem` for the real disk, or `MemoryFileSystem` to unit-test file logic without touching it.
+/// Writes create the file's full directory path, and deletes are recursive, like the crate's own functions.
+///
+/// # Examples
+/// ```
+/// use file_access::{FileSystem, MemoryFileSystem};
+/// use std::path::Path;
+///
+/// // Application code takes any backend
+/// fn bump(fs: &dyn FileSystem, counter: &Path) -> file_access::Result<u64> {
+///     let count = match fs.exists(counter) {
+///         true => fs.read_string(counter)?.parse().unwrap_or(0) + 1,
+///         false => 1,
+///     };
+///     fs.write_string(counter, &count.to_string())?;
+///     return Ok(count);
+/// }
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let fs = MemoryFileSystem::new();
+///         bump(&fs, Path::new("state/counter.txt"))?;
+///         assert_eq!(bump(&fs, Path::new("state/counter.txt"))?, 2);
+///     })
+/// }
+/// ```
+pub trait FileSystem: Send + Sync {
+    /// Reads the contents of a file.
+    fn read_bytes(&self, path: &StdPath) -> Result<Vec<u8>>;
+
+    /// Writes a file, creating it and its full directory path if they don't exist and replacing its contents.
+    fn write_bytes(&self, path: &StdPath, bytes: &[u8]) -> Result<()>;
+
+    /// Appends to a file, creating it and its full directory path if they don't exist.
+    fn append_bytes(&self, path: &StdPath, bytes: &[u8]) -> Result<()>;
+
+    /// Returns `true` if a file or directory exists at the path.
+    fn exists(&self, path: &StdPath) -> bool;
+
+    /// Returns `true` if a directory exists at the path.
+    fn is_dir(&self, path: &StdPath) -> bool;
+
+    /// Creates a directory and its full directory path.
+    fn create_dir_all(&self, path: &StdPath) -> Result<()>;
+
+    /// Deletes a file, or a directory recursively.
+    fn delete(&self, path: &StdPath) -> Result<()>;
+
+    /// Copies a file, creating the destination's full directory path.
+    fn copy(&self, from: &StdPath, to: &StdPath) -> Result<()>;
+
+    /// Renames a file or directory, creating the destination's full directory path. Like the OS, a file only
+    /// replaces a file, and a directory only an empty directory.
+    fn rename(&self, from: &StdPath, to: &StdPath) -> Result<()>;
+
+    /// Lists the entries of a directory, sorted by path.
+    fn list(&self, path: &StdPath) -> Result<Vec<PathBuf>>;
+
+    /// Returns the size of a file in bytes.
+    fn size(&self, path: &StdPath) -> Result<u64>;
+
+    /// Reads the contents of a UTF-8 text file, failing with `InvalidData` if it isn't one.
+    fn read_string(&self, path: &StdPath) -> Result<String> {
+        String::from_utf8(self.read_bytes(path)?)
+            .map_err(|x| Error::new(ErrorKind::InvalidData, x))
+            .context("read_string", path)
+    }
+
+    /// Writes a text file, like `write_bytes`.
+    fn write_string(&self, path: &StdPath, text: &str) -> Result<()> {
+        self.write_bytes(path, text.as_bytes())
+    }
+}
+
+/// The `FileSystem` of the real disk, which forwards to the crate's functions such as `read_bytes` and `copy`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OsFileSystem;
+
+impl FileSystem for OsFileSystem {
+    fn read_bytes(&self, path: &StdPath) -> Result<Vec<u8>> {
+        read_bytes(path)
+    }
+
+    fn write_bytes(&self, path: &StdPath, bytes: &[u8]) -> Result<()> {
+        write_bytes(path, bytes)
+    }
+
+    fn append_bytes(&self, path: &StdPath, bytes: &[u8]) -> Result<()> {
+        append_bytes(path, bytes)
+    }
+
+    fn exists(&self, path: &StdPath) -> bool {
+        path.exists()
+    }
+
+    fn is_dir(&self, path: &StdPath) -> bool {
+        path.is_dir()
+    }
+
+    fn create_dir_all(&self, path: &StdPath) -> Result<()> {
+        fs::create_dir_all(path).context("create_dir_all", path)
+    }
+
+    fn delete(&self, path: &StdPath) -> Result<()> {
+        delete(path)
+    }
+
+    fn copy(&self, from: &StdPath, to: &StdPath) -> Result<()> {
+        copy(from, to)
+    }
+
+    fn rename(&self, from: &StdPath, to: &StdPath) -> Result<()> {
+        rename(from, to)
+    }
+
+    fn list(&self, path: &StdPath) -> Result<Vec<PathBuf>> {
+        let mut entries = vec![];
+        for entry in fs::read_dir(path).context("list", path)? {
+            entries.push(entry.context("list", path)?.path());
+        }
+        entries.sort();
+
+        return Ok(entries);
+    }
+
+    fn size(&self, path: &StdPath) -> Result<u64> {
+        fs::metadata(path)
+            .map(|metadata| metadata.len())
+            .context("size", path)
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    File(Vec<u8>),
+    Dir,
+}
+
+/// A `FileSystem` held in memory, which starts out empty, for tests. Paths are compared lexically:
+/// `.` segments are ignored and `..` ones go up a level, and relative paths live next to absolute ones,
+/// without a current directory in between. There are no permissions, times or symlinks.
+///
+/// # Examples
+/// ```
+/// use file_access::{FileSystem, MemoryFileSystem};
+/// use std::{io::ErrorKind, path::Path};
+///
+/// let fs = MemoryFileSystem::new();
+/// fs.write_string(Path::new("config/app.toml"), "debug = true").unwrap();
+///
+/// assert!(fs.is_dir(Path::new("config")));
+/// assert_eq!(fs.list(Path::new("config")).unwrap(), [Path::new("config/app.toml")]);
+/// assert_eq!(fs.read_bytes(Path::new("missing.txt")).unwrap_err().kind(), ErrorKind::NotFound);
+/// ```
+#[derive(Debug, Default)]
+pub struct MemoryFileSystem {
+    nodes: Mutex<BTreeMap<PathBuf, Node>>,
+}
+
+// Normalizes a path lexically, so that `a/./b` and `a/c/../b` are the same entry
+fn key(path: &StdPath) -> PathBuf {
+    let mut key = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                key.pop();
+            }
+            component => key.push(component),
+        }
+    }
+    return key;
+}
+
+impl MemoryFileSystem {
+    /// Creates an empty file system.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn nodes(&self) -> std::sync::MutexGuard<'_, BTreeMap<PathBuf, Node>> {
+        // A panic while holding the lock can't leave the map half-updated, so a poisoned lock is still usable
+        self.nodes.lock().unwrap_or_else(|x| x.into_inner())
+    }
+
+    // Creates the directories down to `key`, failing if a file is in the way
+    fn make_dirs(nodes: &mut BTreeMap<PathBuf, Node>, key: &StdPath) -> io::Result<()> {
+        for dir in key.ancestors().collect::<Vec<_>>().into_iter().rev() {
+            if dir.as_os_str().is_empty() {
+                continue;
+            }
+            match nodes.get(dir) {
+                Some(Node::File(_)) => return Err(ErrorKind::NotADirectory.into()),
+                Some(Node::Dir) => {}
+                None => {
+                    nodes.insert(dir.to_path_buf(), Node::Dir);
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    // Writes a file through `f`, which gets its current contents, if any
+    fn write_with(&self, path: &StdPath, f: impl FnOnce(&mut Vec<u8>)) -> io::Result<()> {
+        let key = key(path);
+        let mut nodes = self.nodes();
+        if let Some(parent) = key.parent() {
+            Self::make_dirs(&mut nodes, parent)?;
+        }
+        match nodes.entry(key).or_insert_with(|| Node::File(vec![])) {
+            Node::File(contents) => f(contents),
+            Node::Dir => return Err(ErrorKind::IsADirectory.into()),
+        }
+        return Ok(());
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read_bytes(&self, path: &StdPath) -> Result<Vec<u8>> {
+        match self.nodes().get(&key(path)) {
+            Some(Node::File(contents)) => Ok(contents.clone()),
+            Some(Node::Dir) => Err(ErrorKind::IsADirectory).context("read_bytes", path),
+            None => Err(ErrorKind::NotFound).context("read_bytes", path),
+        }
+    }
+
+    fn write_bytes(&self, path: &StdPath, bytes: &[u8]) -> Result<()> {
+        self.write_with(path, |contents| *contents = bytes.to_vec())
+            .context("write_bytes", path)
+    }
+
+    fn append_bytes(&self, path: &StdPath, bytes: &[u8]) -> Result<()> {
+        self.write_with(path, |contents| contents.extend_from_slice(bytes))
+            .context("append_bytes", path)
+    }
+
+    fn exists(&self, path: &StdPath) -> bool {
+        let key = key(path);
+        key.as_os_str().is_empty() || self.nodes().contains_key(&key)
+    }
+
+    fn is_dir(&self, path: &StdPath) -> bool {
+        let key = key(path);
+        key.as_os_str().is_empty() || matches!(self.nodes().get(&key), Some(Node::Dir))
+    }
+
+    fn create_dir_all(&self, path: &StdPath) -> Result<()> {
+        Self::make_dirs(&mut self.nodes(), &key(path)).context("create_dir_all", path)
+    }
+
+    fn delete(&self, path: &StdPath) -> Result<()> {
+        let key = key(path);
+        let mut nodes = self.nodes();
+        if nodes.remove(&key).is_none() {
+            return Err(ErrorKind::NotFound).context("delete", path);
+        }
+        nodes.retain(|entry, _| !entry.starts_with(&key));
+
+        return Ok(());
+    }
+
+    fn copy(&self, from: &StdPath, to: &StdPath) -> Result<()> {
+        let contents = match self.nodes().get(&key(from)) {
+            Some(Node::File(contents)) => contents.clone(),
+            Some(Node::Dir) => return Err(ErrorKind::InvalidInput).context("copy", from),
+            None => return Err(ErrorKind::NotFound).context("copy", from),
+        };
+        self.write_with(to, |dest| *dest = contents)
+            .context("copy", to)
+    }
+
+    fn rename(&self, from: &StdPath, to: &StdPath) -> Result<()> {
+        let (from_key, to_key) = (key(from), key(to));
+        let mut nodes = self.nodes();
+        if !nodes.contains_key(&from_key) {
+            return Err(ErrorKind::NotFound).context("rename", from);
+        }
+        if to_key == from_key {
+            return Ok(());
+        }
+        if to_key.starts_with(&from_key) {
+            return Err(ErrorKind::InvalidInput).context("rename", from);
+        }
+        // Like the OS: a file only replaces a file, and a directory only an empty directory
+        let from_dir = matches!(nodes.get(&from_key), Some(Node::Dir));
+        let kind = match nodes.get(&to_key) {
+            Some(Node::Dir) if !from_dir => Some(ErrorKind::IsADirectory),
+            Some(Node::File(_)) if from_dir => Some(ErrorKind::NotADirectory),
+            Some(Node::Dir) if nodes.keys().any(|entry| entry.parent() == Some(&to_key)) => {
+                Some(ErrorKind::DirectoryNotEmpty)
+            }
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            return Err(kind).context("rename", to);
+        }
+        if let Some(parent) = to_key.parent() {
+            Self::make_dirs(&mut nodes, parent).context("rename", to)?;
+        }
+        let moved: Vec<_> = nodes
+            .keys()
+            .filter(|entry| entry.starts_with(&from_key))
+            .cloned()
+            .collect();
+        nodes.remove(&to_key);
+        for entry in moved {
+            let node = nodes.remove(&entry).unwrap();
+            let relative = entry.strip_prefix(&from_key).unwrap();
+            nodes.insert(to_key.join(relative), node);
+        }
+        return Ok(());
+    }
+
+    fn list(&self, path: &StdPath) -> Result<Vec<PathBuf>> {
+        let key = key(path);
+        let nodes = self.nodes();
+        match nodes.get(&key) {
+            Some(Node::Dir) => {}
+            Some(Node::File(_)) => return Err(ErrorKind::NotADirectory).context("list", path),
+            None if key.as_os_str().is_empty() => {}
+            None => return Err(ErrorKind::NotFound).context("list", path),
+        }
+        return Ok(nodes
+            .keys()
+            .filter(|entry| entry.parent() == Some(&key))
+            .cloned()
+            .collect());
+    }
+
+    fn size(&self, path: &StdPath) -> Result<u64> {
+        match self.nodes().get(&key(path)) {
+            Some(Node::File(contents)) => Ok(contents.len() as u64),
+            Some(Node::Dir) => Ok(0),
+            None => Err(ErrorKind::NotFound).context("size", path),
+        }
+    }
+}
+
+/// A `FilePath` routed through a `FileSystem`, as returned by `FilePath::on`, so the same file logic
+/// can run against the disk or, in tests, against a `MemoryFileSystem`.
+pub struct RoutedPath<'a> {
+    fs: &'a dyn FileSystem,
+    path: PathBuf,
+}
+
+impl fmt::Debug for RoutedPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoutedPath")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> RoutedPath<'a> {
+    pub(crate) fn new(fs: &'a dyn FileSystem, path: PathBuf) -> Self {
+        Self { fs, path }
+    }
+
+    /// The path being routed.
+    pub fn path(&self) -> &StdPath {
+        &self.path
+    }
+
+    /// Reads the contents of the file as a `String`.
+    pub fn read_string(&self) -> Result<String> {
+        self.fs.read_string(&self.path)
+    }
+
+    /// Reads the contents of the file as bytes.
+    pub fn read_bytes(&self) -> Result<Vec<u8>> {
+        self.fs.read_bytes(&self.path)
+    }
+
+    /// Writes text to the file, creating it **and its full directory path** if they don't exist.
+    pub fn write_string<Text: AsRef<str> + ?Sized>(&self, text: &Text) -> Result<()> {
+        self.fs.write_string(&self.path, text.as_ref())
+    }
+
+    /// Writes bytes to the file, creating it **and its full directory path** if they don't exist.
+    pub fn write_bytes<Bytes: AsRef<[u8]> + ?Sized>(&self, bytes: &Bytes) -> Result<()> {
+        self.fs.write_bytes(&self.path, bytes.as_ref())
+    }
+
+    /// Appends text to the file, creating it **and its full directory path** if they don't exist.
+    pub fn append_string<Text: AsRef<str> + ?Sized>(&self, text: &Text) -> Result<()> {
+        self.fs.append_bytes(&self.path, text.as_ref().as_bytes())
+    }
+
+    /// Returns `true` if the file or directory exists.
+    pub fn exists(&self) -> bool {
+        self.fs.exists(&self.path)
+    }
+
+    /// Returns `true` if the path is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.fs.is_dir(&self.path)
+    }
+
+    /// Creates the path as a directory, along with its full directory path.
+    pub fn create_dir_all(&self) -> Result<()> {
+        self.fs.create_dir_all(&self.path)
+    }
+
+    /// Deletes the file, or the directory recursively.
+    pub fn delete(&self) -> Result<()> {
+        self.fs.delete(&self.path)
+    }
+
+    /// Copies the file to a destination on the same `FileSystem`.
+    pub fn copy_to<Path: AsPath + ?Sized>(&self, to: &Path) -> Result<()> {
+        self.fs.copy(&self.path, to.as_path())
+    }
+
+    /// Renames the file or directory to a destination on the same `FileSystem`, and routes the new path.
+    pub fn rename_to<Path: AsPath + ?Sized>(self, to: &Path) -> Result<Self> {
+        self.fs.rename(&self.path, to.as_path())?;
+        return Ok(Self {
+            fs: self.fs,
+            path: to.as_path().to_path_buf(),
+        });
+    }
+
+    /// Lists the entries of the directory, sorted by path, routed through the same `FileSystem`.
+    pub fn list(&self) -> Result<Vec<Self>> {
+        let entries = self.fs.list(&self.path)?;
+        return Ok(entries
+            .into_iter()
+            .map(|path| Self { fs: self.fs, path })
+            .collect());
+    }
+
+    /// Returns the size of the file in bytes.
+    pub fn size(&self) -> Result<u64> {
+        self.fs.size(&self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    // The same file logic, run against either backend
+    fn roundtrip(fs: &dyn FileSystem, root: &str) -> crate::Result<Vec<String>> {
+        let file = FilePath::access(&format!("{root}/a/b.txt"));
+        file.on(fs).write_string(&"Hello")?;
+        file.on(fs).append_string(&", World!")?;
+        let renamed = file.on(fs).rename_to(&format!("{root}/c.txt"))?;
+        renamed.copy_to(&format!("{root}/a/./d.txt"))?;
+
+        let mut seen = vec![renamed.read_string()?];
+        for entry in FilePath::access(&root).on(fs).list()? {
+            let name = entry.path().file_name().unwrap().to_string_lossy();
+            seen.push(format!("{name}:{}", entry.is_dir()));
+        }
+        FilePath::access(&root).on(fs).delete()?;
+        seen.push(fs.exists(StdPath::new(root)).to_string());
+        return Ok(seen);
+    }
+
+    #[test]
+    fn file_system() -> Result<()> {
+        Ok({
+            // Arrange
+            let memory = MemoryFileSystem::new();
+
+            // Action
+            let on_disk = roundtrip(&OsFileSystem, "file_system")?;
+            let in_memory = roundtrip(&memory, "file_system")?;
+
+            // Assert
+            assert_eq!(on_disk, ["Hello, World!", "a:true", "c.txt:false", "false"]);
+            assert_eq!(in_memory, on_disk);
+            assert!(!PathBuf::from("file_system").exists());
+        })
+    }
+
+    #[test]
+    fn memory_file_system() -> Result<()> {
+        Ok({
+            // Arrange
+            let fs = MemoryFileSystem::new();
+            fs.write_string(StdPath::new("/data/x.bin"), "x")?;
+
+            // Action
+            let into_file = fs.write_string(StdPath::new("/data/x.bin/y"), "y");
+            let over_dir = fs.write_string(StdPath::new("/data"), "z");
+            let missing = fs.delete(StdPath::new("/missing"));
+            fs.rename(StdPath::new("/data"), StdPath::new("/moved/data"))?;
+
+            // Assert
+            assert_eq!(into_file.unwrap_err().kind(), ErrorKind::NotADirectory);
+            assert_eq!(over_dir.unwrap_err().kind(), ErrorKind::IsADirectory);
+            assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+            assert_eq!(
+                fs.read_string(StdPath::new("/moved/../moved/data/x.bin"))?,
+                "x"
+            );
+            assert!(!fs.exists(StdPath::new("/data")));
+            assert_eq!(fs.size(StdPath::new("/moved/data/x.bin"))?, 1);
+        })
+    }
+
+    // Renames onto what's already there, returning the error kinds, if any
+    fn rename_over(fs: &dyn FileSystem, root: &str) -> crate::Result<Vec<Option<ErrorKind>>> {
+        let path = |relative: &str| PathBuf::from(format!("{root}/{relative}"));
+        fs.write_string(&path("file.txt"), "file")?;
+        fs.write_string(&path("full/kept.txt"), "kept")?;
+        fs.write_string(&path("dir/a.txt"), "a")?;
+        fs.create_dir_all(&path("empty"))?;
+
+        let kinds = [
+            fs.rename(&path("file.txt"), &path("full")),
+            fs.rename(&path("dir"), &path("file.txt")),
+            fs.rename(&path("dir"), &path("full")),
+            fs.rename(&path("dir"), &path("empty")),
+        ]
+        .map(|result| result.err().map(|x| x.kind()));
+        assert_eq!(fs.read_string(&path("full/kept.txt"))?, "kept");
+        assert_eq!(fs.read_string(&path("empty/a.txt"))?, "a");
+        fs.delete(StdPath::new(root))?;
+
+        return Ok(kinds.to_vec());
+    }
+
+    #[test]
+    fn rename_over_existing() -> Result<()> {
+        Ok({
+            // Arrange
+            let memory = MemoryFileSystem::new();
+
+            // Action
+            let on_disk = rename_over(&OsFileSystem, "rename_over_existing")?;
+            let in_memory = rename_over(&memory, "rename_over_existing")?;
+
+            // Assert
+            assert!(on_disk[0].is_some() && on_disk[1].is_some() && on_disk[2].is_some());
+            assert_eq!(on_disk[3], None);
+            #[cfg(target_os = "linux")]
+            assert_eq!(in_memory, on_disk);
+            assert_eq!(
+                in_memory,
+                [
+                    Some(ErrorKind::IsADirectory),
+                    Some(ErrorKind::NotADirectory),
+                    Some(ErrorKind::DirectoryNotEmpty),
+                    None,
+                ]
+            );
+        })
+    }
+}
@@ -0,0 +1,273 @@
+use crate::*;
+use std::{collections::HashSet, fs::DirEntry, io, vec::IntoIter};
+
+/// A lazy, recursive walk over the descendants of a directory, as started by `walk`.
+/// Each directory is read when the walk reaches it and its entries are visited in order of their names,
+/// parents before their children. Filters are chained onto it builder-style; by default every file,
+/// directory and link is returned at any depth, and symlinks aren't followed.
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let sources = file_access::walk(&"src").extension("rs").files_only();
+///         for file in sources {
+///             println!("{}", file?.as_ref());
+///         }
+///     })
+/// }
+/// ```
+pub struct Walk {
+    root: PathBuf,
+    max_depth: usize,
+    extension: Option<String>,
+    files: bool,
+    dirs: bool,
+    follow_symlinks: bool,
+    cancel: Option<CancelToken>,
+    // How many entries were returned, for `Cancelled`
+    returned: u64,
+    // The entries of each directory being walked, with their depth, from the root down
+    stack: Vec<(IntoIter<DirEntry>, usize)>,
+    // The directories entered so far when following symlinks, to not loop around cycles
+    visited: HashSet<PathBuf>,
+    started: bool,
+}
+
+impl Walk {
+    /// Only descends `depth` levels below the root: `1` returns just its direct entries.
+    /// As the root itself isn't returned, `0` is rejected: the walk then yields a single `InvalidInput` error.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Only returns entries with this extension, given without the dot, such as `"rs"`.
+    pub fn extension<Ext: AsRef<str> + ?Sized>(mut self, extension: &Ext) -> Self {
+        self.extension = Some(extension.as_ref().to_string());
+        self
+    }
+
+    /// Only returns files (and symlinks that aren't followed), skipping directories while still walking into them.
+    pub fn files_only(mut self) -> Self {
+        (self.files, self.dirs) = (true, false);
+        self
+    }
+
+    /// Only returns directories.
+    pub fn dirs_only(mut self) -> Self {
+        (self.files, self.dirs) = (false, true);
+        self
+    }
+
+    /// Whether to follow symlinks, returning and walking into what they point to. Cycles are walked only once.
+    pub fn follow_symlinks(mut self, follow: bool) -> Self {
+        self.follow_symlinks = follow;
+        self
+    }
+
+    /// Stops the walk once the token is cancelled: the next call returns an `Interrupted` error wrapping `Cancelled`,
+    /// and the walk ends there.
+    pub fn cancel_on(mut self, token: &CancelToken) -> Self {
+        self.cancel = Some(token.clone());
+        self
+    }
+
+    // Reads a directory's entries, sorted by name, to walk them at `depth`
+    fn push(&mut self, dir: &std::path::Path, depth: usize) -> io::Result<()> {
+        if self.follow_symlinks && !self.visited.insert(fs::canonicalize(dir)?) {
+            return Ok(());
+        }
+        let mut entries = fs::read_dir(dir)?.collect::<io::Result<Vec<_>>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+        self.stack.push((entries.into_iter(), depth));
+
+        return Ok(());
+    }
+
+    // Tells whether an entry is a directory, following it if it's a symlink to be followed
+    fn is_dir(&self, entry: &DirEntry) -> io::Result<bool> {
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() && self.follow_symlinks {
+            return Ok(fs::metadata(entry.path()).is_ok_and(|metadata| metadata.is_dir()));
+        }
+        return Ok(file_type.is_dir());
+    }
+
+    // Tells whether an entry passes the filters
+    fn matches(&self, path: &std::path::Path, is_dir: bool) -> bool {
+        let kind = match is_dir {
+            true => self.dirs,
+            false => self.files,
+        };
+        let extension = match &self.extension {
+            Some(extension) => path
+                .extension()
+                .is_some_and(|ext| ext == extension.as_str()),
+            None => true,
+        };
+        return kind && extension;
+    }
+}
+
+impl Iterator for Walk {
+    type Item = Result<FilePath>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            let root = self.root.clone();
+            if self.max_depth == 0 {
+                let error = io::Error::new(ErrorKind::InvalidInput, "max_depth must be at least 1");
+                return Some(Err(error).context("walk", &root));
+            }
+            if let Err(x) = self.push(&root, 1) {
+                return Some(Err(x).context("walk", &root));
+            }
+        }
+        while let Some((entries, depth)) = self.stack.last_mut() {
+            let depth = *depth;
+            if self.cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+                self.stack.clear();
+                let cancelled = Cancelled {
+                    bytes: 0,
+                    files: self.returned,
+                };
+                return Some(Err(io::Error::from(cancelled)).context("walk", &self.root));
+            }
+            let Some(entry) = entries.next() else {
+                self.stack.pop();
+                continue;
+            };
+            let path = entry.path();
+            let is_dir = match self.is_dir(&entry) {
+                Ok(is_dir) => is_dir,
+                Err(x) => return Some(Err(x).context("walk", &path)),
+            };
+            if is_dir && depth < self.max_depth {
+                if let Err(x) = self.push(&path, depth + 1) {
+                    return Some(Err(x).context("walk", &path));
+                }
+            }
+            if self.matches(&path, is_dir) {
+                self.returned += 1;
+                return Some(Ok(FilePath::from(path)));
+            }
+        }
+        return None;
+    }
+}
+
+/// Walks the descendants of a directory recursively and lazily, yielding each as a `FilePath`;
+/// see `Walk` for the filters that can be chained onto it. The root itself isn't returned.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// `Walk`, an `Iterator` of Result<`FilePath`>
+///
+/// # Examples
+/// ```
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let dirs = file_access::walk(&"src").dirs_only().max_depth(2);
+///         for dir in dirs {
+///             println!("{}", dir?.as_ref());
+///         }
+///     })
+/// }
+/// ```
+pub fn walk<Path: AsPath + ?Sized>(file_path: &Path) -> Walk {
+    Walk {
+        root: path_of(file_path),
+        max_depth: usize::MAX,
+        extension: None,
+        files: true,
+        dirs: true,
+        follow_symlinks: false,
+        cancel: None,
+        returned: 0,
+        stack: vec![],
+        visited: HashSet::new(),
+        started: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Result;
+
+    // Collects the paths walked, relative to the root
+    fn relative(walk: Walk, root: &str) -> Result<Vec<String>> {
+        walk.map(|file| {
+            let path = path_of(&file?);
+            let relative = path.strip_prefix(root).unwrap().to_path_buf();
+
+            return Ok(relative.to_string_lossy().replace('\\', "/"));
+        })
+        .collect()
+    }
+
+    #[test]
+    fn walk() -> Result<()> {
+        Ok({
+            // Arrange
+            let root = "walk_test";
+            write_string(&"walk_test/a.rs", &"")?;
+            write_string(&"walk_test/b/c.txt", &"")?;
+            write_string(&"walk_test/b/d/e.rs", &"")?;
+
+            // Action
+            let all = relative(super::walk(&root), root)?;
+            let rs = relative(super::walk(&root).extension("rs"), root)?;
+            let dirs = relative(super::walk(&root).dirs_only(), root)?;
+            let shallow = relative(super::walk(&root).files_only().max_depth(2), root)?;
+
+            // Assert
+            assert_eq!(all, ["a.rs", "b", "b/c.txt", "b/d", "b/d/e.rs"]);
+            assert_eq!(rs, ["a.rs", "b/d/e.rs"]);
+            assert_eq!(dirs, ["b", "b/d"]);
+            assert_eq!(shallow, ["a.rs", "b/c.txt"]);
+            assert!(super::walk(&"walk_test/missing").next().unwrap().is_err());
+            let mut zero = super::walk(&root).max_depth(0);
+            let error = zero.next().unwrap().err().unwrap();
+            assert_eq!(error.kind(), ErrorKind::InvalidInput);
+            assert!(zero.next().is_none());
+            let token = CancelToken::new();
+            let mut cancelled = super::walk(&root).cancel_on(&token);
+            assert!(cancelled.next().unwrap().is_ok());
+            token.cancel();
+            let error = cancelled.next().unwrap().err().unwrap();
+            assert_eq!(error.kind(), ErrorKind::Interrupted);
+            assert!(cancelled.next().is_none());
+
+            // Clean-up
+            delete(&root)?;
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn walk_symlinks() -> Result<()> {
+        Ok({
+            // Arrange
+            let root = "walk_symlinks";
+            write_string(&"walk_symlinks/dir/a.txt", &"")?;
+            std::os::unix::fs::symlink("dir", "walk_symlinks/link")?;
+            std::os::unix::fs::symlink("..", "walk_symlinks/dir/loop")?;
+
+            // Action
+            let unfollowed = relative(super::walk(&root).files_only(), root)?;
+            let followed = relative(super::walk(&root).follow_symlinks(true), root)?;
+
+            // Assert
+            assert_eq!(unfollowed, ["dir/a.txt", "dir/loop", "link"]);
+            assert_eq!(followed, ["dir", "dir/a.txt", "dir/loop", "link"]);
+
+            // Clean-up
+            delete(&root)?;
+        })
+    }
+}
@@ -0,0 +1,114 @@
+use crate::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Reads a YAML file and deserializes it into a value. **Requires the `yaml` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+///
+/// # Returns
+/// Result<`T`>, failing with `ErrorKind::InvalidData` if the file isn't valid YAML for `T`
+///
+/// # Examples
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "read_yaml/deployment.yaml";
+///         file_access::write_string(&file_path, &"replicas: 3\nimage: nginx")?;
+///
+///         let manifest: BTreeMap<String, String> = file_access::read_yaml(&file_path)?;
+///         assert_eq!(manifest["replicas"], "3");
+///
+///         // Clean-up:
+///         file_access::delete(&"read_yaml")?; // ./read_yaml/
+///     })
+/// }
+/// ```
+pub fn read_yaml<Path: AsPath + ?Sized, T: DeserializeOwned>(file_path: &Path) -> Result<T> {
+    serde_norway::from_str(&read_string(file_path)?)
+        .map_err(|x| Error::new(ErrorKind::InvalidData, x))
+        .context("read_yaml", file_path)
+}
+
+/// Serializes a value as YAML and writes it to a file. This function will create the file **and its full directory path**
+/// if they don't exist, and will entirely replace the contents. **Requires the `yaml` feature**.
+///
+/// # Parameters
+/// - `file_path`: **borrowed** `AsPath` such as `String`, `&str` or `PathBuf`
+/// - `value`: **borrowed** `Serialize` such as a `#[derive(Serialize)]` struct, a `Vec` or a `BTreeMap`
+///
+/// # Returns
+/// Result<`()`>
+///
+/// # Examples
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// fn main() -> std::io::Result<()> {
+///     Ok({
+///         let file_path: &str = "write_yaml/deployment.yaml";
+///         let manifest = BTreeMap::from([("replicas", 3)]);
+///
+///         file_access::write_yaml(&file_path, &manifest)?;
+///         assert_eq!(file_access::read_string(&file_path)?, "replicas: 3\n");
+///
+///         // Clean-up:
+///         file_access::delete(&"write_yaml")?; // ./write_yaml/
+///     })
+/// }
+/// ```
+pub fn write_yaml<Path: AsPath + ?Sized, T: Serialize + ?Sized>(
+    file_path: &Path,
+    value: &T,
+) -> Result<()> {
+    let text = serde_norway::to_string(value)
+        .map_err(|x| Error::new(ErrorKind::InvalidData, x))
+        .context("write_yaml", file_path)?;
+
+    return write_string(file_path, &text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::io::Result;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Deployment {
+        name: String,
+        replicas: u32,
+        ports: Vec<u16>,
+    }
+
+    #[test]
+    fn yaml() -> Result<()> {
+        Ok({
+            // Arrange
+            let file = "yaml/manifests/web.yaml";
+            let deployment = Deployment {
+                name: "web".to_string(),
+                replicas: 3,
+                ports: vec![80, 443],
+            };
+
+            // Action
+            write_yaml(&file, &deployment)?;
+            write_string(&"yaml/invalid.yaml", &"name: [")?;
+
+            // Assert
+            assert_eq!(read_yaml::<_, Deployment>(&file)?, deployment);
+            assert_eq!(
+                read_yaml::<_, Deployment>(&"yaml/invalid.yaml")
+                    .unwrap_err()
+                    .kind(),
+                ErrorKind::InvalidData
+            );
+
+            // Clean-up
+            delete(&"yaml")?;
+        })
+    }
+}